@@ -367,6 +367,116 @@ impl Add<&str> for &DFUtf8Array {
     }
 }
 
+impl DFDecimalArray {
+    /// Exact arithmetic on two decimal arrays, the result type is decided by
+    /// `decimal_arithmetic_coercion`, overflow and division by zero are errors.
+    pub fn decimal_arithmetic(
+        &self,
+        rhs: &DFDecimalArray,
+        op: &DataValueArithmeticOperator,
+    ) -> Result<DFDecimalArray> {
+        let (_, lhs_scale) = self.precision_scale();
+        let (_, rhs_scale) = rhs.precision_scale();
+        let (precision, scale) =
+            match decimal_arithmetic_coercion(op, &self.data_type(), &rhs.data_type())? {
+                DataType::Decimal(precision, scale) => (precision, scale),
+                other => {
+                    return Err(ErrorCode::IllegalDataType(format!(
+                        "Unexpected decimal arithmetic result type {:?}",
+                        other
+                    )))
+                }
+            };
+
+        let overflow = |a: i128, b: i128| {
+            ErrorCode::Overflow(format!(
+                "Decimal overflow: {} {} {}",
+                format_decimal(a, lhs_scale),
+                op,
+                format_decimal(b, rhs_scale)
+            ))
+        };
+        let division_by_zero = || ErrorCode::BadArguments("Division by zero");
+
+        let compute = |a: i128, b: i128| -> Result<i128> {
+            let value = match op {
+                DataValueArithmeticOperator::Plus => {
+                    let (x, y) = (
+                        rescale_decimal(a, lhs_scale, scale)?,
+                        rescale_decimal(b, rhs_scale, scale)?,
+                    );
+                    x.checked_add(y).ok_or_else(|| overflow(a, b))?
+                }
+                DataValueArithmeticOperator::Minus => {
+                    let (x, y) = (
+                        rescale_decimal(a, lhs_scale, scale)?,
+                        rescale_decimal(b, rhs_scale, scale)?,
+                    );
+                    x.checked_sub(y).ok_or_else(|| overflow(a, b))?
+                }
+                DataValueArithmeticOperator::Mul => {
+                    a.checked_mul(b).ok_or_else(|| overflow(a, b))?
+                }
+                DataValueArithmeticOperator::Div => {
+                    if b == 0 {
+                        return Err(division_by_zero());
+                    }
+                    // a / 10^s1 / (b / 10^s2) = a * 10^(scale + s2 - s1) / b / 10^scale
+                    let x = rescale_decimal(a, lhs_scale, scale + rhs_scale)
+                        .map_err(|_| overflow(a, b))?;
+                    div_round_half_away(x, b)
+                }
+                DataValueArithmeticOperator::Modulo => {
+                    let (x, y) = (
+                        rescale_decimal(a, lhs_scale, scale)?,
+                        rescale_decimal(b, rhs_scale, scale)?,
+                    );
+                    if y == 0 {
+                        return Err(division_by_zero());
+                    }
+                    x % y
+                }
+            };
+            check_decimal_precision(value, precision)
+        };
+
+        let len = match (self.len(), rhs.len()) {
+            (a, b) if a == b => a,
+            (a, 1) => a,
+            (1, b) => b,
+            (a, b) => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Decimal arithmetic requires arrays of the same length, but got {} and {}",
+                    a, b
+                )))
+            }
+        };
+
+        let lhs_array = self.downcast_ref();
+        let rhs_array = rhs.downcast_ref();
+        let index = |array_len: usize, i: usize| if array_len == 1 { 0 } else { i };
+
+        let mut builder = DecimalArrayBuilder::with_capacity(len, precision, scale);
+        for i in 0..len {
+            let (l, r) = (index(self.len(), i), index(rhs.len(), i));
+            if lhs_array.is_null(l) || rhs_array.is_null(r) {
+                builder.append_null();
+            } else {
+                builder.append_value(compute(lhs_array.value(l), rhs_array.value(r))?);
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    pub fn decimal_negative(&self) -> DFDecimalArray {
+        let (precision, scale) = self.precision_scale();
+        let mut builder = DecimalArrayBuilder::with_capacity(self.len(), precision, scale);
+        self.downcast_iter()
+            .for_each(|v| builder.append_option(v.map(|v| -*v)));
+        builder.finish()
+    }
+}
+
 pub trait Pow {
     fn pow_f32(&self, _exp: f32) -> DFFloat32Array {
         unimplemented!()
@@ -397,3 +507,4 @@ where
 impl Pow for DFBooleanArray {}
 impl Pow for DFUtf8Array {}
 impl Pow for DFListArray {}
+impl Pow for DFDecimalArray {}
@@ -0,0 +1,101 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::array::*;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::ArrayDeserializer;
+use crate::arrays::DataArray;
+use crate::prelude::*;
+
+pub struct DecimalArrayBuilder {
+    builder: MutablePrimitiveArray<i128>,
+    precision: usize,
+    scale: usize,
+}
+
+impl ArrayBuilder<i128, DecimalType> for DecimalArrayBuilder {
+    /// Appends an unscaled value into the builder
+    #[inline]
+    fn append_value(&mut self, v: i128) {
+        self.builder.push(Some(v))
+    }
+
+    /// Appends a null slot into the builder
+    #[inline]
+    fn append_null(&mut self) {
+        self.builder.push_null();
+    }
+
+    fn finish(&mut self) -> DFDecimalArray {
+        let array = std::mem::replace(
+            &mut self.builder,
+            MutablePrimitiveArray::<i128>::with_capacity(0),
+        );
+        let array: PrimitiveArray<i128> = array.into();
+
+        // Tag the values with the precision and scale of the builder.
+        let array = PrimitiveArray::<i128>::from_data(
+            DataType::Decimal(self.precision, self.scale).to_arrow(),
+            array.values().clone(),
+            array.validity().clone(),
+        );
+        DataArray::new(Arc::new(array))
+    }
+}
+
+impl ArrayDeserializer for DecimalArrayBuilder {
+    fn de(&mut self, reader: &mut &[u8]) -> Result<()> {
+        let value: i128 = reader.read_scalar()?;
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn de_batch(&mut self, reader: &[u8], step: usize, rows: usize) -> Result<()> {
+        for row in 0..rows {
+            let mut reader = &reader[step * row..];
+            let value: i128 = reader.read_scalar()?;
+            self.append_value(value);
+        }
+        Ok(())
+    }
+
+    fn finish_to_series(&mut self) -> Series {
+        self.finish().into_series()
+    }
+
+    fn de_text(&mut self, reader: &[u8]) {
+        let value = std::str::from_utf8(reader)
+            .ok()
+            .and_then(|text| parse_decimal(text, self.precision, self.scale).ok());
+        self.append_option(value);
+    }
+
+    fn de_null(&mut self) {
+        self.append_null()
+    }
+}
+
+impl DecimalArrayBuilder {
+    pub fn with_capacity(capacity: usize, precision: usize, scale: usize) -> Self {
+        DecimalArrayBuilder {
+            builder: MutablePrimitiveArray::<i128>::with_capacity(capacity),
+            precision,
+            scale,
+        }
+    }
+}
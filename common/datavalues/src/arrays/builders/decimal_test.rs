@@ -0,0 +1,64 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::prelude::*;
+
+#[test]
+fn test_decimal_builder() {
+    let mut builder = DecimalArrayBuilder::with_capacity(16, 10, 2);
+    builder.append_value(12345);
+    builder.append_null();
+    builder.de_text(b"-1.005");
+    builder.de_text(b"abc");
+
+    let data_array = builder.finish();
+    assert_eq!(DataType::Decimal(10, 2), data_array.data_type());
+    assert_eq!((10, 2), data_array.precision_scale());
+
+    let values = data_array.collect_values();
+    assert_eq!(vec![Some(12345), None, Some(-101), None], values);
+}
+
+#[test]
+fn test_decimal_parse_and_format() -> Result<()> {
+    assert_eq!(parse_decimal("123.456", 10, 3)?, 123456);
+    assert_eq!(parse_decimal("-0.5", 10, 0)?, -1);
+    assert_eq!(parse_decimal("1.2", 10, 3)?, 1200);
+    assert!(parse_decimal("12345", 4, 0).is_err());
+    assert!(parse_decimal("1.2.3", 10, 2).is_err());
+    assert!(check_decimal_precision(10_i128.pow(38), 38).is_err());
+    assert!(check_decimal_precision(-(10_i128.pow(38)), 38).is_err());
+    assert!(check_decimal_precision(i128::MIN, 38).is_err());
+    assert_eq!(
+        check_decimal_precision(10_i128.pow(38) - 1, 38)?,
+        10_i128.pow(38) - 1
+    );
+
+    assert_eq!(format_decimal(123456, 3), "123.456");
+    assert_eq!(format_decimal(-5, 2), "-0.05");
+    assert_eq!(format_decimal(42, 0), "42");
+
+    assert_eq!(rescale_decimal(1250, 3, 1)?, 13);
+    assert_eq!(rescale_decimal(-1250, 3, 1)?, -13);
+    assert_eq!(rescale_decimal(12, 0, 2)?, 1200);
+
+    assert_eq!(div_round_half_away(i128::MAX, i128::MAX - 1), 1);
+    assert_eq!(div_round_half_away(i128::MAX - 1, i128::MIN), -1);
+    assert_eq!(div_round_half_away(i128::MAX / 2, i128::MAX), 0);
+    assert_eq!(div_round_half_away(-15, 10), -2);
+    assert_eq!(div_round_half_away(14, -10), -1);
+    Ok(())
+}
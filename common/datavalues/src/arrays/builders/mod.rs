@@ -15,6 +15,9 @@
 #[cfg(test)]
 mod boolean_test;
 
+#[cfg(test)]
+mod decimal_test;
+
 #[cfg(test)]
 mod primitive_test;
 
@@ -23,10 +26,12 @@ mod utf8_test;
 
 mod boolean;
 mod builder;
+mod decimal;
 mod primitive;
 mod utf8;
 
 pub use boolean::*;
 pub use builder::*;
+pub use decimal::*;
 pub use primitive::*;
 pub use utf8::*;
@@ -15,6 +15,7 @@
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use common_arrow::arrow::array::Array;
 use common_arrow::arrow::array::ArrayRef;
 use common_arrow::arrow::compute::comparison::boolean_compare_scalar;
 use common_arrow::arrow::compute::comparison::compare;
@@ -175,6 +176,65 @@ where
     }
 }
 
+impl DFDecimalArray {
+    /// Compare the decimals after rescaling both sides to the larger scale.
+    fn decimal_comparison<F>(&self, rhs: &DFDecimalArray, f: F) -> Result<DFBooleanArray>
+    where F: Fn(&i128, &i128) -> bool {
+        let (_, lhs_scale) = self.precision_scale();
+        let (_, rhs_scale) = rhs.precision_scale();
+        let scale = lhs_scale.max(rhs_scale);
+
+        let len = match (self.len(), rhs.len()) {
+            (a, b) if a == b => a,
+            (a, 1) => a,
+            (1, b) => b,
+            _ => unreachable!(),
+        };
+
+        let (lhs_array, rhs_array) = (self.downcast_ref(), rhs.downcast_ref());
+        let index = |array_len: usize, i: usize| if array_len == 1 { 0 } else { i };
+
+        let mut values = Vec::with_capacity(len);
+        for i in 0..len {
+            let (l, r) = (index(self.len(), i), index(rhs.len(), i));
+            if lhs_array.is_null(l) || rhs_array.is_null(r) {
+                values.push(None);
+                continue;
+            }
+            let l = rescale_decimal(lhs_array.value(l), lhs_scale, scale)?;
+            let r = rescale_decimal(rhs_array.value(r), rhs_scale, scale)?;
+            values.push(Some(f(&l, &r)));
+        }
+        Ok(DFBooleanArray::new_from_opt_iter(values.into_iter()))
+    }
+}
+
+impl ArrayCompare<&DFDecimalArray> for DFDecimalArray {
+    fn eq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        self.decimal_comparison(rhs, |a, b| a == b)
+    }
+
+    fn neq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        self.decimal_comparison(rhs, |a, b| a != b)
+    }
+
+    fn gt(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        self.decimal_comparison(rhs, |a, b| a > b)
+    }
+
+    fn gt_eq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        self.decimal_comparison(rhs, |a, b| a >= b)
+    }
+
+    fn lt(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        self.decimal_comparison(rhs, |a, b| a < b)
+    }
+
+    fn lt_eq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        self.decimal_comparison(rhs, |a, b| a <= b)
+    }
+}
+
 impl DFBooleanArray {
     /// First ensure that the Arrays of lhs and rhs match and then iterates over the Arrays and applies
     /// the comparison operator.
@@ -399,6 +459,22 @@ impl ArrayEqualElement for DFUtf8Array {
     }
 }
 
+impl ArrayEqualElement for DFDecimalArray {
+    unsafe fn equal_element(&self, idx_self: usize, idx_other: usize, other: &Series) -> bool {
+        let ca_other = other.as_ref().as_ref();
+        debug_assert!(self.data_type() == other.data_type());
+        let ca_other = &*(ca_other as *const DFDecimalArray);
+        let get = |array: &DFDecimalArray, idx: usize| {
+            let array = array.downcast_ref();
+            match array.is_null(idx) {
+                true => None,
+                false => Some(array.value(idx)),
+            }
+        };
+        get(self, idx_self) == get(ca_other, idx_other)
+    }
+}
+
 impl ArrayEqualElement for DFListArray {}
 
 impl ArrayEqualElement for DFNullArray {}
@@ -141,6 +141,7 @@ pub type DFBooleanArray = DataArray<BooleanType>;
 
 pub type DFFloat32Array = DataArray<Float32Type>;
 pub type DFFloat64Array = DataArray<Float64Type>;
+pub type DFDecimalArray = DataArray<DecimalType>;
 
 pub type DFUtf8Array = DataArray<Utf8Type>;
 pub type DFListArray = DataArray<ListType>;
@@ -195,6 +196,14 @@ where T: DFDataType
             DataType::Int64 => downcast_and_pack!(Int64Array, Int64),
             DataType::Float32 => downcast_and_pack!(Float32Array, Float32),
             DataType::Float64 => downcast_and_pack!(Float64Array, Float64),
+            DataType::Decimal(precision, scale) => {
+                let array = &*(arr as *const dyn Array as *const PrimitiveArray<i128>);
+                let value = match array.is_null(index) {
+                    true => None,
+                    false => Some(array.value_unchecked(index)),
+                };
+                Ok(DataValue::Decimal128(value, precision, scale))
+            }
 
             DataType::Binary => {
                 downcast_and_pack!(LargeBinaryArray, Binary)
@@ -288,6 +297,38 @@ impl DFListArray {
     }
 }
 
+impl DFDecimalArray {
+    /// Returns the (precision, scale) of the decimal array.
+    pub fn precision_scale(&self) -> (usize, usize) {
+        match self.data_type() {
+            DataType::Decimal(precision, scale) => (precision, scale),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Re-tag the unscaled values with the precision and scale, this is zero copy.
+    pub fn with_precision_scale(&self, precision: usize, scale: usize) -> Self {
+        let array = self.downcast_ref();
+        let array = PrimitiveArray::<i128>::from_data(
+            DataType::Decimal(precision, scale).to_arrow(),
+            array.values().clone(),
+            array.validity().clone(),
+        );
+        Self::from_arrow_array(array)
+    }
+
+    pub fn full_decimal(
+        value: Option<i128>,
+        precision: usize,
+        scale: usize,
+        length: usize,
+    ) -> Self {
+        let mut builder = DecimalArrayBuilder::with_capacity(length, precision, scale);
+        (0..length).for_each(|_| builder.append_option(value));
+        builder.finish()
+    }
+}
+
 impl<T> From<arrow_array::ArrayRef> for DataArray<T> {
     fn from(array: arrow_array::ArrayRef) -> Self {
         Self::new(array)
@@ -365,6 +365,76 @@ impl ArrayAgg for DFUtf8Array {
     }
 }
 
+impl ArrayAgg for DFDecimalArray {
+    fn sum(&self) -> Result<DataValue> {
+        let (_, scale) = self.precision_scale();
+        let mut sum: Option<i128> = None;
+        for value in self.downcast_iter().flatten() {
+            let acc = sum.unwrap_or(0);
+            sum = Some(
+                acc.checked_add(*value)
+                    .and_then(|sum| check_decimal_precision(sum, MAX_DECIMAL_PRECISION).ok())
+                    .ok_or_else(|| {
+                        ErrorCode::Overflow(format!("Decimal overflow in sum of {:?}", self))
+                    })?,
+            );
+        }
+        Ok(DataValue::Decimal128(sum, MAX_DECIMAL_PRECISION, scale))
+    }
+
+    fn min(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_scale();
+        let value = self.downcast_iter().flatten().min().copied();
+        Ok(DataValue::Decimal128(value, precision, scale))
+    }
+
+    fn max(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_scale();
+        let value = self.downcast_iter().flatten().max().copied();
+        Ok(DataValue::Decimal128(value, precision, scale))
+    }
+
+    fn arg_min(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_scale();
+        let value = self
+            .downcast_iter()
+            .enumerate()
+            .filter_map(|(idx, val)| val.map(|val| (idx, *val)))
+            .reduce(|acc, (idx, val)| if acc.1 > val { (idx, val) } else { acc });
+
+        Ok(match value {
+            Some((index, value)) => DataValue::Struct(vec![
+                (index as u64).into(),
+                DataValue::Decimal128(Some(value), precision, scale),
+            ]),
+            None => DataValue::Struct(vec![
+                DataValue::UInt64(None),
+                DataValue::from(self.data_type()),
+            ]),
+        })
+    }
+
+    fn arg_max(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_scale();
+        let value = self
+            .downcast_iter()
+            .enumerate()
+            .filter_map(|(idx, val)| val.map(|val| (idx, *val)))
+            .reduce(|acc, (idx, val)| if acc.1 < val { (idx, val) } else { acc });
+
+        Ok(match value {
+            Some((index, value)) => DataValue::Struct(vec![
+                (index as u64).into(),
+                DataValue::Decimal128(Some(value), precision, scale),
+            ]),
+            None => DataValue::Struct(vec![
+                DataValue::UInt64(None),
+                DataValue::from(self.data_type()),
+            ]),
+        })
+    }
+}
+
 impl ArrayAgg for DFListArray {}
 
 impl ArrayAgg for DFBinaryArray {}
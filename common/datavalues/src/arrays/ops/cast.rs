@@ -20,6 +20,7 @@ use common_arrow::arrow::compute::cast;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use num::NumCast;
+use num::ToPrimitive;
 
 use crate::prelude::*;
 use crate::series::IntoSeries;
@@ -42,6 +43,27 @@ pub trait ArrayCast: Debug {
             self,
         )))
     }
+
+    /// Cast `DataArray<T>` to `DFDecimalArray` with the precision and scale
+    fn cast_to_decimal(&self, _precision: usize, _scale: usize) -> Result<DFDecimalArray> {
+        Err(ErrorCode::BadDataValueType(format!(
+            "Unsupported cast to decimal operation for {:?}",
+            self,
+        )))
+    }
+}
+
+fn collect_decimal(
+    it: impl Iterator<Item = Result<Option<i128>>>,
+    precision: usize,
+    scale: usize,
+    capacity: usize,
+) -> Result<DFDecimalArray> {
+    let mut builder = DecimalArrayBuilder::with_capacity(capacity, precision, scale);
+    for value in it {
+        builder.append_option(value?);
+    }
+    Ok(builder.finish())
 }
 
 fn cast_ca<N, T>(ca: &DataArray<T>) -> Result<DataArray<N>>
@@ -83,6 +105,10 @@ macro_rules! cast_with_type {
             DataType::Date32 => ArrayCast::cast::<Date32Type>($self).map(|ca| ca.into_series()),
             DataType::Date64 => ArrayCast::cast::<Date64Type>($self).map(|ca| ca.into_series()),
 
+            DataType::Decimal(precision, scale) => {
+                ArrayCast::cast_to_decimal($self, *precision, *scale).map(|ca| ca.into_series())
+            }
+
             DataType::List(_) => ArrayCast::cast::<ListType>($self).map(|ca| ca.into_series()),
             dt => Err(ErrorCode::IllegalDataType(format!(
                 "Arrow datatype {:?} not supported by Datafuse",
@@ -105,6 +131,28 @@ where
     fn cast_with_type(&self, data_type: &DataType) -> Result<Series> {
         cast_with_type!(self, data_type)
     }
+
+    fn cast_to_decimal(&self, precision: usize, scale: usize) -> Result<DFDecimalArray> {
+        let multiplier = decimal_scale_multiplier(scale);
+        let it = self.downcast_iter().map(|v| match v {
+            None => Ok(None),
+            Some(v) if T::FLOATING => {
+                let v = v.to_f64().unwrap_or_default();
+                f64_to_decimal(v, precision, scale).map(Some)
+            }
+            Some(v) => {
+                let v = v.to_i128().unwrap_or_default();
+                let v = v.checked_mul(multiplier).ok_or_else(|| {
+                    ErrorCode::Overflow(format!(
+                        "Value {} overflows Decimal({}, {})",
+                        v, precision, scale
+                    ))
+                })?;
+                check_decimal_precision(v, precision).map(Some)
+            }
+        });
+        collect_decimal(it, precision, scale, self.len())
+    }
 }
 
 impl ArrayCast for DFUtf8Array {
@@ -116,6 +164,14 @@ impl ArrayCast for DFUtf8Array {
     fn cast_with_type(&self, data_type: &DataType) -> Result<Series> {
         cast_with_type!(self, data_type)
     }
+
+    fn cast_to_decimal(&self, precision: usize, scale: usize) -> Result<DFDecimalArray> {
+        let it = self.downcast_iter().map(|v| match v {
+            None => Ok(None),
+            Some(v) => parse_decimal(v, precision, scale).map(Some),
+        });
+        collect_decimal(it, precision, scale, self.len())
+    }
 }
 
 impl ArrayCast for DFBooleanArray {
@@ -126,6 +182,74 @@ impl ArrayCast for DFBooleanArray {
     fn cast_with_type(&self, data_type: &DataType) -> Result<Series> {
         cast_with_type!(self, data_type)
     }
+
+    fn cast_to_decimal(&self, precision: usize, scale: usize) -> Result<DFDecimalArray> {
+        let one = decimal_scale_multiplier(scale);
+        let it = self.downcast_iter().map(|v| match v {
+            None => Ok(None),
+            Some(v) => check_decimal_precision(if v { one } else { 0 }, precision).map(Some),
+        });
+        collect_decimal(it, precision, scale, self.len())
+    }
+}
+
+impl ArrayCast for DFDecimalArray {
+    fn cast_with_type(&self, data_type: &DataType) -> Result<Series> {
+        let (_, from_scale) = self.precision_scale();
+        match data_type {
+            DataType::Decimal(precision, scale) => self
+                .cast_to_decimal(*precision, *scale)
+                .map(|ca| ca.into_series()),
+            DataType::Float32 | DataType::Float64 => {
+                let array = DFFloat64Array::new_from_opt_iter(
+                    self.downcast_iter()
+                        .map(|v| v.map(|v| decimal_to_f64(*v, from_scale))),
+                );
+                array.cast_with_type(data_type)
+            }
+            DataType::Utf8 => {
+                let array = DFUtf8Array::new_from_opt_iter(
+                    self.downcast_iter()
+                        .map(|v| v.map(|v| format_decimal(*v, from_scale))),
+                );
+                Ok(array.into_series())
+            }
+            DataType::Boolean => {
+                let array = DFBooleanArray::new_from_opt_iter(
+                    self.downcast_iter().map(|v| v.map(|v| *v != 0)),
+                );
+                Ok(array.into_series())
+            }
+            // Integers truncate the fractional digits toward zero.
+            _ if is_integer(data_type)
+                || matches!(data_type, DataType::Date32 | DataType::Date64) =>
+            {
+                let multiplier = decimal_scale_multiplier(from_scale);
+                let array = DFInt64Array::new_from_opt_iter(
+                    self.downcast_iter()
+                        .map(|v| v.map(|v| (*v / multiplier) as i64)),
+                );
+                array.cast_with_type(data_type)
+            }
+            dt => Err(ErrorCode::IllegalDataType(format!(
+                "Cannot cast {:?} to {:?}",
+                self.data_type(),
+                dt
+            ))),
+        }
+    }
+
+    fn cast_to_decimal(&self, precision: usize, scale: usize) -> Result<DFDecimalArray> {
+        let (_, from_scale) = self.precision_scale();
+        let it = self.downcast_iter().map(|v| match v {
+            None => Ok(None),
+            Some(v) => {
+                let v = rescale_decimal(*v, from_scale, scale)?;
+                check_decimal_precision(v, precision).map(Some)
+            }
+        });
+        collect_decimal(it, precision, scale, self.len())
+    }
 }
 
 impl ArrayCast for DFNullArray {
@@ -153,6 +277,12 @@ impl ArrayCast for DFNullArray {
             DataType::Date64 => Ok(DFDate64Array::full_null(self.len()).into_series()),
             DataType::Binary => Ok(DFBinaryArray::full_null(self.len()).into_series()),
             DataType::List(_) => Ok(DFListArray::full_null(self.len()).into_series()),
+            DataType::Decimal(precision, scale) => {
+                Ok(
+                    DFDecimalArray::full_decimal(None, *precision, *scale, self.len())
+                        .into_series(),
+                )
+            }
 
             _ => Err(ErrorCode::BadDataValueType(format!(
                 "Unsupported cast_with_type operation for {:?}",
@@ -108,6 +108,33 @@ impl GroupHash for DFUtf8Array {
     }
 }
 
+impl GroupHash for DFDecimalArray {
+    fn fixed_hash(&self, ptr: *mut u8, step: usize) -> Result<()> {
+        let array = self.downcast_ref();
+        let mut ptr = ptr;
+
+        for value in array.values().iter() {
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    value as *const i128 as *const u8,
+                    ptr,
+                    std::mem::size_of::<i128>(),
+                );
+                ptr = ptr.add(step);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, vec: &mut Vec<Vec<u8>>) -> Result<()> {
+        assert_eq!(vec.len(), self.len());
+        for (value, vec) in self.into_no_null_iter().zip(vec.iter_mut()) {
+            BinaryWrite::write_scalar(vec, &value)?;
+        }
+        Ok(())
+    }
+}
+
 impl GroupHash for DFListArray {}
 impl GroupHash for DFBinaryArray {}
 impl GroupHash for DFNullArray {}
@@ -14,7 +14,9 @@
 
 use std::fmt::Debug;
 
+use common_arrow::arrow::array::Array;
 use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::array::PrimitiveArray;
 use common_arrow::arrow::compute::if_then_else;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -113,6 +115,31 @@ impl ArrayIf for DFNullArray {
     }
 }
 
+impl ArrayIf for DFDecimalArray {
+    fn if_then_else(&self, rhs: &Self, predicate: &DFBooleanArray) -> Result<Self> {
+        let (precision, scale) = self.precision_scale();
+        let len = predicate.len().max(self.len()).max(rhs.len());
+        let (lhs_array, rhs_array) = (self.downcast_ref(), rhs.downcast_ref());
+        let get = |array: &PrimitiveArray<i128>, i: usize| {
+            let i = if array.len() == 1 { 0 } else { i };
+            match array.is_null(i) {
+                true => None,
+                false => Some(array.value(i)),
+            }
+        };
+
+        let mut builder = DecimalArrayBuilder::with_capacity(len, precision, scale);
+        for i in 0..len {
+            let pre = predicate.get(if predicate.len() == 1 { 0 } else { i });
+            match pre {
+                Some(true) => builder.append_option(get(lhs_array, i)),
+                None | Some(false) => builder.append_option(get(rhs_array, i)),
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
 impl ArrayIf for DFListArray {}
 impl ArrayIf for DFStructArray {}
 impl ArrayIf for DFBinaryArray {}
@@ -21,6 +21,7 @@ use crate::arrays::get_list_builder;
 use crate::arrays::BinaryArrayBuilder;
 use crate::arrays::BooleanArrayBuilder;
 use crate::arrays::DataArray;
+use crate::arrays::DecimalArrayBuilder;
 use crate::arrays::PrimitiveArrayBuilder;
 use crate::arrays::Utf8ArrayBuilder;
 use crate::prelude::*;
@@ -252,5 +253,41 @@ impl ArrayScatter for DFBinaryArray {
     }
 }
 
+impl ArrayScatter for DFDecimalArray {
+    unsafe fn scatter_unchecked(
+        &self,
+        indices: &mut dyn Iterator<Item = u64>,
+        scattered_size: usize,
+    ) -> Result<Vec<Self>>
+    where
+        Self: std::marker::Sized,
+    {
+        let array = self.downcast_ref();
+        let (precision, scale) = self.precision_scale();
+        let mut builders = Vec::with_capacity(scattered_size);
+
+        for _i in 0..scattered_size {
+            builders.push(DecimalArrayBuilder::with_capacity(
+                self.len(),
+                precision,
+                scale,
+            ));
+        }
+
+        indices.zip(0..self.len()).for_each(|(index, row)| {
+            if self.is_null(row) {
+                builders[index as usize].append_null();
+            } else {
+                builders[index as usize].append_value(array.value(row));
+            }
+        });
+
+        Ok(builders
+            .iter_mut()
+            .map(|builder| builder.finish())
+            .collect())
+    }
+}
+
 impl ArrayScatter for DFNullArray {}
 impl ArrayScatter for DFStructArray {}
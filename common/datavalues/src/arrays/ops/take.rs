@@ -252,6 +252,44 @@ impl ArrayTake for DFListArray {
     }
 }
 
+impl ArrayTake for DFDecimalArray {
+    unsafe fn take_unchecked<I, INulls>(&self, indices: TakeIdx<I, INulls>) -> Result<Self>
+    where
+        Self: std::marker::Sized,
+        I: Iterator<Item = usize>,
+        INulls: Iterator<Item = Option<usize>>,
+    {
+        let array = self.downcast_ref();
+        let (precision, scale) = self.precision_scale();
+        let take_one = |idx: Option<usize>| match idx {
+            Some(idx) if !array.is_null(idx) => Some(array.value_unchecked(idx)),
+            _ => None,
+        };
+
+        let values: Vec<Option<i128>> = match indices {
+            TakeIdx::Array(array) => array
+                .iter()
+                .map(|idx| take_one(idx.map(|v| *v as usize)))
+                .collect(),
+            TakeIdx::Iter(iter) => iter.map(|idx| take_one(Some(idx))).collect(),
+            TakeIdx::IterNulls(iter) => iter.map(take_one).collect(),
+        };
+
+        let mut builder = DecimalArrayBuilder::with_capacity(values.len(), precision, scale);
+        values.into_iter().for_each(|v| builder.append_option(v));
+        Ok(builder.finish())
+    }
+
+    fn take<I, INulls>(&self, indices: TakeIdx<I, INulls>) -> Result<Self>
+    where
+        Self: std::marker::Sized,
+        I: Iterator<Item = usize>,
+        INulls: Iterator<Item = Option<usize>>,
+    {
+        unsafe { self.take_unchecked(indices) }
+    }
+}
+
 impl ArrayTake for DFNullArray {}
 impl ArrayTake for DFStructArray {}
 impl ArrayTake for DFBinaryArray {}
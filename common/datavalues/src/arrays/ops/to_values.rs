@@ -161,6 +161,13 @@ impl ToValues for DataArray<Date64Type> {
     }
 }
 
+impl ToValues for DFDecimalArray {
+    fn to_values(&self) -> Result<Vec<DataValue>> {
+        let (precision, scale) = self.precision_scale();
+        primitive_type_to_values_impl(self, |v| DataValue::Decimal128(v, precision, scale))
+    }
+}
+
 impl ToValues for DFUtf8Array {
     fn to_values(&self) -> Result<Vec<DataValue>> {
         let mut values = Vec::with_capacity(self.len());
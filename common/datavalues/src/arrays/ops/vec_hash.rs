@@ -117,6 +117,20 @@ impl VecHash for DFListArray {
     }
 }
 
+impl VecHash for DFDecimalArray {
+    fn vec_hash(&self, hasher: DFHasher) -> Result<DFUInt64Array> {
+        Ok(DFUInt64Array::new_from_opt_iter(self.downcast_iter().map(
+            |v| {
+                v.map(|v| {
+                    let mut h = hasher.clone_initial();
+                    v.hash(&mut h);
+                    h.finish()
+                })
+            },
+        )))
+    }
+}
+
 impl VecHash for DFStructArray {}
 
 impl VecHash for DFNullArray {}
@@ -34,6 +34,7 @@ pub enum DataGroupValue {
     Int16(i16),
     Int32(i32),
     Int64(i64),
+    Decimal128(i128, usize, usize),
     Utf8(Box<String>),
     Boolean(bool),
     TimestampSecond(i64),
@@ -60,6 +61,9 @@ impl TryFrom<&DataValue> for DataGroupValue {
             DataValue::UInt16(Some(v)) => DataGroupValue::UInt16(*v),
             DataValue::UInt32(Some(v)) => DataGroupValue::UInt32(*v),
             DataValue::UInt64(Some(v)) => DataGroupValue::UInt64(*v),
+            DataValue::Decimal128(Some(v), precision, scale) => {
+                DataGroupValue::Decimal128(*v, *precision, *scale)
+            }
             DataValue::TimestampSecond(Some(v)) => DataGroupValue::TimestampSecond(*v),
             DataValue::TimestampMillisecond(Some(v)) => DataGroupValue::TimeMillisecond(*v),
             DataValue::TimestampMicrosecond(Some(v)) => DataGroupValue::TimeMicrosecond(*v),
@@ -79,6 +83,7 @@ impl TryFrom<&DataValue> for DataGroupValue {
             | DataValue::UInt16(None)
             | DataValue::UInt32(None)
             | DataValue::UInt64(None)
+            | DataValue::Decimal128(None, _, _)
             | DataValue::Utf8(None) => {
                 return Err(ErrorCode::BadDataValueType(format!(
                     "Cannot convert a DataValue holding NULL ({:?})",
@@ -110,6 +115,9 @@ impl From<&DataGroupValue> for DataValue {
             DataGroupValue::UInt16(v) => DataValue::UInt16(Some(*v)),
             DataGroupValue::UInt32(v) => DataValue::UInt32(Some(*v)),
             DataGroupValue::UInt64(v) => DataValue::UInt64(Some(*v)),
+            DataGroupValue::Decimal128(v, precision, scale) => {
+                DataValue::Decimal128(Some(*v), *precision, *scale)
+            }
             DataGroupValue::Utf8(v) => DataValue::Utf8(Some(v.to_string())),
             DataGroupValue::TimestampSecond(v) => DataValue::TimestampSecond(Some(*v)),
             DataGroupValue::TimeMillisecond(v) => DataValue::TimestampMillisecond(Some(*v)),
@@ -50,6 +50,8 @@ pub enum DataValue {
     UInt64(Option<u64>),
     Float32(Option<f32>),
     Float64(Option<f64>),
+    /// Decimal stored as the unscaled i128 with precision and scale
+    Decimal128(Option<i128>, usize, usize),
     Binary(Option<Vec<u8>>),
     Utf8(Option<String>),
 
@@ -93,6 +95,7 @@ impl DataValue {
                 | DataValue::UInt64(None)
                 | DataValue::Float32(None)
                 | DataValue::Float64(None)
+                | DataValue::Decimal128(None, _, _)
                 | DataValue::Binary(None)
                 | DataValue::Utf8(None)
                 | DataValue::Date32(None)
@@ -119,6 +122,7 @@ impl DataValue {
             DataValue::UInt64(_) => DataType::UInt64,
             DataValue::Float32(_) => DataType::Float32,
             DataValue::Float64(_) => DataType::Float64,
+            DataValue::Decimal128(_, precision, scale) => DataType::Decimal(*precision, *scale),
             DataValue::Utf8(_) => DataType::Utf8,
             DataValue::Date32(_) => DataType::Date32,
             DataValue::Date64(_) => DataType::Date64,
@@ -168,6 +172,9 @@ impl DataValue {
             DataValue::UInt64(values) => Ok(build_constant_series! {DFUInt64Array, values, size}),
             DataValue::Float32(values) => Ok(build_constant_series! {DFFloat32Array, values, size}),
             DataValue::Float64(values) => Ok(build_constant_series! {DFFloat64Array, values, size}),
+            DataValue::Decimal128(value, precision, scale) => {
+                Ok(DFDecimalArray::full_decimal(*value, *precision, *scale, size).into_series())
+            }
 
             DataValue::Utf8(values) => match values {
                 None => Ok(DFUtf8Array::full_null(size).into_series()),
@@ -291,6 +298,19 @@ impl DataValue {
             ))),
         }
     }
+
+    pub fn as_f64(&self) -> Result<f64> {
+        match self {
+            DataValue::Float32(Some(v)) => Ok(*v as f64),
+            DataValue::Float64(Some(v)) => Ok(*v),
+            other => other.as_i64().map(|v| v as f64).map_err(|_| {
+                ErrorCode::BadDataValueType(format!(
+                    "Unexpected type:{:?} to get f64 number",
+                    other.data_type()
+                ))
+            }),
+        }
+    }
 }
 
 // Did not use std::convert:TryFrom
@@ -357,6 +377,7 @@ impl From<&DataType> for DataValue {
             DataType::UInt64 => DataValue::UInt64(None),
             DataType::Float32 => DataValue::Float32(None),
             DataType::Float64 => DataValue::Float64(None),
+            DataType::Decimal(precision, scale) => DataValue::Decimal128(None, *precision, *scale),
             DataType::Utf8 => DataValue::Utf8(None),
             DataType::Date32 => DataValue::UInt32(None),
             DataType::Date64 => DataValue::UInt64(None),
@@ -395,6 +416,8 @@ impl fmt::Display for DataValue {
             DataValue::UInt32(v) => format_data_value_with_option!(f, v),
             DataValue::UInt64(v) => format_data_value_with_option!(f, v),
             DataValue::Utf8(v) => format_data_value_with_option!(f, v),
+            DataValue::Decimal128(None, ..) => write!(f, "NULL"),
+            DataValue::Decimal128(Some(v), _, scale) => write!(f, "{}", format_decimal(*v, *scale)),
             DataValue::Binary(None) => write!(f, "NULL"),
             DataValue::Binary(Some(v)) => {
                 for c in v {
@@ -445,6 +468,9 @@ impl fmt::Debug for DataValue {
             DataValue::Float32(v) => format_data_value_with_option!(f, v),
             DataValue::Float64(v) => format_data_value_with_option!(f, v),
             DataValue::Utf8(v) => format_data_value_with_option!(f, v),
+            DataValue::Decimal128(_, precision, scale) => {
+                write!(f, "Decimal128({}, {}, {})", self, precision, scale)
+            }
             DataValue::Binary(None) => write!(f, "{}", self),
             DataValue::Binary(Some(_)) => write!(f, "\"{}\"", self),
             DataValue::Date32(_) => write!(f, "Date32(\"{}\")", self),
@@ -188,6 +188,56 @@ impl DataValue {
                     ))
                 }
             },
+            (
+                DataValue::Decimal128(lhs, precision, scale),
+                DataValue::Decimal128(rhs, _, rhs_scale),
+            ) if scale == rhs_scale => {
+                let (precision, scale) = (*precision, *scale);
+                match op {
+                    DataValueAggregateOperator::Min => Result::Ok(DataValue::Decimal128(
+                        match (lhs, rhs) {
+                            (None, _) => *rhs,
+                            (_, None) => *lhs,
+                            (Some(a), Some(b)) => Some(*a.min(b)),
+                        },
+                        precision,
+                        scale,
+                    )),
+                    DataValueAggregateOperator::Max => Result::Ok(DataValue::Decimal128(
+                        match (lhs, rhs) {
+                            (None, _) => *rhs,
+                            (_, None) => *lhs,
+                            (Some(a), Some(b)) => Some(*a.max(b)),
+                        },
+                        precision,
+                        scale,
+                    )),
+                    DataValueAggregateOperator::Sum => {
+                        let value = match (lhs, rhs) {
+                            (None, _) => *rhs,
+                            (_, None) => *lhs,
+                            (Some(a), Some(b)) => Some(a.checked_add(*b).ok_or_else(|| {
+                                ErrorCode::Overflow(format!(
+                                    "Decimal overflow when adding {} and {}",
+                                    left, right
+                                ))
+                            })?),
+                        };
+                        Result::Ok(DataValue::Decimal128(value, precision, scale))
+                    }
+                    DataValueAggregateOperator::Count => Result::Ok(DataValue::UInt64(Some(1))),
+                    _ => {
+                        Result::Err(ErrorCode::BadDataValueType(
+                            format!(
+                                "DataValue Error: Unsupported data_value_{} for data type: left:{:?}, right:{:?}",
+                                op,
+                                left.data_type(),
+                                right.data_type()
+                            )
+                        ))
+                    }
+                }
+            }
             (DataValue::Utf8(lhs), DataValue::Utf8(rhs)) => match op {
                 DataValueAggregateOperator::Min => typed_data_value_min_max_string!(lhs, rhs, Utf8, min),
                 DataValueAggregateOperator::Max => typed_data_value_min_max_string!(lhs, rhs, Utf8, max),
@@ -83,12 +83,96 @@ impl DataValue {
             DataType::Float64 => {
                 try_build_array! {PrimitiveArrayBuilder, Float64Type, Float64, values}
             }
+            DataType::Decimal(precision, scale) => {
+                let mut builder =
+                    DecimalArrayBuilder::with_capacity(values.len(), *precision, *scale);
+                for value in values.iter() {
+                    match value {
+                        DataValue::Decimal128(v, _, value_scale) => match v {
+                            Some(v) => {
+                                builder.append_value(rescale_decimal(*v, *value_scale, *scale)?)
+                            }
+                            None => builder.append_null(),
+                        },
+                        other => {
+                            return Err(ErrorCode::BadDataValueType(format!(
+                                "Unexpected type:{} for DataValue Decimal",
+                                other.data_type()
+                            )))
+                        }
+                    }
+                }
+                Ok(builder.finish().into_series())
+            }
             DataType::Boolean => try_build_array! {values},
             DataType::Utf8 => try_build_array! {Utf8, values},
+            DataType::List(f) => Self::try_into_list_array(values, f.data_type()),
             other => Result::Err(ErrorCode::BadDataValueType(format!(
                 "Unexpected type:{} for DataValue List",
                 other
             ))),
         }
     }
+
+    /// Convert list data values to a list array, `data_type` is the type of the list items.
+    pub fn try_into_list_array(values: &[DataValue], data_type: &DataType) -> Result<Series> {
+        let size = values.len();
+        let mut builder: Box<dyn ListBuilderTrait> = match data_type {
+            DataType::Int8 => Box::new(ListPrimitiveArrayBuilder::<Int8Type>::with_capacity(
+                0, size,
+            )),
+            DataType::Int16 => Box::new(ListPrimitiveArrayBuilder::<Int16Type>::with_capacity(
+                0, size,
+            )),
+            DataType::Int32 => Box::new(ListPrimitiveArrayBuilder::<Int32Type>::with_capacity(
+                0, size,
+            )),
+            DataType::Int64 => Box::new(ListPrimitiveArrayBuilder::<Int64Type>::with_capacity(
+                0, size,
+            )),
+            DataType::UInt8 => Box::new(ListPrimitiveArrayBuilder::<UInt8Type>::with_capacity(
+                0, size,
+            )),
+            DataType::UInt16 => Box::new(ListPrimitiveArrayBuilder::<UInt16Type>::with_capacity(
+                0, size,
+            )),
+            DataType::UInt32 => Box::new(ListPrimitiveArrayBuilder::<UInt32Type>::with_capacity(
+                0, size,
+            )),
+            DataType::UInt64 => Box::new(ListPrimitiveArrayBuilder::<UInt64Type>::with_capacity(
+                0, size,
+            )),
+            DataType::Float32 => Box::new(ListPrimitiveArrayBuilder::<Float32Type>::with_capacity(
+                0, size,
+            )),
+            DataType::Float64 => Box::new(ListPrimitiveArrayBuilder::<Float64Type>::with_capacity(
+                0, size,
+            )),
+            DataType::Boolean => Box::new(ListBooleanArrayBuilder::with_capacity(0, size)),
+            DataType::Utf8 => Box::new(ListUtf8ArrayBuilder::with_capacity(0, size)),
+            other => {
+                return Result::Err(ErrorCode::BadDataValueType(format!(
+                    "Unexpected type:{} for DataValue List",
+                    other
+                )))
+            }
+        };
+
+        for value in values.iter() {
+            match value {
+                DataValue::List(Some(items), _) => {
+                    let series = Self::try_into_data_array(items, data_type)?;
+                    builder.append_series(&series);
+                }
+                DataValue::List(None, _) | DataValue::Null => builder.append_null(),
+                other => {
+                    return Result::Err(ErrorCode::BadDataValueType(format!(
+                        "Unexpected type:{} for DataValue List",
+                        other.data_type()
+                    )))
+                }
+            }
+        }
+        Ok(builder.finish().into_series())
+    }
 }
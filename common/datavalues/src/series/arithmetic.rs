@@ -192,6 +192,37 @@ impl NumOpsDispatch for DFUtf8Array {
         Ok(out.into_series())
     }
 }
+impl NumOpsDispatch for DFDecimalArray {
+    fn subtract(&self, rhs: &Series) -> Result<Series> {
+        let rhs = rhs.decimal()?;
+        let out = self.decimal_arithmetic(rhs, &DataValueArithmeticOperator::Minus)?;
+        Ok(out.into_series())
+    }
+    fn add_to(&self, rhs: &Series) -> Result<Series> {
+        let rhs = rhs.decimal()?;
+        let out = self.decimal_arithmetic(rhs, &DataValueArithmeticOperator::Plus)?;
+        Ok(out.into_series())
+    }
+    fn multiply(&self, rhs: &Series) -> Result<Series> {
+        let rhs = rhs.decimal()?;
+        let out = self.decimal_arithmetic(rhs, &DataValueArithmeticOperator::Mul)?;
+        Ok(out.into_series())
+    }
+    fn divide(&self, rhs: &Series) -> Result<Series> {
+        let rhs = rhs.decimal()?;
+        let out = self.decimal_arithmetic(rhs, &DataValueArithmeticOperator::Div)?;
+        Ok(out.into_series())
+    }
+    fn remainder(&self, rhs: &Series, _dtype: &DataType) -> Result<Series> {
+        let rhs = rhs.decimal()?;
+        let out = self.decimal_arithmetic(rhs, &DataValueArithmeticOperator::Modulo)?;
+        Ok(out.into_series())
+    }
+
+    fn negative(&self) -> Result<Series> {
+        Ok(self.decimal_negative().into_series())
+    }
+}
 impl NumOpsDispatch for DFBooleanArray {}
 impl NumOpsDispatch for DFListArray {}
 impl NumOpsDispatch for DFBinaryArray {}
@@ -205,6 +236,11 @@ fn coerce_lhs_rhs(
 ) -> Result<(Series, Series)> {
     let dtype = numerical_arithmetic_coercion(op, &lhs.data_type(), &rhs.data_type())?;
 
+    // Decimal operands keep their own scale, the kernel computes the result scale.
+    if is_decimal(&dtype) {
+        return Ok((coerce_to_decimal(lhs)?, coerce_to_decimal(rhs)?));
+    }
+
     let mut left = lhs.clone();
     if lhs.data_type() != dtype {
         left = lhs.cast_with_type(&dtype)?;
@@ -234,6 +270,13 @@ fn coerce_lhs_rhs_no_op(lhs: &Series, rhs: &Series) -> Result<(Series, Series)>
     Ok((left, right))
 }
 
+fn coerce_to_decimal(series: &Series) -> Result<Series> {
+    match decimal_type_of_integer(&series.data_type()) {
+        Some(dtype) => series.cast_with_type(&dtype),
+        None => Ok(series.clone()),
+    }
+}
+
 fn coerce_to_signed(lhs: &Series) -> Result<Series> {
     let dtype = numerical_signed_coercion(&lhs.data_type())?;
 
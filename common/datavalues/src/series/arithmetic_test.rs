@@ -302,3 +302,47 @@ fn test_arithmetic_series() {
         }
     }
 }
+
+#[test]
+fn test_arithmetic_decimal_series() -> Result<()> {
+    // 1.50, 2.25, NULL as Decimal(10, 2)
+    let lhs = Series::new(vec!["1.50", "2.25", "3"]).cast_with_type(&DataType::Decimal(10, 2))?;
+    let rhs = Series::new(vec!["0.5", "1.0", "-2.5"]).cast_with_type(&DataType::Decimal(5, 1))?;
+
+    let expect = |series: Series, data_type: DataType, values: Vec<i128>| {
+        assert_eq!(series.data_type(), data_type);
+        assert_eq!(
+            series.decimal().unwrap().collect_values(),
+            values.into_iter().map(Some).collect::<Vec<_>>()
+        );
+    };
+
+    expect((&lhs + &rhs)?, DataType::Decimal(11, 2), vec![200, 325, 50]);
+    expect((&lhs - &rhs)?, DataType::Decimal(11, 2), vec![
+        100, 125, 550,
+    ]);
+    expect((&lhs * &rhs)?, DataType::Decimal(15, 3), vec![
+        750, 2250, -7500,
+    ]);
+    expect((&lhs / &rhs)?, DataType::Decimal(38, 2), vec![
+        300, 225, -120,
+    ]);
+    expect((&lhs % &rhs)?, DataType::Decimal(10, 2), vec![0, 25, 50]);
+    expect((-&lhs)?, DataType::Decimal(10, 2), vec![-150, -225, -300]);
+
+    // Integers are exact decimals with zero scale.
+    let ints = Series::new([1i32, 2, 3]);
+    expect((&lhs + &ints)?, DataType::Decimal(13, 2), vec![
+        250, 425, 600,
+    ]);
+
+    // Division by zero is an error instead of Infinity or NULL.
+    let zero = Series::new(vec!["0"]).cast_with_type(&DataType::Decimal(10, 2))?;
+    assert!((&lhs / &zero).is_err());
+
+    // Comparison with different scales.
+    let eq =
+        lhs.eq(&Series::new(vec!["1.5", "0", "3.0"]).cast_with_type(&DataType::Decimal(5, 1))?)?;
+    assert_eq!(Vec::from(&eq), vec![Some(true), Some(false), Some(true)]);
+    Ok(())
+}
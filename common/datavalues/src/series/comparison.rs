@@ -37,6 +37,7 @@ macro_rules! impl_compare {
             DataType::Float64 => $self.f64().unwrap().$method($rhs.f64().unwrap()),
             DataType::Date32 => $self.date32().unwrap().$method($rhs.date32().unwrap()),
             DataType::Date64 => $self.date64().unwrap().$method($rhs.date64().unwrap()),
+            DataType::Decimal(_, _) => $self.decimal().unwrap().$method($rhs.decimal().unwrap()),
             _ => unimplemented!(),
         }
     }};
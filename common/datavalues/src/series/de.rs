@@ -41,6 +41,10 @@ impl DataType {
 
             DataType::Utf8 => Ok(Box::new(Utf8ArrayBuilder::with_capacity(capacity))),
 
+            DataType::Decimal(precision, scale) => Ok(Box::new(
+                DecimalArrayBuilder::with_capacity(capacity, *precision, *scale),
+            )),
+
             other => Err(ErrorCode::BadDataValueType(format!(
                 "create_deserializer does not support type '{:?}'",
                 other
@@ -132,6 +132,14 @@ pub trait SeriesTrait: Send + Sync + fmt::Debug {
         )))
     }
 
+    /// Unpack to DFArray of data_type decimal
+    fn decimal(&self) -> Result<&DFDecimalArray> {
+        Err(ErrorCode::IllegalDataType(format!(
+            "{:?} != decimal",
+            self.data_type()
+        )))
+    }
+
     /// Unpack to DFArray of data_type u8
     fn u8(&self) -> Result<&DFUInt8Array> {
         Err(ErrorCode::IllegalDataType(format!(
@@ -234,7 +242,9 @@ where T: 'static + DFDataType
     fn as_ref(&self) -> &DataArray<T> {
         if T::data_type() == self.data_type() ||
             // needed because we want to get ref of List no matter what the inner type is.
-            (matches!(T::data_type(), DataType::List(_)) && matches!(self.data_type(), DataType::List(_)) )
+            (matches!(T::data_type(), DataType::List(_)) && matches!(self.data_type(), DataType::List(_)) ) ||
+            // the same for Decimal no matter what the precision and scale are.
+            (matches!(T::data_type(), DataType::Decimal(_, _)) && matches!(self.data_type(), DataType::Decimal(_, _)))
         {
             unsafe { &*(self as *const dyn SeriesTrait as *const DataArray<T>) }
         } else {
@@ -319,6 +329,7 @@ impl IntoSeries for ArrayRef {
 
             DataType::Float32 => DFFloat32Array::new(self).into_series(),
             DataType::Float64 => DFFloat64Array::new(self).into_series(),
+            DataType::Decimal(_, _) => DFDecimalArray::new(self).into_series(),
             DataType::Utf8 => DFUtf8Array::new(self).into_series(),
             DataType::Date32 => DFDate32Array::new(self).into_series(),
             DataType::Date64 => DFDate64Array::new(self).into_series(),
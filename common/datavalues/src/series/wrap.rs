@@ -250,6 +250,18 @@ macro_rules! impl_dyn_array {
                 }
             }
 
+            fn decimal(&self) -> Result<&DFDecimalArray> {
+                if matches!(self.0.data_type(), DataType::Decimal(_, _)) {
+                    unsafe { Ok(&*(self as *const dyn SeriesTrait as *const DFDecimalArray)) }
+                } else {
+                    Err(ErrorCode::IllegalDataType(format!(
+                        "cannot unpack Series: {:?} of type {:?} into decimal",
+                        self.name(),
+                        self.data_type(),
+                    )))
+                }
+            }
+
             fn u8(&self) -> Result<&DFUInt8Array> {
                 if matches!(self.0.data_type(), DataType::UInt8) {
                     unsafe { Ok(&*(self as *const dyn SeriesTrait as *const DFUInt8Array)) }
@@ -391,6 +403,7 @@ macro_rules! impl_dyn_array {
 impl_dyn_array!(DFNullArray);
 impl_dyn_array!(DFFloat32Array);
 impl_dyn_array!(DFFloat64Array);
+impl_dyn_array!(DFDecimalArray);
 impl_dyn_array!(DFUInt8Array);
 impl_dyn_array!(DFUInt16Array);
 impl_dyn_array!(DFUInt32Array);
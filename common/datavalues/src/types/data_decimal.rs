@@ -0,0 +1,223 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::data_type::DataType;
+
+/// The max number of decimal digits an i128 can hold without overflow.
+pub const MAX_DECIMAL_PRECISION: usize = 38;
+
+/// Determine if a DataType is decimal or not
+pub fn is_decimal(dt: &DataType) -> bool {
+    matches!(dt, DataType::Decimal(_, _))
+}
+
+pub fn check_decimal_type(precision: usize, scale: usize) -> Result<()> {
+    if precision == 0 || precision > MAX_DECIMAL_PRECISION {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Decimal precision must be between 1 and {}, but got {}",
+            MAX_DECIMAL_PRECISION, precision
+        )));
+    }
+
+    if scale > precision {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Decimal scale {} must not be greater than precision {}",
+            scale, precision
+        )));
+    }
+    Ok(())
+}
+
+/// The smallest decimal type which can hold every value of the integer type.
+pub fn decimal_type_of_integer(dt: &DataType) -> Option<DataType> {
+    match dt {
+        DataType::Int8 | DataType::UInt8 => Some(DataType::Decimal(3, 0)),
+        DataType::Int16 | DataType::UInt16 => Some(DataType::Decimal(5, 0)),
+        DataType::Int32 | DataType::UInt32 => Some(DataType::Decimal(10, 0)),
+        DataType::Int64 => Some(DataType::Decimal(19, 0)),
+        DataType::UInt64 => Some(DataType::Decimal(20, 0)),
+        _ => None,
+    }
+}
+
+/// 10^scale as i128.
+#[inline]
+pub fn decimal_scale_multiplier(scale: usize) -> i128 {
+    10_i128.pow(scale as u32)
+}
+
+/// Returns an error if the value has more digits than the precision allows.
+#[inline]
+pub fn check_decimal_precision(value: i128, precision: usize) -> Result<i128> {
+    // 10^38 still fits in an i128, thus the widest precision is bounded the same way.
+    let bound = decimal_scale_multiplier(precision.min(MAX_DECIMAL_PRECISION)).unsigned_abs();
+    if value.unsigned_abs() >= bound {
+        return Err(ErrorCode::Overflow(format!(
+            "Decimal value {} overflows precision {}",
+            value, precision
+        )));
+    }
+    Ok(value)
+}
+
+/// Rescale the unscaled value from one scale to another, rounding half away from zero
+/// when digits are dropped.
+#[inline]
+pub fn rescale_decimal(value: i128, from_scale: usize, to_scale: usize) -> Result<i128> {
+    if from_scale == to_scale {
+        return Ok(value);
+    }
+
+    if to_scale > from_scale {
+        let multiplier = decimal_scale_multiplier(to_scale - from_scale);
+        return value.checked_mul(multiplier).ok_or_else(|| {
+            ErrorCode::Overflow(format!(
+                "Decimal value {} overflows when rescaling from {} to {}",
+                value, from_scale, to_scale
+            ))
+        });
+    }
+
+    let divisor = decimal_scale_multiplier(from_scale - to_scale);
+    Ok(div_round_half_away(value, divisor))
+}
+
+/// Integer division rounding half away from zero.
+#[inline]
+pub fn div_round_half_away(value: i128, divisor: i128) -> i128 {
+    let quotient = value / divisor;
+    let remainder = value % divisor;
+    // `remainder * 2` may overflow, compare it with the rest of the divisor instead.
+    let remainder = remainder.unsigned_abs();
+    if remainder >= divisor.unsigned_abs() - remainder {
+        if (value < 0) == (divisor < 0) {
+            quotient + 1
+        } else {
+            quotient - 1
+        }
+    } else {
+        quotient
+    }
+}
+
+/// Parse a decimal literal such as `-12.345` into its unscaled value at the given scale.
+pub fn parse_decimal(text: &str, precision: usize, scale: usize) -> Result<i128> {
+    let bad_format = || {
+        ErrorCode::BadDataValueType(format!(
+            "Cannot parse '{}' as Decimal({}, {})",
+            text, precision, scale
+        ))
+    };
+
+    let text = text.trim();
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+
+    let (integer_part, fraction_part) = match digits.find('.') {
+        Some(pos) => (&digits[..pos], &digits[pos + 1..]),
+        None => (digits, ""),
+    };
+
+    if integer_part.is_empty() && fraction_part.is_empty() {
+        return Err(bad_format());
+    }
+
+    let mut value: i128 = 0;
+    for c in integer_part.bytes() {
+        if !c.is_ascii_digit() {
+            return Err(bad_format());
+        }
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add((c - b'0') as i128))
+            .ok_or_else(bad_format)?;
+    }
+
+    let mut fraction_digits = 0;
+    let mut round_up = false;
+    for c in fraction_part.bytes() {
+        if !c.is_ascii_digit() {
+            return Err(bad_format());
+        }
+
+        if fraction_digits < scale {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((c - b'0') as i128))
+                .ok_or_else(bad_format)?;
+            fraction_digits += 1;
+        } else if fraction_digits == scale {
+            round_up = c >= b'5';
+            fraction_digits += 1;
+        }
+    }
+
+    if fraction_digits < scale {
+        value = value
+            .checked_mul(decimal_scale_multiplier(scale - fraction_digits))
+            .ok_or_else(bad_format)?;
+    }
+
+    if round_up {
+        value += 1;
+    }
+
+    if negative {
+        value = -value;
+    }
+    check_decimal_precision(value, precision)
+}
+
+/// Format the unscaled value with the given scale, e.g. (12345, 2) => "123.45".
+pub fn format_decimal(value: i128, scale: usize) -> String {
+    if scale == 0 {
+        return value.to_string();
+    }
+
+    let multiplier = decimal_scale_multiplier(scale);
+    let integer_part = (value / multiplier).abs();
+    let fraction_part = (value % multiplier).abs();
+    let sign = if value < 0 { "-" } else { "" };
+    format!(
+        "{}{}.{:0width$}",
+        sign,
+        integer_part,
+        fraction_part,
+        width = scale
+    )
+}
+
+/// Convert the unscaled value to the closest f64.
+#[inline]
+pub fn decimal_to_f64(value: i128, scale: usize) -> f64 {
+    value as f64 / decimal_scale_multiplier(scale) as f64
+}
+
+/// Convert a f64 to the unscaled value at the given scale, rounding half away from zero.
+pub fn f64_to_decimal(value: f64, precision: usize, scale: usize) -> Result<i128> {
+    let scaled = (value * decimal_scale_multiplier(scale) as f64).round();
+    if !scaled.is_finite() || scaled.abs() >= i128::MAX as f64 {
+        return Err(ErrorCode::Overflow(format!(
+            "Value {} overflows Decimal({}, {})",
+            value, precision, scale
+        )));
+    }
+    check_decimal_precision(scaled as i128, precision)
+}
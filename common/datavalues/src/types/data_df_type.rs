@@ -14,6 +14,7 @@
 
 use common_arrow::arrow::types::NativeType;
 
+use super::data_decimal::MAX_DECIMAL_PRECISION;
 use super::data_type::*;
 use crate::DataField;
 
@@ -93,6 +94,14 @@ impl DFDataType for IntervalDayTimeType {
     }
 }
 
+pub struct DecimalType;
+impl DFDataType for DecimalType {
+    fn data_type() -> DataType {
+        // the widest decimal as we cannot know the precision and scale without self.
+        DataType::Decimal(MAX_DECIMAL_PRECISION, 0)
+    }
+}
+
 pub struct ListType;
 impl DFDataType for ListType {
     fn data_type() -> DataType {
@@ -143,6 +152,8 @@ impl_primitive!(TimestampNanosecondType, i64);
 impl_primitive!(IntervalYearMonthType, i32);
 impl_primitive!(IntervalDayTimeType, i64);
 
+impl_primitive!(DecimalType, i128);
+
 pub trait DFNumericType: DFPrimitiveType {
     type LargestType: DFNumericType;
     const SIGN: bool;
@@ -36,6 +36,9 @@ pub enum DataType {
     Int64,
     Float32,
     Float64,
+    /// A fixed-point exact number with a precision (total number of digits)
+    /// and a scale (number of digits after the decimal point), stored as i128.
+    Decimal(usize, usize),
     Utf8,
     /// A 32-bit date representing the elapsed time since UNIX epoch (1970-01-01)
     /// in days (32 bits).
@@ -112,6 +115,7 @@ impl DataType {
             Int64 => ArrowDataType::Int64,
             Float32 => ArrowDataType::Float32,
             Float64 => ArrowDataType::Float64,
+            Decimal(precision, scale) => ArrowDataType::Decimal(*precision, *scale),
             Utf8 => ArrowDataType::LargeUtf8,
             Date32 => ArrowDataType::Date32,
             Date64 => ArrowDataType::Date64,
@@ -149,6 +153,7 @@ impl From<&ArrowDataType> for DataType {
             ArrowDataType::Boolean => DataType::Boolean,
             ArrowDataType::Float32 => DataType::Float32,
             ArrowDataType::Float64 => DataType::Float64,
+            ArrowDataType::Decimal(precision, scale) => DataType::Decimal(*precision, *scale),
             ArrowDataType::List(f) | ArrowDataType::LargeList(f) => {
                 let f: DataField = (f.as_ref()).into();
                 DataType::List(Box::new(f))
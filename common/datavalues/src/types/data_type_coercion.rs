@@ -17,6 +17,7 @@ use std::cmp;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use super::data_decimal::*;
 use crate::prelude::DataType;
 use crate::DataField;
 use crate::DataValueArithmeticOperator;
@@ -118,10 +119,101 @@ pub fn string_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataT
     }
 }
 
+/// Returns the (precision, scale) of a decimal or an integer type.
+fn decimal_precision_scale(dt: &DataType) -> Option<(usize, usize)> {
+    match dt {
+        DataType::Decimal(precision, scale) => Some((*precision, *scale)),
+        other => match decimal_type_of_integer(other) {
+            Some(DataType::Decimal(precision, scale)) => Some((precision, scale)),
+            _ => None,
+        },
+    }
+}
+
+/// Coercion rule for decimal with decimal or numeric types: floats make the result Float64,
+/// otherwise the integers are treated as decimals with zero scale.
+pub fn decimal_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType> {
+    if is_floating(lhs_type) || is_floating(rhs_type) {
+        return Ok(DataType::Float64);
+    }
+
+    match (
+        decimal_precision_scale(lhs_type),
+        decimal_precision_scale(rhs_type),
+    ) {
+        (Some((p1, s1)), Some((p2, s2))) => {
+            let scale = cmp::max(s1, s2);
+            let precision = cmp::max(p1 - s1, p2 - s2) + scale;
+            Ok(DataType::Decimal(
+                cmp::min(precision, MAX_DECIMAL_PRECISION),
+                scale,
+            ))
+        }
+        _ => Result::Err(ErrorCode::BadDataValueType(format!(
+            "Can't construct decimal type from {} and {}",
+            lhs_type, rhs_type
+        ))),
+    }
+}
+
+/// Coercion rule for decimal arithmetic, the result type is wide enough to hold
+/// the exact result like most databases do.
+pub fn decimal_arithmetic_coercion(
+    op: &DataValueArithmeticOperator,
+    lhs_type: &DataType,
+    rhs_type: &DataType,
+) -> Result<DataType> {
+    if is_floating(lhs_type) || is_floating(rhs_type) {
+        return Ok(DataType::Float64);
+    }
+
+    let ((p1, s1), (p2, s2)) = match (
+        decimal_precision_scale(lhs_type),
+        decimal_precision_scale(rhs_type),
+    ) {
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+        _ => {
+            return Result::Err(ErrorCode::BadDataValueType(format!(
+                "DataValue Error: Unsupported ({:?}) {} ({:?})",
+                lhs_type, op, rhs_type
+            )))
+        }
+    };
+
+    let (precision, scale) = match op {
+        DataValueArithmeticOperator::Plus | DataValueArithmeticOperator::Minus => {
+            let scale = cmp::max(s1, s2);
+            (cmp::max(p1 - s1, p2 - s2) + scale + 1, scale)
+        }
+        DataValueArithmeticOperator::Mul => (p1 + p2, s1 + s2),
+        DataValueArithmeticOperator::Div => (MAX_DECIMAL_PRECISION, cmp::max(s1, s2)),
+        DataValueArithmeticOperator::Modulo => {
+            let scale = cmp::max(s1, s2);
+            (cmp::max(p1 - s1, p2 - s2) + scale, scale)
+        }
+    };
+
+    if scale > MAX_DECIMAL_PRECISION {
+        return Result::Err(ErrorCode::Overflow(format!(
+            "Decimal scale {} of ({:?}) {} ({:?}) exceeds the max precision {}",
+            scale, lhs_type, op, rhs_type, MAX_DECIMAL_PRECISION
+        )));
+    }
+
+    Ok(DataType::Decimal(
+        cmp::min(precision, MAX_DECIMAL_PRECISION),
+        scale,
+    ))
+}
+
 /// Coercion rule for numerical types: The type that both lhs and rhs
 /// can be casted to for numerical calculation, while maintaining
 /// maximum precision
 pub fn numerical_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType> {
+    if is_decimal(lhs_type) || is_decimal(rhs_type) {
+        return decimal_coercion(lhs_type, rhs_type);
+    }
+
     let has_float = is_floating(lhs_type) || is_floating(rhs_type);
     let has_integer = is_integer(lhs_type) || is_integer(rhs_type);
     let has_signed = is_signed_numeric(lhs_type) || is_signed_numeric(rhs_type);
@@ -204,6 +296,10 @@ pub fn numerical_arithmetic_coercion(
     lhs_type: &DataType,
     rhs_type: &DataType,
 ) -> Result<DataType> {
+    if is_decimal(lhs_type) || is_decimal(rhs_type) {
+        return decimal_arithmetic_coercion(op, lhs_type, rhs_type);
+    }
+
     // error on any non-numeric type
     if !is_numeric(lhs_type) || !is_numeric(rhs_type) {
         return Result::Err(ErrorCode::BadDataValueType(format!(
@@ -246,6 +342,10 @@ pub fn numerical_arithmetic_coercion(
 
 #[inline]
 pub fn numerical_signed_coercion(val_type: &DataType) -> Result<DataType> {
+    if is_decimal(val_type) {
+        return Ok(val_type.clone());
+    }
+
     // error on any non-numeric type
     if !is_numeric(val_type) {
         return Result::Err(ErrorCode::BadDataValueType(format!(
@@ -333,7 +433,9 @@ pub fn merge_types(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType>
             if lhs_type == rhs_type {
                 return Ok(lhs_type.clone());
             }
-            if is_numeric(lhs_type) && is_numeric(rhs_type) {
+            if (is_numeric(lhs_type) || is_decimal(lhs_type))
+                && (is_numeric(rhs_type) || is_decimal(rhs_type))
+            {
                 numerical_coercion(lhs_type, rhs_type)
             } else {
                 Result::Err(ErrorCode::BadDataValueType(format!(
@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod data_decimal;
 mod data_df_type;
 mod data_type;
 mod data_type_coercion;
 
+pub use data_decimal::*;
 pub use data_df_type::*;
 pub use data_type::*;
 pub use data_type_coercion::*;
@@ -168,6 +168,9 @@ build_exceptions! {
     AuthenticateFailure(51),
     TLSConfigurationFailure(52),
     UnknownSession(53),
+    PermissionDenied(54),
+    TooManyQueuedQueries(55),
+    QuotaExceeded(56),
 
 
     // uncategorized
@@ -186,6 +189,7 @@ build_exceptions! {
     // store node errors
 
     UnknownNode(2101),
+    NodeAlreadyExists(2102),
 
     // meta service errors
 
@@ -195,6 +199,8 @@ build_exceptions! {
     MetaServiceShutdown(2202),
     // meta service is unavailable for now.
     MetaServiceUnavailable(2203),
+    // the cluster membership change is not safe to apply.
+    InvalidMembership(2204),
 
     // config errors
 
@@ -236,6 +242,7 @@ build_exceptions! {
     IllegalMetaState(4005),
     MetaNodeInternalError(4006),
     TrancateTableFailedError(4007),
+    UnknownTableVersion(4008),
 
     // storage-api error codes
     IllegalScanPlan(5000),
@@ -17,9 +17,13 @@ use common_exception::Result;
 use common_exception::ToErrorCode;
 use jwt_simple::prelude::*;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct FlightClaim {
     pub username: String,
+    /// The seq of the user record the client is authenticated with.
+    /// It is None for a user that is not stored as a record, e.g., the one in the server config.
+    /// A token is no longer valid once the record is changed, e.g., the password is rotated.
+    pub user_seq: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -0,0 +1,53 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use common_exception::Result;
+pub use common_store_api::BackupApi;
+pub use common_store_api::BackupResult;
+use common_tracing::tracing;
+
+use crate::action_declare;
+use crate::RequestFor;
+use crate::StoreClient;
+use crate::StoreDoAction;
+
+#[async_trait::async_trait]
+impl BackupApi for StoreClient {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn backup(&mut self, dir: String) -> Result<BackupResult> {
+        self.do_action(BackupReq { dir }).await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn restore(&mut self, dir: String) -> Result<BackupResult> {
+        self.do_action(RestoreReq { dir }).await
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct BackupReq {
+    /// The dir on the host of the store node to write the backup into.
+    pub dir: String,
+}
+
+action_declare!(BackupReq, BackupResult, StoreDoAction::Backup);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct RestoreReq {
+    /// The dir on the host of the store node to read the backup from.
+    pub dir: String,
+}
+
+action_declare!(RestoreReq, BackupResult, StoreDoAction::Restore);
@@ -0,0 +1,85 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::BTreeSet;
+
+use common_exception::Result;
+pub use common_store_api::ClusterApi;
+pub use common_store_api::ClusterMembership;
+use common_tracing::tracing;
+
+use crate::action_declare;
+use crate::RequestFor;
+use crate::StoreClient;
+use crate::StoreDoAction;
+
+#[async_trait::async_trait]
+impl ClusterApi for StoreClient {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn get_membership(&mut self) -> Result<ClusterMembership> {
+        self.do_action(GetMembershipReq {}).await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn add_learner(&mut self, node_id: u64, address: String) -> Result<ClusterMembership> {
+        self.do_action(AddLearnerReq { node_id, address }).await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn change_membership(&mut self, voters: BTreeSet<u64>) -> Result<ClusterMembership> {
+        self.do_action(ChangeMembershipReq { voters }).await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn remove_node(&mut self, node_id: u64) -> Result<ClusterMembership> {
+        self.do_action(RemoveNodeReq { node_id }).await
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct GetMembershipReq {}
+
+action_declare!(
+    GetMembershipReq,
+    ClusterMembership,
+    StoreDoAction::GetMembership
+);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct AddLearnerReq {
+    pub node_id: u64,
+    /// The raft service address of the node, which must be already running.
+    pub address: String,
+}
+
+action_declare!(AddLearnerReq, ClusterMembership, StoreDoAction::AddLearner);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ChangeMembershipReq {
+    pub voters: BTreeSet<u64>,
+}
+
+action_declare!(
+    ChangeMembershipReq,
+    ClusterMembership,
+    StoreDoAction::ChangeMembership
+);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoveNodeReq {
+    pub node_id: u64,
+}
+
+action_declare!(RemoveNodeReq, ClusterMembership, StoreDoAction::RemoveNode);
@@ -15,10 +15,12 @@
 
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_metatypes::TableVersionAt;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
+use common_planners::TableStatistics;
 pub use common_store_api::CreateDatabaseActionResult;
 pub use common_store_api::CreateTableActionResult;
 pub use common_store_api::DatabaseMetaReply;
@@ -28,6 +30,8 @@ pub use common_store_api::DropTableActionResult;
 pub use common_store_api::GetDatabaseActionResult;
 pub use common_store_api::GetTableActionResult;
 use common_store_api::MetaApi;
+pub use common_store_api::SetTableStatisticsActionResult;
+pub use common_store_api::TableStatisticsReply;
 
 use crate::action_declare;
 use crate::store_do_action::StoreDoAction;
@@ -93,6 +97,16 @@ impl MetaApi for StoreClient {
         self.do_action(GetTableExtReq { tbl_id, tbl_ver }).await
     }
 
+    async fn get_table_version(
+        &mut self,
+        db: String,
+        table: String,
+        at: TableVersionAt,
+    ) -> common_exception::Result<MetaVersion> {
+        self.do_action(GetTableVersionAction { db, table, at })
+            .await
+    }
+
     async fn get_database_meta(
         &mut self,
         ver_lower_bound: Option<u64>,
@@ -100,6 +114,28 @@ impl MetaApi for StoreClient {
         self.do_action(GetDatabaseMetaAction { ver_lower_bound })
             .await
     }
+
+    async fn set_table_statistics(
+        &mut self,
+        db: String,
+        table: String,
+        statistics: TableStatistics,
+    ) -> common_exception::Result<SetTableStatisticsActionResult> {
+        self.do_action(SetTableStatisticsAction {
+            db,
+            table,
+            statistics,
+        })
+        .await
+    }
+
+    async fn get_table_statistics(
+        &mut self,
+        db: String,
+        table: String,
+    ) -> common_exception::Result<TableStatisticsReply> {
+        self.do_action(GetTableStatisticsAction { db, table }).await
+    }
 }
 
 // == database actions ==
@@ -181,6 +217,18 @@ action_declare!(
     StoreDoAction::GetTableExt
 );
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetTableVersionAction {
+    pub db: String,
+    pub table: String,
+    pub at: TableVersionAt,
+}
+action_declare!(
+    GetTableVersionAction,
+    MetaVersion,
+    StoreDoAction::GetTableVersion
+);
+
 // - get database meta
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -193,3 +241,28 @@ action_declare!(
     DatabaseMetaReply,
     StoreDoAction::GetDatabaseMeta
 );
+
+// - table statistics
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct SetTableStatisticsAction {
+    pub db: String,
+    pub table: String,
+    pub statistics: TableStatistics,
+}
+action_declare!(
+    SetTableStatisticsAction,
+    SetTableStatisticsActionResult,
+    StoreDoAction::SetTableStatistics
+);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetTableStatisticsAction {
+    pub db: String,
+    pub table: String,
+}
+action_declare!(
+    GetTableStatisticsAction,
+    TableStatisticsReply,
+    StoreDoAction::GetTableStatistics
+);
@@ -13,6 +13,8 @@
 // limitations under the License.
 //
 
+pub mod backup_api_impl;
+pub mod cluster_api_impl;
 pub mod kv_api_impl;
 pub mod meta_api_impl;
 pub mod session_api_impl;
@@ -44,6 +44,7 @@ use tonic::Request;
 use crate::action_declare;
 use crate::impls::storage_api_impl_utils;
 pub use crate::impls::storage_api_impl_utils::get_meta;
+pub use crate::impls::storage_api_impl_utils::get_query_id;
 use crate::RequestFor;
 use crate::StoreClient;
 use crate::StoreDoAction;
@@ -110,6 +111,7 @@ impl StorageApi for StoreClient {
 
     async fn append_data(
         &mut self,
+        query_id: String,
         db_name: String,
         tbl_name: String,
         scheme_ref: DataSchemaRef,
@@ -151,6 +153,7 @@ impl StorageApi for StoreClient {
         let mut req = Request::new(flight_stream);
         let meta = req.metadata_mut();
         storage_api_impl_utils::put_meta(meta, &db_name, &tbl_name);
+        storage_api_impl_utils::put_query_id(meta, &query_id);
 
         let res = self.client.do_put(req).await?;
 
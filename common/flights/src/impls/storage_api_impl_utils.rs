@@ -21,6 +21,7 @@ use tonic::metadata::MetadataValue;
 
 pub const META_KEY_DB_NAME: &str = "fq-db-name-bin";
 pub const META_KEY_TBL_NAME: &str = "fq-tbl-name-bin";
+pub const META_KEY_QUERY_ID: &str = "fq-query-id-bin";
 
 pub fn put_meta(meta: &mut MetadataMap, db_name: &str, tbl_name: &str) {
     meta.insert_bin(
@@ -34,24 +35,40 @@ pub fn put_meta(meta: &mut MetadataMap, db_name: &str, tbl_name: &str) {
 }
 
 pub fn get_meta(meta: &MetadataMap) -> Result<(String, String)> {
-    fn deserialize_meta(value: &MetadataValue<Binary>, error_msg: &'static str) -> Result<String> {
-        match value.to_bytes() {
-            Ok(bytes) => Ok(String::from_utf8(bytes.to_vec())?),
-            Err(error) => Err(ErrorCode::InvalidMetaBinaryFormat(format!(
-                "{}, cause {}",
-                error_msg, error
-            ))),
-        }
+    let db_name = fetch_string(meta, META_KEY_DB_NAME, "invalid db_name meta data")?;
+    let tbl_name = fetch_string(meta, META_KEY_TBL_NAME, "invalid tbl_name meta data")?;
+    Ok((db_name, tbl_name))
+}
+
+/// The id of the query that issues the request, with which the request is cancelled if the query is killed.
+pub fn put_query_id(meta: &mut MetadataMap, query_id: &str) {
+    meta.insert_bin(
+        META_KEY_QUERY_ID,
+        MetadataValue::from_bytes(query_id.as_bytes()),
+    );
+}
+
+/// Returns an empty query id if it is absent, e.g., the request is not issued by a query.
+pub fn get_query_id(meta: &MetadataMap) -> Result<String> {
+    match meta.get_bin(META_KEY_QUERY_ID) {
+        None => Ok(String::new()),
+        Some(meta_binary) => deserialize_meta(meta_binary, "invalid query_id meta data"),
     }
+}
 
-    fn fetch_string(meta: &MetadataMap, key: &str, error_msg: &'static str) -> Result<String> {
-        match meta.get_bin(key) {
-            None => Err(ErrorCode::UnknownKey(format!("Unknown meta key {}", key))),
-            Some(meta_binary) => deserialize_meta(meta_binary, error_msg),
-        }
+fn deserialize_meta(value: &MetadataValue<Binary>, error_msg: &'static str) -> Result<String> {
+    match value.to_bytes() {
+        Ok(bytes) => Ok(String::from_utf8(bytes.to_vec())?),
+        Err(error) => Err(ErrorCode::InvalidMetaBinaryFormat(format!(
+            "{}, cause {}",
+            error_msg, error
+        ))),
     }
+}
 
-    let db_name = fetch_string(meta, META_KEY_DB_NAME, "invalid db_name meta data")?;
-    let tbl_name = fetch_string(meta, META_KEY_TBL_NAME, "invalid tbl_name meta data")?;
-    Ok((db_name, tbl_name))
+fn fetch_string(meta: &MetadataMap, key: &str, error_msg: &'static str) -> Result<String> {
+    match meta.get_bin(key) {
+        None => Err(ErrorCode::UnknownKey(format!("Unknown meta key {}", key))),
+        Some(meta_binary) => deserialize_meta(meta_binary, error_msg),
+    }
 }
@@ -18,7 +18,9 @@ mod test {
     use tonic::metadata::MetadataMap;
 
     use crate::impls::storage_api_impl_utils::get_meta;
+    use crate::impls::storage_api_impl_utils::get_query_id;
     use crate::impls::storage_api_impl_utils::put_meta;
+    use crate::impls::storage_api_impl_utils::put_query_id;
 
     #[test]
     fn test_get_set_meta() {
@@ -30,4 +32,13 @@ mod test {
         assert_eq!(test_db, db);
         assert_eq!(test_tbl, tbl);
     }
+
+    #[test]
+    fn test_get_set_query_id() {
+        let mut meta = MetadataMap::new();
+        assert_eq!("", get_query_id(&meta).unwrap());
+
+        put_query_id(&mut meta, "query-1");
+        assert_eq!("query-1", get_query_id(&meta).unwrap());
+    }
 }
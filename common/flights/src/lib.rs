@@ -14,6 +14,8 @@
 
 pub use common::flight_result_to_str;
 pub use common::RpcClientTlsConfig;
+pub use common_store_api::BackupApi;
+pub use common_store_api::ClusterApi;
 pub use common_store_api::KVApi;
 pub use common_store_api::MetaApi;
 pub use common_store_api::StorageApi;
@@ -21,6 +23,8 @@ pub use dns_resolver::ConnectionFactory;
 pub use dns_resolver::DNSResolver;
 pub use flight_token::FlightClaim;
 pub use flight_token::FlightToken;
+pub use impls::backup_api_impl;
+pub use impls::cluster_api_impl;
 pub use impls::kv_api_impl;
 pub use impls::meta_api_impl;
 pub use impls::session_api_impl;
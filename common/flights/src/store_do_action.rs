@@ -20,6 +20,12 @@ use common_exception::ErrorCode;
 use prost::Message;
 use tonic::Request;
 
+use crate::impls::backup_api_impl::BackupReq;
+use crate::impls::backup_api_impl::RestoreReq;
+use crate::impls::cluster_api_impl::AddLearnerReq;
+use crate::impls::cluster_api_impl::ChangeMembershipReq;
+use crate::impls::cluster_api_impl::GetMembershipReq;
+use crate::impls::cluster_api_impl::RemoveNodeReq;
 use crate::impls::kv_api_impl::GetKVAction;
 use crate::impls::kv_api_impl::MGetKVAction;
 use crate::impls::kv_api_impl::PrefixListReq;
@@ -35,6 +41,9 @@ use crate::impls::session_api_impl::KillQueryReq;
 use crate::impls::storage_api_impl::ReadPlanAction;
 use crate::impls::storage_api_impl::TruncateTableAction;
 use crate::meta_api_impl::GetTableExtReq;
+use crate::meta_api_impl::GetTableStatisticsAction;
+use crate::meta_api_impl::GetTableVersionAction;
+use crate::meta_api_impl::SetTableStatisticsAction;
 use crate::protobuf::FlightStoreRequest;
 
 pub trait RequestFor {
@@ -67,7 +76,10 @@ pub enum StoreDoAction {
     DropTable(DropTableAction),
     GetTable(GetTableAction),
     GetTableExt(GetTableExtReq),
+    GetTableVersion(GetTableVersionAction),
     GetDatabaseMeta(GetDatabaseMetaAction),
+    SetTableStatistics(SetTableStatisticsAction),
+    GetTableStatistics(GetTableStatisticsAction),
     ReadPlan(ReadPlanAction),
     TruncateTable(TruncateTableAction),
 
@@ -79,6 +91,16 @@ pub enum StoreDoAction {
 
     // session
     KillQuery(KillQueryReq),
+
+    // backup
+    Backup(BackupReq),
+    Restore(RestoreReq),
+
+    // cluster membership
+    GetMembership(GetMembershipReq),
+    AddLearner(AddLearnerReq),
+    ChangeMembership(ChangeMembershipReq),
+    RemoveNode(RemoveNodeReq),
 }
 
 /// Try convert tonic::Request<Action> to DoActionAction.
@@ -218,6 +218,137 @@ where
     }
 }
 
+/// Average of decimals, the result has 4 more digits of scale like MySQL.
+#[derive(Clone)]
+pub struct AggregateDecimalAvgFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    scale: usize,
+}
+
+impl AggregateDecimalAvgFunction {
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        scale: usize,
+    ) -> Result<AggregateFunctionRef> {
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            arguments,
+            scale,
+        }))
+    }
+
+    fn result_scale(&self) -> usize {
+        std::cmp::min(self.scale + 4, MAX_DECIMAL_PRECISION)
+    }
+
+    fn add(&self, place: StateAddr, value: i128, count: u64) -> Result<()> {
+        let state = place.get::<AggregateAvgState<i128>>();
+        state.value = state
+            .value
+            .checked_add(value)
+            .and_then(|sum| check_decimal_precision(sum, MAX_DECIMAL_PRECISION).ok())
+            .ok_or_else(|| {
+                ErrorCode::Overflow(format!("Decimal overflow in {}", self.display_name))
+            })?;
+        state.count += count;
+        Ok(())
+    }
+}
+
+impl AggregateFunction for AggregateDecimalAvgFunction {
+    fn name(&self) -> &str {
+        "AggregateDecimalAvgFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Decimal(
+            MAX_DECIMAL_PRECISION,
+            self.result_scale(),
+        ))
+    }
+
+    // The average of no values is NULL.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateAvgState::<i128> { value: 0, count: 0 });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateAvgState<i128>>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let count = arrays[0].len() - arrays[0].null_count();
+        if let DataValue::Decimal128(Some(sum), _, _) = arrays[0].sum()? {
+            self.add(place, sum, count as u64)?;
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let array = arrays[0].decimal()?;
+        for (value, place) in array.downcast_iter().zip(places.iter()) {
+            if let Some(value) = value {
+                self.add(place.next(offset), *value, 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateAvgState<i128>>();
+        state.value.serialize_to_buf(writer)?;
+        state.count.serialize_to_buf(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateAvgState<i128>>();
+        state.value = i128::deserialize(reader)?;
+        state.count = u64::deserialize(reader)?;
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let rhs = rhs.get::<AggregateAvgState<i128>>();
+        self.add(place, rhs.value, rhs.count)
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateAvgState<i128>>();
+        let scale = self.result_scale();
+
+        if state.count == 0 {
+            return Ok(DataValue::Decimal128(None, MAX_DECIMAL_PRECISION, scale));
+        }
+
+        let sum = rescale_decimal(state.value, self.scale, scale)?;
+        let avg = div_round_half_away(sum, state.count as i128);
+        let avg = check_decimal_precision(avg, MAX_DECIMAL_PRECISION)?;
+        Ok(DataValue::Decimal128(
+            Some(avg),
+            MAX_DECIMAL_PRECISION,
+            scale,
+        ))
+    }
+}
+
+impl fmt::Display for AggregateDecimalAvgFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
 macro_rules! creator {
     ($T: ident, $data_type: expr, $display_name: expr, $arguments: expr) => {
         if $T::data_type() == $data_type {
@@ -239,6 +370,11 @@ pub fn try_create_aggregate_avg_function(
     let data_type = arguments[0].data_type();
     dispatch_numeric_types! {creator, data_type.clone(), display_name, arguments}
 
+    if let DataType::Decimal(_, scale) = data_type {
+        let scale = *scale;
+        return AggregateDecimalAvgFunction::try_create(display_name, arguments, scale);
+    }
+
     Err(ErrorCode::BadDataValueType(format!(
         "AggregateSumFunction does not support type '{:?}'",
         data_type
@@ -0,0 +1,225 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_binary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CovarianceKind {
+    CovarPop,
+    CovarSamp,
+    Corr,
+}
+
+/// Online co-moment of (x, y), states of different blocks are merged by Chan's formula.
+/// The second moments are kept for the correlation.
+#[derive(Default)]
+pub struct AggregateCovarianceState {
+    pub count: u64,
+    pub mean_x: f64,
+    pub mean_y: f64,
+    pub co_moment: f64,
+    pub m2_x: f64,
+    pub m2_y: f64,
+}
+
+impl AggregateCovarianceState {
+    #[inline(always)]
+    pub fn add(&mut self, x: f64, y: f64) {
+        self.count += 1;
+        let delta_x = x - self.mean_x;
+        let delta_y = y - self.mean_y;
+        self.mean_x += delta_x / self.count as f64;
+        self.mean_y += delta_y / self.count as f64;
+        self.co_moment += delta_x * (y - self.mean_y);
+        self.m2_x += delta_x * (x - self.mean_x);
+        self.m2_y += delta_y * (y - self.mean_y);
+    }
+
+    #[inline(always)]
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+
+        let count = self.count + other.count;
+        let delta_x = other.mean_x - self.mean_x;
+        let delta_y = other.mean_y - self.mean_y;
+        let factor = self.count as f64 * other.count as f64 / count as f64;
+        self.mean_x += delta_x * other.count as f64 / count as f64;
+        self.mean_y += delta_y * other.count as f64 / count as f64;
+        self.co_moment += other.co_moment + delta_x * delta_y * factor;
+        self.m2_x += other.m2_x + delta_x * delta_x * factor;
+        self.m2_y += other.m2_y + delta_y * delta_y * factor;
+        self.count = count;
+    }
+
+    pub fn serialize(&self, writer: &mut BytesMut) -> Result<()> {
+        self.count.serialize_to_buf(writer)?;
+        self.mean_x.serialize_to_buf(writer)?;
+        self.mean_y.serialize_to_buf(writer)?;
+        self.co_moment.serialize_to_buf(writer)?;
+        self.m2_x.serialize_to_buf(writer)?;
+        self.m2_y.serialize_to_buf(writer)
+    }
+
+    pub fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        self.count = u64::deserialize(reader)?;
+        self.mean_x = f64::deserialize(reader)?;
+        self.mean_y = f64::deserialize(reader)?;
+        self.co_moment = f64::deserialize(reader)?;
+        self.m2_x = f64::deserialize(reader)?;
+        self.m2_y = f64::deserialize(reader)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateCovarianceFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    kind: CovarianceKind,
+}
+
+impl AggregateCovarianceFunction {
+    pub fn try_create(
+        kind: CovarianceKind,
+        display_name: &str,
+        _params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_binary_arguments(display_name, arguments.len())?;
+
+        for argument in arguments.iter() {
+            if !is_numeric(argument.data_type()) {
+                return Err(ErrorCode::BadDataValueType(format!(
+                    "{} does not support type '{:?}'",
+                    display_name,
+                    argument.data_type()
+                )));
+            }
+        }
+
+        Ok(Arc::new(AggregateCovarianceFunction {
+            display_name: display_name.to_string(),
+            arguments,
+            kind,
+        }))
+    }
+}
+
+impl AggregateFunction for AggregateCovarianceFunction {
+    fn name(&self) -> &str {
+        "AggregateCovarianceFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(AggregateCovarianceState::default);
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateCovarianceState>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let state = place.get::<AggregateCovarianceState>();
+        let x = arrays[0].cast_with_type(&DataType::Float64)?;
+        let y = arrays[1].cast_with_type(&DataType::Float64)?;
+
+        let values = x.f64()?.downcast_iter().zip(y.f64()?.downcast_iter());
+        for (x, y) in values {
+            if let (Some(x), Some(y)) = (x, y) {
+                state.add(*x, *y);
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let x = arrays[0].cast_with_type(&DataType::Float64)?;
+        let y = arrays[1].cast_with_type(&DataType::Float64)?;
+
+        let values = x.f64()?.downcast_iter().zip(y.f64()?.downcast_iter());
+        for ((x, y), place) in values.zip(places.iter()) {
+            if let (Some(x), Some(y)) = (x, y) {
+                let place = place.next(offset);
+                place.get::<AggregateCovarianceState>().add(*x, *y);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateCovarianceState>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateCovarianceState>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateCovarianceState>();
+        let rhs = rhs.get::<AggregateCovarianceState>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateCovarianceState>();
+        let value = match self.kind {
+            CovarianceKind::CovarPop if state.count > 0 => state.co_moment / state.count as f64,
+            CovarianceKind::CovarSamp if state.count > 1 => {
+                state.co_moment / (state.count - 1) as f64
+            }
+            // The correlation is undefined if any of the variances is zero.
+            CovarianceKind::Corr if state.m2_x > 0.0 && state.m2_y > 0.0 => {
+                state.co_moment / (state.m2_x * state.m2_y).sqrt()
+            }
+            _ => return Ok(DataValue::Float64(None)),
+        };
+        Ok(DataValue::Float64(Some(value)))
+    }
+}
+
+impl fmt::Display for AggregateCovarianceFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -14,7 +14,9 @@
 
 use bumpalo::Bump;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_io::prelude::*;
 use pretty_assertions::assert_eq;
 
 use crate::aggregates::*;
@@ -143,6 +145,153 @@ fn test_aggregate_function() -> Result<()> {
             expect: DataValue::UInt64(Some(4)),
             error: "",
         },
+        Test {
+            name: "groupArray-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "groupArray",
+            func_name: "groupArray",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::List(
+                Some(vec![
+                    DataValue::Int64(Some(4)),
+                    DataValue::Int64(Some(3)),
+                    DataValue::Int64(Some(2)),
+                    DataValue::Int64(Some(1)),
+                ]),
+                DataType::Int64,
+            ),
+            error: "",
+        },
+        Test {
+            name: "groupArray-max-size-passed",
+            eval_nums: 2,
+            params: vec![DataValue::UInt64(Some(3))],
+            args: vec![args[1].clone()],
+            display: "groupArray",
+            func_name: "groupArray",
+            arrays: vec![arrays[1].clone()],
+            expect: DataValue::List(
+                Some(vec![
+                    DataValue::Int64(Some(1)),
+                    DataValue::Int64(Some(2)),
+                    DataValue::Int64(Some(3)),
+                ]),
+                DataType::Int64,
+            ),
+            error: "",
+        },
+        Test {
+            name: "var_pop-passed",
+            eval_nums: 2,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "var_pop",
+            func_name: "var_pop",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(Some(1.25)),
+            error: "",
+        },
+        Test {
+            name: "var_samp-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "var_samp",
+            func_name: "var_samp",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(Some(1.6666666666666667)),
+            error: "",
+        },
+        Test {
+            name: "stddev_pop-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "stddev_pop",
+            func_name: "stddev_pop",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(Some(1.118033988749895)),
+            error: "",
+        },
+        Test {
+            name: "stddev_samp-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "stddev_samp",
+            func_name: "stddev_samp",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(Some(1.2909944487358056)),
+            error: "",
+        },
+        Test {
+            name: "covar_pop-passed",
+            eval_nums: 2,
+            params: vec![],
+            args: args.clone(),
+            display: "covar_pop",
+            func_name: "covar_pop",
+            arrays: arrays.clone(),
+            expect: DataValue::Float64(Some(-1.25)),
+            error: "",
+        },
+        Test {
+            name: "covar_samp-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: args.clone(),
+            display: "covar_samp",
+            func_name: "covar_samp",
+            arrays: arrays.clone(),
+            expect: DataValue::Float64(Some(-1.6666666666666667)),
+            error: "",
+        },
+        Test {
+            name: "corr-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: args.clone(),
+            display: "corr",
+            func_name: "corr",
+            arrays: arrays.clone(),
+            expect: DataValue::Float64(Some(-1.0)),
+            error: "",
+        },
+        Test {
+            name: "quantile-passed",
+            eval_nums: 2,
+            params: vec![DataValue::Float64(Some(0.25))],
+            args: vec![args[0].clone()],
+            display: "quantile",
+            func_name: "quantile",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(Some(1.5)),
+            error: "",
+        },
+        Test {
+            name: "median-passed",
+            eval_nums: 2,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "median",
+            func_name: "median",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(Some(2.5)),
+            error: "",
+        },
+        Test {
+            name: "uniqHLL-passed",
+            eval_nums: 2,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "uniqHLL",
+            func_name: "uniqHLL",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::UInt64(Some(4)),
+            error: "",
+        },
     ];
 
     for t in tests {
@@ -294,6 +443,50 @@ fn test_aggregate_function_on_empty_data() -> Result<()> {
             expect: DataValue::UInt64(Some(0)),
             error: "",
         },
+        Test {
+            name: "var_samp-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "var_samp",
+            func_name: "var_samp",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(None),
+            error: "",
+        },
+        Test {
+            name: "corr-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone(), args[0].clone()],
+            display: "corr",
+            func_name: "corr",
+            arrays: vec![arrays[0].clone(), arrays[0].clone()],
+            expect: DataValue::Float64(None),
+            error: "",
+        },
+        Test {
+            name: "median-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "median",
+            func_name: "median",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(None),
+            error: "",
+        },
+        Test {
+            name: "uniqHLL-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "uniqHLL",
+            func_name: "uniqHLL",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::UInt64(Some(0)),
+            error: "",
+        },
     ];
 
     for t in tests {
@@ -330,3 +523,156 @@ fn test_aggregate_function_on_empty_data() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_aggregate_function_on_decimal() -> Result<()> {
+    let data_type = DataType::Decimal(5, 2);
+    let arrays = vec![Series::new(vec!["1.25", "2.50", "-0.75"]).cast_with_type(&data_type)?];
+    let args = vec![DataField::new("a", data_type, false)];
+
+    let tests = vec![
+        ("sum", DataValue::Decimal128(Some(300), 38, 2)),
+        ("avg", DataValue::Decimal128(Some(1000000), 38, 6)),
+        ("min", DataValue::Decimal128(Some(-75), 5, 2)),
+        ("max", DataValue::Decimal128(Some(250), 5, 2)),
+    ];
+
+    for (func_name, expect) in tests {
+        let arena = Bump::new();
+        let func = AggregateFunctionFactory::get(func_name, vec![], args.clone())?;
+        assert_eq!(func.return_type()?, expect.data_type(), "{}", func_name);
+
+        let addr1 = arena.alloc_layout(func.state_layout());
+        func.init_state(addr1.into());
+        func.accumulate(addr1.into(), &arrays, arrays[0].len())?;
+
+        let addr2 = arena.alloc_layout(func.state_layout());
+        func.init_state(addr2.into());
+        func.merge(addr1.into(), addr2.into())?;
+
+        let result = func.merge_result(addr1.into())?;
+        assert_eq!(&expect, &result, "{}", func_name);
+    }
+
+    // No values.
+    for func_name in ["sum", "avg"] {
+        let arena = Bump::new();
+        let func = AggregateFunctionFactory::get(func_name, vec![], args.clone())?;
+        assert!(func.nullable(&DataSchema::empty())?, "{}", func_name);
+
+        let addr = arena.alloc_layout(func.state_layout());
+        func.init_state(addr.into());
+        assert!(func.merge_result(addr.into())?.is_null(), "{}", func_name);
+    }
+
+    // The sum exceeds the max precision.
+    let data_type = DataType::Decimal(38, 0);
+    let max = "99999999999999999999999999999999999999";
+    let arrays = vec![Series::new(vec![max, "1"]).cast_with_type(&data_type)?];
+    let args = vec![DataField::new("a", data_type, false)];
+    for func_name in ["sum", "avg"] {
+        let arena = Bump::new();
+        let func = AggregateFunctionFactory::get(func_name, vec![], args.clone())?;
+
+        let addr = arena.alloc_layout(func.state_layout());
+        func.init_state(addr.into());
+        let error = func
+            .accumulate(addr.into(), &arrays, arrays[0].len())
+            .unwrap_err();
+        assert_eq!(
+            error.code(),
+            ErrorCode::Overflow("").code(),
+            "{}",
+            func_name
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_function_state_serialization() -> Result<()> {
+    // Partial states are serialized by the partial aggregator and merged by the final one.
+    let arrays = vec![
+        Series::new((1..=1000).map(|v| v as f64).collect::<Vec<_>>()),
+        Series::new((1001..=2000).map(|v| v as f64).collect::<Vec<_>>()),
+    ];
+    let args = vec![DataField::new("a", DataType::Float64, false)];
+
+    let tests = vec![
+        ("var_pop", vec![], 333333.25, 0.000001),
+        ("median", vec![], 1000.5, 0.01),
+        (
+            "quantile",
+            vec![DataValue::Float64(Some(0.9))],
+            1800.5,
+            0.01,
+        ),
+        ("uniqHLL", vec![], 2000.0, 0.05),
+    ];
+
+    for (func_name, params, expect, tolerance) in tests {
+        let arena = Bump::new();
+        let func = AggregateFunctionFactory::get(func_name, params, args.clone())?;
+
+        let place = arena.alloc_layout(func.state_layout());
+        func.init_state(place.into());
+
+        for array in arrays.iter() {
+            let partial = arena.alloc_layout(func.state_layout());
+            func.init_state(partial.into());
+            func.accumulate(partial.into(), &[array.clone()], array.len())?;
+
+            let mut buffer = BytesMut::new();
+            func.serialize(partial.into(), &mut buffer)?;
+
+            let deserialized = arena.alloc_layout(func.state_layout());
+            func.init_state(deserialized.into());
+            func.deserialize(deserialized.into(), &mut buffer.as_ref())?;
+            func.merge(place.into(), deserialized.into())?;
+        }
+
+        let result = match func.merge_result(place.into())? {
+            DataValue::Float64(Some(v)) => v,
+            DataValue::UInt64(Some(v)) => v as f64,
+            other => panic!("{}: unexpected result {:?}", func_name, other),
+        };
+        let error = ((result - expect) / expect).abs();
+        assert!(
+            error <= tolerance,
+            "{}: {} vs {}",
+            func_name,
+            result,
+            expect
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_tdigest_non_finite_values() -> Result<()> {
+    let mut digest = TDigest::default();
+    for value in [
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+        1.0,
+        3.0,
+    ] {
+        digest.add(value);
+    }
+
+    // A state from another node carrying non-finite centroids.
+    let mut buffer = BytesMut::new();
+    buffer.write_uvarint(3)?;
+    for (mean, weight) in [(f64::NAN, 1.0), (f64::INFINITY, 2.0), (2.0, 1.0)] {
+        mean.serialize_to_buf(&mut buffer)?;
+        weight.serialize_to_buf(&mut buffer)?;
+    }
+    let mut other = TDigest::default();
+    other.deserialize(&mut buffer.as_ref())?;
+
+    digest.merge(&other);
+    assert_eq!(digest.quantile(0.5), Some(2.0));
+    Ok(())
+}
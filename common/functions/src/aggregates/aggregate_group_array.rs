@@ -0,0 +1,177 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+
+use bytes::BytesMut;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+pub struct AggregateGroupArrayState {
+    values: Vec<DataValue>,
+}
+
+impl AggregateGroupArrayState {
+    #[inline(always)]
+    fn add(&mut self, value: DataValue, max_size: Option<usize>) {
+        if value.is_null() {
+            return;
+        }
+        if let Some(max_size) = max_size {
+            if self.values.len() >= max_size {
+                return;
+            }
+        }
+        self.values.push(value);
+    }
+}
+
+/// groupArray(x) or groupArray(max_size)(x) collects the non-NULL values of x into a list.
+#[derive(Clone)]
+pub struct AggregateGroupArrayFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    max_size: Option<usize>,
+}
+
+impl AggregateGroupArrayFunction {
+    pub fn try_create(
+        display_name: &str,
+        params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        assert_variadic_arguments(display_name, params.len(), (0, 1))?;
+
+        let max_size = match params.get(0) {
+            Some(param) => match param.as_u64()? {
+                0 => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "The max size of {} must be greater than 0",
+                        display_name
+                    )))
+                }
+                v => Some(v as usize),
+            },
+            None => None,
+        };
+
+        Ok(Arc::new(AggregateGroupArrayFunction {
+            display_name: display_name.to_string(),
+            arguments,
+            max_size,
+        }))
+    }
+}
+
+impl AggregateFunction for AggregateGroupArrayFunction {
+    fn name(&self) -> &str {
+        "AggregateGroupArrayFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::List(Box::new(DataField::new(
+            "item",
+            self.arguments[0].data_type().clone(),
+            true,
+        ))))
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateGroupArrayState { values: vec![] });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateGroupArrayState>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], input_rows: usize) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        for row in 0..input_rows {
+            state.add(arrays[0].try_get(row)?, self.max_size);
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        for (row, place) in places.iter().enumerate() {
+            let place = place.next(offset);
+            let state = place.get::<AggregateGroupArrayState>();
+            state.add(arrays[0].try_get(row)?, self.max_size);
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        writer.write_uvarint(state.values.len() as u64)?;
+        for value in state.values.iter() {
+            value.serialize_to_buf(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        let size = reader.read_uvarint()?;
+        state.values.clear();
+        state.values.reserve(size as usize);
+        for _i in 0..size {
+            state.values.push(DataValue::deserialize(reader)?);
+        }
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        let rhs = rhs.get::<AggregateGroupArrayState>();
+        for value in rhs.values.iter() {
+            state.add(value.clone(), self.max_size);
+        }
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateGroupArrayState>();
+        Ok(DataValue::List(
+            Some(state.values.clone()),
+            self.arguments[0].data_type().clone(),
+        ))
+    }
+}
+
+impl fmt::Display for AggregateGroupArrayFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
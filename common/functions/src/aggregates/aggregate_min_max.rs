@@ -52,6 +52,13 @@ struct Utf8State {
     pub value: Option<String>,
 }
 
+/// The precision and scale are taken from the first batch as the state has no type info.
+struct DecimalState {
+    pub value: Option<i128>,
+    pub precision: usize,
+    pub scale: usize,
+}
+
 impl<T> NumericState<T>
 where
     T: DFNumericType,
@@ -203,6 +210,95 @@ impl AggregateMinMaxState for Utf8State {
     }
 }
 
+impl DecimalState {
+    #[inline]
+    fn merge_value(&mut self, other: i128, is_min: bool) {
+        match self.value {
+            Some(a) if (is_min && a <= other) || (!is_min && a >= other) => {}
+            _ => self.value = Some(other),
+        }
+    }
+
+    fn set_type(&mut self, data_type: &DataType) {
+        if let DataType::Decimal(precision, scale) = data_type {
+            self.precision = *precision;
+            self.scale = *scale;
+        }
+    }
+}
+
+impl AggregateMinMaxState for DecimalState {
+    fn default() -> Self {
+        Self {
+            value: None,
+            precision: MAX_DECIMAL_PRECISION,
+            scale: 0,
+        }
+    }
+
+    fn add_keys(
+        places: &[StateAddr],
+        offset: usize,
+        series: &Series,
+        _rows: usize,
+        is_min: bool,
+    ) -> Result<()> {
+        let data_type = series.data_type();
+        let array = series.decimal()?;
+        array
+            .downcast_iter()
+            .zip(places.iter())
+            .for_each(|(x, place)| {
+                if let Some(x) = x {
+                    let place = place.next(offset);
+                    let state = place.get::<Self>();
+                    state.set_type(&data_type);
+                    state.merge_value(*x, is_min);
+                }
+            });
+        Ok(())
+    }
+
+    fn add_batch(&mut self, series: &Series, is_min: bool) -> Result<()> {
+        self.set_type(&series.data_type());
+        let c = if is_min { series.min() } else { series.max() }?;
+        if let DataValue::Decimal128(Some(other), _, _) = c {
+            self.merge_value(other, is_min);
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self, is_min: bool) -> Result<()> {
+        if let Some(other) = rhs.value {
+            self.precision = rhs.precision;
+            self.scale = rhs.scale;
+            self.merge_value(other, is_min);
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, writer: &mut BytesMut) -> Result<()> {
+        self.value.serialize_to_buf(writer)?;
+        (self.precision as u64).serialize_to_buf(writer)?;
+        (self.scale as u64).serialize_to_buf(writer)
+    }
+
+    fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        self.value = Option::<i128>::deserialize(reader)?;
+        self.precision = u64::deserialize(reader)? as usize;
+        self.scale = u64::deserialize(reader)? as usize;
+        Ok(())
+    }
+
+    fn merge_result(&mut self) -> Result<DataValue> {
+        Ok(DataValue::Decimal128(
+            self.value,
+            self.precision,
+            self.scale,
+        ))
+    }
+}
+
 #[derive(Clone)]
 pub struct AggregateMinMaxFunction<T> {
     display_name: String,
@@ -334,6 +430,20 @@ pub fn try_create_aggregate_minmax_function(
     let data_type = arguments[0].data_type();
 
     dispatch_numeric_types! {creator, data_type.clone(), is_min, display_name, arguments}
+    if is_decimal(data_type) {
+        if is_min {
+            return AggregateMinMaxFunction::<DecimalState>::try_create_min(
+                display_name,
+                arguments,
+            );
+        } else {
+            return AggregateMinMaxFunction::<DecimalState>::try_create_max(
+                display_name,
+                arguments,
+            );
+        }
+    }
+
     if data_type == &DataType::Utf8 {
         if is_min {
             return AggregateMinMaxFunction::<Utf8State>::try_create_min(display_name, arguments);
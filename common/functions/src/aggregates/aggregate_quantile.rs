@@ -0,0 +1,299 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+const TDIGEST_COMPRESSION: f64 = 100.0;
+const TDIGEST_MAX_UNMERGED: usize = 512;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Centroid {
+    pub mean: f64,
+    pub weight: f64,
+}
+
+impl Centroid {
+    fn is_valid(&self) -> bool {
+        self.mean.is_finite() && self.weight.is_finite() && self.weight > 0.0
+    }
+}
+
+/// A merging t-digest: incoming values are buffered and periodically compressed into
+/// centroids whose size is bounded by `q * (1 - q)`, so the tails stay accurate.
+#[derive(Default)]
+pub struct TDigest {
+    centroids: Vec<Centroid>,
+    unmerged: Vec<Centroid>,
+}
+
+impl TDigest {
+    #[inline(always)]
+    pub fn add(&mut self, value: f64) {
+        // Merging infinite centroids would make NaN means.
+        if !value.is_finite() {
+            return;
+        }
+        self.unmerged.push(Centroid {
+            mean: value,
+            weight: 1.0,
+        });
+        if self.unmerged.len() >= TDIGEST_MAX_UNMERGED {
+            self.compress();
+        }
+    }
+
+    pub fn merge(&mut self, other: &TDigest) {
+        let centroids = other.centroids.iter().chain(other.unmerged.iter());
+        self.unmerged
+            .extend(centroids.filter(|centroid| centroid.is_valid()));
+        self.compress();
+    }
+
+    pub fn compress(&mut self) {
+        if self.unmerged.is_empty() {
+            return;
+        }
+
+        let mut all = std::mem::take(&mut self.centroids);
+        all.append(&mut self.unmerged);
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+        let mut centroids: Vec<Centroid> = Vec::with_capacity(all.len());
+        let mut current = all[0];
+        let mut weight_so_far = 0.0;
+
+        for next in all.into_iter().skip(1) {
+            let q = (weight_so_far + (current.weight + next.weight) / 2.0) / total;
+            let limit = 4.0 * total * q * (1.0 - q) / TDIGEST_COMPRESSION;
+
+            if current.weight + next.weight <= limit.max(1.0) {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_so_far += current.weight;
+                centroids.push(current);
+                current = next;
+            }
+        }
+
+        centroids.push(current);
+        self.centroids = centroids;
+    }
+
+    /// Returns the estimated value at `level`, interpolating between the centers of the
+    /// neighbouring centroids. The digest must be compressed before.
+    pub fn quantile(&self, level: f64) -> Option<f64> {
+        let centroids = &self.centroids;
+        match centroids.len() {
+            0 => return None,
+            1 => return Some(centroids[0].mean),
+            _ => {}
+        }
+
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        let rank = level * total;
+
+        let mut cumulative = 0.0;
+        let mut prev_center = centroids[0].weight / 2.0;
+        if rank <= prev_center {
+            return Some(centroids[0].mean);
+        }
+
+        for (index, centroid) in centroids.iter().enumerate() {
+            let center = cumulative + centroid.weight / 2.0;
+            if index > 0 && rank <= center {
+                let prev = &centroids[index - 1];
+                let ratio = (rank - prev_center) / (center - prev_center);
+                return Some(prev.mean + (centroid.mean - prev.mean) * ratio);
+            }
+            prev_center = center;
+            cumulative += centroid.weight;
+        }
+
+        centroids.last().map(|c| c.mean)
+    }
+
+    pub fn serialize(&self, writer: &mut BytesMut) -> Result<()> {
+        let size = self.centroids.len() + self.unmerged.len();
+        writer.write_uvarint(size as u64)?;
+        for centroid in self.centroids.iter().chain(self.unmerged.iter()) {
+            centroid.mean.serialize_to_buf(writer)?;
+            centroid.weight.serialize_to_buf(writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        let size = reader.read_uvarint()? as usize;
+        self.centroids.clear();
+        self.unmerged.clear();
+        self.unmerged.reserve(size);
+        for _i in 0..size {
+            let mean = f64::deserialize(reader)?;
+            let weight = f64::deserialize(reader)?;
+            // The state may come from another node, keep it from poisoning the digest.
+            let centroid = Centroid { mean, weight };
+            if centroid.is_valid() {
+                self.unmerged.push(centroid);
+            }
+        }
+        self.compress();
+        Ok(())
+    }
+}
+
+/// quantile(level)(x) estimates the quantile of x with a t-digest, level defaults to 0.5.
+/// median(x) is the alias of quantile(0.5)(x).
+#[derive(Clone)]
+pub struct AggregateQuantileFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    level: f64,
+}
+
+impl AggregateQuantileFunction {
+    pub fn try_create(
+        display_name: &str,
+        params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        assert_variadic_arguments(display_name, params.len(), (0, 1))?;
+
+        if !is_numeric(arguments[0].data_type()) {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "{} does not support type '{:?}'",
+                display_name,
+                arguments[0].data_type()
+            )));
+        }
+
+        let level = match params.get(0) {
+            Some(param) => param.as_f64()?,
+            None => 0.5,
+        };
+        if !(0.0..=1.0).contains(&level) {
+            return Err(ErrorCode::BadArguments(format!(
+                "The level of {} must be between 0 and 1, but got: {}",
+                display_name, level
+            )));
+        }
+
+        Ok(Arc::new(AggregateQuantileFunction {
+            display_name: display_name.to_string(),
+            arguments,
+            level,
+        }))
+    }
+
+    pub fn try_create_median(
+        display_name: &str,
+        params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_variadic_arguments(display_name, params.len(), (0, 0))?;
+        Self::try_create(display_name, params, arguments)
+    }
+}
+
+impl AggregateFunction for AggregateQuantileFunction {
+    fn name(&self) -> &str {
+        "AggregateQuantileFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(TDigest::default);
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<TDigest>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let state = place.get::<TDigest>();
+        let values = arrays[0].cast_with_type(&DataType::Float64)?;
+        for value in values.f64()?.downcast_iter().flatten() {
+            state.add(*value);
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let values = arrays[0].cast_with_type(&DataType::Float64)?;
+        for (value, place) in values.f64()?.downcast_iter().zip(places.iter()) {
+            if let Some(value) = value {
+                let place = place.next(offset);
+                place.get::<TDigest>().add(*value);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<TDigest>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<TDigest>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<TDigest>();
+        let rhs = rhs.get::<TDigest>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<TDigest>();
+        state.compress();
+        Ok(DataValue::Float64(state.quantile(self.level)))
+    }
+}
+
+impl fmt::Display for AggregateQuantileFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
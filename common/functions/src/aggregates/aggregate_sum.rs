@@ -227,6 +227,121 @@ where
     }
 }
 
+/// Sum of decimals is exact, the result keeps the scale and widens to the max precision.
+#[derive(Clone)]
+pub struct AggregateDecimalSumFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    scale: usize,
+}
+
+impl AggregateDecimalSumFunction {
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        scale: usize,
+    ) -> Result<AggregateFunctionRef> {
+        Ok(Arc::new(Self {
+            display_name: display_name.to_owned(),
+            arguments,
+            scale,
+        }))
+    }
+
+    fn add(&self, place: StateAddr, value: i128) -> Result<()> {
+        let state = place.get::<AggregateSumState<i128>>();
+        state.value = Some(match state.value {
+            None => value,
+            Some(sum) => sum
+                .checked_add(value)
+                .and_then(|sum| check_decimal_precision(sum, MAX_DECIMAL_PRECISION).ok())
+                .ok_or_else(|| {
+                    ErrorCode::Overflow(format!("Decimal overflow in {}", self.display_name))
+                })?,
+        });
+        Ok(())
+    }
+}
+
+impl AggregateFunction for AggregateDecimalSumFunction {
+    fn name(&self) -> &str {
+        "AggregateDecimalSumFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Decimal(MAX_DECIMAL_PRECISION, self.scale))
+    }
+
+    // The sum of no values is NULL.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateSumState::<i128> { value: None });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateSumState<i128>>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        if let DataValue::Decimal128(Some(sum), _, _) = arrays[0].sum()? {
+            self.add(place, sum)?;
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let array = arrays[0].decimal()?;
+        for (value, place) in array.downcast_iter().zip(places.iter()) {
+            if let Some(value) = value {
+                self.add(place.next(offset), *value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateSumState<i128>>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateSumState<i128>>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let rhs = rhs.get::<AggregateSumState<i128>>();
+        if let Some(s) = rhs.value {
+            self.add(place, s)?;
+        }
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateSumState<i128>>();
+        Ok(DataValue::Decimal128(
+            state.value,
+            MAX_DECIMAL_PRECISION,
+            self.scale,
+        ))
+    }
+}
+
+impl fmt::Display for AggregateDecimalSumFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
 macro_rules! creator {
     ($T: ident, $data_type: expr, $display_name: expr, $arguments: expr) => {
         if $T::data_type() == $data_type {
@@ -248,6 +363,11 @@ pub fn try_create_aggregate_sum_function(
     let data_type = arguments[0].data_type();
     dispatch_numeric_types! {creator, data_type.clone(), display_name, arguments}
 
+    if let DataType::Decimal(_, scale) = data_type {
+        let scale = *scale;
+        return AggregateDecimalSumFunction::try_create(display_name, arguments, scale);
+    }
+
     Err(ErrorCode::BadDataValueType(format!(
         "AggregateSumFunction does not support type '{:?}'",
         data_type
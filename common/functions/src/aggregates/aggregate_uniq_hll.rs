@@ -0,0 +1,200 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::io::Read;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+const HLL_PRECISION: u32 = 12;
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// HyperLogLog sketch with 2^12 one-byte registers, the standard error is about 1.6%.
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        HyperLogLog {
+            registers: vec![0; HLL_REGISTERS],
+        }
+    }
+
+    #[inline(always)]
+    pub fn add_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+        let rest = hash << HLL_PRECISION;
+        let rank = (rest.leading_zeros() + 1).min(64 - HLL_PRECISION + 1) as u8;
+        if self.registers[index] < rank {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (lhs, rhs) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *lhs < *rhs {
+                *lhs = *rhs;
+            }
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        let m = HLL_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let mut sum = 0.0;
+        let mut zeros = 0;
+        for register in self.registers.iter() {
+            sum += 1.0 / (1u64 << *register) as f64;
+            if *register == 0 {
+                zeros += 1;
+            }
+        }
+
+        let estimate = alpha * m * m / sum;
+        // Small range correction, the 64-bit hash makes the large range correction unnecessary.
+        if estimate <= 2.5 * m && zeros > 0 {
+            return (m * (m / zeros as f64).ln()).round() as u64;
+        }
+        estimate.round() as u64
+    }
+
+    pub fn serialize(&self, writer: &mut BytesMut) -> Result<()> {
+        writer.put_slice(&self.registers);
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        reader.read_exact(&mut self.registers)?;
+        Ok(())
+    }
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// uniqHLL(x) approximately counts the distinct non-NULL values of x with a HyperLogLog sketch.
+#[derive(Clone)]
+pub struct AggregateUniqHLLFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+}
+
+impl AggregateUniqHLLFunction {
+    pub fn try_create(
+        display_name: &str,
+        _params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+
+        Ok(Arc::new(AggregateUniqHLLFunction {
+            display_name: display_name.to_string(),
+            arguments,
+        }))
+    }
+
+    fn hashes(series: &Series) -> Result<DFUInt64Array> {
+        series.vec_hash(DFHasher::SipHasher(DefaultHasher::new()))
+    }
+}
+
+impl AggregateFunction for AggregateUniqHLLFunction {
+    fn name(&self) -> &str {
+        "AggregateUniqHLLFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(HyperLogLog::new);
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<HyperLogLog>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let state = place.get::<HyperLogLog>();
+        let hashes = Self::hashes(&arrays[0])?;
+        for hash in hashes.downcast_iter().flatten() {
+            state.add_hash(*hash);
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let hashes = Self::hashes(&arrays[0])?;
+        for (hash, place) in hashes.downcast_iter().zip(places.iter()) {
+            if let Some(hash) = hash {
+                let place = place.next(offset);
+                place.get::<HyperLogLog>().add_hash(*hash);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<HyperLogLog>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<HyperLogLog>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<HyperLogLog>();
+        let rhs = rhs.get::<HyperLogLog>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<HyperLogLog>();
+        Ok(DataValue::UInt64(Some(state.count())))
+    }
+}
+
+impl fmt::Display for AggregateUniqHLLFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
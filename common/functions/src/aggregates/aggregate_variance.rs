@@ -0,0 +1,203 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VarianceKind {
+    VarPop,
+    VarSamp,
+    StddevPop,
+    StddevSamp,
+}
+
+/// Welford's online algorithm, states of different blocks are merged by Chan's formula.
+#[derive(Default)]
+pub struct AggregateVarianceState {
+    pub count: u64,
+    pub mean: f64,
+    pub m2: f64,
+}
+
+impl AggregateVarianceState {
+    #[inline(always)]
+    pub fn add(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    #[inline(always)]
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let factor = self.count as f64 * other.count as f64 / count as f64;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * factor;
+        self.count = count;
+    }
+
+    pub fn serialize(&self, writer: &mut BytesMut) -> Result<()> {
+        self.count.serialize_to_buf(writer)?;
+        self.mean.serialize_to_buf(writer)?;
+        self.m2.serialize_to_buf(writer)
+    }
+
+    pub fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        self.count = u64::deserialize(reader)?;
+        self.mean = f64::deserialize(reader)?;
+        self.m2 = f64::deserialize(reader)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateVarianceFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    kind: VarianceKind,
+}
+
+impl AggregateVarianceFunction {
+    pub fn try_create(
+        kind: VarianceKind,
+        display_name: &str,
+        _params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+
+        let data_type = arguments[0].data_type();
+        if !is_numeric(data_type) {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "{} does not support type '{:?}'",
+                display_name, data_type
+            )));
+        }
+
+        Ok(Arc::new(AggregateVarianceFunction {
+            display_name: display_name.to_string(),
+            arguments,
+            kind,
+        }))
+    }
+}
+
+impl AggregateFunction for AggregateVarianceFunction {
+    fn name(&self) -> &str {
+        "AggregateVarianceFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(AggregateVarianceState::default);
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateVarianceState>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let state = place.get::<AggregateVarianceState>();
+        let series = arrays[0].cast_with_type(&DataType::Float64)?;
+        for value in series.f64()?.downcast_iter().flatten() {
+            state.add(*value);
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let series = arrays[0].cast_with_type(&DataType::Float64)?;
+        let array = series.f64()?;
+        for (value, place) in array.downcast_iter().zip(places.iter()) {
+            if let Some(value) = value {
+                let place = place.next(offset);
+                place.get::<AggregateVarianceState>().add(*value);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateVarianceState>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateVarianceState>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateVarianceState>();
+        let rhs = rhs.get::<AggregateVarianceState>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateVarianceState>();
+        let variance = match self.kind {
+            VarianceKind::VarPop | VarianceKind::StddevPop if state.count > 0 => {
+                state.m2 / state.count as f64
+            }
+            VarianceKind::VarSamp | VarianceKind::StddevSamp if state.count > 1 => {
+                state.m2 / (state.count - 1) as f64
+            }
+            _ => return Ok(DataValue::Float64(None)),
+        };
+
+        match self.kind {
+            VarianceKind::StddevPop | VarianceKind::StddevSamp => {
+                Ok(DataValue::Float64(Some(variance.sqrt())))
+            }
+            _ => Ok(DataValue::Float64(Some(variance))),
+        }
+    }
+}
+
+impl fmt::Display for AggregateVarianceFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -22,8 +22,15 @@ use super::aggregate_window_funnel::try_create_aggregate_window_funnel_function;
 use crate::aggregates::aggregate_function_factory::FactoryCombinatorFuncRef;
 use crate::aggregates::aggregate_function_factory::FactoryFuncRef;
 use crate::aggregates::AggregateCountFunction;
+use crate::aggregates::AggregateCovarianceFunction;
 use crate::aggregates::AggregateDistinctCombinator;
+use crate::aggregates::AggregateGroupArrayFunction;
 use crate::aggregates::AggregateIfCombinator;
+use crate::aggregates::AggregateQuantileFunction;
+use crate::aggregates::AggregateUniqHLLFunction;
+use crate::aggregates::AggregateVarianceFunction;
+use crate::aggregates::CovarianceKind;
+use crate::aggregates::VarianceKind;
 
 pub struct Aggregators;
 
@@ -54,6 +61,71 @@ impl Aggregators {
         );
 
         map.insert("uniq".into(), AggregateDistinctCombinator::try_create_uniq);
+        map.insert("groupArray".into(), AggregateGroupArrayFunction::try_create);
+
+        map.insert("var_pop".into(), |display_name, params, arguments| {
+            AggregateVarianceFunction::try_create(
+                VarianceKind::VarPop,
+                display_name,
+                params,
+                arguments,
+            )
+        });
+        map.insert("var_samp".into(), |display_name, params, arguments| {
+            AggregateVarianceFunction::try_create(
+                VarianceKind::VarSamp,
+                display_name,
+                params,
+                arguments,
+            )
+        });
+        map.insert("stddev_pop".into(), |display_name, params, arguments| {
+            AggregateVarianceFunction::try_create(
+                VarianceKind::StddevPop,
+                display_name,
+                params,
+                arguments,
+            )
+        });
+        map.insert("stddev_samp".into(), |display_name, params, arguments| {
+            AggregateVarianceFunction::try_create(
+                VarianceKind::StddevSamp,
+                display_name,
+                params,
+                arguments,
+            )
+        });
+        map.insert("covar_pop".into(), |display_name, params, arguments| {
+            AggregateCovarianceFunction::try_create(
+                CovarianceKind::CovarPop,
+                display_name,
+                params,
+                arguments,
+            )
+        });
+        map.insert("covar_samp".into(), |display_name, params, arguments| {
+            AggregateCovarianceFunction::try_create(
+                CovarianceKind::CovarSamp,
+                display_name,
+                params,
+                arguments,
+            )
+        });
+        map.insert("corr".into(), |display_name, params, arguments| {
+            AggregateCovarianceFunction::try_create(
+                CovarianceKind::Corr,
+                display_name,
+                params,
+                arguments,
+            )
+        });
+
+        map.insert("quantile".into(), AggregateQuantileFunction::try_create);
+        map.insert(
+            "median".into(),
+            AggregateQuantileFunction::try_create_median,
+        );
+        map.insert("uniqHLL".into(), AggregateUniqHLLFunction::try_create);
         Ok(())
     }
 
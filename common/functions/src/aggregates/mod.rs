@@ -22,10 +22,15 @@ mod aggregate_avg;
 mod aggregate_combinator_distinct;
 mod aggregate_combinator_if;
 mod aggregate_count;
+mod aggregate_covariance;
 mod aggregate_function;
 mod aggregate_function_factory;
 mod aggregate_function_state;
+mod aggregate_group_array;
 mod aggregate_min_max;
+mod aggregate_quantile;
+mod aggregate_uniq_hll;
+mod aggregate_variance;
 mod aggregate_window_funnel;
 
 // mod aggregate_min_max;
@@ -41,12 +46,21 @@ pub use aggregate_avg::AggregateAvgFunction;
 pub use aggregate_combinator_distinct::AggregateDistinctCombinator;
 pub use aggregate_combinator_if::AggregateIfCombinator;
 pub use aggregate_count::AggregateCountFunction;
+pub use aggregate_covariance::AggregateCovarianceFunction;
+pub use aggregate_covariance::CovarianceKind;
 pub use aggregate_function::AggregateFunction;
 pub use aggregate_function::AggregateFunctionRef;
 pub use aggregate_function_factory::AggregateFunctionFactory;
 pub use aggregate_function_state::get_layout_offsets;
 pub use aggregate_function_state::StateAddr;
+pub use aggregate_group_array::AggregateGroupArrayFunction;
 pub use aggregate_min_max::AggregateMinMaxFunction;
+pub use aggregate_quantile::AggregateQuantileFunction;
+pub use aggregate_quantile::TDigest;
 pub use aggregate_sum::AggregateSumFunction;
+pub use aggregate_uniq_hll::AggregateUniqHLLFunction;
+pub use aggregate_uniq_hll::HyperLogLog;
+pub use aggregate_variance::AggregateVarianceFunction;
+pub use aggregate_variance::VarianceKind;
 pub use aggregator::Aggregators;
 pub use aggregator_common::*;
@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#![feature(total_cmp)]
+
 pub mod aggregates;
 pub mod scalars;
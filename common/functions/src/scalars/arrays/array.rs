@@ -0,0 +1,92 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::ArrayCreateFunction;
+use crate::scalars::ArrayElementFunction;
+use crate::scalars::ArrayHasFunction;
+use crate::scalars::ArrayIndexOfFunction;
+use crate::scalars::ArrayJoinFunction;
+use crate::scalars::ArrayLengthFunction;
+use crate::scalars::FactoryFuncRef;
+
+#[derive(Clone)]
+pub struct ArrayFunction;
+
+impl ArrayFunction {
+    pub fn register(map: FactoryFuncRef) -> Result<()> {
+        let mut map = map.write();
+        map.insert("array".into(), ArrayCreateFunction::try_create);
+        map.insert("length".into(), ArrayLengthFunction::try_create);
+        map.insert("has".into(), ArrayHasFunction::try_create);
+        map.insert("indexOf".into(), ArrayIndexOfFunction::try_create);
+        map.insert("arrayElement".into(), ArrayElementFunction::try_create);
+        map.insert("arrayJoin".into(), ArrayJoinFunction::try_create);
+
+        Ok(())
+    }
+}
+
+/// Returns the item type of the list type, or an error if the argument is not a list.
+pub fn list_item_type(display_name: &str, data_type: &DataType) -> Result<DataType> {
+    match data_type {
+        DataType::List(field) => Ok(field.data_type().clone()),
+        other => Err(ErrorCode::IllegalDataType(format!(
+            "{} expects an Array argument, but got {}",
+            display_name, other
+        ))),
+    }
+}
+
+/// Evaluate a function row by row on data values.
+/// If all the arguments are constant, the result is a constant column.
+pub fn eval_by_rows<F>(
+    columns: &[DataColumn],
+    input_rows: usize,
+    return_type: &DataType,
+    f: F,
+) -> Result<DataColumn>
+where
+    F: Fn(&[DataValue]) -> Result<DataValue>,
+{
+    if columns
+        .iter()
+        .all(|c| matches!(c, DataColumn::Constant(_, _)))
+    {
+        let row = columns
+            .iter()
+            .map(|c| c.try_get(0))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(DataColumn::Constant(f(&row)?, input_rows));
+    }
+
+    let values = columns
+        .iter()
+        .map(|c| c.to_values())
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut row = Vec::with_capacity(columns.len());
+    let mut results = Vec::with_capacity(input_rows);
+    for i in 0..input_rows {
+        row.clear();
+        row.extend(values.iter().map(|v| v[i].clone()));
+        results.push(f(&row)?);
+    }
+
+    let series = DataValue::try_into_data_array(&results, return_type)?;
+    Ok(DataColumn::Array(series))
+}
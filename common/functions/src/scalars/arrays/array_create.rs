@@ -0,0 +1,77 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::arrays::array::eval_by_rows;
+use crate::scalars::Function;
+
+/// array(x1, x2, ...) creates an array from the arguments, all the arguments are
+/// casted to their common super type.
+#[derive(Clone)]
+pub struct ArrayCreateFunction {
+    display_name: String,
+}
+
+impl ArrayCreateFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayCreateFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+}
+
+impl Function for ArrayCreateFunction {
+    fn name(&self) -> &str {
+        "ArrayCreateFunction"
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((1, usize::MAX))
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        let item_type = aggregate_types(args)?;
+        Ok(DataType::List(Box::new(DataField::new(
+            "item", item_type, true,
+        ))))
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let args = columns.iter().map(|c| c.data_type()).collect::<Vec<_>>();
+        let item_type = aggregate_types(&args)?;
+        let columns = columns
+            .iter()
+            .map(|c| c.cast_with_type(&item_type))
+            .collect::<Result<Vec<_>>>()?;
+
+        let return_type = self.return_type(&args)?;
+        eval_by_rows(&columns, input_rows, &return_type, |row| {
+            Ok(DataValue::List(Some(row.to_vec()), item_type.clone()))
+        })
+    }
+}
+
+impl fmt::Display for ArrayCreateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::arrays::array::eval_by_rows;
+use crate::scalars::arrays::array::list_item_type;
+use crate::scalars::Function;
+
+/// arrayElement(arr, n) returns the n-th item of the array, it is also what arr[n] is
+/// parsed to. Indexes are 1-based, negative indexes count from the end of the array.
+/// NULL is returned if the index is out of bounds.
+#[derive(Clone)]
+pub struct ArrayElementFunction {
+    display_name: String,
+}
+
+impl ArrayElementFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayElementFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+}
+
+impl Function for ArrayElementFunction {
+    fn name(&self) -> &str {
+        "ArrayElementFunction"
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        if !is_integer(&args[1]) {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "{} expects an integer index, but got {}",
+                self.display_name, args[1]
+            )));
+        }
+        list_item_type(&self.display_name, &args[0])
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let args = [columns[0].data_type(), columns[1].data_type()];
+        let item_type = self.return_type(&args)?;
+        let columns = [
+            columns[0].clone(),
+            columns[1].cast_with_type(&DataType::Int64)?,
+        ];
+
+        eval_by_rows(&columns, input_rows, &item_type, |row| {
+            let null = DataValue::from(&item_type);
+            match (&row[0], &row[1]) {
+                (DataValue::List(Some(items), _), DataValue::Int64(Some(n))) => {
+                    let len = items.len() as i64;
+                    let index = match *n {
+                        n if n > 0 && n <= len => n - 1,
+                        // -n overflows for i64::MIN.
+                        n if n < 0 && n.unsigned_abs() <= len as u64 => len + n,
+                        _ => return Ok(null),
+                    };
+                    Ok(items[index as usize].clone())
+                }
+                _ => Ok(null),
+            }
+        })
+    }
+}
+
+impl fmt::Display for ArrayElementFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
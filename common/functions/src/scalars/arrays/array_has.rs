@@ -0,0 +1,75 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::arrays::array::eval_by_rows;
+use crate::scalars::arrays::array::list_item_type;
+use crate::scalars::Function;
+
+/// has(arr, x) returns true if the array contains the element x.
+#[derive(Clone)]
+pub struct ArrayHasFunction {
+    display_name: String,
+}
+
+impl ArrayHasFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayHasFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+}
+
+impl Function for ArrayHasFunction {
+    fn name(&self) -> &str {
+        "ArrayHasFunction"
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        list_item_type(&self.display_name, &args[0])?;
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let item_type = list_item_type(&self.display_name, &columns[0].data_type())?;
+        let columns = [columns[0].clone(), columns[1].cast_with_type(&item_type)?];
+
+        eval_by_rows(&columns, input_rows, &DataType::Boolean, |row| {
+            match &row[0] {
+                DataValue::List(Some(items), _) => {
+                    Ok(DataValue::Boolean(Some(items.contains(&row[1]))))
+                }
+                _ => Ok(DataValue::Boolean(None)),
+            }
+        })
+    }
+}
+
+impl fmt::Display for ArrayHasFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
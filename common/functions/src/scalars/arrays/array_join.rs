@@ -0,0 +1,96 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::arrays::array::list_item_type;
+use crate::scalars::Function;
+
+/// arrayJoin(arr) unfolds the array into rows: each input row is replicated once per array
+/// item, rows with empty or NULL arrays are dropped.
+/// The function itself returns the flattened items, the replication of the other columns is
+/// done by the expression executor with the indices from `replicate_indices`.
+#[derive(Clone)]
+pub struct ArrayJoinFunction {
+    display_name: String,
+}
+
+impl ArrayJoinFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayJoinFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn is_array_join(name: &str) -> bool {
+        name.eq_ignore_ascii_case("arrayJoin")
+    }
+
+    /// Returns the input row index of each output row.
+    pub fn replicate_indices(column: &DataColumn) -> Result<Vec<usize>> {
+        let mut indices = vec![];
+        for (row, value) in column.to_values()?.iter().enumerate() {
+            if let DataValue::List(Some(items), _) = value {
+                indices.extend(std::iter::repeat(row).take(items.len()));
+            }
+        }
+        Ok(indices)
+    }
+}
+
+impl Function for ArrayJoinFunction {
+    fn name(&self) -> &str {
+        "ArrayJoinFunction"
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        list_item_type(&self.display_name, &args[0])
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    // The result has a different number of rows from the input, it must not be folded
+    // into a constant even if the argument is constant.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, columns: &[DataColumn], _input_rows: usize) -> Result<DataColumn> {
+        let item_type = list_item_type(&self.display_name, &columns[0].data_type())?;
+        let mut items = vec![];
+        for value in columns[0].to_values()? {
+            if let DataValue::List(Some(values), _) = value {
+                items.extend(values);
+            }
+        }
+
+        let series = DataValue::try_into_data_array(&items, &item_type)?;
+        Ok(DataColumn::Array(series))
+    }
+}
+
+impl fmt::Display for ArrayJoinFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
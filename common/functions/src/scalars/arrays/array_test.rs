@@ -0,0 +1,155 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::*;
+
+fn int64_list(values: Option<Vec<i64>>) -> DataValue {
+    DataValue::List(
+        values.map(|v| v.into_iter().map(|x| DataValue::Int64(Some(x))).collect()),
+        DataType::Int64,
+    )
+}
+
+#[test]
+fn test_array_functions() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        func: Box<dyn Function>,
+        columns: Vec<DataColumn>,
+        expect: Vec<DataValue>,
+    }
+
+    let lists = DataValue::try_into_list_array(
+        &[
+            int64_list(Some(vec![1, 2, 3])),
+            int64_list(Some(vec![])),
+            int64_list(None),
+        ],
+        &DataType::Int64,
+    )?;
+
+    let tests = vec![
+        Test {
+            name: "array-passed",
+            func: ArrayCreateFunction::try_create("array")?,
+            columns: vec![
+                Series::new(vec![1i64, 2, 3]).into(),
+                DataColumn::Constant(DataValue::UInt8(Some(9)), 3),
+            ],
+            expect: vec![
+                int64_list(Some(vec![1, 9])),
+                int64_list(Some(vec![2, 9])),
+                int64_list(Some(vec![3, 9])),
+            ],
+        },
+        Test {
+            name: "length-passed",
+            func: ArrayLengthFunction::try_create("length")?,
+            columns: vec![lists.clone().into()],
+            expect: vec![
+                DataValue::UInt64(Some(3)),
+                DataValue::UInt64(Some(0)),
+                DataValue::UInt64(None),
+            ],
+        },
+        Test {
+            name: "has-passed",
+            func: ArrayHasFunction::try_create("has")?,
+            columns: vec![
+                lists.clone().into(),
+                DataColumn::Constant(DataValue::UInt8(Some(2)), 3),
+            ],
+            expect: vec![
+                DataValue::Boolean(Some(true)),
+                DataValue::Boolean(Some(false)),
+                DataValue::Boolean(None),
+            ],
+        },
+        Test {
+            name: "indexOf-passed",
+            func: ArrayIndexOfFunction::try_create("indexOf")?,
+            columns: vec![lists.clone().into(), Series::new(vec![3i64, 3, 3]).into()],
+            expect: vec![
+                DataValue::UInt64(Some(3)),
+                DataValue::UInt64(Some(0)),
+                DataValue::UInt64(None),
+            ],
+        },
+        Test {
+            name: "arrayElement-passed",
+            func: ArrayElementFunction::try_create("arrayElement")?,
+            columns: vec![lists.clone().into(), Series::new(vec![-1i64, 1, 1]).into()],
+            expect: vec![
+                DataValue::Int64(Some(3)),
+                DataValue::Int64(None),
+                DataValue::Int64(None),
+            ],
+        },
+        Test {
+            name: "arrayElement-out-of-bounds-passed",
+            func: ArrayElementFunction::try_create("arrayElement")?,
+            columns: vec![
+                lists.clone().into(),
+                DataColumn::Constant(DataValue::Int64(Some(4)), 3),
+            ],
+            expect: vec![
+                DataValue::Int64(None),
+                DataValue::Int64(None),
+                DataValue::Int64(None),
+            ],
+        },
+        Test {
+            name: "arrayElement-extreme-index-passed",
+            func: ArrayElementFunction::try_create("arrayElement")?,
+            columns: vec![
+                lists.clone().into(),
+                Series::new(vec![i64::MIN, i64::MAX, -3]).into(),
+            ],
+            expect: vec![
+                DataValue::Int64(None),
+                DataValue::Int64(None),
+                DataValue::Int64(None),
+            ],
+        },
+        Test {
+            name: "arrayJoin-passed",
+            func: ArrayJoinFunction::try_create("arrayJoin")?,
+            columns: vec![lists.clone().into()],
+            expect: vec![
+                DataValue::Int64(Some(1)),
+                DataValue::Int64(Some(2)),
+                DataValue::Int64(Some(3)),
+            ],
+        },
+    ];
+
+    for t in tests {
+        let func = t.func;
+        let args = t.columns.iter().map(|c| c.data_type()).collect::<Vec<_>>();
+
+        let v = func.eval(&t.columns, t.columns[0].len())?;
+        assert_eq!(func.return_type(&args)?, v.data_type(), "{}", t.name);
+        assert_eq!(t.expect, v.to_values()?, "{}", t.name);
+    }
+
+    assert_eq!(
+        vec![0usize, 0, 0],
+        ArrayJoinFunction::replicate_indices(&lists.into())?
+    );
+    Ok(())
+}
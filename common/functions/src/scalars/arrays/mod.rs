@@ -0,0 +1,32 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod array_test;
+
+mod array;
+mod array_create;
+mod array_element;
+mod array_has;
+mod array_index_of;
+mod array_join;
+mod array_length;
+
+pub use array::ArrayFunction;
+pub use array_create::ArrayCreateFunction;
+pub use array_element::ArrayElementFunction;
+pub use array_has::ArrayHasFunction;
+pub use array_index_of::ArrayIndexOfFunction;
+pub use array_join::ArrayJoinFunction;
+pub use array_length::ArrayLengthFunction;
@@ -22,6 +22,7 @@ use lazy_static::lazy_static;
 use unicase::UniCase;
 
 use crate::scalars::ArithmeticFunction;
+use crate::scalars::ArrayFunction;
 use crate::scalars::ComparisonFunction;
 use crate::scalars::ConditionalFunction;
 use crate::scalars::Function;
@@ -48,6 +49,7 @@ lazy_static! {
         HashesFunction::register(map.clone()).unwrap();
         ToCastFunction::register(map.clone()).unwrap();
         ConditionalFunction::register(map.clone()).unwrap();
+        ArrayFunction::register(map.clone()).unwrap();
 
         map
     };
@@ -16,6 +16,7 @@
 mod function_column_test;
 
 mod arithmetics;
+mod arrays;
 mod comparisons;
 mod conditionals;
 mod expressions;
@@ -30,6 +31,7 @@ mod strings;
 mod udfs;
 
 pub use arithmetics::*;
+pub use arrays::*;
 pub use comparisons::*;
 pub use conditionals::*;
 pub use expressions::*;
@@ -33,7 +33,7 @@ macro_rules! apply_scalar_de {
 }
 
 // primitive types and boolean
-apply_scalar_de! {u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, bool}
+apply_scalar_de! {u8, u16, u32, u64, i8, i16, i32, i64, i128, f32, f64, bool}
 
 impl BinaryDe for String {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> Result<Self> {
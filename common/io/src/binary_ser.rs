@@ -38,7 +38,7 @@ macro_rules! apply_scalar_ser {
 }
 
 // primitive types and boolean
-apply_scalar_ser! {u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, bool}
+apply_scalar_ser! {u8, u16, u32, u64, i8, i16, i32, i64, i128, f32, f64, bool}
 
 impl BinarySer for String {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
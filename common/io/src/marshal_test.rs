@@ -78,6 +78,11 @@ fn test_i64() {
     test_some::<i64>()
 }
 
+#[test]
+fn test_i128() {
+    test_some::<i128>()
+}
+
 #[test]
 fn test_f32() {
     test_some::<f32>()
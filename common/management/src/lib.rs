@@ -17,4 +17,6 @@ mod user;
 
 pub use user::user_api::UserInfo;
 pub use user::user_api::UserMgrApi;
+pub use user::user_api::UserProfile;
 pub use user::user_mgr::UserMgr;
+pub use user::user_mgr::USER_API_KEY_PREFIX;
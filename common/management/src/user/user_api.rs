@@ -13,6 +13,7 @@
 // limitations under the License.
 //
 
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
 use async_trait::async_trait;
@@ -25,6 +26,17 @@ pub struct UserInfo {
     pub name: String,
     pub password_sha256: [u8; 32],
     pub salt_sha256: [u8; 32],
+    #[serde(default)]
+    pub profile: UserProfile,
+}
+
+/// The settings applied to the sessions of a user, e.g. the limits of its queries,
+/// by setting name. They override the defaults, and a limit is the most the user can SET it to.
+#[derive(
+    serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd,
+)]
+pub struct UserProfile {
+    pub settings: BTreeMap<String, String>,
 }
 
 #[async_trait]
@@ -63,6 +75,15 @@ pub trait UserMgrApi {
     where
         V: AsRef<str> + Sync + Send;
 
+    async fn set_user_profile<V>(
+        &mut self,
+        username: V,
+        profile: UserProfile,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>>
+    where
+        V: AsRef<str> + Sync + Send;
+
     async fn drop_user<V>(&mut self, username: V, seq: Option<u64>) -> Result<()>
     where V: AsRef<str> + Send;
 }
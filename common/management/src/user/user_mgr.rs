@@ -27,6 +27,7 @@ use sha2::Digest;
 
 use crate::user::user_api::UserInfo;
 use crate::user::user_api::UserMgrApi;
+use crate::user::user_api::UserProfile;
 use crate::user::utils;
 use crate::user::utils::NewUser;
 
@@ -150,26 +151,18 @@ impl<T: KVApi + Send> UserMgrApi for UserMgr<T> {
         if new_password.is_none() && new_salt.is_none() {
             return Ok(seq);
         }
-        let partial_update = new_salt.is_none() || new_password.is_none();
-        let user_info = if partial_update {
-            let user_val_seq = self.get_user(username.as_ref(), seq).await?;
-            let user_info = user_val_seq.1;
-            UserInfo {
-                password_sha256: new_password.map_or(user_info.password_sha256, |v| {
-                    sha2::Sha256::digest(v.as_ref().as_bytes()).into()
-                }),
-                salt_sha256: new_salt.map_or(user_info.salt_sha256, |v| {
-                    sha2::Sha256::digest(v.as_ref().as_bytes()).into()
-                }),
-                name: username.as_ref().to_string(),
-            }
-        } else {
-            NewUser::new(
-                username.as_ref(),
-                new_password.unwrap().as_ref(),
-                new_salt.unwrap().as_ref(),
-            )
-            .into()
+        // The user is read even for a full update, to keep its profile.
+        let user_val_seq = self.get_user(username.as_ref(), seq).await?;
+        let user_info = user_val_seq.1;
+        let user_info = UserInfo {
+            password_sha256: new_password.map_or(user_info.password_sha256, |v| {
+                sha2::Sha256::digest(v.as_ref().as_bytes()).into()
+            }),
+            salt_sha256: new_salt.map_or(user_info.salt_sha256, |v| {
+                sha2::Sha256::digest(v.as_ref().as_bytes()).into()
+            }),
+            name: username.as_ref().to_string(),
+            profile: user_info.profile,
         };
 
         let value = serde_json::to_vec(&user_info)?;
@@ -192,6 +185,36 @@ impl<T: KVApi + Send> UserMgrApi for UserMgr<T> {
         }
     }
 
+    async fn set_user_profile<V: AsRef<str> + Sync + Send>(
+        &mut self,
+        username: V,
+        profile: UserProfile,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>> {
+        let (current_seq, user_info) = self.get_user(username.as_ref(), seq).await?;
+        let user_info = UserInfo {
+            profile,
+            ..user_info
+        };
+
+        let value = serde_json::to_vec(&user_info)?;
+        let key = utils::prepend(&user_info.name);
+
+        // Fails if the user is changed meanwhile, instead of overwriting the change.
+        let match_seq = MatchSeq::Exact(current_seq);
+        let res = self
+            .kv_api
+            .upsert_kv(&key, match_seq, Some(value), None)
+            .await?;
+        match res.result {
+            Some((s, _)) => Ok(Some(s)),
+            None => Err(ErrorCode::UnknownUser(format!(
+                "unknown user, or seq not match {}",
+                username.as_ref()
+            ))),
+        }
+    }
+
     async fn drop_user<V: AsRef<str> + Send>(
         &mut self,
         username: V,
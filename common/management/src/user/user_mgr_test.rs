@@ -598,17 +598,40 @@ mod update {
         let test_key = USER_API_KEY_PREFIX.to_string() + test_name;
         let test_seq = None;
 
-        // - get_kv should NOT be called
+        let old_user = NewUser::new(test_name, "old_pass", "old_salt");
+        let mut old_user_info = UserInfo::from(old_user);
+        old_user_info
+            .profile
+            .settings
+            .insert("max_result_rows".to_string(), "100".to_string());
+        let prev_value = serde_json::to_vec(&old_user_info)?;
+
+        // - get_kv should be called, to keep the profile
         // - update_kv should be called
 
         let new_pass = "new_pass";
         let new_salt = "new_salt";
         let new_user = NewUser::new(test_name, new_pass, new_salt);
 
-        let new_user_info = UserInfo::from(new_user);
+        let mut new_user_info = UserInfo::from(new_user);
+        new_user_info.profile = old_user_info.profile;
         let new_value = serde_json::to_vec(&new_user_info)?;
 
         let mut kv = MockKV::new();
+        {
+            let test_key = test_key.clone();
+            kv.expect_get_kv()
+                .with(predicate::function(move |v| v == test_key.as_str()))
+                .times(1)
+                .return_once(move |_k| {
+                    Ok(GetKVActionResult {
+                        result: Some((1, KVValue {
+                            meta: None,
+                            value: prev_value,
+                        })),
+                    })
+                });
+        }
         kv.expect_upsert_kv()
             .with(
                 predicate::function(move |v| v == test_key.as_str()),
@@ -682,32 +705,100 @@ mod update {
         let test_key = USER_API_KEY_PREFIX.to_string() + test_name;
         let test_seq = None;
 
-        // get_kv should not be called
+        // if full update, and get_kv returns None
+        // update_kv should NOT be called
         let mut kv = MockKV::new();
         let test_key = test_key.clone();
+        kv.expect_get_kv()
+            .with(predicate::function(move |v| v == test_key.as_str()))
+            .times(1)
+            .return_once(move |_k| Ok(GetKVActionResult { result: None }));
+
+        let mut user_mgr = UserMgr::new(kv);
+
+        let res = user_mgr
+            .update_user(test_name, Some("new_pass"), Some("new_salt"), test_seq)
+            .await;
+        assert_eq!(res.unwrap_err().code(), ErrorCode::UnknownUser("").code());
+        Ok(())
+    }
+}
+
+mod profile {
+    use common_metatypes::KVValue;
+
+    use super::*;
+    use crate::user::user_api::UserProfile;
+
+    #[test]
+    fn test_user_info_without_profile() -> common_exception::Result<()> {
+        // The users stored before the profiles have none.
+        let user_info = UserInfo::from(NewUser::new("name", "pass", "salt"));
+        let mut value = serde_json::to_value(&user_info)?;
+        value.as_object_mut().unwrap().remove("profile");
+
+        let decoded: UserInfo = serde_json::from_value(value)?;
+        assert_eq!(decoded, user_info);
+        assert_eq!(decoded.profile, UserProfile::default());
+        Ok(())
+    }
 
-        // upsert should be called
+    #[tokio::test]
+    async fn test_set_user_profile() -> common_exception::Result<()> {
+        let test_name = "name";
+        let test_key = USER_API_KEY_PREFIX.to_string() + test_name;
+
+        let user_info = UserInfo::from(NewUser::new(test_name, "pass", "salt"));
+        let prev_value = serde_json::to_vec(&user_info)?;
+
+        let mut profile = UserProfile::default();
+        profile
+            .settings
+            .insert("max_rows_to_read".to_string(), "1000000".to_string());
+        let new_user_info = UserInfo {
+            profile: profile.clone(),
+            ..user_info
+        };
+        let new_value = serde_json::to_vec(&new_user_info)?;
+
+        let mut kv = MockKV::new();
+        {
+            let test_key = test_key.clone();
+            kv.expect_get_kv()
+                .with(predicate::function(move |v| v == test_key.as_str()))
+                .times(1)
+                .return_once(move |_k| {
+                    Ok(GetKVActionResult {
+                        result: Some((3, KVValue {
+                            meta: None,
+                            value: prev_value,
+                        })),
+                    })
+                });
+        }
+
+        // The user must not have changed since it was read.
         kv.expect_upsert_kv()
             .with(
                 predicate::function(move |v| v == test_key.as_str()),
-                predicate::eq(MatchSeq::GE(1)),
-                predicate::always(), // a little bit relax here, as we've covered it before
+                predicate::eq(MatchSeq::Exact(3)),
+                predicate::eq(Some(new_value)),
                 predicate::eq(None),
             )
             .times(1)
-            .returning(|_u, _s, _salt, _meta| {
+            .return_once(|_, _, _, _meta| {
                 Ok(UpsertKVActionResult {
                     prev: None,
-                    result: None,
+                    result: Some((4, KVValue {
+                        meta: None,
+                        value: vec![],
+                    })),
                 })
             });
 
         let mut user_mgr = UserMgr::new(kv);
-
-        let res = user_mgr
-            .update_user(test_name, Some("new_pass"), Some("new_salt"), test_seq)
-            .await;
-        assert_eq!(res.unwrap_err().code(), ErrorCode::UnknownUser("").code());
+        let res = user_mgr.set_user_profile(test_name, profile, None).await?;
+        assert_eq!(res, Some(4));
         Ok(())
     }
 }
@@ -16,6 +16,7 @@
 use sha2::Digest;
 
 use crate::user::user_api::UserInfo;
+use crate::user::user_api::UserProfile;
 use crate::user::user_mgr::USER_API_KEY_PREFIX;
 
 pub(crate) fn prepend(v: impl AsRef<str>) -> String {
@@ -50,6 +51,7 @@ impl From<&NewUser> for UserInfo {
             name: new_user.name.clone(),
             password_sha256: sha2::Sha256::digest(new_user.password.as_bytes()).into(),
             salt_sha256: sha2::Sha256::digest(new_user.salt.as_bytes()).into(),
+            profile: UserProfile::default(),
         }
     }
 }
@@ -105,3 +105,23 @@ impl fmt::Display for Table {
 
 pub type MetaVersion = u64;
 pub type MetaId = u64;
+
+/// A point in the history of a table, as of which the table is read.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TableVersionAt {
+    Version(MetaVersion),
+    /// Milliseconds since 1970.
+    Timestamp(u64),
+    /// The version the table is at now.
+    Latest,
+}
+
+impl fmt::Display for TableVersionAt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TableVersionAt::Version(v) => write!(f, "version {}", v),
+            TableVersionAt::Timestamp(ts) => write!(f, "timestamp {}ms", ts),
+            TableVersionAt::Latest => write!(f, "the latest version"),
+        }
+    }
+}
@@ -41,10 +41,15 @@ mod plan_scan_test;
 #[cfg(test)]
 mod plan_select_test;
 #[cfg(test)]
+mod plan_statistics_test;
+#[cfg(test)]
+mod plan_window_test;
+#[cfg(test)]
 mod test;
 
 mod plan_aggregator_final;
 mod plan_aggregator_partial;
+mod plan_analyze_table;
 mod plan_broadcast;
 mod plan_builder;
 mod plan_builder_scan;
@@ -60,6 +65,7 @@ mod plan_expression_action;
 mod plan_expression_chain;
 mod plan_expression_column;
 mod plan_expression_common;
+mod plan_expression_executor;
 mod plan_expression_function;
 mod plan_expression_literal;
 mod plan_expression_rewriter;
@@ -92,9 +98,11 @@ mod plan_table_drop;
 mod plan_truncate_table;
 mod plan_use_database;
 mod plan_visitor;
+mod plan_window;
 
 pub use plan_aggregator_final::AggregatorFinalPlan;
 pub use plan_aggregator_partial::AggregatorPartialPlan;
+pub use plan_analyze_table::AnalyzeTablePlan;
 pub use plan_broadcast::BroadcastPlan;
 pub use plan_builder::PlanBuilder;
 pub use plan_builder_scan::TableScanInfo;
@@ -114,15 +122,19 @@ pub use plan_expression_chain::ExpressionChain;
 pub use plan_expression_column::col;
 pub use plan_expression_common::expand_aggregate_arg_exprs;
 pub use plan_expression_common::expand_wildcard;
+pub use plan_expression_common::expand_window_arg_exprs;
 pub use plan_expression_common::expr_as_column_expr;
 pub use plan_expression_common::extract_aliases;
 pub use plan_expression_common::find_aggregate_exprs;
+pub use plan_expression_common::find_column_exprs;
 pub use plan_expression_common::find_columns_not_satisfy_exprs;
+pub use plan_expression_common::find_window_exprs;
 pub use plan_expression_common::rebase_expr;
 pub use plan_expression_common::rebase_expr_from_input;
 pub use plan_expression_common::resolve_aliases_to_exprs;
 pub use plan_expression_common::sort_to_inner_expr;
 pub use plan_expression_common::unwrap_alias_exprs;
+pub use plan_expression_executor::ExpressionExecutor;
 pub use plan_expression_function::add;
 pub use plan_expression_function::avg;
 pub use plan_expression_function::modular;
@@ -157,7 +169,10 @@ pub use plan_show_table_create::ShowCreateTablePlan;
 pub use plan_sort::SortPlan;
 pub use plan_stage::StageKind;
 pub use plan_stage::StagePlan;
+pub use plan_statistics::ColumnStatistics;
+pub use plan_statistics::Histogram;
 pub use plan_statistics::Statistics;
+pub use plan_statistics::TableStatistics;
 pub use plan_subqueries_set::SubQueriesSetPlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableEngineType;
@@ -166,3 +181,7 @@ pub use plan_table_drop::DropTablePlan;
 pub use plan_truncate_table::TruncateTablePlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_visitor::PlanVisitor;
+pub use plan_window::WindowFrame;
+pub use plan_window::WindowFrameBound;
+pub use plan_window::WindowFrameUnits;
+pub use plan_window::WindowPlan;
@@ -41,6 +41,7 @@ use crate::ProjectionPlan;
 use crate::RewriteHelper;
 use crate::SelectPlan;
 use crate::SortPlan;
+use crate::WindowPlan;
 
 pub enum AggregateMode {
     Partial,
@@ -226,6 +227,27 @@ impl PlanBuilder {
         })))
     }
 
+    /// Apply window functions, their results are appended to the input columns.
+    pub fn window(&self, exprs: &[Expression]) -> Result<Self> {
+        let input_schema = self.plan.schema();
+        let mut fields = input_schema.fields().clone();
+        for field in RewriteHelper::exprs_to_fields(exprs, &input_schema)? {
+            if !fields.iter().any(|x| x.name() == field.name()) {
+                fields.push(DataField::new(
+                    field.name(),
+                    field.data_type().clone(),
+                    true,
+                ));
+            }
+        }
+
+        Ok(Self::from(&PlanNode::Window(WindowPlan {
+            window_exprs: exprs.to_vec(),
+            schema: DataSchemaRefExt::create(fields),
+            input: Arc::new(self.plan.clone()),
+        })))
+    }
+
     /// Apply a limit
     pub fn limit(&self, n: usize) -> Result<Self> {
         Ok(Self::from(&PlanNode::Limit(LimitPlan {
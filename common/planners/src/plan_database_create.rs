@@ -42,6 +42,9 @@ pub struct CreateDatabasePlan {
     pub db: String,
     pub engine: DatabaseEngineType,
     pub options: DatabaseOptions,
+    /// The source database of `CREATE DATABASE ... CLONE`,
+    /// every table of which is cloned into the new database.
+    pub clone_from: Option<String>,
 }
 
 impl CreateDatabasePlan {
@@ -31,6 +31,7 @@ use crate::ReadDataSourcePlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::SubQueriesSetPlan;
+use crate::WindowPlan;
 
 pub struct PlanNodeIndentFormatDisplay<'a> {
     indent: usize,
@@ -64,6 +65,7 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::Filter(plan) => write!(f, "Filter: {:?}", plan.predicate),
             PlanNode::Having(plan) => write!(f, "Having: {:?}", plan.predicate),
             PlanNode::Sort(plan) => Self::format_sort(f, plan),
+            PlanNode::Window(plan) => Self::format_window(f, plan),
             PlanNode::Limit(plan) => Self::format_limit(f, plan),
             PlanNode::SubQueryExpression(plan) => Self::format_subquery_expr(f, plan),
             PlanNode::ReadSource(plan) => Self::format_read_source(f, plan),
@@ -183,6 +185,10 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         fmt::Result::Ok(())
     }
 
+    fn format_window(f: &mut Formatter, plan: &WindowPlan) -> fmt::Result {
+        write!(f, "Window: [{:?}]", plan.window_exprs)
+    }
+
     fn format_limit(f: &mut Formatter, plan: &LimitPlan) -> fmt::Result {
         match (plan.n, plan.offset) {
             (Some(n), 0) => write!(f, "Limit: {}", n),
@@ -218,7 +224,11 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         write!(f, "Create database {:},", plan.db)?;
         write!(f, " engine: {},", plan.engine.to_string())?;
         write!(f, " if_not_exists:{:},", plan.if_not_exists)?;
-        write!(f, " option: {:?}", plan.options)
+        write!(f, " option: {:?}", plan.options)?;
+        if let Some(src) = &plan.clone_from {
+            write!(f, ", clone from: {:}", src)?;
+        }
+        Ok(())
     }
 
     fn format_drop_database(f: &mut Formatter, plan: &DropDatabasePlan) -> fmt::Result {
@@ -232,7 +242,11 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         // need engine to impl Display
         write!(f, " engine: {},", plan.engine.to_string())?;
         write!(f, " if_not_exists:{:},", plan.if_not_exists)?;
-        write!(f, " option: {:?}", plan.options)
+        write!(f, " option: {:?}", plan.options)?;
+        if let Some((src_db, src_table)) = &plan.clone_from {
+            write!(f, ", clone from: {:}.{:}", src_db, src_table)?;
+        }
+        Ok(())
     }
 
     fn format_drop_table(f: &mut Formatter, plan: &DropTablePlan) -> fmt::Result {
@@ -38,6 +38,7 @@ fn test_plan_display_indent() -> Result<()> {
         schema,
         engine: TableEngineType::JSONEachRow,
         options,
+        clone_from: None,
     });
 
     assert_eq!(
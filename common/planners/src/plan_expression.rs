@@ -28,6 +28,7 @@ use common_functions::scalars::FunctionFactory;
 use lazy_static::lazy_static;
 
 use crate::PlanNode;
+use crate::WindowFrame;
 
 lazy_static! {
     static ref OP_SET: HashSet<&'static str> = ["database", "version",].iter().copied().collect();
@@ -85,6 +86,19 @@ pub enum Expression {
         args: Vec<Expression>,
     },
 
+    /// WindowFunction evaluated over a window of rows, such as `rank() OVER (ORDER BY x)`.
+    WindowFunction {
+        op: String,
+        params: Vec<DataValue>,
+        args: Vec<Expression>,
+        /// The expressions to partition the rows by
+        partition_by: Vec<Expression>,
+        /// The sort expressions to order the rows of each partition
+        order_by: Vec<Expression>,
+        /// The frame of the window, None means the default frame
+        frame: Option<WindowFrame>,
+    },
+
     /// A sort expression, that can be used to sort values.
     Sort {
         /// The expression to sort on
@@ -177,6 +191,7 @@ impl Expression {
                     false => format!("{}({})", prefix, args_column_name.join(", ")),
                 }
             }
+            Expression::WindowFunction { .. } => format!("{:?}", self),
             Expression::Sort { expr, .. } => expr.column_name(),
             Expression::Cast { expr, data_type } => {
                 format!("cast({} as {:?})", expr.column_name(), data_type)
@@ -267,6 +282,20 @@ impl Expression {
                 let func = self.to_aggregate_function(input_schema)?;
                 func.return_type()
             }
+            Expression::WindowFunction { op, args, .. } => match op.to_lowercase().as_str() {
+                "row_number" | "rank" | "dense_rank" => Ok(DataType::UInt64),
+                "lag" | "lead" | "first_value" | "last_value" => match args.first() {
+                    Some(arg) => arg.to_data_type(input_schema),
+                    None => Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                        "Window function {} expect to have at least one argument",
+                        op
+                    ))),
+                },
+                _ => {
+                    let func = self.to_aggregate_function(input_schema)?;
+                    func.return_type()
+                }
+            },
             Expression::Wildcard => Result::Err(ErrorCode::IllegalDataType(
                 "Wildcard expressions are not valid to get return type",
             )),
@@ -294,6 +323,16 @@ impl Expression {
                 }
                 AggregateFunctionFactory::get(&func_name, params.clone(), fields)
             }
+            // The window aggregate function is evaluated over the frame of each row.
+            Expression::WindowFunction {
+                op, params, args, ..
+            } => {
+                let mut fields = Vec::with_capacity(args.len());
+                for arg in args.iter() {
+                    fields.push(arg.to_data_field(schema)?);
+                }
+                AggregateFunctionFactory::get(op, params.clone(), fields)
+            }
             _ => Err(ErrorCode::LogicalError(
                 "Expression must be aggregated function",
             )),
@@ -370,6 +409,54 @@ impl fmt::Debug for Expression {
                 Ok(())
             }
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                let args_column_name = args.iter().map(Expression::column_name).collect::<Vec<_>>();
+                let params_name = params
+                    .iter()
+                    .map(|v| DataValue::custom_display(v, true))
+                    .collect::<Vec<_>>();
+
+                if params.is_empty() {
+                    write!(f, "{}", op)?;
+                } else {
+                    write!(f, "{}({})", op, params_name.join(", "))?;
+                };
+                write!(f, "({}) OVER (", args_column_name.join(", "))?;
+
+                let mut clauses = vec![];
+                if !partition_by.is_empty() {
+                    let names = partition_by
+                        .iter()
+                        .map(Expression::column_name)
+                        .collect::<Vec<_>>();
+                    clauses.push(format!("PARTITION BY {}", names.join(", ")));
+                }
+                if !order_by.is_empty() {
+                    let names = order_by
+                        .iter()
+                        .map(|expr| match expr {
+                            Expression::Sort { expr, asc, .. } => match asc {
+                                true => expr.column_name(),
+                                false => format!("{} DESC", expr.column_name()),
+                            },
+                            _ => expr.column_name(),
+                        })
+                        .collect::<Vec<_>>();
+                    clauses.push(format!("ORDER BY {}", names.join(", ")));
+                }
+                if let Some(frame) = frame {
+                    clauses.push(format!("{}", frame));
+                }
+                write!(f, "{})", clauses.join(" "))
+            }
+
             Expression::Sort { expr, .. } => write!(f, "{:?}", expr),
             Expression::Wildcard => write!(f, "*"),
             Expression::Cast { expr, data_type } => {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::scalars::FunctionFactory;
 
@@ -182,6 +183,12 @@ impl ExpressionChain {
 
                 self.actions.push(ExpressionAction::Function(function));
             }
+            Expression::WindowFunction { .. } => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Window function {:?} must be evaluated by the window plan",
+                    expr
+                )));
+            }
             Expression::Sort { expr, .. } => {
                 self.add_expr(expr)?;
             }
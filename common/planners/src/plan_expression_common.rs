@@ -46,6 +46,38 @@ pub fn find_aggregate_exprs(exprs: &[Expression]) -> Vec<Expression> {
 /// Collect all arguments from aggregation function and append to this exprs
 /// [ColumnExpr(b), Aggr(sum(a, b))] ---> [ColumnExpr(b), ColumnExpr(a)]
 
+pub fn find_window_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    find_exprs_in_exprs(exprs, &|nest_exprs| {
+        matches!(nest_exprs, Expression::WindowFunction { .. })
+    })
+}
+
+/// Collect the arguments, partition and order keys which should be evaluated before the window.
+pub fn expand_window_arg_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    let mut res = vec![];
+    for expr in exprs {
+        if let Expression::WindowFunction {
+            args,
+            partition_by,
+            order_by,
+            ..
+        } = expr
+        {
+            let order_by = order_by.iter().map(sort_to_inner_expr).collect::<Vec<_>>();
+            for arg in args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter())
+            {
+                if !res.contains(arg) {
+                    res.push(arg.clone());
+                }
+            }
+        }
+    }
+    res
+}
+
 pub fn expand_aggregate_arg_exprs(exprs: &[Expression]) -> Vec<Expression> {
     let mut res = vec![];
     for expr in exprs {
@@ -296,6 +328,31 @@ where F: Fn(&Expression) -> Result<Option<Expression>> {
                     .collect::<Result<Vec<Expression>>>()?,
             }),
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => Ok(Expression::WindowFunction {
+                op: op.clone(),
+                params: params.clone(),
+                args: args
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                partition_by: partition_by
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                order_by: order_by
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                frame: *frame,
+            }),
+
             Expression::Sort {
                 expr: nested_expr,
                 asc,
@@ -20,11 +20,13 @@ use common_datavalues::columns::DataColumn;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
-use common_planners::Expression;
-use common_planners::ExpressionAction;
-use common_planners::ExpressionChain;
+use common_functions::scalars::ArrayJoinFunction;
 use common_tracing::tracing;
 
+use crate::Expression;
+use crate::ExpressionAction;
+use crate::ExpressionChain;
+
 /// ExpressionExecutor is a helper struct for expressions and projections
 /// Aggregate functions is not covered, because all expressions in aggregate functions functions are executed.
 #[derive(Debug, Clone)]
@@ -80,7 +82,7 @@ impl ExpressionExecutor {
             );
         }
 
-        let rows = block.num_rows();
+        let mut rows = block.num_rows();
 
         for action in self.chain.actions.iter() {
             if let ExpressionAction::Alias(alias) = action {
@@ -116,6 +118,22 @@ impl ExpressionExecutor {
 
                     let func = f.to_function()?;
                     let column = func.eval(&arg_columns, rows)?;
+
+                    // arrayJoin changes the number of rows, replicate the evaluated columns.
+                    if ArrayJoinFunction::is_array_join(&f.func_name) {
+                        let indices = ArrayJoinFunction::replicate_indices(&arg_columns[0])?;
+                        for column in column_map.values_mut() {
+                            *column = match column {
+                                DataColumn::Array(array) => DataColumn::Array(
+                                    array.take_iter(&mut indices.iter().copied())?,
+                                ),
+                                DataColumn::Constant(value, _) => {
+                                    DataColumn::Constant(value.clone(), indices.len())
+                                }
+                            };
+                        }
+                        rows = indices.len();
+                    }
                     column_map.insert(f.name.clone(), column);
                 }
                 ExpressionAction::Constant(constant) => {
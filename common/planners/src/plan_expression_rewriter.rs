@@ -106,6 +106,35 @@ impl Expression {
                     args: new_args,
                 }
             }
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                let mut new_args = Vec::with_capacity(args.len());
+                for arg in args {
+                    new_args.push(arg.rewrite(rewriter)?);
+                }
+                let mut new_partition_by = Vec::with_capacity(partition_by.len());
+                for expr in partition_by {
+                    new_partition_by.push(expr.rewrite(rewriter)?);
+                }
+                let mut new_order_by = Vec::with_capacity(order_by.len());
+                for expr in order_by {
+                    new_order_by.push(expr.rewrite(rewriter)?);
+                }
+                Expression::WindowFunction {
+                    op,
+                    params,
+                    args: new_args,
+                    partition_by: new_partition_by,
+                    order_by: new_order_by,
+                    frame,
+                }
+            }
             Expression::Cast { expr, data_type } => {
                 let expr = expr.rewrite(rewriter)?;
                 Expression::Cast {
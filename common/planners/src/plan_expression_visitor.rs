@@ -104,6 +104,22 @@ impl Expression {
                 }
                 Ok(visitor)
             }
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let mut visitor = visitor;
+                for arg in args
+                    .iter()
+                    .chain(partition_by.iter())
+                    .chain(order_by.iter())
+                {
+                    visitor = arg.accept(visitor)?;
+                }
+                Ok(visitor)
+            }
             Expression::Cast { expr, .. } => expr.accept(visitor),
             Expression::Sort { expr, .. } => expr.accept(visitor),
 
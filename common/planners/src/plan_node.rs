@@ -22,6 +22,7 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AnalyzeTablePlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
@@ -47,6 +48,7 @@ use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
 use crate::UseDatabasePlan;
+use crate::WindowPlan;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
 pub enum PlanNode {
@@ -61,6 +63,7 @@ pub enum PlanNode {
     Filter(FilterPlan),
     Having(HavingPlan),
     Sort(SortPlan),
+    Window(WindowPlan),
     Limit(LimitPlan),
     LimitBy(LimitByPlan),
     Scan(ScanPlan),
@@ -73,6 +76,7 @@ pub enum PlanNode {
     DescribeTable(DescribeTablePlan),
     DropTable(DropTablePlan),
     TruncateTable(TruncateTablePlan),
+    AnalyzeTable(AnalyzeTablePlan),
     UseDatabase(UseDatabasePlan),
     SetVariable(SettingPlan),
     InsertInto(InsertIntoPlan),
@@ -107,8 +111,10 @@ impl PlanNode {
             PlanNode::DropTable(v) => v.schema(),
             PlanNode::DescribeTable(v) => v.schema(),
             PlanNode::TruncateTable(v) => v.schema(),
+            PlanNode::AnalyzeTable(v) => v.schema(),
             PlanNode::SetVariable(v) => v.schema(),
             PlanNode::Sort(v) => v.schema(),
+            PlanNode::Window(v) => v.schema(),
             PlanNode::UseDatabase(v) => v.schema(),
             PlanNode::InsertInto(v) => v.schema(),
             PlanNode::ShowCreateTable(v) => v.schema(),
@@ -141,8 +147,10 @@ impl PlanNode {
             PlanNode::DescribeTable(_) => "DescribeTablePlan",
             PlanNode::DropTable(_) => "DropTablePlan",
             PlanNode::TruncateTable(_) => "TruncateTablePlan",
+            PlanNode::AnalyzeTable(_) => "AnalyzeTablePlan",
             PlanNode::SetVariable(_) => "SetVariablePlan",
             PlanNode::Sort(_) => "SortPlan",
+            PlanNode::Window(_) => "WindowPlan",
             PlanNode::UseDatabase(_) => "UseDatabasePlan",
             PlanNode::InsertInto(_) => "InsertIntoPlan",
             PlanNode::ShowCreateTable(_) => "ShowCreateTablePlan",
@@ -165,6 +173,7 @@ impl PlanNode {
             PlanNode::Explain(v) => vec![v.input.clone()],
             PlanNode::Select(v) => vec![v.input.clone()],
             PlanNode::Sort(v) => vec![v.input.clone()],
+            PlanNode::Window(v) => vec![v.input.clone()],
             PlanNode::SubQueryExpression(v) => v.get_inputs(),
 
             _ => vec![],
@@ -193,6 +202,7 @@ impl PlanNode {
             PlanNode::Explain(v) => v.set_input(inputs[0]),
             PlanNode::Select(v) => v.set_input(inputs[0]),
             PlanNode::Sort(v) => v.set_input(inputs[0]),
+            PlanNode::Window(v) => v.set_input(inputs[0]),
             PlanNode::SubQueryExpression(v) => v.set_inputs(inputs),
             _ => {
                 return Err(ErrorCode::UnImplement(format!(
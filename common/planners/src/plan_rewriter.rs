@@ -25,6 +25,7 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AnalyzeTablePlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
@@ -54,6 +55,7 @@ use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
 use crate::UseDatabasePlan;
+use crate::WindowPlan;
 
 /// `PlanRewriter` is a visitor that can help to rewrite `PlanNode`
 /// By default, a `PlanRewriter` will traverse the plan tree in pre-order and return rewritten plan tree.
@@ -83,6 +85,7 @@ pub trait PlanRewriter {
             PlanNode::Projection(plan) => self.rewrite_projection(plan),
             PlanNode::Filter(plan) => self.rewrite_filter(plan),
             PlanNode::Sort(plan) => self.rewrite_sort(plan),
+            PlanNode::Window(plan) => self.rewrite_window(plan),
             PlanNode::Limit(plan) => self.rewrite_limit(plan),
             PlanNode::LimitBy(plan) => self.rewrite_limit_by(plan),
             PlanNode::Scan(plan) => self.rewrite_scan(plan),
@@ -105,6 +108,7 @@ pub trait PlanRewriter {
             PlanNode::ShowCreateTable(plan) => self.rewrite_show_create_table(plan),
             PlanNode::SubQueryExpression(plan) => self.rewrite_sub_queries_sets(plan),
             PlanNode::TruncateTable(plan) => self.rewrite_truncate_table(plan),
+            PlanNode::AnalyzeTable(plan) => self.rewrite_analyze_table(plan),
             PlanNode::Kill(plan) => self.rewrite_kill(plan),
         }
     }
@@ -144,6 +148,21 @@ pub trait PlanRewriter {
                 params: params.clone(),
                 args: self.rewrite_exprs(schema, args)?,
             }),
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => Ok(Expression::WindowFunction {
+                op: op.clone(),
+                params: params.clone(),
+                args: self.rewrite_exprs(schema, args)?,
+                partition_by: self.rewrite_exprs(schema, partition_by)?,
+                order_by: self.rewrite_exprs(schema, order_by)?,
+                frame: *frame,
+            }),
             Expression::Sort {
                 expr,
                 asc,
@@ -257,6 +276,14 @@ pub trait PlanRewriter {
         PlanBuilder::from(&new_input).sort(&new_order_by)?.build()
     }
 
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_window_exprs = self.rewrite_exprs(&new_input.schema(), &plan.window_exprs)?;
+        PlanBuilder::from(&new_input)
+            .window(&new_window_exprs)?
+            .build()
+    }
+
     fn rewrite_limit(&mut self, plan: &LimitPlan) -> Result<PlanNode> {
         let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
         PlanBuilder::from(&new_input)
@@ -343,6 +370,10 @@ pub trait PlanRewriter {
         Ok(PlanNode::TruncateTable(plan.clone()))
     }
 
+    fn rewrite_analyze_table(&mut self, plan: &AnalyzeTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::AnalyzeTable(plan.clone()))
+    }
+
     fn rewrite_kill(&mut self, plan: &KillPlan) -> Result<PlanNode> {
         Ok(PlanNode::Kill(plan.clone()))
     }
@@ -507,6 +538,30 @@ impl RewriteHelper {
 
                 Ok(Expression::Alias(alias.clone(), Box::new(new_expr)))
             }
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                let mut rewrite_exprs = |exprs: &[Expression]| {
+                    exprs
+                        .iter()
+                        .map(|v| RewriteHelper::expr_rewrite_alias(v, data))
+                        .collect::<Result<Vec<_>>>()
+                };
+
+                Ok(Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: rewrite_exprs(args)?,
+                    partition_by: rewrite_exprs(partition_by)?,
+                    order_by: rewrite_exprs(order_by)?,
+                    frame: *frame,
+                })
+            }
             Expression::Cast { expr, data_type } => {
                 let new_expr = RewriteHelper::expr_rewrite_alias(expr, data)?;
                 Ok(Expression::Cast {
@@ -579,6 +634,17 @@ impl RewriteHelper {
             }
             Expression::ScalarFunction { args, .. } => args.clone(),
             Expression::AggregateFunction { args, .. } => args.clone(),
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter())
+                .cloned()
+                .collect(),
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => vec![expr.as_ref().clone()],
             Expression::Cast { expr, .. } => vec![expr.as_ref().clone()],
@@ -616,6 +682,14 @@ impl RewriteHelper {
                 }
                 v
             }
+            Expression::WindowFunction { .. } => {
+                let mut v = vec![];
+                for child in Self::expression_plan_children(expr)? {
+                    let mut col = Self::expression_plan_columns(&child)?;
+                    v.append(&mut col);
+                }
+                v
+            }
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => Self::expression_plan_columns(expr)?,
             Expression::Cast { expr, .. } => Self::expression_plan_columns(expr)?,
@@ -684,6 +758,25 @@ impl RewriteHelper {
                 params: params.clone(),
                 args: expressions.to_vec(),
             },
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                frame,
+                ..
+            } => {
+                let (new_args, rest) = expressions.split_at(args.len());
+                let (new_partition_by, new_order_by) = rest.split_at(partition_by.len());
+                Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: new_args.to_vec(),
+                    partition_by: new_partition_by.to_vec(),
+                    order_by: new_order_by.to_vec(),
+                    frame: *frame,
+                }
+            }
             other => other.clone(),
         }
     }
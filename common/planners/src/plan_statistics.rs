@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_datavalues::DataValue;
+
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Debug, Default)]
 pub struct Statistics {
     /// Total rows of the query read.
@@ -43,3 +45,76 @@ impl Statistics {
         *self = Self::default();
     }
 }
+
+/// The statistics of a table collected by `ANALYZE TABLE`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct TableStatistics {
+    /// The rows of the table when it was analyzed.
+    pub row_count: u64,
+    pub columns: Vec<ColumnStatistics>,
+}
+
+impl TableStatistics {
+    pub fn column(&self, name: &str) -> Option<&ColumnStatistics> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ColumnStatistics {
+    pub name: String,
+    /// Approximate number of distinct non-null values.
+    pub ndv: u64,
+    pub null_count: u64,
+    /// Null if the column has no non-null value.
+    pub min: DataValue,
+    pub max: DataValue,
+    /// Only numeric columns have a histogram.
+    pub histogram: Option<Histogram>,
+}
+
+/// An equi-height histogram: every bucket between two adjacent bounds holds
+/// the same number of non-null values.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct Histogram {
+    /// Ascending bucket bounds, the first is the min and the last is the max.
+    pub bounds: Vec<f64>,
+}
+
+impl Histogram {
+    pub fn buckets(&self) -> usize {
+        self.bounds.len().saturating_sub(1)
+    }
+
+    /// The fraction of the non-null values which are less than or equal to `value`,
+    /// interpolated linearly inside a bucket.
+    pub fn fraction_le(&self, value: f64) -> f64 {
+        let buckets = self.buckets();
+        if buckets == 0 {
+            return match self.bounds.first() {
+                Some(bound) if value >= *bound => 1.0,
+                _ => 0.0,
+            };
+        }
+        if value < self.bounds[0] {
+            return 0.0;
+        }
+        if value >= self.bounds[buckets] {
+            return 1.0;
+        }
+
+        let mut fraction = 0.0;
+        for i in 0..buckets {
+            let (low, high) = (self.bounds[i], self.bounds[i + 1]);
+            if value >= high {
+                fraction += 1.0;
+                continue;
+            }
+            if high > low {
+                fraction += (value - low) / (high - low);
+            }
+            break;
+        }
+        fraction / buckets as f64
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataValue;
+use pretty_assertions::assert_eq;
+
+use crate::*;
+
+#[test]
+fn test_histogram_fraction_le() {
+    let histogram = Histogram {
+        bounds: vec![0.0, 10.0, 20.0, 40.0, 100.0],
+    };
+    assert_eq!(histogram.buckets(), 4);
+    assert_eq!(histogram.fraction_le(-1.0), 0.0);
+    assert_eq!(histogram.fraction_le(0.0), 0.0);
+    assert_eq!(histogram.fraction_le(5.0), 0.125);
+    assert_eq!(histogram.fraction_le(20.0), 0.5);
+    assert_eq!(histogram.fraction_le(30.0), 0.625);
+    assert_eq!(histogram.fraction_le(100.0), 1.0);
+    assert_eq!(histogram.fraction_le(1000.0), 1.0);
+
+    let single = Histogram { bounds: vec![7.0] };
+    assert_eq!(single.buckets(), 0);
+    assert_eq!(single.fraction_le(6.0), 0.0);
+    assert_eq!(single.fraction_le(7.0), 1.0);
+}
+
+#[test]
+fn test_table_statistics_column() {
+    let stats = TableStatistics {
+        row_count: 3,
+        columns: vec![ColumnStatistics {
+            name: "a".to_string(),
+            ndv: 2,
+            null_count: 1,
+            min: DataValue::Int64(Some(1)),
+            max: DataValue::Int64(Some(2)),
+            histogram: None,
+        }],
+    };
+    assert_eq!(stats.column("a").map(|c| c.ndv), Some(2));
+    assert!(stats.column("b").is_none());
+}
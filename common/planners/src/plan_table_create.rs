@@ -55,6 +55,9 @@ pub struct CreateTablePlan {
     /// The file type of physical file
     pub engine: TableEngineType,
     pub options: TableOptions,
+    /// The source table (db, table) of `CREATE TABLE ... CLONE`,
+    /// the new table shares the schema and the data of the source table.
+    pub clone_from: Option<(String, String)>,
 }
 
 impl CreateTablePlan {
@@ -18,6 +18,7 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AnalyzeTablePlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
@@ -45,6 +46,7 @@ use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
 use crate::UseDatabasePlan;
+use crate::WindowPlan;
 
 /// `PlanVisitor` implements visitor pattern(reference [syn](https://docs.rs/syn/1.0.72/syn/visit/trait.Visit.html)) for `PlanNode`.
 ///
@@ -96,6 +98,7 @@ pub trait PlanVisitor {
             PlanNode::Projection(plan) => self.visit_projection(plan),
             PlanNode::Filter(plan) => self.visit_filter(plan),
             PlanNode::Sort(plan) => self.visit_sort(plan),
+            PlanNode::Window(plan) => self.visit_window(plan),
             PlanNode::Limit(plan) => self.visit_limit(plan),
             PlanNode::LimitBy(plan) => self.visit_limit_by(plan),
             PlanNode::Scan(plan) => self.visit_scan(plan),
@@ -108,6 +111,7 @@ pub trait PlanVisitor {
             PlanNode::DropTable(plan) => self.visit_drop_table(plan),
             PlanNode::DescribeTable(plan) => self.visit_describe_table(plan),
             PlanNode::TruncateTable(plan) => self.visit_truncate_table(plan),
+            PlanNode::AnalyzeTable(plan) => self.visit_analyze_table(plan),
             PlanNode::UseDatabase(plan) => self.visit_use_database(plan),
             PlanNode::SetVariable(plan) => self.visit_set_variable(plan),
             PlanNode::Stage(plan) => self.visit_stage(plan),
@@ -206,6 +210,11 @@ pub trait PlanVisitor {
         self.visit_exprs(&plan.order_by)
     }
 
+    fn visit_window(&mut self, plan: &WindowPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())?;
+        self.visit_exprs(&plan.window_exprs)
+    }
+
     fn visit_limit(&mut self, plan: &LimitPlan) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref())
     }
@@ -270,6 +279,10 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_analyze_table(&mut self, _: &AnalyzeTablePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_kill_query(&mut self, _: &KillPlan) -> Result<()> {
         Ok(())
     }
@@ -0,0 +1,81 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::test::Test;
+use crate::*;
+
+#[test]
+fn test_window_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let window_expr = Expression::WindowFunction {
+        op: "sum".to_string(),
+        params: vec![],
+        args: vec![col("number")],
+        partition_by: vec![],
+        order_by: vec![sort("number", true, false)],
+        frame: Some(WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start: WindowFrameBound::Preceding(1),
+            end: WindowFrameBound::CurrentRow,
+        }),
+    };
+
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let plan = PlanBuilder::from(&source)
+        .window(&[window_expr.clone()])?
+        .project(&[col("number"), col(&window_expr.column_name())])?
+        .build()?;
+
+    let expect = "\
+    Projection: number:UInt64, sum(number) OVER (ORDER BY number ROWS BETWEEN 1 PRECEDING AND CURRENT ROW):UInt64\
+    \n  Window: [[sum(number) OVER (ORDER BY number ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)]]\
+    \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]";
+    let actual = format!("{:?}", plan);
+    assert_eq!(expect, actual);
+
+    // The window columns are appended to the input columns.
+    let schema = plan.schema();
+    assert_eq!(2, schema.fields().len());
+    assert!(schema.field(1).is_nullable());
+    Ok(())
+}
+
+#[test]
+fn test_find_window_exprs() -> Result<()> {
+    let window_expr = Expression::WindowFunction {
+        op: "row_number".to_string(),
+        params: vec![],
+        args: vec![],
+        partition_by: vec![col("a")],
+        order_by: vec![sort("b", false, false)],
+        frame: None,
+    };
+
+    let exprs = vec![
+        col("a"),
+        Expression::Alias("rn".to_string(), Box::new(window_expr.clone())),
+        add(window_expr.clone(), lit(1i64)),
+    ];
+    assert_eq!(find_window_exprs(&exprs), vec![window_expr.clone()]);
+
+    // Partition by and order by columns are needed before the window plan.
+    assert_eq!(expand_window_arg_exprs(&[window_expr]), vec![
+        col("a"),
+        col("b")
+    ]);
+    Ok(())
+}
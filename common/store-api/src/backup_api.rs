@@ -0,0 +1,46 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::fmt;
+
+/// What is written into a backup, or what is restored from one.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct BackupResult {
+    /// The id of the state machine snapshot the backup is taken from.
+    pub snapshot_id: String,
+    pub databases: usize,
+    pub tables: usize,
+    pub parts: usize,
+}
+
+impl fmt::Display for BackupResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "snapshot: {}, databases: {}, tables: {}, parts: {}",
+            self.snapshot_id, self.databases, self.tables, self.parts
+        )
+    }
+}
+
+#[async_trait::async_trait]
+pub trait BackupApi {
+    /// Writes a consistent backup of the meta and the data parts into `dir`, on the host of the store node.
+    async fn backup(&mut self, dir: String) -> common_exception::Result<BackupResult>;
+
+    /// Rebuilds the catalog and the data parts of a fresh cluster from the backup in `dir`,
+    /// on the host of the store node.
+    async fn restore(&mut self, dir: String) -> common_exception::Result<BackupResult>;
+}
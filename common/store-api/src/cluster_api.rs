@@ -0,0 +1,51 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+/// The nodes of a store cluster and their roles in the raft group.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ClusterMembership {
+    pub leader: Option<u64>,
+    /// The nodes that vote for the leader and commit the logs.
+    pub voters: BTreeSet<u64>,
+    /// The nodes that replicate the logs but do not vote.
+    pub learners: BTreeSet<u64>,
+    /// The raft service address of every node.
+    pub nodes: BTreeMap<u64, String>,
+}
+
+#[async_trait::async_trait]
+pub trait ClusterApi {
+    async fn get_membership(&mut self) -> common_exception::Result<ClusterMembership>;
+
+    /// Adds a node as a learner and returns when it has caught up with the leader's logs.
+    async fn add_learner(
+        &mut self,
+        node_id: u64,
+        address: String,
+    ) -> common_exception::Result<ClusterMembership>;
+
+    /// Replaces the voters with `voters`, of which every one must have been added as a node.
+    /// A learner in `voters` is promoted, a voter not in `voters` is demoted to a learner.
+    async fn change_membership(
+        &mut self,
+        voters: BTreeSet<u64>,
+    ) -> common_exception::Result<ClusterMembership>;
+
+    /// Removes a node from the voters, if it is one, and then from the cluster.
+    async fn remove_node(&mut self, node_id: u64) -> common_exception::Result<ClusterMembership>;
+}
@@ -13,11 +13,17 @@
 // limitations under the License.
 //
 
+mod backup_api;
+mod cluster_api;
 pub mod kv_api;
 mod meta_api;
 mod session_api;
 mod storage_api;
 
+pub use backup_api::BackupApi;
+pub use backup_api::BackupResult;
+pub use cluster_api::ClusterApi;
+pub use cluster_api::ClusterMembership;
 pub use kv_api::GetKVActionResult;
 pub use kv_api::KVApi;
 pub use kv_api::PrefixListReply;
@@ -31,6 +37,8 @@ pub use meta_api::DropTableActionResult;
 pub use meta_api::GetDatabaseActionResult;
 pub use meta_api::GetTableActionResult;
 pub use meta_api::MetaApi;
+pub use meta_api::SetTableStatisticsActionResult;
+pub use meta_api::TableStatisticsReply;
 pub use session_api::SessionApi;
 pub use storage_api::AppendResult;
 pub use storage_api::BlockStream;
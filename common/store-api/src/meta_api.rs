@@ -18,10 +18,12 @@ use common_metatypes::Database;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
 use common_metatypes::Table;
+use common_metatypes::TableVersionAt;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
+use common_planners::TableStatistics;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct CreateDatabaseActionResult {
@@ -61,6 +63,11 @@ pub struct DatabaseMetaSnapshot {
 }
 pub type DatabaseMetaReply = Option<DatabaseMetaSnapshot>;
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetTableStatisticsActionResult {}
+
+pub type TableStatisticsReply = Option<TableStatistics>;
+
 #[async_trait::async_trait]
 pub trait MetaApi {
     async fn create_database(
@@ -98,8 +105,31 @@ pub trait MetaApi {
         db_ver: Option<MetaVersion>,
     ) -> common_exception::Result<GetTableActionResult>;
 
+    /// Resolves the version of the table as of a point in its history.
+    async fn get_table_version(
+        &mut self,
+        db: String,
+        table: String,
+        at: TableVersionAt,
+    ) -> common_exception::Result<MetaVersion>;
+
     async fn get_database_meta(
         &mut self,
         current_ver: Option<u64>,
     ) -> common_exception::Result<DatabaseMetaReply>;
+
+    /// Replaces the statistics of the table collected by `ANALYZE TABLE`.
+    async fn set_table_statistics(
+        &mut self,
+        db: String,
+        table: String,
+        statistics: TableStatistics,
+    ) -> common_exception::Result<SetTableStatisticsActionResult>;
+
+    /// Returns None if the table has never been analyzed.
+    async fn get_table_statistics(
+        &mut self,
+        db: String,
+        table: String,
+    ) -> common_exception::Result<TableStatisticsReply>;
 }
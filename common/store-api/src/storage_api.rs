@@ -30,11 +30,13 @@ pub type ReadPlanResult = Option<Vec<DataPartInfo>>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct ReadAction {
+    /// The id of the query that reads the part, with which the read is cancelled if the query is killed.
+    pub query_id: String,
     pub part: Part,
     pub push_down: PlanNode,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
 pub struct Summary {
     pub rows: usize,
     pub wire_bytes: usize,
@@ -48,7 +50,7 @@ impl Summary {
     }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
 pub struct PartitionInfo {
     pub rows: usize,
     pub cols: usize,
@@ -78,7 +80,7 @@ impl AppendResult {
     }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
 pub struct AppendResult {
     pub summary: Summary,
     pub parts: Vec<PartitionInfo>,
@@ -111,8 +113,10 @@ pub trait StorageApi {
         read_action: &ReadAction,
     ) -> common_exception::Result<SendableDataBlockStream>;
 
+    /// Appends the data of the query, which is discarded if the query is killed before it is committed.
     async fn append_data(
         &mut self,
+        query_id: String,
         db_name: String,
         tbl_name: String,
         scheme_ref: DataSchemaRef,
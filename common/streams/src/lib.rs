@@ -12,12 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod limits_test;
+#[cfg(test)]
+mod stream_abort_test;
 #[cfg(test)]
 mod stream_datablock_test;
 
 #[cfg(test)]
 mod stream_progress_test;
 
+mod limits;
 mod sources;
 mod stream;
 mod stream_abort;
@@ -32,6 +37,10 @@ mod stream_source;
 mod stream_sub_queries;
 mod stream_take;
 
+pub use limits::OverflowMode;
+pub use limits::ReadLimits;
+pub use limits::ReadReservation;
+pub use limits::ResultLimits;
 pub use sources::*;
 pub use stream::SendableDataBlockStream;
 pub use stream_abort::AbortStream;
@@ -0,0 +1,176 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use futures::StreamExt;
+
+use crate::SendableDataBlockStream;
+
+/// What a query does once it reaches one of its limits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverflowMode {
+    /// Fail the query.
+    Throw,
+    /// Stop the query and return what it got so far.
+    Truncate,
+}
+
+impl FromStr for OverflowMode {
+    type Err = ErrorCode;
+
+    fn from_str(mode: &str) -> Result<Self> {
+        match mode.to_lowercase().as_str() {
+            "throw" => Ok(OverflowMode::Throw),
+            "truncate" => Ok(OverflowMode::Truncate),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unknown overflow mode {}, expected throw or truncate",
+                mode
+            ))),
+        }
+    }
+}
+
+/// The rows and bytes read by a query, shared by all its sources.
+#[derive(Debug, Default)]
+pub struct ReadReservation {
+    pub rows: usize,
+    pub bytes: usize,
+}
+
+/// The limits on the rows and bytes read by a query, 0 means no limit.
+#[derive(Clone)]
+pub struct ReadLimits {
+    pub max_rows: usize,
+    pub max_bytes: usize,
+    pub overflow_mode: OverflowMode,
+    /// A block is reserved under the lock before it is passed on, thus the sources
+    /// of the query reading in parallel cannot exceed the limits together.
+    pub reserved: Arc<Mutex<ReadReservation>>,
+}
+
+impl ReadLimits {
+    pub fn unlimited() -> ReadLimits {
+        ReadLimits {
+            max_rows: 0,
+            max_bytes: 0,
+            overflow_mode: OverflowMode::Throw,
+            reserved: Arc::new(Mutex::new(ReadReservation::default())),
+        }
+    }
+
+    /// The block to read next from a source, it is cut short or dropped once
+    /// the limits are reached in truncate mode.
+    pub fn limit_block(&self, block: DataBlock) -> Result<Option<DataBlock>> {
+        let mut reserved = self.reserved.lock();
+        let limited = check_limits(
+            "read",
+            (self.max_rows, self.max_bytes, self.overflow_mode),
+            (reserved.rows, reserved.bytes),
+            block,
+        )?;
+
+        if let Some(block) = &limited {
+            reserved.rows += block.num_rows();
+            reserved.bytes += block.memory_size();
+        }
+        Ok(limited)
+    }
+}
+
+/// The limits on the rows and bytes of a query result, 0 means no limit.
+pub struct ResultLimits {
+    pub max_rows: usize,
+    pub max_bytes: usize,
+    pub overflow_mode: OverflowMode,
+    rows: usize,
+    bytes: usize,
+}
+
+impl ResultLimits {
+    pub fn create(max_rows: usize, max_bytes: usize, overflow_mode: OverflowMode) -> Self {
+        ResultLimits {
+            max_rows,
+            max_bytes,
+            overflow_mode,
+            rows: 0,
+            bytes: 0,
+        }
+    }
+
+    /// The next block of the result to write, it is cut short or dropped once
+    /// the limits are reached in truncate mode.
+    pub fn limit_block(&mut self, block: DataBlock) -> Result<Option<DataBlock>> {
+        let limited = check_limits(
+            "result",
+            (self.max_rows, self.max_bytes, self.overflow_mode),
+            (self.rows, self.bytes),
+            block,
+        )?;
+
+        if let Some(block) = &limited {
+            self.rows += block.num_rows();
+            self.bytes += block.memory_size();
+        }
+        Ok(limited)
+    }
+
+    /// Collect the result within the limits, the stream is dropped once they are
+    /// reached in truncate mode.
+    pub async fn collect(mut self, mut stream: SendableDataBlockStream) -> Result<Vec<DataBlock>> {
+        let mut blocks = vec![];
+        while let Some(block) = stream.next().await {
+            match self.limit_block(block?)? {
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+        }
+        Ok(blocks)
+    }
+}
+
+fn check_limits(
+    what: &str,
+    (max_rows, max_bytes, overflow_mode): (usize, usize, OverflowMode),
+    (rows, bytes): (usize, usize),
+    block: DataBlock,
+) -> Result<Option<DataBlock>> {
+    let rows_exceeded = max_rows != 0 && rows + block.num_rows() > max_rows;
+    let bytes_exceeded = max_bytes != 0 && bytes + block.memory_size() > max_bytes;
+
+    match overflow_mode {
+        _ if !rows_exceeded && !bytes_exceeded => Ok(Some(block)),
+        OverflowMode::Throw if rows_exceeded => Err(ErrorCode::QuotaExceeded(format!(
+            "Limit for {} rows exceeded, max {} rows, got {} rows",
+            what,
+            max_rows,
+            rows + block.num_rows()
+        ))),
+        OverflowMode::Throw => Err(ErrorCode::QuotaExceeded(format!(
+            "Limit for {} bytes exceeded, max {} bytes, got {} bytes",
+            what,
+            max_bytes,
+            bytes + block.memory_size()
+        ))),
+        // The block crossing the bytes limit is kept whole, the next ones are dropped.
+        OverflowMode::Truncate if (max_rows != 0 && rows >= max_rows) => Ok(None),
+        OverflowMode::Truncate if (max_bytes != 0 && bytes >= max_bytes) => Ok(None),
+        OverflowMode::Truncate if rows_exceeded => Ok(Some(block.slice(0, max_rows - rows))),
+        OverflowMode::Truncate => Ok(Some(block)),
+    }
+}
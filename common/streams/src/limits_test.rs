@@ -0,0 +1,102 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datablocks::*;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::*;
+
+fn create_block(rows: i64) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+    DataBlock::create_by_array(schema, vec![Series::new((0..rows).collect::<Vec<_>>())])
+}
+
+#[test]
+fn test_overflow_mode() -> Result<()> {
+    assert_eq!(OverflowMode::Throw, "throw".parse::<OverflowMode>()?);
+    assert_eq!(OverflowMode::Truncate, "TRUNCATE".parse::<OverflowMode>()?);
+
+    let error = "break".parse::<OverflowMode>().unwrap_err();
+    assert_eq!(error.code(), ErrorCode::BadArguments("").code());
+    Ok(())
+}
+
+#[test]
+fn test_result_limits_throw() -> Result<()> {
+    let mut limits = ResultLimits::create(5, 0, OverflowMode::Throw);
+    assert_eq!(3, limits.limit_block(create_block(3))?.unwrap().num_rows());
+
+    let error = limits.limit_block(create_block(3)).unwrap_err();
+    assert_eq!(error.code(), ErrorCode::QuotaExceeded("").code());
+    assert_eq!(
+        error.message(),
+        "Limit for result rows exceeded, max 5 rows, got 6 rows"
+    );
+
+    let block = create_block(3);
+    let mut limits = ResultLimits::create(0, block.memory_size(), OverflowMode::Throw);
+    assert!(limits.limit_block(block)?.is_some());
+    let error = limits.limit_block(create_block(1)).unwrap_err();
+    assert_eq!(error.code(), ErrorCode::QuotaExceeded("").code());
+    Ok(())
+}
+
+#[test]
+fn test_result_limits_truncate() -> Result<()> {
+    let mut limits = ResultLimits::create(5, 0, OverflowMode::Truncate);
+    assert_eq!(3, limits.limit_block(create_block(3))?.unwrap().num_rows());
+    assert_eq!(2, limits.limit_block(create_block(3))?.unwrap().num_rows());
+    assert!(limits.limit_block(create_block(3))?.is_none());
+
+    // The block crossing the bytes limit is kept whole.
+    let block = create_block(3);
+    let mut limits = ResultLimits::create(0, block.memory_size() + 1, OverflowMode::Truncate);
+    assert!(limits.limit_block(block)?.is_some());
+    assert_eq!(3, limits.limit_block(create_block(3))?.unwrap().num_rows());
+    assert!(limits.limit_block(create_block(3))?.is_none());
+
+    // No limits.
+    let mut limits = ResultLimits::create(0, 0, OverflowMode::Truncate);
+    assert_eq!(3, limits.limit_block(create_block(3))?.unwrap().num_rows());
+    Ok(())
+}
+
+#[test]
+fn test_read_limits_shared() -> Result<()> {
+    let limits = ReadLimits {
+        max_rows: 5,
+        max_bytes: 0,
+        overflow_mode: OverflowMode::Truncate,
+        ..ReadLimits::unlimited()
+    };
+
+    // The sources of a query reserve their blocks from the same limits.
+    let sources = (0..4).map(|_| limits.clone()).collect::<Vec<_>>();
+    let handles = sources
+        .into_iter()
+        .map(|source| std::thread::spawn(move || source.limit_block(create_block(3))))
+        .collect::<Vec<_>>();
+
+    let mut rows = 0;
+    for handle in handles {
+        if let Some(block) = handle.join().unwrap()? {
+            rows += block.num_rows();
+        }
+    }
+    assert_eq!(5, rows);
+    assert_eq!(5, limits.reserved.lock().rows);
+    Ok(())
+}
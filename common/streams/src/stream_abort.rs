@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Instant;
 
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
@@ -28,15 +32,33 @@ use crate::SendableDataBlockStream;
 pin_project! {
     pub struct AbortStream {
         #[pin]
-        input: Abortable<SendableDataBlockStream>
+        input: Abortable<SendableDataBlockStream>,
+        deadline: Option<Instant>,
+        // Set by whoever aborts the stream because the deadline is exceeded.
+        deadline_exceeded: Arc<AtomicBool>,
+        timed_out: bool,
     }
 }
 
 impl AbortStream {
     pub fn try_create(input: SendableDataBlockStream) -> Result<(AbortHandle, Self)> {
+        Self::try_create_with_deadline(input, None, Arc::new(AtomicBool::new(false)))
+    }
+
+    /// The stream ends with a `Timeout` error once the deadline is exceeded.
+    /// It is only checked when the stream is polled, abort it to wake up a stream waiting for data,
+    /// after setting `deadline_exceeded` so that it still ends with a `Timeout` error.
+    pub fn try_create_with_deadline(
+        input: SendableDataBlockStream,
+        deadline: Option<Instant>,
+        deadline_exceeded: Arc<AtomicBool>,
+    ) -> Result<(AbortHandle, Self)> {
         let (handle, reg) = AbortHandle::new_pair();
         Ok((handle, Self {
             input: Abortable::new(input, reg),
+            deadline,
+            deadline_exceeded,
+            timed_out: false,
         }))
     }
 }
@@ -49,9 +71,30 @@ impl Stream for AbortStream {
         ctx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
         let this = self.project();
+        if *this.timed_out {
+            return Poll::Ready(None);
+        }
+
+        let exceeded = match this.deadline {
+            Some(deadline) => Instant::now() >= *deadline,
+            None => false,
+        };
+        if exceeded || this.deadline_exceeded.load(Ordering::SeqCst) {
+            *this.timed_out = true;
+            return Poll::Ready(Some(Err(ErrorCode::Timeout(
+                "Aborted query, because it exceeded the max execution time",
+            ))));
+        }
+
         let is_aborted = this.input.is_aborted();
 
         match this.input.poll_next(ctx) {
+            Poll::Ready(None) if is_aborted && this.deadline_exceeded.load(Ordering::SeqCst) => {
+                *this.timed_out = true;
+                Poll::Ready(Some(Err(ErrorCode::Timeout(
+                    "Aborted query, because it exceeded the max execution time",
+                ))))
+            }
             Poll::Ready(None) => match is_aborted {
                 false => Poll::Ready(None),
                 true => Poll::Ready(Some(Err(ErrorCode::AbortedQuery(
@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::*;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::StreamExt;
+
+use crate::*;
+
+fn create_input() -> SendableDataBlockStream {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+    let block = DataBlock::create_by_array(schema, vec![Series::new(vec![1i64, 2, 3]).into()]);
+    Box::pin(DataBlockStream::create(
+        Arc::new(DataSchema::empty()),
+        None,
+        vec![block.clone(), block],
+    ))
+}
+
+#[tokio::test]
+async fn test_abort_stream() -> Result<()> {
+    let (handle, mut stream) = AbortStream::try_create(create_input())?;
+    assert!(stream.next().await.unwrap().is_ok());
+
+    handle.abort();
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(error.code(), ErrorCode::AbortedQuery("").code());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_abort_stream_with_deadline() -> Result<()> {
+    // Not exceeded.
+    {
+        let deadline = Instant::now() + Duration::from_secs(3600);
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let (_, stream) =
+            AbortStream::try_create_with_deadline(create_input(), Some(deadline), exceeded)?;
+        let blocks = stream.collect::<Vec<_>>().await;
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks.iter().all(|block| block.is_ok()));
+    }
+
+    // Exceeded.
+    {
+        let deadline = Instant::now();
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let (_, mut stream) =
+            AbortStream::try_create_with_deadline(create_input(), Some(deadline), exceeded)?;
+        let error = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Timeout("").code());
+        assert!(stream.next().await.is_none());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_abort_stream_past_deadline() -> Result<()> {
+    // Aborted by the deadline watchdog while it is waiting for data.
+    let deadline = Instant::now() + Duration::from_secs(3600);
+    let exceeded = Arc::new(AtomicBool::new(false));
+    let (handle, mut stream) =
+        AbortStream::try_create_with_deadline(create_input(), Some(deadline), exceeded.clone())?;
+    assert!(stream.next().await.unwrap().is_ok());
+
+    exceeded.store(true, Ordering::SeqCst);
+    handle.abort();
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(error.code(), ErrorCode::Timeout("").code());
+    assert!(stream.next().await.is_none());
+
+    Ok(())
+}
@@ -22,6 +22,7 @@ use common_progress::ProgressValues;
 use futures::Stream;
 use pin_project_lite::pin_project;
 
+use crate::ReadLimits;
 use crate::SendableDataBlockStream;
 
 pin_project! {
@@ -29,12 +30,29 @@ pin_project! {
         #[pin]
         input: SendableDataBlockStream,
         callback: ProgressCallback,
+        limits: ReadLimits,
+        finished: bool,
     }
 }
 
 impl ProgressStream {
     pub fn try_create(input: SendableDataBlockStream, callback: ProgressCallback) -> Result<Self> {
-        Ok(Self { input, callback })
+        Self::try_create_with_limits(input, callback, ReadLimits::unlimited())
+    }
+
+    /// The stream fails or ends once the query has read more than the limits,
+    /// which count the rows and bytes passed on by all the sources sharing them.
+    pub fn try_create_with_limits(
+        input: SendableDataBlockStream,
+        callback: ProgressCallback,
+        limits: ReadLimits,
+    ) -> Result<Self> {
+        Ok(Self {
+            input,
+            callback,
+            limits,
+            finished: false,
+        })
     }
 }
 
@@ -46,11 +64,14 @@ impl Stream for ProgressStream {
         ctx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
         let this = self.project();
+        if *this.finished {
+            return Poll::Ready(None);
+        }
 
         match this.input.poll_next(ctx) {
             Poll::Ready(x) => match x {
-                Some(result) => match result {
-                    Ok(block) => {
+                Some(result) => match result.and_then(|block| this.limits.limit_block(block)) {
+                    Ok(Some(block)) => {
                         let progress_values = ProgressValues {
                             read_rows: block.num_rows(),
                             read_bytes: block.memory_size(),
@@ -60,6 +81,10 @@ impl Stream for ProgressStream {
                         (this.callback)(&progress_values);
                         Poll::Ready(Some(Ok(block)))
                     }
+                    Ok(None) => {
+                        *this.finished = true;
+                        Poll::Ready(None)
+                    }
                     Err(e) => Poll::Ready(Some(Err(e))),
                 },
                 None => Poll::Ready(None),
@@ -16,9 +16,12 @@ use std::sync::Arc;
 
 use common_datablocks::*;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_infallible::Mutex;
 use common_progress::*;
 use common_runtime::tokio;
+use futures::StreamExt;
 use futures::TryStreamExt;
 
 use crate::*;
@@ -55,3 +58,43 @@ async fn test_progress_stream() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_progress_stream_with_limits() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+    let block = DataBlock::create_by_array(schema, vec![Series::new(vec![1i64, 2, 3]).into()]);
+    let create_stream = |overflow_mode: OverflowMode| -> Result<ProgressStream> {
+        let input = DataBlockStream::create(Arc::new(DataSchema::empty()), None, vec![
+            block.clone(),
+            block.clone(),
+            block.clone(),
+        ]);
+
+        let progress = Box::new(|_: &ProgressValues| {});
+        let limits = ReadLimits {
+            max_rows: 4,
+            max_bytes: 0,
+            overflow_mode,
+            reserved: Arc::new(Mutex::new(ReadReservation::default())),
+        };
+        ProgressStream::try_create_with_limits(Box::pin(input), progress, limits)
+    };
+
+    // Throw.
+    {
+        let mut stream = create_stream(OverflowMode::Throw)?;
+        assert!(stream.next().await.unwrap().is_ok());
+        let error = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::QuotaExceeded("").code());
+    }
+
+    // Truncate.
+    {
+        let stream = create_stream(OverflowMode::Truncate)?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let rows: usize = result.iter().map(DataBlock::num_rows).sum();
+        assert_eq!(rows, 4);
+    }
+
+    Ok(())
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::convert::TryInto;
+use std::time::Duration;
 
 use common_arrow::arrow_flight::Action;
 use common_exception::ErrorCode;
@@ -28,6 +29,12 @@ pub struct ShuffleAction {
     pub plan: PlanNode,
     pub sinks: Vec<String>,
     pub scatters_expression: Expression,
+    /// Collect the processor statistics of the stage and send them back with its streams.
+    #[serde(default)]
+    pub profiling: bool,
+    /// The execution time left for the query in milliseconds, 0 means no limit.
+    #[serde(default)]
+    pub execution_timeout_millis: u64,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -36,6 +43,12 @@ pub struct BroadcastAction {
     pub stage_id: String,
     pub plan: PlanNode,
     pub sinks: Vec<String>,
+    /// Collect the processor statistics of the stage and send them back with its streams.
+    #[serde(default)]
+    pub profiling: bool,
+    /// The execution time left for the query in milliseconds, 0 means no limit.
+    #[serde(default)]
+    pub execution_timeout_millis: u64,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -155,6 +168,27 @@ impl FlightAction {
         }
     }
 
+    pub fn get_profiling(&self) -> bool {
+        match self {
+            FlightAction::BroadcastAction(action) => action.profiling,
+            FlightAction::PrepareShuffleAction(action) => action.profiling,
+            _ => unimplemented!(),
+        }
+    }
+
+    pub fn get_execution_timeout(&self) -> Option<Duration> {
+        let millis = match self {
+            FlightAction::BroadcastAction(action) => action.execution_timeout_millis,
+            FlightAction::PrepareShuffleAction(action) => action.execution_timeout_millis,
+            _ => unimplemented!(),
+        };
+
+        match millis {
+            0 => None,
+            millis => Some(Duration::from_millis(millis)),
+        }
+    }
+
     pub fn get_scatter_expression(&self) -> Option<Expression> {
         match self {
             FlightAction::BroadcastAction(_) => None,
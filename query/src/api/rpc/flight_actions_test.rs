@@ -32,6 +32,8 @@ async fn test_shuffle_action_try_into() -> Result<()> {
         plan: parse_query("SELECT number FROM numbers(5)")?,
         sinks: vec![String::from("stream_id")],
         scatters_expression: Expression::create_literal(DataValue::UInt64(Some(1))),
+        profiling: false,
+        execution_timeout_millis: 0,
     };
 
     let from_action = FlightAction::PrepareShuffleAction(shuffle_action);
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use common_arrow::arrow_flight::flight_service_client::FlightServiceClient;
 use common_arrow::arrow_flight::Action;
@@ -21,6 +22,7 @@ use common_arrow::arrow_flight::Ticket;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_infallible::RwLock;
 use common_runtime::tokio::time::Duration;
 use common_streams::SendableDataBlockStream;
 use tonic::transport::channel::Channel;
@@ -45,11 +47,14 @@ impl FlightClient {
         &mut self,
         ticket: FlightTicket,
         schema: DataSchemaRef,
+        profile: Arc<RwLock<Option<String>>>,
         timeout: u64,
     ) -> Result<SendableDataBlockStream> {
         let ticket = ticket.try_into()?;
         let inner = self.do_get(ticket, timeout).await?;
-        Ok(Box::pin(FlightDataStream::from_remote(schema, inner)))
+        Ok(Box::pin(FlightDataStream::from_remote(
+            schema, inner, profile,
+        )))
     }
 
     pub async fn execute_action(&mut self, action: FlightAction, timeout: u64) -> Result<()> {
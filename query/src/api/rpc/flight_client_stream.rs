@@ -20,6 +20,7 @@ use common_arrow::arrow_flight::FlightData;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
+use common_infallible::RwLock;
 use common_runtime::tokio::sync::mpsc::Receiver;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::Stream;
@@ -34,10 +35,17 @@ impl FlightDataStream {
     pub fn from_remote(
         schema: DataSchemaRef,
         inner: Streaming<FlightData>,
+        profile: Arc<RwLock<Option<String>>>,
     ) -> impl Stream<Item = Result<DataBlock, ErrorCode>> {
-        inner.map(move |flight_data| -> Result<DataBlock, ErrorCode> {
+        inner.filter_map(move |flight_data| -> Option<Result<DataBlock, ErrorCode>> {
             match flight_data {
-                Err(status) => Err(ErrorCode::UnknownException(status.message())),
+                Err(status) => Some(Err(ErrorCode::UnknownException(status.message()))),
+                // The remote stage profile comes as a last message without data.
+                Ok(flight_data) if flight_data.data_header.is_empty() => {
+                    let remote_profile = String::from_utf8_lossy(&flight_data.app_metadata);
+                    *profile.write() = Some(remote_profile.to_string());
+                    None
+                }
                 Ok(flight_data) => {
                     fn create_data_block(record_batch: RecordBatch) -> DataBlock {
                         let columns = record_batch
@@ -53,9 +61,10 @@ impl FlightDataStream {
                     }
 
                     let arrow_schema = Arc::new(schema.to_arrow());
-                    Ok(
+                    Some(
                         flight_data_to_arrow_batch(&flight_data, arrow_schema, true, &[])
-                            .map(create_data_block)?,
+                            .map(create_data_block)
+                            .map_err(ErrorCode::from),
                     )
                 }
             }
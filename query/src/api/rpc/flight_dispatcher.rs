@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
@@ -41,6 +42,7 @@ struct StreamInfo {
     schema: DataSchemaRef,
     tx: mpsc::Sender<Result<DataBlock>>,
     rx: mpsc::Receiver<Result<DataBlock>>,
+    profile: Arc<RwLock<Option<String>>>,
 }
 
 pub struct DatafuseQueryFlightDispatcher {
@@ -67,7 +69,15 @@ impl DatafuseQueryFlightDispatcher {
         self.abort.load(Ordering::Relaxed)
     }
 
-    pub fn get_stream(&self, ticket: &StreamTicket) -> Result<mpsc::Receiver<Result<DataBlock>>> {
+    /// Take the receiver of the stream, with the slot where the stage leaves its
+    /// profiled pipeline when the query runs with processor profiling.
+    pub fn get_stream(
+        &self,
+        ticket: &StreamTicket,
+    ) -> Result<(
+        mpsc::Receiver<Result<DataBlock>>,
+        Arc<RwLock<Option<String>>>,
+    )> {
         let stage_name = format!("{}/{}", ticket.query_id, ticket.stage_id);
         if let Some(notify) = self.stages_notify.write().remove(&stage_name) {
             notify.notify_waiters();
@@ -75,7 +85,7 @@ impl DatafuseQueryFlightDispatcher {
 
         let stream_name = format!("{}/{}", stage_name, ticket.stream);
         match self.streams.write().remove(&stream_name) {
-            Some(stream_info) => Ok(stream_info.rx),
+            Some(stream_info) => Ok((stream_info.rx, stream_info.profile)),
             None => Err(ErrorCode::NotFoundStream("Stream is not found")),
         }
     }
@@ -111,6 +121,12 @@ impl DatafuseQueryFlightDispatcher {
     fn one_sink_action(&self, session: SessionRef, action: &FlightAction) -> Result<()> {
         let query_context = session.create_context();
         let action_context = DatafuseQueryContext::new(query_context.clone());
+        if action.get_profiling() {
+            action_context.enable_processor_profiling();
+        }
+        if let Some(timeout) = action.get_execution_timeout() {
+            action_context.set_deadline(Some(Instant::now() + timeout));
+        }
         let pipeline_builder = PipelineBuilder::create(action_context.clone());
 
         let query_plan = action.get_plan();
@@ -126,8 +142,13 @@ impl DatafuseQueryFlightDispatcher {
         let stages_notify = self.stages_notify.clone();
 
         let stream_name = format!("{}/{}", stage_name, action_sinks[0]);
-        let tx_ref = self.streams.read().get(&stream_name).map(|x| x.tx.clone());
-        let tx = tx_ref.ok_or_else(|| ErrorCode::NotFoundStream("Not found stream"))?;
+        let stream_ref = self
+            .streams
+            .read()
+            .get(&stream_name)
+            .map(|x| (x.tx.clone(), x.profile.clone()));
+        let (tx, profile) =
+            stream_ref.ok_or_else(|| ErrorCode::NotFoundStream("Not found stream"))?;
 
         query_context.execute_task(async move {
             let _session = session;
@@ -149,6 +170,11 @@ impl DatafuseQueryFlightDispatcher {
                     }
                 }
             };
+
+            // Must be set before the tx is dropped, the end of the stream sends it.
+            if action_context.is_processor_profiling() {
+                *profile.write() = Some(format!("{:?}", pipeline));
+            }
         })?;
         Ok(())
     }
@@ -157,6 +183,12 @@ impl DatafuseQueryFlightDispatcher {
     where T: FlightScatter + Send + 'static {
         let query_context = session.create_context();
         let action_context = DatafuseQueryContext::new(query_context.clone());
+        if action.get_profiling() {
+            action_context.enable_processor_profiling();
+        }
+        if let Some(timeout) = action.get_execution_timeout() {
+            action_context.set_deadline(Some(Instant::now() + timeout));
+        }
         let pipeline_builder = PipelineBuilder::create(action_context.clone());
 
         let query_plan = action.get_plan();
@@ -166,16 +198,20 @@ impl DatafuseQueryFlightDispatcher {
         let action_query_id = action.get_query_id();
         let action_stage_id = action.get_stage_id();
 
-        let sinks_tx = {
+        let (sinks_tx, profile) = {
             let action_sinks = action.get_sinks();
 
             assert!(action_sinks.len() > 1);
             let mut sinks_tx = Vec::with_capacity(action_sinks.len());
+            let mut profile = None;
 
             for sink in &action_sinks {
                 let stream_name = format!("{}/{}/{}", action_query_id, action_stage_id, sink);
                 match self.streams.read().get(&stream_name) {
-                    Some(stream) => sinks_tx.push(stream.tx.clone()),
+                    Some(stream) => {
+                        sinks_tx.push(stream.tx.clone());
+                        profile = Some(stream.profile.clone());
+                    }
                     None => {
                         return Err(ErrorCode::NotFoundStream(format!(
                             "Not found stream {}",
@@ -185,7 +221,7 @@ impl DatafuseQueryFlightDispatcher {
                 }
             }
 
-            Result::Ok(sinks_tx)
+            Result::Ok((sinks_tx, profile))
         }?;
 
         let stage_name = format!("{}/{}", action_query_id, action_stage_id);
@@ -202,8 +238,9 @@ impl DatafuseQueryFlightDispatcher {
             wait_start(stage_name, stages_notify).await;
 
             let sinks_tx_ref = &sinks_tx;
+            let pipeline_ref = &mut pipeline;
             let forward_blocks = async move {
-                let mut abortable_stream = pipeline.execute().await?;
+                let mut abortable_stream = pipeline_ref.execute().await?;
                 while let Some(item) = abortable_stream.next().await {
                     let forward_blocks = flight_scatter.execute(&item?)?;
 
@@ -230,6 +267,11 @@ impl DatafuseQueryFlightDispatcher {
                     }
                 }
             }
+
+            // Must be set before the sinks_tx are dropped, the end of the streams sends it.
+            if let (true, Some(profile)) = (action_context.is_processor_profiling(), profile) {
+                *profile.write() = Some(format!("{:?}", pipeline));
+            }
         })?;
 
         Ok(())
@@ -248,6 +290,7 @@ impl DatafuseQueryFlightDispatcher {
             .insert(stage_name.clone(), Arc::new(Notify::new()));
 
         let mut streams = self.streams.write();
+        let profile = Arc::new(RwLock::new(None));
 
         for stream_name in streams_name {
             let (tx, rx) = mpsc::channel(5);
@@ -257,6 +300,7 @@ impl DatafuseQueryFlightDispatcher {
                 schema: schema.clone(),
                 tx,
                 rx,
+                profile: profile.clone(),
             });
         }
     }
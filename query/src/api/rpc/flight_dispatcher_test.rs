@@ -64,11 +64,13 @@ async fn test_run_shuffle_action_with_no_scatters() -> Result<()> {
                 plan: parse_query("SELECT number FROM numbers(5)")?,
                 sinks: vec![stream_id.clone()],
                 scatters_expression: Expression::create_literal(DataValue::UInt64(Some(1))),
+                profiling: false,
+                execution_timeout_millis: 0,
             }),
         )?;
 
         let stream = stream_ticket(&query_id, &stage_id, &stream_id);
-        let receiver = flight_dispatcher.get_stream(&stream)?;
+        let (receiver, _) = flight_dispatcher.get_stream(&stream)?;
         let receiver_stream = ReceiverStream::new(receiver);
         let collect_data_blocks = receiver_stream.collect::<Result<Vec<_>>>();
 
@@ -106,11 +108,13 @@ async fn test_run_shuffle_action_with_scatter() -> Result<()> {
                 plan: parse_query("SELECT number FROM numbers(5)")?,
                 sinks: vec!["stream_1".to_string(), "stream_2".to_string()],
                 scatters_expression: Expression::Column("number".to_string()),
+                profiling: false,
+                execution_timeout_millis: 0,
             }),
         )?;
 
         let stream_1 = stream_ticket(&query_id, &stage_id, "stream_1");
-        let receiver = flight_dispatcher.get_stream(&stream_1)?;
+        let (receiver, _) = flight_dispatcher.get_stream(&stream_1)?;
         let receiver_stream = ReceiverStream::new(receiver);
         let collect_data_blocks = receiver_stream.collect::<Result<Vec<_>>>();
 
@@ -127,7 +131,7 @@ async fn test_run_shuffle_action_with_scatter() -> Result<()> {
         assert_blocks_eq(expect, &collect_data_blocks.await?);
 
         let stream_2 = stream_ticket(&query_id, &stage_id, "stream_2");
-        let receiver = flight_dispatcher.get_stream(&stream_2)?;
+        let (receiver, _) = flight_dispatcher.get_stream(&stream_2)?;
         let receiver_stream = ReceiverStream::new(receiver);
         let collect_data_blocks = receiver_stream.collect::<Result<Vec<_>>>();
 
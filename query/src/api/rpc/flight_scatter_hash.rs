@@ -23,9 +23,9 @@ use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Expression;
+use common_planners::ExpressionExecutor;
 
 use crate::api::rpc::flight_scatter::FlightScatter;
-use crate::pipelines::transforms::ExpressionExecutor;
 
 pub struct HashFlightScatter {
     scatter_expression_executor: Arc<ExpressionExecutor>,
@@ -105,10 +105,11 @@ impl FlightService for DatafuseQueryFlightService {
 
         match ticket {
             FlightTicket::StreamTicket(steam_ticket) => {
-                let receiver = self.dispatcher.get_stream(&steam_ticket)?;
+                let (receiver, profile) = self.dispatcher.get_stream(&steam_ticket)?;
 
                 Ok(RawResponse::new(
-                    Box::pin(FlightDataStream::create(receiver)) as FlightStream<FlightData>,
+                    Box::pin(FlightDataStream::create(receiver, profile))
+                        as FlightStream<FlightData>,
                 ))
             }
         }
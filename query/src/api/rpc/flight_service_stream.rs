@@ -13,11 +13,13 @@
 // limitations under the License.
 
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
 use common_arrow::arrow_flight::utils::flight_data_from_arrow_batch;
 use common_arrow::arrow_flight::FlightData;
 use common_datablocks::DataBlock;
+use common_infallible::RwLock;
 use common_runtime::tokio::macros::support::Pin;
 use common_runtime::tokio::macros::support::Poll;
 use common_runtime::tokio::sync::mpsc::Receiver;
@@ -28,15 +30,35 @@ use tonic::Status;
 pub struct FlightDataStream {
     input: Receiver<common_exception::Result<DataBlock>>,
     options: IpcWriteOptions,
+    profile: Arc<RwLock<Option<String>>>,
+    profile_sent: bool,
 }
 
 impl FlightDataStream {
-    pub fn create(input: Receiver<common_exception::Result<DataBlock>>) -> FlightDataStream {
+    pub fn create(
+        input: Receiver<common_exception::Result<DataBlock>>,
+        profile: Arc<RwLock<Option<String>>>,
+    ) -> FlightDataStream {
         FlightDataStream {
             input,
             options: IpcWriteOptions::default(),
+            profile,
+            profile_sent: false,
         }
     }
+
+    // The profiled stage pipeline is sent as a last message without data.
+    fn take_profile(&mut self) -> Option<FlightData> {
+        if self.profile_sent {
+            return None;
+        }
+
+        self.profile_sent = true;
+        self.profile.read().as_ref().map(|profile| FlightData {
+            app_metadata: profile.as_bytes().to_vec(),
+            ..Default::default()
+        })
+    }
 }
 
 impl Stream for FlightDataStream {
@@ -44,7 +66,7 @@ impl Stream for FlightDataStream {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         self.input.poll_recv(cx).map(|x| match x {
-            None => None,
+            None => self.take_profile().map(Ok),
             Some(Err(error)) => Some(Err(Status::from(error))),
             Some(Ok(block)) => match block.try_into() {
                 Err(error) => Some(Err(Status::from(error))),
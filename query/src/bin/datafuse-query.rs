@@ -23,6 +23,7 @@ use datafuse_query::configs::Config;
 use datafuse_query::metrics::MetricService;
 use datafuse_query::servers::ClickHouseHandler;
 use datafuse_query::servers::MySQLHandler;
+use datafuse_query::servers::PostgresHandler;
 use datafuse_query::servers::Server;
 use datafuse_query::servers::ShutdownHandle;
 use datafuse_query::sessions::SessionManager;
@@ -103,6 +104,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
     }
 
+    // PostgreSQL handler.
+    {
+        let hostname = conf.postgres_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.postgres_handler_port);
+        let listening = listening.parse::<SocketAddr>()?;
+
+        let mut srv = PostgresHandler::create(session_manager.clone());
+        let listening = srv.start(listening).await?;
+        shutdown_handle.add_service(srv);
+
+        info!(
+            "PostgreSQL handler listening on {}, Usage: psql -h {} -p {}",
+            listening,
+            listening.ip(),
+            listening.port(),
+        );
+    }
+
     // Metric API service.
     {
         let listening = conf.metric_api_address.parse::<std::net::SocketAddr>()?;
@@ -224,6 +224,12 @@ impl Catalog for DatabaseCatalog {
         }
 
         match plan.engine {
+            DatabaseEngineType::Local if plan.clone_from.is_some() => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Local database engine does not support cloning database '{}'",
+                    plan.db
+                )));
+            }
             DatabaseEngineType::Local => {
                 let database = LocalDatabase::create();
                 self.databases.write().insert(plan.db, Arc::new(database));
@@ -28,6 +28,8 @@ use common_flights::meta_api_impl::DropDatabaseActionResult;
 use common_flights::meta_api_impl::DropTableActionResult;
 use common_flights::meta_api_impl::GetDatabaseActionResult;
 use common_flights::meta_api_impl::GetTableActionResult;
+use common_flights::meta_api_impl::SetTableStatisticsActionResult;
+use common_flights::meta_api_impl::TableStatisticsReply;
 use common_flights::storage_api_impl::AppendResult;
 use common_flights::storage_api_impl::BlockStream;
 use common_flights::storage_api_impl::ReadAction;
@@ -36,12 +38,14 @@ use common_flights::storage_api_impl::TruncateTableResult;
 use common_flights::StorageApi;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_metatypes::TableVersionAt;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
 use common_planners::ScanPlan;
 use common_planners::TableEngineType;
+use common_planners::TableStatistics;
 use common_runtime::tokio;
 use common_store_api::DatabaseMetaSnapshot;
 use common_store_api::MetaApi;
@@ -146,6 +150,7 @@ impl StorageApi for FakeStoreApis {
 
     async fn append_data(
         &mut self,
+        _query_id: String,
         _db_name: String,
         _tbl_name: String,
         _scheme_ref: DataSchemaRef,
@@ -262,6 +267,15 @@ impl MetaApi for FakeStoreApis {
         Err(ErrorCode::UnknownTable(""))
     }
 
+    async fn get_table_version(
+        &mut self,
+        _db: String,
+        _table: String,
+        _at: TableVersionAt,
+    ) -> Result<MetaVersion> {
+        todo!()
+    }
+
     async fn get_database_meta(&mut self, _current_ver: Option<u64>) -> Result<DatabaseMetaReply> {
         use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
         let tbl_metas = if self.inject_inconsistent_meta_state {
@@ -308,6 +322,23 @@ impl MetaApi for FakeStoreApis {
         });
         Ok(reply)
     }
+
+    async fn set_table_statistics(
+        &mut self,
+        _db: String,
+        _table: String,
+        _statistics: TableStatistics,
+    ) -> Result<SetTableStatisticsActionResult> {
+        todo!()
+    }
+
+    async fn get_table_statistics(
+        &mut self,
+        _db: String,
+        _table: String,
+    ) -> Result<TableStatisticsReply> {
+        todo!()
+    }
 }
 
 #[test]
@@ -501,6 +532,7 @@ async fn test_create_table() -> common_exception::Result<()> {
             schema: Arc::new(DataSchema::empty()),
             engine: JSONEachRow,
             options: Default::default(),
+            clone_from: None,
         })
         .await;
     assert!(res.is_err());
@@ -517,6 +549,7 @@ async fn test_create_table() -> common_exception::Result<()> {
             schema: Arc::new(DataSchema::empty()),
             engine: JSONEachRow,
             options: Default::default(),
+            clone_from: None,
         })
         .await;
     assert!(res.is_ok());
@@ -530,6 +563,7 @@ async fn test_create_table() -> common_exception::Result<()> {
             schema: Arc::new(DataSchema::empty()),
             engine: JSONEachRow,
             options: Default::default(),
+            clone_from: None,
         })
         .await;
     assert!(res.is_ok());
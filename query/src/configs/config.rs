@@ -61,10 +61,17 @@ const NUM_CPUS: &str = "QUERY_NUM_CPUS";
 const MYSQL_HANDLER_HOST: &str = "QUERY_MYSQL_HANDLER_HOST";
 const MYSQL_HANDLER_PORT: &str = "QUERY_MYSQL_HANDLER_PORT";
 const MAX_ACTIVE_SESSIONS: &str = "QUERY_MAX_ACTIVE_SESSIONS";
+const QUERY_LOG_MAX_ENTRIES: &str = "QUERY_QUERY_LOG_MAX_ENTRIES";
+const QUERY_LOG_FILE: &str = "QUERY_QUERY_LOG_FILE";
+const QUERY_CACHE_MAX_BYTES: &str = "QUERY_QUERY_CACHE_MAX_BYTES";
+const WORKLOAD_GROUPS: &str = "QUERY_WORKLOAD_GROUPS";
 
 const CLICKHOUSE_HANDLER_HOST: &str = "QUERY_CLICKHOUSE_HANDLER_HOST";
 const CLICKHOUSE_HANDLER_PORT: &str = "QUERY_CLICKHOUSE_HANDLER_PORT";
 
+const POSTGRES_HANDLER_HOST: &str = "QUERY_POSTGRES_HANDLER_HOST";
+const POSTGRES_HANDLER_PORT: &str = "QUERY_POSTGRES_HANDLER_PORT";
+
 const FLIGHT_API_ADDRESS: &str = "QUERY_FLIGHT_API_ADDRESS";
 const HTTP_API_ADDRESS: &str = "QUERY_HTTP_API_ADDRESS";
 const METRICS_API_ADDRESS: &str = "QUERY_METRIC_API_ADDRESS";
@@ -116,6 +123,38 @@ pub struct Config {
     )]
     pub max_active_sessions: u64,
 
+    #[structopt(
+    long,
+    env = QUERY_LOG_MAX_ENTRIES,
+    default_value = "10000",
+    help = "The number of finished queries kept in system.query_log"
+    )]
+    pub query_log_max_entries: u64,
+
+    #[structopt(
+    long,
+    env = QUERY_LOG_FILE,
+    default_value = "",
+    help = "Append the finished queries to this file and reload them on restart, disabled if empty. It holds 2 * query_log_max_entries queries at most"
+    )]
+    pub query_log_file: String,
+
+    #[structopt(
+    long,
+    env = QUERY_CACHE_MAX_BYTES,
+    default_value = "268435456",
+    help = "The memory size of the cached query results, the least recently used are evicted first"
+    )]
+    pub query_cache_max_bytes: u64,
+
+    #[structopt(
+    long,
+    env = WORKLOAD_GROUPS,
+    default_value = "",
+    help = "The workload groups limiting the concurrent queries of their users, separated by ';', e.g. adhoc:users=alice|bob,max_concurrent_queries=2,max_queue_length=16,queue_timeout=60"
+    )]
+    pub workload_groups: String,
+
     #[structopt(
     long,
     env = CLICKHOUSE_HANDLER_HOST,
@@ -130,6 +169,21 @@ pub struct Config {
     )]
     pub clickhouse_handler_port: u16,
 
+    #[structopt(
+    long,
+    env = POSTGRES_HANDLER_HOST,
+    default_value = "127.0.0.1",
+    help = "The PostgreSQL handler has no SSL, the clients send their passwords in cleartext, only listen on a trusted network"
+    )]
+    pub postgres_handler_host: String,
+
+    #[structopt(
+    long,
+    env = POSTGRES_HANDLER_PORT,
+    default_value = "5432"
+    )]
+    pub postgres_handler_port: u16,
+
     #[structopt(
     long,
     env = FLIGHT_API_ADDRESS,
@@ -295,8 +349,14 @@ impl Config {
             mysql_handler_host: "127.0.0.1".to_string(),
             mysql_handler_port: 3307,
             max_active_sessions: 256,
+            query_log_max_entries: 10000,
+            query_log_file: "".to_string(),
+            query_cache_max_bytes: 256 * 1024 * 1024,
+            workload_groups: "".to_string(),
             clickhouse_handler_host: "127.0.0.1".to_string(),
             clickhouse_handler_port: 9000,
+            postgres_handler_host: "127.0.0.1".to_string(),
+            postgres_handler_port: 5432,
             flight_api_address: "127.0.0.1:9090".to_string(),
             http_api_address: "127.0.0.1:8080".to_string(),
             metric_api_address: "127.0.0.1:7070".to_string(),
@@ -355,6 +415,20 @@ impl Config {
         env_helper!(mut_config, mysql_handler_host, String, MYSQL_HANDLER_HOST);
         env_helper!(mut_config, mysql_handler_port, u16, MYSQL_HANDLER_PORT);
         env_helper!(mut_config, max_active_sessions, u64, MAX_ACTIVE_SESSIONS);
+        env_helper!(
+            mut_config,
+            query_log_max_entries,
+            u64,
+            QUERY_LOG_MAX_ENTRIES
+        );
+        env_helper!(mut_config, query_log_file, String, QUERY_LOG_FILE);
+        env_helper!(
+            mut_config,
+            query_cache_max_bytes,
+            u64,
+            QUERY_CACHE_MAX_BYTES
+        );
+        env_helper!(mut_config, workload_groups, String, WORKLOAD_GROUPS);
         env_helper!(
             mut_config,
             clickhouse_handler_host,
@@ -367,6 +441,18 @@ impl Config {
             u16,
             CLICKHOUSE_HANDLER_PORT
         );
+        env_helper!(
+            mut_config,
+            postgres_handler_host,
+            String,
+            POSTGRES_HANDLER_HOST
+        );
+        env_helper!(
+            mut_config,
+            postgres_handler_port,
+            u16,
+            POSTGRES_HANDLER_PORT
+        );
         env_helper!(mut_config, flight_api_address, String, FLIGHT_API_ADDRESS);
         env_helper!(mut_config, http_api_address, String, HTTP_API_ADDRESS);
         env_helper!(mut_config, metric_api_address, String, METRICS_API_ADDRESS);
@@ -29,8 +29,14 @@ fn test_default_config() -> Result<()> {
         mysql_handler_host: "127.0.0.1".to_string(),
         mysql_handler_port: 3307,
         max_active_sessions: 256,
+        query_log_max_entries: 10000,
+        query_log_file: "".to_string(),
+        query_cache_max_bytes: 268435456,
+        workload_groups: "".to_string(),
         clickhouse_handler_host: "127.0.0.1".to_string(),
         clickhouse_handler_port: 9000,
+        postgres_handler_host: "127.0.0.1".to_string(),
+        postgres_handler_port: 5432,
         flight_api_address: "127.0.0.1:9090".to_string(),
         http_api_address: "127.0.0.1:8080".to_string(),
         metric_api_address: "127.0.0.1:7070".to_string(),
@@ -64,8 +70,17 @@ fn test_env_config() -> Result<()> {
     std::env::set_var("QUERY_MYSQL_HANDLER_HOST", "0.0.0.0");
     std::env::set_var("QUERY_MYSQL_HANDLER_PORT", "3306");
     std::env::set_var("QUERY_MAX_ACTIVE_SESSIONS", "255");
+    std::env::set_var("QUERY_QUERY_LOG_MAX_ENTRIES", "100");
+    std::env::set_var("QUERY_QUERY_LOG_FILE", "./_logs/query_log.json");
+    std::env::set_var("QUERY_QUERY_CACHE_MAX_BYTES", "1024");
+    std::env::set_var(
+        "QUERY_WORKLOAD_GROUPS",
+        "adhoc:users=alice,max_concurrent_queries=2",
+    );
     std::env::set_var("QUERY_CLICKHOUSE_HANDLER_HOST", "1.2.3.4");
     std::env::set_var("QUERY_CLICKHOUSE_HANDLER_PORT", "9000");
+    std::env::set_var("QUERY_POSTGRES_HANDLER_HOST", "1.2.3.4");
+    std::env::set_var("QUERY_POSTGRES_HANDLER_PORT", "5433");
     std::env::set_var("QUERY_FLIGHT_API_ADDRESS", "1.2.3.4:9091");
     std::env::set_var("QUERY_HTTP_API_ADDRESS", "1.2.3.4:8081");
     std::env::set_var("QUERY_METRIC_API_ADDRESS", "1.2.3.4:7071");
@@ -80,8 +95,17 @@ fn test_env_config() -> Result<()> {
     assert_eq!("0.0.0.0", configured.mysql_handler_host);
     assert_eq!(3306, configured.mysql_handler_port);
     assert_eq!(255, configured.max_active_sessions);
+    assert_eq!(100, configured.query_log_max_entries);
+    assert_eq!("./_logs/query_log.json", configured.query_log_file);
+    assert_eq!(1024, configured.query_cache_max_bytes);
+    assert_eq!(
+        "adhoc:users=alice,max_concurrent_queries=2",
+        configured.workload_groups
+    );
     assert_eq!("1.2.3.4", configured.clickhouse_handler_host);
     assert_eq!(9000, configured.clickhouse_handler_port);
+    assert_eq!("1.2.3.4", configured.postgres_handler_host);
+    assert_eq!(5433, configured.postgres_handler_port);
 
     assert_eq!("1.2.3.4:9091", configured.flight_api_address);
     assert_eq!("1.2.3.4:8081", configured.http_api_address);
@@ -97,9 +121,15 @@ fn test_env_config() -> Result<()> {
     std::env::remove_var("QUERY_MYSQL_HANDLER_HOST");
     std::env::remove_var("QUERY_MYSQL_HANDLER_PORT");
     std::env::remove_var("QUERY_MYSQL_HANDLER_THREAD_NUM");
+    std::env::remove_var("QUERY_QUERY_LOG_MAX_ENTRIES");
+    std::env::remove_var("QUERY_QUERY_LOG_FILE");
+    std::env::remove_var("QUERY_QUERY_CACHE_MAX_BYTES");
+    std::env::remove_var("QUERY_WORKLOAD_GROUPS");
     std::env::remove_var("QUERY_CLICKHOUSE_HANDLER_HOST");
     std::env::remove_var("QUERY_CLICKHOUSE_HANDLER_PORT");
     std::env::remove_var("QUERY_CLICKHOUSE_HANDLER_THREAD_NUM");
+    std::env::remove_var("QUERY_POSTGRES_HANDLER_HOST");
+    std::env::remove_var("QUERY_POSTGRES_HANDLER_PORT");
     std::env::remove_var("QUERY_FLIGHT_API_ADDRESS");
     std::env::remove_var("QUERY_HTTP_API_ADDRESS");
     std::env::remove_var("QUERY_METRIC_API_ADDRESS");
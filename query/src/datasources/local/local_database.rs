@@ -114,6 +114,13 @@ impl Database for LocalDatabase {
             };
         }
 
+        if plan.clone_from.is_some() {
+            return Err(ErrorCode::UnImplement(format!(
+                "Local database does not support cloning table '{}.{}'",
+                db_name, table_name
+            )));
+        }
+
         let table = match &plan.engine {
             TableEngineType::Parquet => {
                 ParquetTable::try_create(plan.db, plan.table, plan.schema, plan.options)?
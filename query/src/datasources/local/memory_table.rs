@@ -24,6 +24,7 @@ use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
 use common_planners::Statistics;
 use common_planners::TableOptions;
+use common_planners::TableStatistics;
 use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
 use futures::stream::StreamExt;
@@ -38,6 +39,7 @@ pub struct MemoryTable {
     name: String,
     schema: DataSchemaRef,
     blocks: Arc<RwLock<Vec<DataBlock>>>,
+    statistics: Arc<RwLock<Option<TableStatistics>>>,
 }
 
 impl MemoryTable {
@@ -52,6 +54,7 @@ impl MemoryTable {
             name,
             schema,
             blocks: Arc::new(RwLock::new(vec![])),
+            statistics: Arc::new(RwLock::new(None)),
         };
         Ok(Box::new(table))
     }
@@ -107,6 +110,10 @@ impl Table for MemoryTable {
         })
     }
 
+    fn statistics(&self, _ctx: DatafuseQueryContextRef) -> Result<Option<TableStatistics>> {
+        Ok(self.statistics.read().clone())
+    }
+
     async fn read(
         &self,
         ctx: DatafuseQueryContextRef,
@@ -150,4 +157,13 @@ impl Table for MemoryTable {
         blocks.clear();
         Ok(())
     }
+
+    async fn set_statistics(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        statistics: TableStatistics,
+    ) -> Result<()> {
+        *self.statistics.write() = Some(statistics);
+        Ok(())
+    }
 }
@@ -47,7 +47,11 @@ impl MemoryTableStream {
             block_ranges: vec![],
             blocks,
         });
-        ProgressStream::try_create(stream, ctx.progress_callback()?)
+        ProgressStream::try_create_with_limits(
+            stream,
+            ctx.progress_callback()?,
+            ctx.get_read_limits()?,
+        )
     }
 
     fn try_get_one_block(&mut self) -> Result<Option<DataBlock>> {
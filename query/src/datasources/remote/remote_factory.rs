@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_flights::session_api_impl::SessionApi;
 use common_flights::StoreClient;
 
 use crate::configs::Config;
@@ -36,6 +37,19 @@ impl RemoteFactory {
     pub fn store_client_provider(&self) -> StoreClientProvider {
         self.store_client_provider.clone()
     }
+
+    /// Cancels the reads and appends of the query in progress in the store.
+    /// It is best-effort: the query is aborted anyway, and the store streams end once the query goes away.
+    pub async fn kill_store_query(&self, query_id: String) {
+        let res = match self.store_client_provider.try_get_client().await {
+            Ok(mut client) => client.kill_query(query_id.clone()).await,
+            Err(cause) => Err(cause),
+        };
+
+        if let Err(cause) = res {
+            log::warn!("Failed to kill query {} in store: {}", query_id, cause);
+        }
+    }
 }
 struct ClientProvider {
     conf: Config,
@@ -19,12 +19,15 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_metatypes::MetaVersion;
+use common_metatypes::TableVersionAt;
 use common_planners::InsertIntoPlan;
 use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
 use common_planners::Statistics;
 use common_planners::TableOptions;
+use common_planners::TableStatistics;
 use common_planners::TruncateTablePlan;
 use common_store_api::ReadPlanResult;
 use common_streams::SendableDataBlockStream;
@@ -100,6 +103,54 @@ where T: 'static + StoreApis + Clone
             .map(|v| self.partitions_to_plan(v, scan.clone()))
     }
 
+    fn resolve_version(
+        &self,
+        ctx: DatafuseQueryContextRef,
+        at: TableVersionAt,
+    ) -> Result<MetaVersion> {
+        let (tx, rx) = channel();
+        let cli_provider = self.store_api_provider.clone();
+        let db_name = self.db.clone();
+        let tbl_name = self.name.clone();
+        ctx.execute_task(async move {
+            match cli_provider.try_get_store_apis().await {
+                Ok(mut client) => {
+                    let version = client.get_table_version(db_name, tbl_name, at).await;
+                    let _ = tx.send(version);
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                }
+            }
+        })?;
+
+        rx.recv().map_err(ErrorCode::from_std_error)?
+    }
+
+    fn current_version(&self, ctx: DatafuseQueryContextRef) -> Result<Option<MetaVersion>> {
+        self.resolve_version(ctx, TableVersionAt::Latest).map(Some)
+    }
+
+    fn statistics(&self, ctx: DatafuseQueryContextRef) -> Result<Option<TableStatistics>> {
+        let (tx, rx) = channel();
+        let cli_provider = self.store_api_provider.clone();
+        let db_name = self.db.clone();
+        let tbl_name = self.name.clone();
+        ctx.execute_task(async move {
+            match cli_provider.try_get_store_apis().await {
+                Ok(mut client) => {
+                    let statistics = client.get_table_statistics(db_name, tbl_name).await;
+                    let _ = tx.send(statistics);
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                }
+            }
+        })?;
+
+        rx.recv().map_err(ErrorCode::from_std_error)?
+    }
+
     async fn read(
         &self,
         ctx: DatafuseQueryContextRef,
@@ -108,7 +159,7 @@ where T: 'static + StoreApis + Clone
         self.do_read(ctx, source_plan).await
     }
 
-    async fn append_data(&self, _ctx: DatafuseQueryContextRef, plan: InsertIntoPlan) -> Result<()> {
+    async fn append_data(&self, ctx: DatafuseQueryContextRef, plan: InsertIntoPlan) -> Result<()> {
         let opt_stream = {
             let mut inner = plan.input_stream.lock();
             (*inner).take()
@@ -122,6 +173,7 @@ where T: 'static + StoreApis + Clone
 
             client
                 .append_data(
+                    ctx.get_id(),
                     plan.db_name.clone(),
                     plan.tbl_name.clone(),
                     (&plan).schema().clone(),
@@ -138,6 +190,18 @@ where T: 'static + StoreApis + Clone
         client.truncate(plan.db.clone(), plan.table.clone()).await?;
         Ok(())
     }
+
+    async fn set_statistics(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        statistics: TableStatistics,
+    ) -> Result<()> {
+        let mut client = self.store_api_provider.try_get_store_apis().await?;
+        client
+            .set_table_statistics(self.db.clone(), self.name.clone(), statistics)
+            .await?;
+        Ok(())
+    }
 }
 
 impl<T> RemoteTable<T>
@@ -36,14 +36,17 @@ where T: 'static + StoreApis + Clone
     ) -> Result<SendableDataBlockStream> {
         let client = self.store_api_provider.try_get_store_apis().await?;
         let progress_callback = ctx.progress_callback();
+        let read_limits = ctx.get_read_limits();
 
         let plan = source_plan.clone();
+        let query_id = ctx.get_id();
         let iter = std::iter::from_fn(move || match ctx.try_get_partitions(1) {
             Err(_) => None,
             Ok(parts) if parts.is_empty() => None,
             Ok(parts) => {
                 let plan = plan.clone();
                 Some(ReadAction {
+                    query_id: query_id.clone(),
                     part: parts[0].clone(),
                     push_down: PlanNode::ReadSource(plan),
                 })
@@ -68,7 +71,11 @@ where T: 'static + StoreApis + Clone
             }
         });
 
-        let stream = ProgressStream::try_create(Box::pin(streams.flatten()), progress_callback?)?;
+        let stream = ProgressStream::try_create_with_limits(
+            Box::pin(streams.flatten()),
+            progress_callback?,
+            read_limits?,
+        )?;
         Ok(Box::pin(stream))
     }
 }
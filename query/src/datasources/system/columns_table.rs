@@ -0,0 +1,144 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::catalog::Catalog;
+use crate::datasources::Table;
+use crate::sessions::DatafuseQueryContextRef;
+
+pub struct ColumnsTable {
+    schema: DataSchemaRef,
+}
+
+impl ColumnsTable {
+    pub fn create() -> Self {
+        ColumnsTable {
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("database", DataType::Utf8, false),
+                DataField::new("table", DataType::Utf8, false),
+                DataField::new("name", DataType::Utf8, false),
+                DataField::new("type", DataType::Utf8, false),
+                DataField::new("position", DataType::UInt64, false),
+                DataField::new("nullable", DataType::Boolean, false),
+                DataField::new("default", DataType::Utf8, true),
+            ]),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ColumnsTable {
+    fn name(&self) -> &str {
+        "columns"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemColumns"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            table_id: scan.table_id,
+            table_version: scan.table_version,
+            schema: self.schema.clone(),
+            parts: vec![Part {
+                name: "".to_string(),
+                version: 0,
+            }],
+            statistics: Statistics::default(),
+            description: "(Read from system.columns table)".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: DatafuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let database_tables = ctx.get_datasource().get_all_tables()?;
+
+        let mut databases = vec![];
+        let mut tables = vec![];
+        let mut names = vec![];
+        let mut types = vec![];
+        let mut positions = vec![];
+        let mut nullables = vec![];
+        let mut defaults: Vec<Option<String>> = vec![];
+
+        for (database, table) in database_tables.iter() {
+            let table = table.datasource();
+            let schema = table.schema()?;
+            for (position, field) in schema.fields().iter().enumerate() {
+                databases.push(database.clone());
+                tables.push(table.name().to_string());
+                names.push(field.name().clone());
+                types.push(format!("{}", field.data_type()));
+                // Positions start from 1, as in the select list.
+                positions.push(position as u64 + 1);
+                nullables.push(field.is_nullable());
+                // Columns have no default expression yet.
+                defaults.push(None);
+            }
+        }
+
+        let block = DataBlock::create_by_array(self.schema.clone(), vec![
+            Series::new(databases),
+            Series::new(tables),
+            Series::new(names),
+            Series::new(types),
+            Series::new(positions),
+            Series::new(nullables),
+            Series::new(defaults),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            vec![block],
+        )))
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::configs::Config;
+use crate::datasources::system::*;
+use crate::datasources::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_columns_table() -> Result<()> {
+    let config = Config {
+        disable_remote_catalog: true,
+        ..Config::default()
+    };
+
+    let ctx = crate::tests::try_create_context_with_conf(config)?;
+    let table = ColumnsTable::create();
+    let source_plan = table.read_plan(
+        ctx.clone(),
+        &ScanPlan::empty(),
+        ctx.get_settings().get_max_threads()? as usize,
+    )?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 7);
+
+    // Keep the columns of system.tables only.
+    let tables = block.try_column_by_name("table")?.to_values()?;
+    let indices = tables
+        .iter()
+        .enumerate()
+        .filter(|(_, table)| **table == DataValue::Utf8(Some("tables".to_string())))
+        .map(|(index, _)| index as u32)
+        .collect::<Vec<_>>();
+    let block = DataBlock::block_take_by_indices(block, &[], &indices)?;
+
+    let expected = vec![
+        "+----------+--------+----------+------+----------+----------+---------+",
+        "| database | table  | name     | type | position | nullable | default |",
+        "+----------+--------+----------+------+----------+----------+---------+",
+        "| system   | tables | database | Utf8 | 1        | false    |         |",
+        "| system   | tables | name     | Utf8 | 2        | false    |         |",
+        "| system   | tables | engine   | Utf8 | 3        | false    |         |",
+        "+----------+--------+----------+------+----------+----------+---------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, &[block]);
+
+    Ok(())
+}
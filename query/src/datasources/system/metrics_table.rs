@@ -0,0 +1,179 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::Table;
+use crate::metrics::try_get_prometheus_handle;
+use crate::sessions::DatafuseQueryContextRef;
+
+pub struct MetricsTable {
+    schema: DataSchemaRef,
+}
+
+/// One sample of the prometheus exposition text.
+struct MetricSample {
+    metric: String,
+    kind: String,
+    labels: String,
+    value: f64,
+}
+
+impl MetricsTable {
+    pub fn create() -> Self {
+        MetricsTable {
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("metric", DataType::Utf8, false),
+                DataField::new("kind", DataType::Utf8, false),
+                DataField::new("labels", DataType::Utf8, false),
+                DataField::new("value", DataType::Float64, false),
+            ]),
+        }
+    }
+
+    // The recorder only exposes its values as the prometheus text format,
+    // a sample line is `name{labels} value` after its `# TYPE name kind` line.
+    // Histograms are rendered as summaries with `_sum` and `_count` samples.
+    fn parse_samples(text: &str) -> Vec<MetricSample> {
+        let mut kind = "untyped";
+        let mut samples = vec![];
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(comment) = line.strip_prefix('#') {
+                let mut words = comment.split_whitespace();
+                if let (Some("TYPE"), Some(_), Some(metric_kind)) =
+                    (words.next(), words.next(), words.next())
+                {
+                    kind = metric_kind;
+                }
+                continue;
+            }
+
+            let (series, value) = match line.rsplit_once(' ') {
+                Some((series, value)) => (series.trim(), value),
+                None => continue,
+            };
+            let value = match value.parse::<f64>() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            let (metric, labels) = match series.split_once('{') {
+                Some((metric, labels)) => (metric, labels.trim_end_matches('}')),
+                None => (series, ""),
+            };
+
+            samples.push(MetricSample {
+                metric: metric.to_string(),
+                kind: kind.to_string(),
+                labels: labels.to_string(),
+                value,
+            });
+        }
+        samples
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for MetricsTable {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemMetrics"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            table_id: scan.table_id,
+            table_version: scan.table_version,
+            schema: self.schema.clone(),
+            parts: vec![Part {
+                name: "".to_string(),
+                version: 0,
+            }],
+            statistics: Statistics::default(),
+            description: "(Read from system.metrics table)".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let handle = try_get_prometheus_handle()?;
+        let samples = Self::parse_samples(&handle.render());
+
+        let mut metrics = Vec::with_capacity(samples.len());
+        let mut kinds = Vec::with_capacity(samples.len());
+        let mut labels = Vec::with_capacity(samples.len());
+        let mut values = Vec::with_capacity(samples.len());
+        for sample in samples {
+            metrics.push(sample.metric);
+            kinds.push(sample.kind);
+            labels.push(sample.labels);
+            values.push(sample.value);
+        }
+
+        let block = DataBlock::create_by_array(self.schema.clone(), vec![
+            Series::new(metrics),
+            Series::new(kinds),
+            Series::new(labels),
+            Series::new(values),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            vec![block],
+        )))
+    }
+}
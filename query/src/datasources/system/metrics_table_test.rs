@@ -0,0 +1,55 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use metrics::counter;
+
+use crate::datasources::system::*;
+use crate::datasources::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_metrics_table() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let table = MetricsTable::create();
+    let source_plan = table.read_plan(
+        ctx.clone(),
+        &ScanPlan::empty(),
+        ctx.get_settings().get_max_threads()? as usize,
+    )?;
+
+    // Reading the table installs the recorder, so the counter is kept.
+    table.read(ctx.clone(), &source_plan).await?;
+    counter!("test.system_metrics_table", 3);
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 4);
+
+    let metrics = block.try_column_by_name("metric")?.to_values()?;
+    let row = metrics
+        .iter()
+        .position(|v| *v == DataValue::Utf8(Some("test_system_metrics_table".to_string())))
+        .expect("the counter is in system.metrics");
+
+    let kind = block.try_column_by_name("kind")?.try_get(row)?;
+    let value = block.try_column_by_name("value")?.try_get(row)?;
+    assert_eq!(kind, DataValue::Utf8(Some("counter".to_string())));
+    assert_eq!(value, DataValue::Float64(Some(3.0)));
+
+    Ok(())
+}
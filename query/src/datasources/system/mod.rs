@@ -15,14 +15,20 @@
 #[cfg(test)]
 mod clusters_table_test;
 #[cfg(test)]
+mod columns_table_test;
+#[cfg(test)]
 mod contributors_table_test;
 #[cfg(test)]
 mod databases_table_test;
 #[cfg(test)]
 mod functions_table_test;
 #[cfg(test)]
+mod metrics_table_test;
+#[cfg(test)]
 mod numbers_table_test;
 #[cfg(test)]
+mod query_log_table_test;
+#[cfg(test)]
 mod settings_table_test;
 #[cfg(test)]
 mod tables_table_test;
@@ -30,13 +36,16 @@ mod tables_table_test;
 mod tracing_table_test;
 
 mod clusters_table;
+mod columns_table;
 mod contributors_table;
 mod databases_table;
 mod functions_table;
+mod metrics_table;
 mod numbers_stream;
 mod numbers_table;
 mod one_table;
 mod processes_table;
+mod query_log_table;
 mod settings_table;
 mod system_database;
 mod system_factory;
@@ -45,13 +54,16 @@ mod tracing_table;
 mod tracing_table_stream;
 
 pub use clusters_table::ClustersTable;
+pub use columns_table::ColumnsTable;
 pub use contributors_table::ContributorsTable;
 pub use databases_table::DatabasesTable;
 pub use functions_table::FunctionsTable;
+pub use metrics_table::MetricsTable;
 pub use numbers_stream::NumbersStream;
 pub use numbers_table::NumbersTable;
 pub use one_table::OneTable;
 pub use processes_table::ProcessesTable;
+pub use query_log_table::QueryLogTable;
 pub use settings_table::SettingsTable;
 pub use system_database::SystemDatabase;
 pub use system_factory::SystemFactory;
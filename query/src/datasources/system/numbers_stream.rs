@@ -48,7 +48,11 @@ impl NumbersStream {
             block_index: 0,
             blocks: vec![],
         });
-        ProgressStream::try_create(stream, ctx.progress_callback()?)
+        ProgressStream::try_create_with_limits(
+            stream,
+            ctx.progress_callback()?,
+            ctx.get_read_limits()?,
+        )
     }
 
     #[inline]
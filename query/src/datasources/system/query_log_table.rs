@@ -0,0 +1,160 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::Table;
+use crate::sessions::DatafuseQueryContextRef;
+
+pub struct QueryLogTable {
+    schema: DataSchemaRef,
+}
+
+impl QueryLogTable {
+    pub fn create() -> Self {
+        QueryLogTable {
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("query_id", DataType::Utf8, false),
+                DataField::new("user", DataType::Utf8, true),
+                DataField::new("query_text", DataType::Utf8, false),
+                DataField::new("query_start_time", DataType::UInt64, false),
+                DataField::new("query_end_time", DataType::UInt64, false),
+                DataField::new("query_duration_ms", DataType::UInt64, false),
+                DataField::new("read_rows", DataType::UInt64, false),
+                DataField::new("read_bytes", DataType::UInt64, false),
+                DataField::new("result_rows", DataType::UInt64, false),
+                DataField::new("error_code", DataType::UInt16, true),
+                DataField::new("error_message", DataType::Utf8, true),
+                DataField::new("client_address", DataType::Utf8, true),
+            ]),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QueryLogTable {
+    fn name(&self) -> &str {
+        "query_log"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemQueryLog"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            table_id: scan.table_id,
+            table_version: scan.table_version,
+            schema: self.schema.clone(),
+            parts: vec![Part {
+                name: "".to_string(),
+                version: 0,
+            }],
+            statistics: Statistics::default(),
+            description: "(Read from system.query_log table)".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: DatafuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let query_log = ctx.get_sessions_manager().get_query_log();
+        let entries = query_log.entries();
+
+        let mut query_id = Vec::with_capacity(entries.len());
+        let mut user = Vec::with_capacity(entries.len());
+        let mut query_text = Vec::with_capacity(entries.len());
+        let mut query_start_time = Vec::with_capacity(entries.len());
+        let mut query_end_time = Vec::with_capacity(entries.len());
+        let mut query_duration_ms = Vec::with_capacity(entries.len());
+        let mut read_rows = Vec::with_capacity(entries.len());
+        let mut read_bytes = Vec::with_capacity(entries.len());
+        let mut result_rows = Vec::with_capacity(entries.len());
+        let mut error_code = Vec::with_capacity(entries.len());
+        let mut error_message = Vec::with_capacity(entries.len());
+        let mut client_address = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            query_id.push(entry.query_id);
+            user.push(entry.user);
+            query_text.push(entry.query_text);
+            query_start_time.push(entry.query_start_time);
+            query_end_time.push(entry.query_end_time);
+            query_duration_ms.push(entry.query_end_time.saturating_sub(entry.query_start_time));
+            read_rows.push(entry.read_rows);
+            read_bytes.push(entry.read_bytes);
+            result_rows.push(entry.result_rows);
+            error_code.push(entry.error_code);
+            error_message.push(entry.error_message);
+            client_address.push(entry.client_address);
+        }
+
+        let schema = self.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(query_id),
+            Series::new(user),
+            Series::new(query_text),
+            Series::new(query_start_time),
+            Series::new(query_end_time),
+            Series::new(query_duration_ms),
+            Series::new(read_rows),
+            Series::new(read_bytes),
+            Series::new(result_rows),
+            Series::new(error_code),
+            Series::new(error_message),
+            Series::new(client_address),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::system::*;
+use crate::datasources::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_log_table() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    ctx.attach_query_str("SELECT 1");
+    ctx.log_query_finish(1, None);
+    ctx.attach_query_str("SELECT x");
+    ctx.log_query_finish(0, Some(&ErrorCode::UnknownException("Unknown column x")));
+
+    let table = QueryLogTable::create();
+    let source_plan = table.read_plan(
+        ctx.clone(),
+        &ScanPlan::empty(),
+        ctx.get_settings().get_max_threads()? as usize,
+    )?;
+
+    let stream = table.read(ctx.clone(), &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 12);
+    assert_eq!(block.num_rows(), 2);
+
+    let query_id = DataValue::Utf8(Some(ctx.get_id()));
+    assert_eq!(block.first("query_id")?, query_id);
+    assert_eq!(
+        block.first("query_text")?,
+        DataValue::Utf8(Some("SELECT 1".into()))
+    );
+    assert_eq!(block.first("result_rows")?, DataValue::UInt64(Some(1)));
+    assert_eq!(block.first("error_code")?, DataValue::UInt16(None));
+
+    assert_eq!(
+        block.last("query_text")?,
+        DataValue::Utf8(Some("SELECT x".into()))
+    );
+    assert_eq!(block.last("error_code")?, DataValue::UInt16(Some(1000)));
+    assert_eq!(
+        block.last("error_message")?,
+        DataValue::Utf8(Some("Unknown column x".into()))
+    );
+
+    Ok(())
+}
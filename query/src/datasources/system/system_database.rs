@@ -65,6 +65,9 @@ impl SystemDatabase {
             Arc::new(system::DatabasesTable::create()),
             Arc::new(system::TracingTable::create()),
             Arc::new(system::ProcessesTable::create()),
+            Arc::new(system::QueryLogTable::create()),
+            Arc::new(system::ColumnsTable::create()),
+            Arc::new(system::MetricsTable::create()),
         ];
         let tbl_meta_list = table_list
             .iter()
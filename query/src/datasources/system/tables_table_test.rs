@@ -46,14 +46,17 @@ async fn test_tables_table() -> Result<()> {
         "| database | name          | engine             |",
         "+----------+---------------+--------------------+",
         "| system   | clusters      | SystemClusters     |",
+        "| system   | columns       | SystemColumns      |",
         "| system   | contributors  | SystemContributors |",
         "| system   | databases     | SystemDatabases    |",
         "| system   | functions     | SystemFunctions    |",
+        "| system   | metrics       | SystemMetrics      |",
         "| system   | numbers       | SystemNumbers      |",
         "| system   | numbers_local | SystemNumbersLocal |",
         "| system   | numbers_mt    | SystemNumbersMt    |",
         "| system   | one           | SystemOne          |",
         "| system   | processes     | SystemProcesses    |",
+        "| system   | query_log     | SystemQueryLog     |",
         "| system   | settings      | SystemSettings     |",
         "| system   | tables        | SystemTables       |",
         "| system   | tracing       | SystemTracing      |",
@@ -18,9 +18,12 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_metatypes::MetaVersion;
+use common_metatypes::TableVersionAt;
 use common_planners::InsertIntoPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
+use common_planners::TableStatistics;
 use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
 
@@ -41,6 +44,27 @@ pub trait Table: Sync + Send {
         scan: &ScanPlan,
         partitions: usize,
     ) -> Result<ReadDataSourcePlan>;
+    // Resolve the version of the table as of a point in its history, for time travel reads.
+    fn resolve_version(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        at: TableVersionAt,
+    ) -> Result<MetaVersion> {
+        Err(ErrorCode::UnImplement(format!(
+            "reading table {} at {} is not supported by engine {}",
+            self.name(),
+            at,
+            self.engine()
+        )))
+    }
+    // Get the version a read without a version is pinned to, None if the table has no versions.
+    fn current_version(&self, _ctx: DatafuseQueryContextRef) -> Result<Option<MetaVersion>> {
+        Ok(None)
+    }
+    // Get the statistics collected by the last `ANALYZE TABLE`, for the cost model.
+    fn statistics(&self, _ctx: DatafuseQueryContextRef) -> Result<Option<TableStatistics>> {
+        Ok(None)
+    }
     // Read block data from the underling.
     async fn read(
         &self,
@@ -70,6 +94,19 @@ pub trait Table: Sync + Send {
             self.name()
         )))
     }
+
+    // Keep the statistics collected by `ANALYZE TABLE`.
+    async fn set_statistics(
+        &self,
+        _ctx: DatafuseQueryContextRef,
+        _statistics: TableStatistics,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "analyze for table {} is not supported by engine {}",
+            self.name(),
+            self.engine()
+        )))
+    }
 }
 
 pub type TablePtr = Arc<dyn Table>;
@@ -41,6 +41,7 @@ async fn test_datasource() -> Result<()> {
                 db: "test_db".to_string(),
                 engine: DatabaseEngineType::Local,
                 options: Default::default(),
+                clone_from: None,
             })
             .await?;
 
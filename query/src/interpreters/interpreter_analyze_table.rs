@@ -0,0 +1,211 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::is_decimal;
+use common_datavalues::is_numeric;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AnalyzeTablePlan;
+use common_planners::ColumnStatistics;
+use common_planners::Histogram;
+use common_planners::PlanNode;
+use common_planners::TableStatistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures::TryStreamExt;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::interpreters::SelectInterpreter;
+use crate::sessions::DatafuseQueryContextRef;
+use crate::sql::PlanParser;
+
+/// The number of buckets of the histogram of a numeric column.
+pub const HISTOGRAM_BUCKETS: usize = 8;
+
+pub struct AnalyzeTableInterpreter {
+    ctx: DatafuseQueryContextRef,
+    plan: AnalyzeTablePlan,
+}
+
+/// How much of a column is analyzed, depending on what the aggregate functions support.
+#[derive(Clone, Copy, PartialEq)]
+enum ColumnKind {
+    /// NDV, null count, min, max and histogram.
+    Numeric,
+    /// NDV, null count, min and max.
+    Ordered,
+    /// Not analyzed.
+    Other,
+}
+
+impl ColumnKind {
+    fn of(data_type: &DataType) -> ColumnKind {
+        if is_numeric(data_type) {
+            ColumnKind::Numeric
+        } else if is_decimal(data_type) || data_type == &DataType::Utf8 {
+            ColumnKind::Ordered
+        } else {
+            ColumnKind::Other
+        }
+    }
+
+    /// The number of aggregates computed for a column of this kind.
+    fn aggregates(&self) -> usize {
+        match self {
+            ColumnKind::Numeric => 4 + HISTOGRAM_BUCKETS - 1,
+            ColumnKind::Ordered => 4,
+            ColumnKind::Other => 0,
+        }
+    }
+}
+
+impl AnalyzeTableInterpreter {
+    pub fn try_create(
+        ctx: DatafuseQueryContextRef,
+        plan: AnalyzeTablePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AnalyzeTableInterpreter { ctx, plan }))
+    }
+
+    /// Builds a query computing the statistics of all the analyzed columns in one table scan.
+    fn statistics_query(&self, schema: &DataSchemaRef) -> String {
+        let mut aggregates = vec!["count()".to_string()];
+        for field in schema.fields() {
+            let kind = ColumnKind::of(field.data_type());
+            if kind == ColumnKind::Other {
+                continue;
+            }
+
+            let column = format!("`{}`", field.name());
+            aggregates.push(format!("count({})", column));
+            aggregates.push(format!("uniqHLL({})", column));
+            aggregates.push(format!("min({})", column));
+            aggregates.push(format!("max({})", column));
+            if kind == ColumnKind::Numeric {
+                for bucket in 1..HISTOGRAM_BUCKETS {
+                    let level = bucket as f64 / HISTOGRAM_BUCKETS as f64;
+                    aggregates.push(format!("quantile({})({})", level, column));
+                }
+            }
+        }
+
+        format!(
+            "SELECT {} FROM `{}`.`{}`",
+            aggregates.join(", "),
+            self.plan.db,
+            self.plan.table
+        )
+    }
+
+    fn to_statistics(schema: &DataSchemaRef, block: &DataBlock) -> Result<TableStatistics> {
+        let value = |index: usize| block.column(index).try_get(0);
+        let row_count = value(0)?.as_u64()?;
+
+        let mut columns = vec![];
+        let mut index = 1;
+        for field in schema.fields() {
+            let kind = ColumnKind::of(field.data_type());
+            if kind == ColumnKind::Other {
+                continue;
+            }
+
+            let not_null_count = value(index)?.as_u64()?;
+            let min = value(index + 2)?;
+            let max = value(index + 3)?;
+            let histogram = match kind {
+                ColumnKind::Numeric if not_null_count > 0 => {
+                    let mut bounds = vec![min.as_f64()?];
+                    for bucket in 1..HISTOGRAM_BUCKETS {
+                        bounds.push(value(index + 3 + bucket)?.as_f64()?);
+                    }
+                    bounds.push(max.as_f64()?);
+                    Some(Histogram { bounds })
+                }
+                _ => None,
+            };
+
+            columns.push(ColumnStatistics {
+                name: field.name().clone(),
+                ndv: value(index + 1)?.as_u64()?.min(not_null_count),
+                null_count: row_count - not_null_count,
+                min: if not_null_count > 0 {
+                    min
+                } else {
+                    DataValue::Null
+                },
+                max: if not_null_count > 0 {
+                    max
+                } else {
+                    DataValue::Null
+                },
+                histogram,
+            });
+            index += kind.aggregates();
+        }
+
+        Ok(TableStatistics { row_count, columns })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AnalyzeTableInterpreter {
+    fn name(&self) -> &str {
+        "AnalyzeTableInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let table = self
+            .ctx
+            .get_table(self.plan.db.as_str(), self.plan.table.as_str())?;
+        let schema = table.datasource().schema()?;
+
+        let query = self.statistics_query(&schema);
+        let select = match PlanParser::create(self.ctx.clone()).build_from_sql(&query)? {
+            PlanNode::Select(select) => select,
+            other => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Statistics query must be a select, but got {}",
+                    other.name()
+                )))
+            }
+        };
+        let stream = SelectInterpreter::try_create(self.ctx.clone(), select)?
+            .execute()
+            .await?;
+        let blocks = stream.try_collect::<Vec<_>>().await?;
+        let block = blocks
+            .iter()
+            .find(|block| block.num_rows() > 0)
+            .ok_or_else(|| ErrorCode::EmptyData("Statistics query returned no rows"))?;
+
+        let statistics = Self::to_statistics(&schema, block)?;
+        table
+            .datasource()
+            .set_statistics(self.ctx.clone(), statistics)
+            .await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_analyze_table_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create table.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.a(a Int64, b String, c Int64) Engine = Memory")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Insert into.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "insert into default.a values(1, 'x', 10), (2, 'y', 10), (3, 'y', 10), (4, 'z', 10)",
+        )? {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // A table is not analyzed until ANALYZE TABLE.
+    let table = ctx.get_table("default", "a")?;
+    assert!(table.datasource().statistics(ctx.clone())?.is_none());
+
+    // Analyze table.
+    {
+        if let PlanNode::AnalyzeTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("analyze table default.a")?
+        {
+            let executor = AnalyzeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "AnalyzeTableInterpreter");
+
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    let statistics = table.datasource().statistics(ctx.clone())?.unwrap();
+    assert_eq!(statistics.row_count, 4);
+    assert_eq!(statistics.columns.len(), 3);
+
+    let a = statistics.column("a").unwrap();
+    assert_eq!(a.ndv, 4);
+    assert_eq!(a.null_count, 0);
+    assert_eq!(a.min, DataValue::Int64(Some(1)));
+    assert_eq!(a.max, DataValue::Int64(Some(4)));
+    let histogram = a.histogram.as_ref().unwrap();
+    assert_eq!(histogram.buckets(), 8);
+    assert_eq!(histogram.bounds.first(), Some(&1.0));
+    assert_eq!(histogram.bounds.last(), Some(&4.0));
+
+    let b = statistics.column("b").unwrap();
+    assert_eq!(b.ndv, 3);
+    assert_eq!(b.min, DataValue::Utf8(Some("x".to_string())));
+    assert_eq!(b.max, DataValue::Utf8(Some("z".to_string())));
+    assert!(b.histogram.is_none());
+
+    let c = statistics.column("c").unwrap();
+    assert_eq!(c.ndv, 1);
+
+    Ok(())
+}
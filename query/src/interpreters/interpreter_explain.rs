@@ -16,14 +16,17 @@ use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::ExplainPlan;
 use common_planners::ExplainType;
+use common_planners::PlanNode;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
+use crate::interpreters::SelectInterpreter;
 use crate::optimizers::Optimizers;
 use crate::pipelines::processors::PipelineBuilder;
 use crate::sessions::DatafuseQueryContextRef;
@@ -46,6 +49,7 @@ impl Interpreter for ExplainInterpreter {
             ExplainType::Graph => self.explain_graph(),
             ExplainType::Syntax => self.explain_syntax(),
             ExplainType::Pipeline => self.explain_pipeline(),
+            ExplainType::Analyze => self.explain_analyze().await,
         }?;
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -90,4 +94,21 @@ impl ExplainInterpreter {
         let formatted_pipeline = Series::new(format!("{:?}", pipeline).lines().collect::<Vec<_>>());
         Ok(DataBlock::create_by_array(schema, vec![formatted_pipeline]))
     }
+
+    async fn explain_analyze(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let select = match self.explain.input.as_ref() {
+            PlanNode::Select(select) => Ok(select.clone()),
+            other => Err(ErrorCode::SyntaxException(format!(
+                "EXPLAIN ANALYZE only supports SELECT, but got {}",
+                other.name()
+            ))),
+        }?;
+
+        // Every pipeline of the query, local and remote, counts what its processors produce.
+        self.ctx.enable_processor_profiling();
+        let pipeline = SelectInterpreter::execute_to_end(self.ctx.clone(), select).await?;
+        let formatted_pipeline = Series::new(format!("{:?}", pipeline).lines().collect::<Vec<_>>());
+        Ok(DataBlock::create_by_array(schema, vec![formatted_pipeline]))
+    }
 }
@@ -54,3 +54,56 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_analyze_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::Explain(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("explain analyze select sum(number) from numbers_mt(10)")?
+    {
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let block = &result[0];
+        assert_eq!(block.num_columns(), 1);
+
+        let lines = (0..block.num_rows())
+            .map(|row| block.column(0).try_get(row)?.to_string())
+            .collect::<Result<Vec<_>>>()?;
+
+        // Every processor is annotated with what it produced.
+        assert!(lines[0].starts_with("ProjectionTransform × 1 processor [rows: 1, blocks: 1, "));
+        assert!(lines
+            .iter()
+            .all(|line| line.trim().is_empty() || line.contains(" [rows: ")));
+
+        let source = lines.last().unwrap();
+        assert!(source.trim_start().starts_with("SourceTransform × "));
+        assert!(source.contains("[rows: 10, "));
+    } else {
+        assert!(false)
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_analyze_not_select() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::Explain(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("explain analyze set max_threads = 1")?
+    {
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let result = executor.execute().await;
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.code(), 5);
+        }
+    } else {
+        assert!(false)
+    }
+
+    Ok(())
+}
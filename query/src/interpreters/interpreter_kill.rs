@@ -21,6 +21,7 @@ use common_planners::KillPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
+use crate::datasources::remote::RemoteFactory;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatafuseQueryContextRef;
@@ -44,21 +45,38 @@ impl Interpreter for KillInterpreter {
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         let id = &self.plan.id;
-        match self.ctx.get_sessions_manager().get_session(id) {
-            None => Err(ErrorCode::UnknownSession(format!(
-                "Not found session id {}",
-                id
-            ))),
-            Some(kill_session) if self.plan.kill_connection => {
-                kill_session.force_kill_session();
-                let schema = Arc::new(DataSchema::empty());
-                Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
-            }
-            Some(kill_session) => {
-                kill_session.force_kill_query();
-                let schema = Arc::new(DataSchema::empty());
-                Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
+        let kill_session = match self.ctx.get_sessions_manager().get_session(id) {
+            None => {
+                return Err(ErrorCode::UnknownSession(format!(
+                    "Not found session id {}",
+                    id
+                )))
             }
+            Some(kill_session) => kill_session,
+        };
+
+        let query_id = kill_session.get_running_query_id();
+        match self.plan.kill_connection {
+            true => kill_session.force_kill_session(),
+            false => kill_session.force_kill_query(),
+        }
+
+        if let Some(query_id) = query_id {
+            self.kill_store_query(query_id).await;
         }
+
+        let schema = Arc::new(DataSchema::empty());
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
+    }
+}
+
+impl KillInterpreter {
+    async fn kill_store_query(&self, query_id: String) {
+        let conf = self.ctx.get_config();
+        if conf.disable_remote_catalog {
+            return;
+        }
+
+        RemoteFactory::new(&conf).kill_store_query(query_id).await;
     }
 }
@@ -17,13 +17,16 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::Context;
+use std::time::Duration;
 
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_exception::Result;
+use common_planners::PlanNode;
 use common_planners::SelectPlan;
 use common_runtime::tokio::macros::support::Pin;
 use common_runtime::tokio::macros::support::Poll;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use futures::Stream;
@@ -35,7 +38,9 @@ use crate::clusters::Node;
 use crate::interpreters::plan_scheduler::PlanScheduler;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
+use crate::interpreters::QueryResultCache;
 use crate::optimizers::Optimizers;
+use crate::pipelines::processors::Pipeline;
 use crate::pipelines::processors::PipelineBuilder;
 use crate::sessions::DatafuseQueryContextRef;
 
@@ -48,6 +53,39 @@ impl SelectInterpreter {
     pub fn try_create(ctx: DatafuseQueryContextRef, select: SelectPlan) -> Result<InterpreterPtr> {
         Ok(Arc::new(SelectInterpreter { ctx, select }))
     }
+
+    /// Run the query to the end and return its local pipeline, which holds the
+    /// processor statistics when the context has processor profiling enabled.
+    pub async fn execute_to_end(
+        ctx: DatafuseQueryContextRef,
+        select: SelectPlan,
+    ) -> Result<Pipeline> {
+        let interpreter = SelectInterpreter { ctx, select };
+
+        let mut scheduled = Scheduled::new();
+        let timeout = interpreter.ctx.get_settings().get_flight_client_timeout()?;
+        let scheduling = async {
+            let plan = interpreter.optimize()?;
+            let mut in_local_pipeline =
+                interpreter.schedule_pipeline(&plan, &mut scheduled).await?;
+            let stream = in_local_pipeline.execute().await?;
+            Result::Ok((in_local_pipeline, stream))
+        };
+
+        match scheduling.await {
+            Ok((in_local_pipeline, stream)) => {
+                let mut stream = ScheduledStream::create(scheduled, stream, interpreter.ctx);
+                while let Some(block) = stream.next().await {
+                    block?;
+                }
+                Ok(in_local_pipeline)
+            }
+            Err(error) => {
+                Self::error_handler(scheduled, &interpreter.ctx, timeout).await;
+                Err(error)
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -58,11 +96,39 @@ impl Interpreter for SelectInterpreter {
 
     #[tracing::instrument(level = "info", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
     async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let optimized_plan = self.optimize()?;
+
+        let settings = self.ctx.get_settings();
+        let query_cache = self.ctx.get_sessions_manager().get_query_cache();
+        // With the read limits, the result may be truncated, and a cached result would be
+        // returned without reading anything, thus the cache is not used at all.
+        let read_limited =
+            settings.get_max_rows_to_read()? != 0 || settings.get_max_bytes_to_read()? != 0;
+        let cache_key = match settings.get_use_query_cache()? {
+            0 => None,
+            _ if read_limited => None,
+            _ => QueryResultCache::cache_key(&optimized_plan)?,
+        };
+
+        if let Some(cache_key) = &cache_key {
+            let ttl = Duration::from_secs(settings.get_query_cache_ttl()?);
+            if let Some(blocks) = query_cache.get(cache_key, ttl) {
+                let schema = self.select.schema();
+                return Ok(Box::pin(DataBlockStream::create(schema, None, blocks)));
+            }
+        }
+
         // TODO: maybe panic?
         let mut scheduled = Scheduled::new();
-        let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
-        match self.schedule_query(&mut scheduled).await {
-            Ok(stream) => Ok(ScheduledStream::create(scheduled, stream, self.ctx.clone())),
+        let timeout = settings.get_flight_client_timeout()?;
+        match self.schedule_query(&optimized_plan, &mut scheduled).await {
+            Ok(stream) => {
+                let stream = ScheduledStream::create(scheduled, stream, self.ctx.clone());
+                Ok(match cache_key {
+                    None => stream,
+                    Some(cache_key) => query_cache.cache_stream(cache_key, stream),
+                })
+            }
             Err(error) => {
                 Self::error_handler(scheduled, &self.ctx, timeout).await;
                 Err(error)
@@ -78,11 +144,26 @@ impl Interpreter for SelectInterpreter {
 type Scheduled = HashMap<String, Arc<Node>>;
 
 impl SelectInterpreter {
-    async fn schedule_query(&self, scheduled: &mut Scheduled) -> Result<SendableDataBlockStream> {
-        let optimized_plan = Optimizers::create(self.ctx.clone()).optimize(&self.select.input)?;
+    fn optimize(&self) -> Result<PlanNode> {
+        Optimizers::create(self.ctx.clone()).optimize(&self.select.input)
+    }
 
+    async fn schedule_query(
+        &self,
+        plan: &PlanNode,
+        scheduled: &mut Scheduled,
+    ) -> Result<SendableDataBlockStream> {
+        let mut in_local_pipeline = self.schedule_pipeline(plan, scheduled).await?;
+        in_local_pipeline.execute().await
+    }
+
+    async fn schedule_pipeline(
+        &self,
+        plan: &PlanNode,
+        scheduled: &mut Scheduled,
+    ) -> Result<Pipeline> {
         let scheduler = PlanScheduler::try_create(self.ctx.clone())?;
-        let scheduled_tasks = scheduler.reschedule(&optimized_plan)?;
+        let scheduled_tasks = scheduler.reschedule(plan)?;
         let remote_stage_actions = scheduled_tasks.get_tasks()?;
 
         let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
@@ -95,8 +176,7 @@ impl SelectInterpreter {
         }
 
         let pipeline_builder = PipelineBuilder::create(self.ctx.clone());
-        let mut in_local_pipeline = pipeline_builder.build(&scheduled_tasks.get_local_task())?;
-        in_local_pipeline.execute().await
+        pipeline_builder.build(&scheduled_tasks.get_local_task())
     }
 
     async fn error_handler(scheduled: Scheduled, context: &DatafuseQueryContextRef, timeout: u64) {
@@ -20,6 +20,7 @@ use common_datavalues::DataType;
 use common_exception::Result;
 use common_planners::SettingPlan;
 use common_streams::DataBlockStream;
+use common_streams::OverflowMode;
 use common_streams::SendableDataBlockStream;
 
 use crate::interpreters::Interpreter;
@@ -46,13 +47,23 @@ impl Interpreter for SettingInterpreter {
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         let plan = self.set.clone();
         for var in plan.vars {
-            match var.variable.to_lowercase().as_str() {
+            let name = var.variable.to_lowercase();
+            self.ctx.check_profile_limit(&name, &var.value)?;
+            match name.as_str() {
                 // To be compatible with some drivers
                 "sql_mode" | "autocommit" => {}
                 "max_threads" => {
                     let threads: u64 = var.value.parse()?;
                     self.ctx.get_settings().set_max_threads(threads)?;
                 }
+                // Checked here, so that a bad mode fails the SET rather than the next queries.
+                "read_overflow_mode" | "result_overflow_mode" => {
+                    let mode = var.value.trim_matches('\'').to_lowercase();
+                    mode.parse::<OverflowMode>()?;
+                    self.ctx
+                        .get_settings()
+                        .update_settings(&var.variable, mode)?;
+                }
                 _ => {
                     self.ctx
                         .get_settings()
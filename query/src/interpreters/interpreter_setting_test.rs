@@ -12,13 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_management::UserProfile;
 use common_planners::*;
 use common_runtime::tokio;
 use futures::stream::StreamExt;
 use pretty_assertions::assert_eq;
 
+use crate::clusters::Cluster;
+use crate::configs::Config;
 use crate::interpreters::*;
+use crate::sessions::DatafuseQueryContextRef;
+use crate::sessions::SessionManager;
 use crate::sql::*;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -58,3 +64,79 @@ async fn test_setting_interpreter_error() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_setting_interpreter_overflow_mode() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::SetVariable(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("set result_overflow_mode='Truncate'")?
+    {
+        let executor = SettingInterpreter::try_create(ctx.clone(), plan)?;
+        executor.execute().await?;
+        let mode = ctx.get_settings().get_result_overflow_mode()?;
+        assert_eq!("truncate", mode);
+    } else {
+        assert!(false)
+    }
+
+    if let PlanNode::SetVariable(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("set read_overflow_mode=break")?
+    {
+        let executor = SettingInterpreter::try_create(ctx, plan)?;
+        if let Err(e) = executor.execute().await {
+            let expect =
+                "Code: 6, displayText = Unknown overflow mode break, expected throw or truncate.";
+            assert_eq!(expect, format!("{}", e));
+        } else {
+            assert!(false);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_setting_interpreter_profile_limit() -> Result<()> {
+    let sessions = SessionManager::from_conf(Config::default(), Cluster::empty())?;
+    let session = sessions.create_session("TestSession")?;
+    let settings = [("max_result_rows", "100"), ("max_block_size", "1000")];
+    session.apply_profile(&UserProfile {
+        settings: settings
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    })?;
+    let ctx = session.create_context();
+
+    async fn set(ctx: &DatafuseQueryContextRef, query: &str) -> Result<()> {
+        match PlanParser::create(ctx.clone()).build_from_sql(query)? {
+            PlanNode::SetVariable(plan) => {
+                let executor = SettingInterpreter::try_create(ctx.clone(), plan)?;
+                executor.execute().await?;
+                Ok(())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // A limit of the profile can be lowered, but not raised or removed.
+    set(&ctx, "set max_result_rows=50").await?;
+    assert_eq!(50, ctx.get_settings().get_max_result_rows()?);
+    for query in ["set max_result_rows=1000", "set max_result_rows=0"] {
+        let e = set(&ctx, query).await.unwrap_err();
+        assert_eq!(
+            ErrorCode::PermissionDenied("").code(),
+            e.code(),
+            "{}",
+            query
+        );
+    }
+    assert_eq!(50, ctx.get_settings().get_max_result_rows()?);
+
+    // The other settings of the profile are defaults only.
+    set(&ctx, "set max_block_size=2000").await?;
+    assert_eq!(2000, ctx.get_settings().get_max_block_size()?);
+
+    Ok(())
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod interpreter_analyze_table_test;
 #[cfg(test)]
 mod interpreter_database_create_test;
 #[cfg(test)]
@@ -36,8 +38,11 @@ mod interpreter_truncate_table_test;
 mod interpreter_use_database_test;
 #[cfg(test)]
 mod plan_scheduler_test;
+#[cfg(test)]
+mod query_result_cache_test;
 
 mod interpreter;
+mod interpreter_analyze_table;
 mod interpreter_database_create;
 mod interpreter_database_drop;
 mod interpreter_describe_table;
@@ -54,9 +59,11 @@ mod interpreter_truncate_table;
 mod interpreter_use_database;
 #[allow(clippy::needless_range_loop)]
 mod plan_scheduler;
+mod query_result_cache;
 
 pub use interpreter::Interpreter;
 pub use interpreter::InterpreterPtr;
+pub use interpreter_analyze_table::AnalyzeTableInterpreter;
 pub use interpreter_database_create::CreateDatabaseInterpreter;
 pub use interpreter_database_drop::DropDatabaseInterpreter;
 pub use interpreter_describe_table::DescribeTableInterpreter;
@@ -70,3 +77,5 @@ pub use interpreter_table_create::CreateTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
 pub use interpreter_truncate_table::TruncateTableInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
+pub use query_result_cache::QueryResultCache;
+pub use query_result_cache::QueryResultCacheKey;
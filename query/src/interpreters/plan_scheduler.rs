@@ -41,6 +41,7 @@ use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::WindowPlan;
 use common_tracing::tracing;
 
 use crate::api::BroadcastAction;
@@ -172,6 +173,8 @@ impl PlanScheduler {
             plan: input.clone(),
             sinks: self.cluster_nodes.clone(),
             scatters_expression: stage.scatters_expr.clone(),
+            profiling: self.query_context.is_processor_profiling(),
+            execution_timeout_millis: self.query_context.get_remaining_execution_millis(),
         }
     }
 
@@ -212,6 +215,8 @@ impl PlanScheduler {
             plan: input.clone(),
             sinks: self.cluster_nodes.clone(),
             scatters_expression: stage.scatters_expr.clone(),
+            profiling: self.query_context.is_processor_profiling(),
+            execution_timeout_millis: self.query_context.get_remaining_execution_millis(),
         }
     }
 
@@ -255,6 +260,8 @@ impl PlanScheduler {
             plan: input.clone(),
             sinks: vec![self.cluster_nodes[self.local_pos].clone()],
             scatters_expression: stage.scatters_expr.clone(),
+            profiling: self.query_context.is_processor_profiling(),
+            execution_timeout_millis: self.query_context.get_remaining_execution_millis(),
         }
     }
 
@@ -301,6 +308,7 @@ impl PlanScheduler {
             PlanNode::Projection(plan) => self.visit_projection(plan, tasks),
             PlanNode::Filter(plan) => self.visit_filter(plan, tasks),
             PlanNode::Sort(plan) => self.visit_sort(plan, tasks),
+            PlanNode::Window(plan) => self.visit_window(plan, tasks),
             PlanNode::Limit(plan) => self.visit_limit(plan, tasks),
             PlanNode::LimitBy(plan) => self.visit_limit_by(plan, tasks),
             PlanNode::ReadSource(plan) => self.visit_data_source(plan, tasks),
@@ -435,6 +443,8 @@ impl PlanScheduler {
             query_id: self.query_context.get_id(),
             plan: input.clone(),
             sinks: self.cluster_nodes.clone(),
+            profiling: self.query_context.is_processor_profiling(),
+            execution_timeout_millis: self.query_context.get_remaining_execution_millis(),
         }
     }
 
@@ -726,6 +736,33 @@ impl PlanScheduler {
         }
     }
 
+    fn visit_window(&mut self, plan: &WindowPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref(), tasks)?;
+        match self.running_mode {
+            RunningMode::Cluster => self.visit_cluster_window(plan),
+            RunningMode::Standalone => self.visit_local_window(plan),
+        };
+        Ok(())
+    }
+
+    fn visit_local_window(&mut self, plan: &WindowPlan) {
+        self.nodes_plan[self.local_pos] = PlanNode::Window(WindowPlan {
+            schema: plan.schema.clone(),
+            window_exprs: plan.window_exprs.clone(),
+            input: Arc::new(self.nodes_plan[self.local_pos].clone()),
+        });
+    }
+
+    fn visit_cluster_window(&mut self, plan: &WindowPlan) {
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] = PlanNode::Window(WindowPlan {
+                schema: plan.schema.clone(),
+                window_exprs: plan.window_exprs.clone(),
+                input: Arc::new(self.nodes_plan[index].clone()),
+            });
+        }
+    }
+
     fn visit_limit(&mut self, plan: &LimitPlan, tasks: &mut Tasks) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref(), tasks)?;
         match self.running_mode {
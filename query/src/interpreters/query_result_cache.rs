@@ -0,0 +1,314 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_infallible::Mutex;
+use common_metatypes::MetaId;
+use common_metatypes::MetaVersion;
+use common_planners::Expression;
+use common_planners::ExpressionVisitor;
+use common_planners::PlanNode;
+use common_planners::PlanVisitor;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Recursion;
+use common_planners::RemotePlan;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
+use lru::LruCache;
+
+/// The cached results are found by the optimized plan and the versions of the
+/// tables it reads. The plan holds the versions already, they are kept apart to
+/// drop the results read at an older version once a newer one is seen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryResultCacheKey {
+    plan: String,
+    tables: Vec<(MetaId, MetaVersion)>,
+}
+
+struct CachedResult {
+    blocks: Vec<DataBlock>,
+    bytes: usize,
+    tables: Vec<(MetaId, MetaVersion)>,
+    created: Instant,
+}
+
+struct CacheState {
+    results: LruCache<String, Arc<CachedResult>>,
+    bytes: usize,
+    // The newest version of each table seen by the cache.
+    latest_versions: HashMap<MetaId, MetaVersion>,
+}
+
+/// The results of the SELECTs over versioned tables, shared by all the
+/// sessions. The least recently used are evicted once they take more than
+/// `query_cache_max_bytes`.
+pub struct QueryResultCache {
+    max_bytes: usize,
+    state: Mutex<CacheState>,
+}
+
+impl QueryResultCache {
+    pub fn create(max_bytes: usize) -> QueryResultCache {
+        QueryResultCache {
+            max_bytes,
+            state: Mutex::new(CacheState {
+                results: LruCache::unbounded(),
+                bytes: 0,
+                latest_versions: HashMap::new(),
+            }),
+        }
+    }
+
+    /// The key of the plan's result, None if a table it reads has no version:
+    /// its data may change while the version stays, such as a memory table,
+    /// or if it calls a non-deterministic function, such as `now()`.
+    pub fn cache_key(plan: &PlanNode) -> Result<Option<QueryResultCacheKey>> {
+        let mut scanned = ScannedTables {
+            tables: vec![],
+            cacheable: true,
+        };
+        scanned.visit_plan_node(plan)?;
+
+        if !scanned.cacheable {
+            return Ok(None);
+        }
+
+        let mut tables = scanned.tables;
+        tables.sort_unstable();
+        tables.dedup();
+        Ok(Some(QueryResultCacheKey {
+            plan: serde_json::to_string(plan)?,
+            tables,
+        }))
+    }
+
+    /// The cached result if it is younger than the ttl, and the tables have
+    /// no newer version.
+    pub fn get(&self, key: &QueryResultCacheKey, ttl: Duration) -> Option<Vec<DataBlock>> {
+        let mut state = self.state.lock();
+        state.see_versions(&key.tables);
+
+        let cached = state.results.get(&key.plan).cloned()?;
+        if cached.created.elapsed() > ttl {
+            state.remove(key.plan.clone());
+            return None;
+        }
+        Some(cached.blocks.clone())
+    }
+
+    pub fn put(&self, key: QueryResultCacheKey, blocks: Vec<DataBlock>) {
+        let bytes = blocks
+            .iter()
+            .map(|block| block.memory_size())
+            .sum::<usize>();
+        if bytes > self.max_bytes {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        state.see_versions(&key.tables);
+        if state.is_outdated(&key.tables) {
+            return;
+        }
+
+        state.remove(key.plan.clone());
+        state.bytes += bytes;
+        state.results.put(
+            key.plan,
+            Arc::new(CachedResult {
+                blocks,
+                bytes,
+                tables: key.tables,
+                created: Instant::now(),
+            }),
+        );
+
+        while state.bytes > self.max_bytes {
+            match state.results.pop_lru() {
+                None => break,
+                Some((_, evicted)) => state.bytes -= evicted.bytes,
+            }
+        }
+    }
+
+    pub fn get_bytes(&self) -> usize {
+        self.state.lock().bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cache the blocks of the stream once it ends without an error,
+    /// unless they would not fit in the cache.
+    pub fn cache_stream(
+        self: &Arc<Self>,
+        key: QueryResultCacheKey,
+        inner: SendableDataBlockStream,
+    ) -> SendableDataBlockStream {
+        Box::pin(CachingStream {
+            cache: self.clone(),
+            key: Some(key),
+            blocks: vec![],
+            bytes: 0,
+            inner,
+        })
+    }
+}
+
+impl CacheState {
+    fn remove(&mut self, plan: String) {
+        if let Some(removed) = self.results.pop(&plan) {
+            self.bytes -= removed.bytes;
+        }
+    }
+
+    fn is_outdated(&self, tables: &[(MetaId, MetaVersion)]) -> bool {
+        tables.iter().any(|(table_id, version)| {
+            matches!(self.latest_versions.get(table_id), Some(latest) if latest > version)
+        })
+    }
+
+    // Drop the results read at an older version of the tables.
+    fn see_versions(&mut self, tables: &[(MetaId, MetaVersion)]) {
+        let mut newer = false;
+        for (table_id, version) in tables {
+            let latest = self.latest_versions.entry(*table_id).or_insert(*version);
+            if *latest < *version {
+                *latest = *version;
+                newer = true;
+            }
+        }
+
+        if newer {
+            let outdated = self
+                .results
+                .iter()
+                .filter(|(_, cached)| self.is_outdated(&cached.tables))
+                .map(|(plan, _)| plan.clone())
+                .collect::<Vec<_>>();
+
+            for plan in outdated {
+                self.remove(plan);
+            }
+        }
+    }
+}
+
+struct ScannedTables {
+    tables: Vec<(MetaId, MetaVersion)>,
+    cacheable: bool,
+}
+
+impl PlanVisitor for ScannedTables {
+    fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
+        match plan.table_version {
+            Some(version) => self.tables.push((plan.table_id, version)),
+            None => self.cacheable = false,
+        }
+        Ok(())
+    }
+
+    fn visit_remote(&mut self, _: &RemotePlan) -> Result<()> {
+        self.cacheable = false;
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &Expression) -> Result<()> {
+        match expr {
+            Expression::Subquery { query_plan, .. }
+            | Expression::ScalarSubquery { query_plan, .. } => {
+                self.visit_subquery_plan(query_plan.as_ref())
+            }
+            _ => {
+                let finder = expr.accept(NonDeterministicFinder { found: false })?;
+                if finder.found {
+                    self.cacheable = false;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+struct NonDeterministicFinder {
+    found: bool,
+}
+
+impl ExpressionVisitor for NonDeterministicFinder {
+    fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+        if let Expression::ScalarFunction { op, .. } = expr {
+            let deterministic = FunctionFactory::get(op)
+                .map(|function| function.is_deterministic())
+                .unwrap_or(false);
+            if !deterministic {
+                self.found = true;
+                return Ok(Recursion::Stop(self));
+            }
+        }
+        Ok(Recursion::Continue(self))
+    }
+}
+
+struct CachingStream {
+    cache: Arc<QueryResultCache>,
+    // Taken when the result cannot be cached.
+    key: Option<QueryResultCacheKey>,
+    blocks: Vec<DataBlock>,
+    bytes: usize,
+    inner: SendableDataBlockStream,
+}
+
+impl Stream for CachingStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.inner.poll_next_unpin(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(block))) if self.key.is_some() => {
+                self.bytes += block.memory_size();
+                match self.bytes > self.cache.max_bytes {
+                    true => {
+                        self.key = None;
+                        self.blocks.clear();
+                    }
+                    false => self.blocks.push(block.clone()),
+                }
+            }
+            Poll::Ready(Some(Err(_))) => self.key = None,
+            Poll::Ready(None) => {
+                if let Some(key) = self.key.take() {
+                    let blocks = std::mem::take(&mut self.blocks);
+                    self.cache.put(key, blocks);
+                }
+            }
+            _ => {}
+        }
+        poll
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use common_streams::DataBlockStream;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::PlanParser;
+
+fn read_plan(table_id: u64, version: u64) -> PlanNode {
+    PlanNode::ReadSource(ReadDataSourcePlan::empty(table_id, Some(version)))
+}
+
+fn block(numbers: Vec<u64>) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    DataBlock::create_by_array(schema, vec![Series::new(numbers)])
+}
+
+fn key(plan: &PlanNode) -> Result<QueryResultCacheKey> {
+    Ok(QueryResultCache::cache_key(plan)?.expect("the plan reads versioned tables"))
+}
+
+#[test]
+fn test_query_result_cache_key() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // The tables without versions may change at any time.
+    let plan =
+        PlanParser::create(ctx.clone()).build_from_sql("select number from numbers_mt(10)")?;
+    assert_eq!(QueryResultCache::cache_key(&plan)?, None);
+    let plan = PlanParser::create(ctx).build_from_sql("select * from system.settings")?;
+    assert_eq!(QueryResultCache::cache_key(&plan)?, None);
+
+    assert_eq!(key(&read_plan(1, 2))?, key(&read_plan(1, 2))?);
+    assert!(key(&read_plan(1, 2))? != key(&read_plan(1, 3))?);
+
+    // A non-deterministic function may give another result at the same versions.
+    let filter = |predicate: Expression| {
+        PlanNode::Filter(FilterPlan {
+            predicate,
+            input: Arc::new(read_plan(1, 2)),
+            schema: read_plan(1, 2).schema(),
+        })
+    };
+    let database = Expression::ScalarFunction {
+        op: "database".to_string(),
+        args: vec![],
+    };
+    let plan = filter(database.eq(lit("default")));
+    assert_eq!(QueryResultCache::cache_key(&plan)?, None);
+    let plan = filter(col("a").eq(lit(1u64)));
+    assert!(QueryResultCache::cache_key(&plan)?.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_query_result_cache_ttl() -> Result<()> {
+    let cache = QueryResultCache::create(1024 * 1024);
+    let ttl = Duration::from_secs(60);
+
+    let key = key(&read_plan(1, 1))?;
+    assert!(cache.get(&key, ttl).is_none());
+
+    cache.put(key.clone(), vec![block(vec![1, 2, 3])]);
+    let cached = cache.get(&key, ttl).expect("the result is cached");
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].num_rows(), 3);
+
+    std::thread::sleep(Duration::from_millis(10));
+    assert!(cache.get(&key, Duration::from_millis(1)).is_none());
+    assert!(cache.is_empty());
+    assert_eq!(cache.get_bytes(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_query_result_cache_newer_version() -> Result<()> {
+    let cache = QueryResultCache::create(1024 * 1024);
+    let ttl = Duration::from_secs(60);
+
+    let old = key(&read_plan(1, 1))?;
+    let other_table = key(&read_plan(2, 1))?;
+    cache.put(old.clone(), vec![block(vec![1])]);
+    cache.put(other_table.clone(), vec![block(vec![1])]);
+    assert_eq!(cache.len(), 2);
+
+    // A query reads the table at a newer version, the old result is dropped.
+    let new = key(&read_plan(1, 2))?;
+    assert!(cache.get(&new, ttl).is_none());
+    assert!(cache.get(&old, ttl).is_none());
+    assert!(cache.get(&other_table, ttl).is_some());
+
+    // A result read at the older version is not cached anymore.
+    cache.put(old.clone(), vec![block(vec![1])]);
+    assert!(cache.get(&old, ttl).is_none());
+    assert_eq!(cache.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_query_result_cache_eviction() -> Result<()> {
+    let one_block_bytes = block(vec![1, 2, 3]).memory_size();
+    let cache = QueryResultCache::create(one_block_bytes * 2);
+    let ttl = Duration::from_secs(60);
+
+    let first = key(&read_plan(1, 1))?;
+    let second = key(&read_plan(2, 1))?;
+    let third = key(&read_plan(3, 1))?;
+    cache.put(first.clone(), vec![block(vec![1, 2, 3])]);
+    cache.put(second.clone(), vec![block(vec![1, 2, 3])]);
+
+    // The first one is used lately, the second one is evicted.
+    assert!(cache.get(&first, ttl).is_some());
+    cache.put(third.clone(), vec![block(vec![1, 2, 3])]);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get_bytes(), one_block_bytes * 2);
+    assert!(cache.get(&second, ttl).is_none());
+    assert!(cache.get(&first, ttl).is_some());
+    assert!(cache.get(&third, ttl).is_some());
+
+    // Too large to be cached at all.
+    let large = key(&read_plan(4, 1))?;
+    cache.put(large.clone(), vec![block(vec![1, 2, 3]); 3]);
+    assert!(cache.get(&large, ttl).is_none());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_result_cache_stream() -> Result<()> {
+    let cache = Arc::new(QueryResultCache::create(1024 * 1024));
+    let ttl = Duration::from_secs(60);
+    let key = key(&read_plan(1, 1))?;
+
+    let schema = block(vec![]).schema().clone();
+    let blocks = vec![block(vec![1, 2]), block(vec![3])];
+    let stream = Box::pin(DataBlockStream::create(schema, None, blocks));
+    let stream = cache.cache_stream(key.clone(), stream);
+
+    // Cached once the stream ends.
+    assert!(cache.get(&key, ttl).is_none());
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 2);
+
+    let cached = cache.get(&key, ttl).expect("the result is cached");
+    assert_eq!(cached.len(), 2);
+    assert_eq!(cached[1].num_rows(), 1);
+    Ok(())
+}
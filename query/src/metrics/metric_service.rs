@@ -19,6 +19,7 @@ use std::sync::Arc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
+use common_infallible::Mutex;
 use common_runtime::tokio;
 use common_runtime::tokio::sync::Notify;
 use common_runtime::tokio::task::JoinHandle;
@@ -31,6 +32,33 @@ use warp::Reply;
 
 use crate::servers::Server;
 
+lazy_static::lazy_static! {
+    static ref PROMETHEUS_HANDLE: Mutex<Option<PrometheusHandle>> = Mutex::new(None);
+}
+
+/// The handle of the process wide prometheus recorder, installed on first use.
+/// Both the metric api and system.metrics render the metrics through it.
+pub fn try_get_prometheus_handle() -> Result<PrometheusHandle> {
+    let mut handle = PROMETHEUS_HANDLE.lock();
+    if let Some(handle) = handle.as_ref() {
+        return Ok(handle.clone());
+    }
+
+    let builder = PrometheusBuilder::new();
+    let prometheus_recorder = builder.build();
+    let prometheus_handle = prometheus_recorder.handle();
+    match metrics::set_boxed_recorder(Box::new(prometheus_recorder)) {
+        Ok(_) => {
+            *handle = Some(prometheus_handle.clone());
+            Ok(prometheus_handle)
+        }
+        Err(error) => Err(ErrorCode::InitPrometheusFailure(format!(
+            "Cannot init prometheus recorder. cause: {}",
+            error
+        ))),
+    }
+}
+
 pub struct MetricService {
     abort_notify: Arc<Notify>,
     join_handle: Option<JoinHandle<()>>,
@@ -44,19 +72,6 @@ impl MetricService {
         })
     }
 
-    fn create_prometheus_handle() -> Result<PrometheusHandle> {
-        let builder = PrometheusBuilder::new();
-        let prometheus_recorder = builder.build();
-        let prometheus_handle = prometheus_recorder.handle();
-        match metrics::set_boxed_recorder(Box::new(prometheus_recorder)) {
-            Ok(_) => Ok(prometheus_handle),
-            Err(error) => Err(ErrorCode::InitPrometheusFailure(format!(
-                "Cannot init prometheus recorder. cause: {}",
-                error
-            ))),
-        }
-    }
-
     fn shutdown_notify(&self) -> impl Future<Output = ()> + 'static {
         let notified = self.abort_notify.clone();
         async move {
@@ -81,7 +96,7 @@ impl Server for MetricService {
     }
 
     async fn start(&mut self, listening: SocketAddr) -> Result<SocketAddr> {
-        let handle = MetricService::create_prometheus_handle()?;
+        let handle = try_get_prometheus_handle()?;
 
         let server = warp::serve(warp::any().map(move || MetricsReply(handle.render())));
         let (listening, server) = server
@@ -17,4 +17,5 @@ mod metric_service_test;
 
 mod metric_service;
 
+pub use metric_service::try_get_prometheus_handle;
 pub use metric_service::MetricService;
@@ -0,0 +1,366 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::AggregatorPartialPlan;
+use common_planners::ColumnStatistics;
+use common_planners::Expression;
+use common_planners::Histogram;
+use common_planners::PlanNode;
+use common_planners::ReadDataSourcePlan;
+use common_tracing::tracing;
+
+use crate::sessions::DatafuseQueryContextRef;
+
+/// Selectivity of `col = literal` when the column has not been analyzed.
+const DEFAULT_EQUALITY_SELECTIVITY: f64 = 0.1;
+/// Selectivity of a range or an unknown predicate when the column has not been analyzed.
+const DEFAULT_SELECTIVITY: f64 = 1.0 / 3.0;
+/// The cost of an extra shuffle stage, in rows merged on one node.
+/// Shuffling only pays off when the convergent merge is bigger than this.
+const SHUFFLE_STAGE_COST: f64 = 100_000.0;
+
+/// The estimated output of a plan node.
+#[derive(Clone, Debug, Default)]
+pub struct Cardinality {
+    pub rows: f64,
+    /// The output columns which can be traced back to an analyzed table column.
+    pub columns: HashMap<String, ColumnEstimate>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ColumnEstimate {
+    pub ndv: f64,
+    pub null_fraction: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub histogram: Option<Histogram>,
+}
+
+impl ColumnEstimate {
+    fn create(stats: &ColumnStatistics, row_count: u64) -> ColumnEstimate {
+        let null_fraction = match row_count {
+            0 => 0.0,
+            _ => stats.null_count as f64 / row_count as f64,
+        };
+
+        ColumnEstimate {
+            ndv: stats.ndv as f64,
+            null_fraction,
+            min: stats.min.as_f64().ok(),
+            max: stats.max.as_f64().ok(),
+            histogram: stats.histogram.clone(),
+        }
+    }
+
+    /// The fraction of the non-null values which are less than or equal to `value`.
+    fn fraction_le(&self, value: f64) -> Option<f64> {
+        if let Some(histogram) = &self.histogram {
+            return Some(histogram.fraction_le(value));
+        }
+
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if max > min => {
+                Some(((value - min) / (max - min)).clamp(0.0, 1.0))
+            }
+            (Some(min), Some(_)) => Some(if value >= min { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AggregatePlacement {
+    /// Merge the partial results of every node on one node.
+    Convergent,
+    /// Shuffle the partial results by the group by key and merge them on every node.
+    Shuffle,
+}
+
+/// Estimates cardinalities from the statistics collected by `ANALYZE TABLE`.
+/// Without statistics the estimates fall back to the read statistics and default selectivities.
+pub struct CostModel {
+    ctx: DatafuseQueryContextRef,
+}
+
+impl CostModel {
+    pub fn create(ctx: DatafuseQueryContextRef) -> CostModel {
+        CostModel { ctx }
+    }
+
+    pub fn estimate(&self, plan: &PlanNode) -> Result<Cardinality> {
+        match plan {
+            PlanNode::ReadSource(plan) => self.estimate_read_source(plan),
+            PlanNode::Filter(plan) => self.estimate_filter(&plan.predicate, &plan.input),
+            PlanNode::Having(plan) => self.estimate_filter(&plan.predicate, &plan.input),
+            PlanNode::Expression(plan) => self.estimate_expressions(&plan.exprs, &plan.input),
+            PlanNode::Projection(plan) => self.estimate_expressions(&plan.expr, &plan.input),
+            PlanNode::AggregatorPartial(plan) => {
+                let input = self.estimate(&plan.input)?;
+                Ok(Self::aggregate_cardinality(&plan.group_expr, &input))
+            }
+            PlanNode::AggregatorFinal(plan) => {
+                let input = self.estimate(&plan.input)?;
+                Ok(Self::aggregate_cardinality(&plan.group_expr, &input))
+            }
+            PlanNode::Limit(plan) => {
+                let mut cardinality = self.estimate(&plan.input)?;
+                if let Some(n) = plan.n {
+                    cardinality.rows = cardinality.rows.min(n as f64);
+                }
+                Ok(cardinality)
+            }
+            other => match other.inputs().last() {
+                Some(input) => self.estimate(input),
+                None => Ok(Cardinality {
+                    rows: 1.0,
+                    columns: HashMap::new(),
+                }),
+            },
+        }
+    }
+
+    /// The estimated number of groups, None if any key can not be traced back to an analyzed column.
+    pub fn group_count(group_expr: &[Expression], input: &Cardinality) -> Option<f64> {
+        let mut groups = 1.0_f64;
+        for expr in group_expr {
+            let column = match expr {
+                Expression::Column(name) => input.columns.get(name)?,
+                _ => return None,
+            };
+
+            // Nulls make up a group of their own.
+            let nulls = if column.null_fraction > 0.0 { 1.0 } else { 0.0 };
+            groups *= column.ndv.max(1.0) + nulls;
+        }
+        Some(groups.min(input.rows.max(1.0)))
+    }
+
+    /// Choose where to merge the partial aggregation of a cluster query running on `nodes` nodes.
+    /// Shuffle is kept unless the statistics show that there are few enough groups to merge on one node.
+    pub fn aggregate_placement(
+        &self,
+        plan: &AggregatorPartialPlan,
+        input: &PlanNode,
+        nodes: usize,
+    ) -> Result<AggregatePlacement> {
+        let input = self.estimate(input)?;
+        let groups = match Self::group_count(&plan.group_expr, &input) {
+            None => return Ok(AggregatePlacement::Shuffle),
+            Some(groups) => groups,
+        };
+
+        let nodes = nodes.max(1) as f64;
+        let groups_per_node = groups.min((input.rows / nodes).max(1.0));
+        let convergent_cost = groups_per_node * nodes;
+        let shuffle_cost = groups_per_node + SHUFFLE_STAGE_COST;
+
+        match convergent_cost <= shuffle_cost {
+            true => Ok(AggregatePlacement::Convergent),
+            false => Ok(AggregatePlacement::Shuffle),
+        }
+    }
+
+    /// The estimated fraction of the input rows which satisfy the predicate.
+    pub fn selectivity(predicate: &Expression, input: &Cardinality) -> f64 {
+        let selectivity = match predicate {
+            Expression::Alias(_, expr) => Self::selectivity(expr, input),
+            Expression::Literal { value, .. } => match value {
+                DataValue::Boolean(Some(true)) => 1.0,
+                DataValue::Boolean(_) => 0.0,
+                _ => DEFAULT_SELECTIVITY,
+            },
+            Expression::UnaryExpression { op, expr } if op.to_lowercase() == "not" => {
+                1.0 - Self::selectivity(expr, input)
+            }
+            Expression::BinaryExpression { left, op, right } => match op.to_lowercase().as_str() {
+                "and" => Self::selectivity(left, input) * Self::selectivity(right, input),
+                "or" => {
+                    let (left, right) = (
+                        Self::selectivity(left, input),
+                        Self::selectivity(right, input),
+                    );
+                    left + right - left * right
+                }
+                op => Self::comparison_selectivity(op, left, right, input),
+            },
+            _ => DEFAULT_SELECTIVITY,
+        };
+        selectivity.clamp(0.0, 1.0)
+    }
+
+    fn comparison_selectivity(
+        op: &str,
+        left: &Expression,
+        right: &Expression,
+        input: &Cardinality,
+    ) -> f64 {
+        // Normalize to `column op literal`.
+        let (op, name, value) = match (left, right) {
+            (Expression::Column(name), Expression::Literal { value, .. }) => (op, name, value),
+            (Expression::Literal { value, .. }, Expression::Column(name)) => {
+                let op = match op {
+                    "<" => ">",
+                    "<=" => ">=",
+                    ">" => "<",
+                    ">=" => "<=",
+                    op => op,
+                };
+                (op, name, value)
+            }
+            _ => {
+                return match op {
+                    "=" => DEFAULT_EQUALITY_SELECTIVITY,
+                    _ => DEFAULT_SELECTIVITY,
+                };
+            }
+        };
+
+        let column = match input.columns.get(name) {
+            Some(column) => column,
+            None => {
+                return match op {
+                    "=" => DEFAULT_EQUALITY_SELECTIVITY,
+                    "!=" | "<>" => 1.0 - DEFAULT_EQUALITY_SELECTIVITY,
+                    _ => DEFAULT_SELECTIVITY,
+                };
+            }
+        };
+
+        // Comparing with null never satisfies the predicate.
+        if value.is_null() {
+            return 0.0;
+        }
+
+        let not_null = 1.0 - column.null_fraction;
+        let value = value.as_f64().ok();
+        let out_of_range = match (value, column.min, column.max) {
+            (Some(value), Some(min), Some(max)) => value < min || value > max,
+            _ => false,
+        };
+
+        let equality = match out_of_range {
+            true => 0.0,
+            false => not_null / column.ndv.max(1.0),
+        };
+
+        let fraction_le = value.and_then(|value| column.fraction_le(value));
+        match (op, fraction_le) {
+            ("=", _) => equality,
+            ("!=", _) | ("<>", _) => not_null - equality,
+            ("<=", Some(fraction)) => not_null * fraction,
+            ("<", Some(fraction)) => not_null * fraction - equality,
+            (">", Some(fraction)) => not_null * (1.0 - fraction),
+            (">=", Some(fraction)) => not_null * (1.0 - fraction) + equality,
+            _ => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    fn estimate_read_source(&self, plan: &ReadDataSourcePlan) -> Result<Cardinality> {
+        let mut cardinality = Cardinality {
+            rows: plan.statistics.read_rows as f64,
+            columns: HashMap::new(),
+        };
+
+        let table_meta = self.ctx.get_table(&plan.db, &plan.table)?;
+        let statistics = match table_meta.datasource().statistics(self.ctx.clone()) {
+            Ok(statistics) => statistics,
+            Err(cause) => {
+                // The statistics are only a hint, the query can go on without them.
+                tracing::warn!(
+                    "Cannot get the statistics of table {}.{}: {}",
+                    plan.db,
+                    plan.table,
+                    cause
+                );
+                None
+            }
+        };
+
+        if let Some(statistics) = statistics {
+            if cardinality.rows == 0.0 {
+                cardinality.rows = statistics.row_count as f64;
+            }
+
+            for field in plan.schema.fields() {
+                if let Some(column) = statistics.column(field.name()) {
+                    let mut estimate = ColumnEstimate::create(column, statistics.row_count);
+                    estimate.ndv = estimate.ndv.min(cardinality.rows);
+                    cardinality.columns.insert(field.name().clone(), estimate);
+                }
+            }
+        }
+
+        Ok(cardinality)
+    }
+
+    fn estimate_filter(&self, predicate: &Expression, input: &PlanNode) -> Result<Cardinality> {
+        let mut cardinality = self.estimate(input)?;
+        cardinality.rows *= Self::selectivity(predicate, &cardinality);
+        for column in cardinality.columns.values_mut() {
+            column.ndv = column.ndv.min(cardinality.rows);
+        }
+        Ok(cardinality)
+    }
+
+    fn estimate_expressions(&self, exprs: &[Expression], input: &PlanNode) -> Result<Cardinality> {
+        let input = self.estimate(input)?;
+        let mut columns = HashMap::with_capacity(exprs.len());
+        for expr in exprs {
+            match expr {
+                Expression::Column(name) => {
+                    if let Some(column) = input.columns.get(name) {
+                        columns.insert(name.clone(), column.clone());
+                    }
+                }
+                Expression::Alias(alias, expr) => {
+                    if let Expression::Column(name) = expr.as_ref() {
+                        if let Some(column) = input.columns.get(name) {
+                            columns.insert(alias.clone(), column.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Cardinality {
+            rows: input.rows,
+            columns,
+        })
+    }
+
+    fn aggregate_cardinality(group_expr: &[Expression], input: &Cardinality) -> Cardinality {
+        let rows = match group_expr.len() {
+            0 => 1.0,
+            _ => Self::group_count(group_expr, input).unwrap_or(input.rows),
+        };
+
+        let mut columns = HashMap::with_capacity(group_expr.len());
+        for expr in group_expr {
+            if let Expression::Column(name) = expr {
+                if let Some(column) = input.columns.get(name) {
+                    let mut column = column.clone();
+                    column.ndv = column.ndv.min(rows);
+                    columns.insert(name.clone(), column);
+                }
+            }
+        }
+
+        Cardinality { rows, columns }
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+
+use crate::interpreters::*;
+use crate::optimizers::*;
+use crate::sessions::DatafuseQueryContextRef;
+use crate::sql::*;
+
+async fn create_analyzed_table(ctx: DatafuseQueryContextRef) -> Result<()> {
+    if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("create table default.t(a Int64, b String, c Int64) Engine = Memory")?
+    {
+        let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+        let _ = executor.execute().await?;
+    }
+
+    let statistics = TableStatistics {
+        row_count: 10_000_000,
+        columns: vec![
+            ColumnStatistics {
+                name: "a".to_string(),
+                ndv: 10_000_000,
+                null_count: 0,
+                min: DataValue::Int64(Some(0)),
+                max: DataValue::Int64(Some(10_000_000)),
+                histogram: Some(Histogram {
+                    bounds: vec![0.0, 10_000_000.0],
+                }),
+            },
+            ColumnStatistics {
+                name: "b".to_string(),
+                ndv: 4,
+                null_count: 0,
+                min: DataValue::Utf8(Some("a".to_string())),
+                max: DataValue::Utf8(Some("d".to_string())),
+                histogram: None,
+            },
+        ],
+    };
+
+    let table = ctx.get_table("default", "t")?;
+    table
+        .datasource()
+        .set_statistics(ctx.clone(), statistics)
+        .await
+}
+
+fn find_aggregate_partial(plan: &PlanNode) -> Option<AggregatorPartialPlan> {
+    match plan {
+        PlanNode::AggregatorPartial(plan) => Some(plan.clone()),
+        other => other
+            .inputs()
+            .iter()
+            .find_map(|input| find_aggregate_partial(input)),
+    }
+}
+
+#[tokio::test]
+async fn test_cost_model_filter_cardinality() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: f64,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Range on histogram",
+            query: "select a from default.t where a <= 5000000",
+            expect: 5_000_000.0,
+        },
+        Test {
+            name: "Equality on ndv",
+            query: "select a from default.t where a = 7",
+            expect: 1.0,
+        },
+        Test {
+            name: "Equality out of range",
+            query: "select a from default.t where a = 20000000",
+            expect: 0.0,
+        },
+        Test {
+            name: "Disjunction",
+            query: "select a from default.t where a > 8000000 or b = 'x'",
+            expect: 4_000_000.0,
+        },
+        Test {
+            name: "Negation",
+            query: "select a from default.t where not (b = 'x')",
+            expect: 7_500_000.0,
+        },
+        Test {
+            name: "Column without statistics",
+            query: "select a from default.t where c = 1",
+            expect: 1_000_000.0,
+        },
+    ];
+
+    let ctx = crate::tests::try_create_context()?;
+    create_analyzed_table(ctx.clone()).await?;
+
+    let cost_model = CostModel::create(ctx.clone());
+    for test in tests {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(test.query)?;
+        let cardinality = cost_model.estimate(&plan)?;
+        assert!(
+            (cardinality.rows - test.expect).abs() < 1.0,
+            "{}: expect {}, actual {}",
+            test.name,
+            test.expect,
+            cardinality.rows
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cost_model_aggregate_placement() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        groups: Option<f64>,
+        expect: AggregatePlacement,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Few groups",
+            query: "select count() from default.t group by b",
+            groups: Some(4.0),
+            expect: AggregatePlacement::Convergent,
+        },
+        Test {
+            name: "Many groups",
+            query: "select count() from default.t group by a",
+            groups: Some(10_000_000.0),
+            expect: AggregatePlacement::Shuffle,
+        },
+        Test {
+            name: "Many groups after filter",
+            query: "select count() from default.t where a <= 1000 group by a",
+            groups: Some(1000.0),
+            expect: AggregatePlacement::Convergent,
+        },
+        Test {
+            name: "Group by column without statistics",
+            query: "select count() from default.t group by c",
+            groups: None,
+            expect: AggregatePlacement::Shuffle,
+        },
+        Test {
+            name: "Group by expression",
+            query: "select count() from default.t group by a % 3",
+            groups: None,
+            expect: AggregatePlacement::Shuffle,
+        },
+    ];
+
+    let ctx = crate::tests::try_create_context()?;
+    create_analyzed_table(ctx.clone()).await?;
+
+    let cost_model = CostModel::create(ctx.clone());
+    for test in tests {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(test.query)?;
+        let aggregate = find_aggregate_partial(&plan).unwrap();
+
+        let input = cost_model.estimate(&aggregate.input)?;
+        let groups = CostModel::group_count(&aggregate.group_expr, &input);
+        match (groups, test.groups) {
+            (Some(actual), Some(expect)) => {
+                assert!((actual - expect).abs() < 1.0, "{}", test.name)
+            }
+            (actual, expect) => assert_eq!(actual, expect, "{}", test.name),
+        }
+
+        let placement = cost_model.aggregate_placement(&aggregate, &aggregate.input, 3)?;
+        assert_eq!(placement, test.expect, "{}", test.name);
+    }
+
+    Ok(())
+}
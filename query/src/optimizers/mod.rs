@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod cost_model_test;
 #[cfg(test)]
 mod optimizer_constant_folding_test;
 #[cfg(test)]
+mod optimizer_filter_push_down_test;
+#[cfg(test)]
 mod optimizer_projection_push_down_test;
 #[cfg(test)]
 mod optimizer_scatters_test;
@@ -23,16 +27,22 @@ mod optimizer_statistics_exact_test;
 #[cfg(test)]
 mod optimizer_test;
 
+mod cost_model;
 mod metrics;
 mod optimizer;
 mod optimizer_constant_folding;
+mod optimizer_filter_push_down;
 mod optimizer_projection_push_down;
 mod optimizer_scatters;
 mod optimizer_statistics_exact;
 
+pub use cost_model::AggregatePlacement;
+pub use cost_model::Cardinality;
+pub use cost_model::CostModel;
 pub use optimizer::Optimizer;
 pub use optimizer::Optimizers;
 pub use optimizer_constant_folding::ConstantFoldingOptimizer;
+pub use optimizer_filter_push_down::FilterPushDownOptimizer;
 pub use optimizer_projection_push_down::ProjectionPushDownOptimizer;
 pub use optimizer_scatters::ScattersOptimizer;
 pub use optimizer_statistics_exact::StatisticsExactOptimizer;
@@ -20,13 +20,13 @@ use common_functions::scalars::FunctionFactory;
 use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
 use common_planners::Expression;
+use common_planners::ExpressionExecutor;
 use common_planners::Expressions;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
 
 use crate::optimizers::Optimizer;
-use crate::pipelines::transforms::ExpressionExecutor;
 use crate::sessions::DatafuseQueryContextRef;
 
 pub struct ConstantFoldingOptimizer {}
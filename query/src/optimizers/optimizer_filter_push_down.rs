@@ -0,0 +1,318 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_planners::find_column_exprs;
+use common_planners::not;
+use common_planners::resolve_aliases_to_exprs;
+use common_planners::ExprRewriter;
+use common_planners::Expression;
+use common_planners::ExpressionPlan;
+use common_planners::FilterPlan;
+use common_planners::HavingPlan;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::ProjectionPlan;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::SelectPlan;
+use common_planners::SortPlan;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::DatafuseQueryContextRef;
+
+/// Pushes the filters down to the read source:
+/// - the predicate is simplified, e.g. `NOT (a > b)` becomes `a <= b` and `x AND true` becomes `x`
+/// - the filters are pushed below projections, expressions, sorts and subqueries in FROM,
+///   with the aliases resolved
+/// - the conjunctions of the predicate are pushed into the `Extras` of the scan, so that
+///   the sources which understand them can prune
+///
+/// The filter is kept above the read source, the sources are free to ignore the push downs.
+pub struct FilterPushDownOptimizer {}
+
+struct FilterPushDownImpl {}
+
+impl PlanRewriter for FilterPushDownImpl {
+    fn rewrite_filter(&mut self, plan: &FilterPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let predicate = simplify_predicate(&plan.predicate)?;
+        self.push_down(predicate, &new_input)
+    }
+
+    fn rewrite_having(&mut self, plan: &HavingPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        match simplify_predicate(&plan.predicate)? {
+            predicate if is_true(&predicate) => Ok(new_input),
+            predicate => PlanBuilder::from(&new_input).having(predicate)?.build(),
+        }
+    }
+}
+
+impl FilterPushDownImpl {
+    fn push_down(&mut self, predicate: Expression, input: &PlanNode) -> Result<PlanNode> {
+        if is_true(&predicate) {
+            return Ok(input.clone());
+        }
+
+        match input {
+            PlanNode::Filter(plan) => {
+                let predicate = simplify_predicate(&plan.predicate.and(predicate))?;
+                self.push_down(predicate, &plan.input)
+            }
+            PlanNode::Projection(plan) => {
+                match resolve_predicate(&predicate, &plan.expr, &plan.input.schema())? {
+                    None => Self::filter(predicate, input),
+                    Some(resolved) => Ok(PlanNode::Projection(ProjectionPlan {
+                        input: Arc::new(self.push_down(resolved, &plan.input)?),
+                        ..plan.clone()
+                    })),
+                }
+            }
+            PlanNode::Expression(plan) => {
+                match resolve_predicate(&predicate, &plan.exprs, &plan.input.schema())? {
+                    None => Self::filter(predicate, input),
+                    Some(resolved) => Ok(PlanNode::Expression(ExpressionPlan {
+                        input: Arc::new(self.push_down(resolved, &plan.input)?),
+                        ..plan.clone()
+                    })),
+                }
+            }
+            PlanNode::Sort(plan) => Ok(PlanNode::Sort(SortPlan {
+                input: Arc::new(self.push_down(predicate, &plan.input)?),
+                ..plan.clone()
+            })),
+            PlanNode::Select(plan) => Ok(PlanNode::Select(SelectPlan {
+                input: Arc::new(self.push_down(predicate, &plan.input)?),
+            })),
+            PlanNode::ReadSource(plan) => {
+                let read_source = Self::read_source_with_filters(plan, &predicate);
+                Self::filter(predicate, &read_source)
+            }
+            _ => Self::filter(predicate, input),
+        }
+    }
+
+    fn filter(predicate: Expression, input: &PlanNode) -> Result<PlanNode> {
+        PlanBuilder::from(input).filter(predicate)?.build()
+    }
+
+    fn read_source_with_filters(plan: &ReadDataSourcePlan, predicate: &Expression) -> PlanNode {
+        let mut scan_plan = ScanPlan::clone(&plan.scan_plan);
+        for conjunction in split_conjunctions(predicate) {
+            // Constant conjunctions are left to the filter, they prune nothing.
+            if find_column_exprs(&[conjunction.clone()]).is_empty() {
+                continue;
+            }
+            if !scan_plan.push_downs.filters.contains(&conjunction) {
+                scan_plan.push_downs.filters.push(conjunction);
+            }
+        }
+
+        let mut plan = plan.clone();
+        plan.scan_plan = Arc::new(scan_plan);
+        PlanNode::ReadSource(plan)
+    }
+}
+
+/// Rewrites the predicate over the output of `exprs` into a predicate over their input,
+/// None if the predicate can't be evaluated before the `exprs`.
+fn resolve_predicate(
+    predicate: &Expression,
+    exprs: &[Expression],
+    input_schema: &DataSchemaRef,
+) -> Result<Option<Expression>> {
+    let outputs = exprs
+        .iter()
+        .map(|expr| match expr {
+            Expression::Alias(alias, nested_expr) => (alias.clone(), *nested_expr.clone()),
+            _ => (expr.column_name(), expr.clone()),
+        })
+        .collect::<HashMap<String, Expression>>();
+
+    let resolved = resolve_aliases_to_exprs(predicate, &outputs)?;
+    if !is_pushable(&resolved) {
+        return Ok(None);
+    }
+
+    for column in find_column_exprs(&[resolved.clone()]) {
+        if input_schema.field_with_name(&column.column_name()).is_err() {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(resolved))
+}
+
+/// Aggregates, window functions, subqueries and non-deterministic functions
+/// must be evaluated where they are.
+fn is_pushable(expr: &Expression) -> bool {
+    match expr {
+        Expression::Alias(_, expr) => is_pushable(expr),
+        Expression::Column(_) | Expression::Literal { .. } => true,
+        Expression::UnaryExpression { expr, .. } => is_pushable(expr),
+        Expression::BinaryExpression { left, right, .. } => is_pushable(left) && is_pushable(right),
+        Expression::ScalarFunction { op, args } => {
+            let deterministic = FunctionFactory::get(op)
+                .map(|function| function.is_deterministic())
+                .unwrap_or(false);
+            deterministic && args.iter().all(is_pushable)
+        }
+        Expression::Cast { expr, .. } => is_pushable(expr),
+        _ => false,
+    }
+}
+
+fn split_conjunctions(predicate: &Expression) -> Vec<Expression> {
+    match predicate {
+        Expression::BinaryExpression { left, op, right } if op.eq_ignore_ascii_case("and") => {
+            let mut conjunctions = split_conjunctions(left);
+            conjunctions.extend(split_conjunctions(right));
+            conjunctions
+        }
+        _ => vec![predicate.clone()],
+    }
+}
+
+fn is_true(expr: &Expression) -> bool {
+    matches!(expr, Expression::Literal {
+        value: DataValue::Boolean(Some(true)),
+        ..
+    })
+}
+
+fn is_false(expr: &Expression) -> bool {
+    matches!(expr, Expression::Literal {
+        value: DataValue::Boolean(Some(false)),
+        ..
+    })
+}
+
+fn boolean(value: bool) -> Expression {
+    Expression::create_literal(DataValue::Boolean(Some(value)))
+}
+
+/// Simplifies a predicate, keeping the three-valued logic of nulls.
+pub fn simplify_predicate(predicate: &Expression) -> Result<Expression> {
+    predicate.clone().rewrite(&mut PredicateSimplifier {})
+}
+
+struct PredicateSimplifier {}
+
+impl ExprRewriter for PredicateSimplifier {
+    fn pre_visit(&mut self, expr: &Expression) -> Result<bool> {
+        // The predicates of subqueries are simplified by their own filters.
+        Ok(!matches!(
+            expr,
+            Expression::Subquery { .. } | Expression::ScalarSubquery { .. }
+        ))
+    }
+
+    fn mutate(&mut self, expr: Expression) -> Result<Expression> {
+        Ok(match expr {
+            Expression::UnaryExpression { op, expr } if op.eq_ignore_ascii_case("not") => {
+                negate(*expr)
+            }
+            Expression::BinaryExpression { left, op, right } if op.eq_ignore_ascii_case("and") => {
+                and(*left, op, *right)
+            }
+            Expression::BinaryExpression { left, op, right } if op.eq_ignore_ascii_case("or") => {
+                or(*left, op, *right)
+            }
+            expr => expr,
+        })
+    }
+}
+
+fn binary(left: Expression, op: String, right: Expression) -> Expression {
+    Expression::BinaryExpression {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+fn and(left: Expression, op: String, right: Expression) -> Expression {
+    match (left, right) {
+        (left, right) if is_true(&left) => right,
+        (left, right) if is_true(&right) => left,
+        (left, _) if is_false(&left) => boolean(false),
+        (_, right) if is_false(&right) => boolean(false),
+        (left, right) if left == right => left,
+        (left, right) => binary(left, op, right),
+    }
+}
+
+fn or(left: Expression, op: String, right: Expression) -> Expression {
+    match (left, right) {
+        (left, _) if is_true(&left) => boolean(true),
+        (_, right) if is_true(&right) => boolean(true),
+        (left, right) if is_false(&left) => right,
+        (left, right) if is_false(&right) => left,
+        (left, right) if left == right => left,
+        (left, right) => binary(left, op, right),
+    }
+}
+
+/// `NOT expr` with the negation pushed down to the comparisons.
+fn negate(expr: Expression) -> Expression {
+    match expr {
+        expr if is_true(&expr) => boolean(false),
+        expr if is_false(&expr) => boolean(true),
+        Expression::UnaryExpression { op, expr } if op.eq_ignore_ascii_case("not") => *expr,
+        Expression::BinaryExpression { left, op, right } => {
+            let negated_op = match op.to_lowercase().as_str() {
+                "and" => return or(negate(*left), "or".to_string(), negate(*right)),
+                "or" => return and(negate(*left), "and".to_string(), negate(*right)),
+                "=" => Some("!="),
+                "!=" | "<>" => Some("="),
+                "<" => Some(">="),
+                "<=" => Some(">"),
+                ">" => Some("<="),
+                ">=" => Some("<"),
+                _ => None,
+            };
+
+            match negated_op {
+                Some(negated_op) => binary(*left, negated_op.to_string(), *right),
+                None => not(binary(*left, op, *right)),
+            }
+        }
+        expr => not(expr),
+    }
+}
+
+impl Optimizer for FilterPushDownOptimizer {
+    fn name(&self) -> &str {
+        "FilterPushDown"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = FilterPushDownImpl {};
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl FilterPushDownOptimizer {
+    pub fn create(_ctx: DatafuseQueryContextRef) -> FilterPushDownOptimizer {
+        FilterPushDownOptimizer {}
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::*;
+use crate::sql::*;
+
+fn find_push_down_filters(plan: &PlanNode) -> Vec<String> {
+    match plan {
+        PlanNode::ReadSource(plan) => plan
+            .get_push_downs()
+            .filters
+            .iter()
+            .map(|filter| format!("{:?}", filter))
+            .collect(),
+        other => other
+            .inputs()
+            .iter()
+            .flat_map(|input| find_push_down_filters(input))
+            .collect(),
+    }
+}
+
+#[test]
+fn test_filter_push_down_optimizer() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+        push_downs: Vec<&'static str>,
+    }
+
+    let tests: Vec<Test> = vec![
+        Test {
+            name: "Filter above read source",
+            query: "select number from numbers(10) where number > 1 and number < 5",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: ((number > 1) AND (number < 5))\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec!["(number > 1)", "(number < 5)"],
+        },
+        Test {
+            name: "Negated comparison",
+            query: "select number from numbers(10) where not (number > 1)",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: (number <= 1)\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec!["(number <= 1)"],
+        },
+        Test {
+            name: "Negated conjunction",
+            query: "select number from numbers(10) where not (number > 1 and number < 5)",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: ((number <= 1) or (number >= 5))\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec!["((number <= 1) or (number >= 5))"],
+        },
+        Test {
+            name: "Tautology in conjunction",
+            query: "select number from numbers(10) where number > 1 and true",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: (number > 1)\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec!["(number > 1)"],
+        },
+        Test {
+            name: "Tautology",
+            query: "select number from numbers(10) where number > 1 or true",
+            expect: "\
+            Projection: number:UInt64\
+            \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec![],
+        },
+        Test {
+            name: "Null is not a tautology",
+            query: "select number from numbers(10) where null and true",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: NULL\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec![],
+        },
+        Test {
+            name: "Filter above subquery in FROM",
+            query: "select a from (select number + 1 as a from numbers(10)) where a > 2",
+            expect: "\
+            Projection: a:UInt64\
+            \n  Projection: (number + 1) as a:UInt64\
+            \n    Expression: (number + 1):UInt64 (Before Projection)\
+            \n      Filter: ((number + 1) > 2)\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec!["((number + 1) > 2)"],
+        },
+        Test {
+            name: "Filters merged below subquery in FROM",
+            query: "select a from (select number as a from numbers(10) where number < 8) where a > 2",
+            expect: "\
+            Projection: a:UInt64\
+            \n  Projection: number as a:UInt64\
+            \n    Filter: ((number < 8) and (number > 2))\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec!["(number < 8)", "(number > 2)"],
+        },
+        Test {
+            name: "Filter is not pushed below limit",
+            query: "select a from (select number as a from numbers(10) limit 3) where a > 2",
+            expect: "\
+            Projection: a:UInt64\
+            \n  Filter: (a > 2)\
+            \n    Limit: 3\
+            \n      Projection: number as a:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            push_downs: vec![],
+        },
+    ];
+
+    for test in tests {
+        let ctx = crate::tests::try_create_context()?;
+
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(test.query)?;
+        let mut optimizer = FilterPushDownOptimizer::create(ctx);
+        let optimized = optimizer.optimize(&plan)?;
+
+        let actual = format!("{:?}", optimized);
+        assert_eq!(test.expect, actual, "{:#?}", test.name);
+        assert_eq!(
+            test.push_downs,
+            find_push_down_filters(&optimized),
+            "{:#?}",
+            test.name
+        );
+    }
+
+    Ok(())
+}
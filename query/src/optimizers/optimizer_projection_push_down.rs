@@ -34,6 +34,7 @@ use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Recursion;
 use common_planners::SortPlan;
+use common_planners::WindowPlan;
 
 use crate::optimizers::Optimizer;
 use crate::sessions::DatafuseQueryContextRef;
@@ -117,6 +118,14 @@ impl PlanRewriter for ProjectionPushDownImpl {
             .build()
     }
 
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        self.collect_column_names_from_expr_vec(plan.window_exprs.as_slice())?;
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .window(&self.rewrite_exprs(&new_input.schema(), &plan.window_exprs)?)?
+            .build()
+    }
+
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
         // TODO: rewrite scan
         self.get_projected_schema(plan.schema.as_ref())
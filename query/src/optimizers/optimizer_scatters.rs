@@ -31,7 +31,10 @@ use common_planners::ReadDataSourcePlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
+use common_planners::WindowPlan;
 
+use crate::optimizers::cost_model::AggregatePlacement;
+use crate::optimizers::cost_model::CostModel;
 use crate::optimizers::Optimizer;
 use crate::sessions::DatafuseQueryContext;
 use crate::sessions::DatafuseQueryContextRef;
@@ -80,17 +83,28 @@ impl ScattersOptimizerImpl {
     }
 
     fn cluster_aggregate_with_key(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
-        // Keep running in cluster mode
-        self.running_mode = RunningMode::Cluster;
+        let input = match self.input.take() {
+            None => return Err(ErrorCode::LogicalError("Cluster aggr input is None")),
+            Some(input) => input,
+        };
 
-        match self.input.take() {
-            None => Err(ErrorCode::LogicalError("Cluster aggr input is None")),
-            Some(input) => Self::normal_shuffle_stage(
-                "_group_by_key",
-                PlanBuilder::from(input.as_ref())
-                    .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
-                    .build()?,
-            ),
+        // Few groups are cheaper to merge in local node than to shuffle
+        let nodes = self.ctx.try_get_cluster()?.get_nodes()?.len();
+        let cost_model = CostModel::create(self.ctx.clone());
+        let partial = PlanBuilder::from(input.as_ref())
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()?;
+
+        match cost_model.aggregate_placement(plan, input.as_ref(), nodes)? {
+            AggregatePlacement::Convergent => {
+                self.running_mode = RunningMode::Standalone;
+                Self::convergent_shuffle_stage(partial)
+            }
+            AggregatePlacement::Shuffle => {
+                // Keep running in cluster mode
+                self.running_mode = RunningMode::Cluster;
+                Self::normal_shuffle_stage("_group_by_key", partial)
+            }
         }
     }
 
@@ -131,6 +145,27 @@ impl ScattersOptimizerImpl {
         }
     }
 
+    fn cluster_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        // Window functions need all the rows, we convergent it in local node
+        self.running_mode = RunningMode::Standalone;
+
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Cluster window input is None")),
+            Some(input) => Self::convergent_shuffle_stage_builder(input)
+                .window(&plan.window_exprs)?
+                .build(),
+        }
+    }
+
+    fn standalone_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Standalone window input is None")),
+            Some(input) => PlanBuilder::from(input.as_ref())
+                .window(&plan.window_exprs)?
+                .build(),
+        }
+    }
+
     fn cluster_limit(&mut self, plan: &LimitPlan) -> Result<PlanNode> {
         // Limit we convergent it in local node
         self.running_mode = RunningMode::Standalone;
@@ -261,6 +296,15 @@ impl PlanRewriter for ScattersOptimizerImpl {
         }
     }
 
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        self.input = Some(Arc::new(self.rewrite_plan_node(plan.input.as_ref())?));
+
+        match self.running_mode {
+            RunningMode::Cluster => self.cluster_window(plan),
+            RunningMode::Standalone => self.standalone_window(plan),
+        }
+    }
+
     fn rewrite_limit(&mut self, plan: &LimitPlan) -> Result<PlanNode> {
         self.input = Some(Arc::new(self.rewrite_plan_node(plan.input.as_ref())?));
 
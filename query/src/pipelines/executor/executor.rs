@@ -0,0 +1,166 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Instant;
+
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_runtime::tokio;
+use common_runtime::tokio::sync::Notify;
+use common_runtime::Runtime;
+
+use crate::pipelines::executor::executor_query::CURRENT_WORKER;
+use crate::pipelines::executor::executor_task::ExecutorTask;
+use crate::pipelines::executor::executor_task::TaskStep;
+use crate::pipelines::executor::ExecutorQuery;
+
+pub(crate) struct ExecutorShared {
+    workers: usize,
+    queries: Mutex<Vec<Weak<ExecutorQuery>>>,
+    // The vruntime of the latest query picked, it only grows. A new or a
+    // woken up query starts from it instead of getting ahead of the others.
+    min_vruntime: AtomicU64,
+    notify: Notify,
+    shutdown: AtomicBool,
+}
+
+impl ExecutorShared {
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    pub fn min_vruntime(&self) -> u64 {
+        self.min_vruntime.load(Ordering::Relaxed)
+    }
+
+    pub fn notify_worker(&self) {
+        self.notify.notify_one();
+    }
+
+    // The next task of the runnable query with the least weighted CPU time.
+    fn next_task(&self, worker: usize) -> Option<(Arc<ExecutorQuery>, Arc<ExecutorTask>)> {
+        let mut runnable = {
+            let mut queries = self.queries.lock();
+            queries.retain(|query| query.strong_count() > 0);
+            queries
+                .iter()
+                .filter_map(Weak::upgrade)
+                .filter(|query| query.is_runnable())
+                .collect::<Vec<_>>()
+        };
+
+        runnable.sort_by_key(|query| query.vruntime());
+        for query in runnable {
+            if let Some(task) = query.pop(worker) {
+                self.min_vruntime
+                    .fetch_max(query.vruntime(), Ordering::Relaxed);
+                return Some((query, task));
+            }
+        }
+        None
+    }
+
+    async fn work(self: Arc<Self>, worker: usize) {
+        while !self.shutdown.load(Ordering::Relaxed) {
+            match self.next_task(worker) {
+                None => self.notify.notified().await,
+                Some((query, task)) => {
+                    Self::run_task(worker, query, task);
+
+                    // Let the other tasks of the runtime run, such as the
+                    // connections spawned by the remote streams polled here.
+                    tokio::task::yield_now().await;
+                }
+            }
+        }
+    }
+
+    fn run_task(worker: usize, query: Arc<ExecutorQuery>, task: Arc<ExecutorTask>) {
+        let start = Instant::now();
+        CURRENT_WORKER.with(|current| current.set(Some(worker)));
+        if let TaskStep::Yield = task.run() {
+            query.schedule(task);
+        }
+        CURRENT_WORKER.with(|current| current.set(None));
+        query.account(start.elapsed());
+    }
+}
+
+/// Executes the pipes of every query on one fixed pool of workers, instead of
+/// a task per processor on the runtime of each query.
+///
+/// A processor is a task which is polled a block (a morsel) at a time, so a
+/// worker is never held by a waiting processor, a worker without work steals
+/// the tasks queued on the others, and the queries share the workers by their
+/// `cpu_shares` setting.
+pub struct PipelineExecutor {
+    shared: Arc<ExecutorShared>,
+    // Started by the first query.
+    runtime: Mutex<Option<Runtime>>,
+}
+
+impl PipelineExecutor {
+    pub fn create(workers: usize) -> Arc<PipelineExecutor> {
+        Arc::new(PipelineExecutor {
+            shared: Arc::new(ExecutorShared {
+                workers: workers.max(1),
+                queries: Mutex::new(vec![]),
+                min_vruntime: AtomicU64::new(0),
+                notify: Notify::new(),
+                shutdown: AtomicBool::new(false),
+            }),
+            runtime: Mutex::new(None),
+        })
+    }
+
+    pub fn workers(&self) -> usize {
+        self.shared.workers
+    }
+
+    pub fn create_query(&self, shares: u64) -> Result<Arc<ExecutorQuery>> {
+        self.try_start()?;
+
+        let query = Arc::new(ExecutorQuery::create(
+            self.shared.clone(),
+            shares,
+            self.shared.min_vruntime(),
+        ));
+        self.shared.queries.lock().push(Arc::downgrade(&query));
+        Ok(query)
+    }
+
+    fn try_start(&self) -> Result<()> {
+        let mut runtime = self.runtime.lock();
+        if runtime.is_none() {
+            let workers = self.shared.workers;
+            let executor_runtime = Runtime::with_worker_threads(workers)?;
+            for worker in 0..workers {
+                executor_runtime.spawn(self.shared.clone().work(worker));
+            }
+            *runtime = Some(executor_runtime);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PipelineExecutor {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+        self.shared.notify.notify_waiters();
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_infallible::Mutex;
+use futures::task::ArcWake;
+use futures::Stream;
+
+use crate::pipelines::executor::executor_task::ExecutorTask;
+
+struct OutputState {
+    blocks: VecDeque<Result<DataBlock>>,
+    // The tasks which have not finished pushing yet.
+    producers: usize,
+    // The consumer stream is dropped, the tasks stop pulling their inputs.
+    closed: bool,
+    consumer: Option<Waker>,
+    // The tasks waiting for room in the queue.
+    blocked: Vec<Waker>,
+    tasks: Vec<Weak<ExecutorTask>>,
+}
+
+/// The bounded queue the tasks of one `ExecutorQuery::execute` push their blocks into.
+/// A task pushes only when there is room, so a slow consumer pauses its producers.
+pub(crate) struct ExecutorOutput {
+    capacity: usize,
+    state: Mutex<OutputState>,
+}
+
+impl ExecutorOutput {
+    pub fn create(capacity: usize, producers: usize) -> Arc<ExecutorOutput> {
+        Arc::new(ExecutorOutput {
+            capacity: capacity.max(1),
+            state: Mutex::new(OutputState {
+                blocks: VecDeque::with_capacity(capacity),
+                producers,
+                closed: false,
+                consumer: None,
+                blocked: vec![],
+                tasks: Vec::with_capacity(producers),
+            }),
+        })
+    }
+
+    pub fn add_task(&self, task: &Arc<ExecutorTask>) {
+        self.state.lock().tasks.push(Arc::downgrade(task));
+    }
+
+    /// Ready(true) if one more block can be pushed, Ready(false) if the consumer is gone.
+    pub fn poll_reserve(&self, cx: &mut Context<'_>) -> Poll<bool> {
+        let mut state = self.state.lock();
+        if state.closed {
+            return Poll::Ready(false);
+        }
+
+        if state.blocks.len() < self.capacity {
+            return Poll::Ready(true);
+        }
+
+        state.blocked.push(cx.waker().clone());
+        Poll::Pending
+    }
+
+    pub fn push(&self, block: Result<DataBlock>) {
+        let consumer = {
+            let mut state = self.state.lock();
+            if state.closed {
+                return;
+            }
+
+            state.blocks.push_back(block);
+            state.consumer.take()
+        };
+
+        if let Some(consumer) = consumer {
+            consumer.wake();
+        }
+    }
+
+    pub fn finish_producer(&self) {
+        let consumer = {
+            let mut state = self.state.lock();
+            state.producers -= 1;
+            match state.producers {
+                0 => state.consumer.take(),
+                _ => None,
+            }
+        };
+
+        if let Some(consumer) = consumer {
+            consumer.wake();
+        }
+    }
+}
+
+/// The consumer side of an `ExecutorOutput`, it ends when every task has finished.
+pub(crate) struct ExecutorStream {
+    output: Arc<ExecutorOutput>,
+}
+
+impl ExecutorStream {
+    pub fn create(output: Arc<ExecutorOutput>) -> ExecutorStream {
+        ExecutorStream { output }
+    }
+}
+
+impl Stream for ExecutorStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (block, blocked) = {
+            let mut state = self.output.state.lock();
+            match state.blocks.pop_front() {
+                Some(block) => (block, std::mem::take(&mut state.blocked)),
+                None if state.producers == 0 => return Poll::Ready(None),
+                None => {
+                    state.consumer = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+        };
+
+        for waker in blocked {
+            waker.wake();
+        }
+        Poll::Ready(Some(block))
+    }
+}
+
+impl Drop for ExecutorStream {
+    fn drop(&mut self) {
+        let (blocked, tasks) = {
+            let mut state = self.output.state.lock();
+            state.closed = true;
+            state.blocks.clear();
+            (
+                std::mem::take(&mut state.blocked),
+                std::mem::take(&mut state.tasks),
+            )
+        };
+
+        // Run every task once more, so that they see the closed output and
+        // release their inputs, even the ones waiting for their input.
+        for waker in blocked {
+            waker.wake();
+        }
+        for task in tasks.iter().filter_map(Weak::upgrade) {
+            ArcWake::wake_by_ref(&task);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_streams::SendableDataBlockStream;
+use futures::task::ArcWake;
+
+use crate::pipelines::executor::executor::ExecutorShared;
+use crate::pipelines::executor::executor_output::ExecutorOutput;
+use crate::pipelines::executor::executor_output::ExecutorStream;
+use crate::pipelines::executor::executor_task::ExecutorTask;
+use crate::pipelines::processors::Processor;
+
+/// The default `cpu_shares` of a query. When the workers are busy, a query
+/// with twice of it gets twice the CPU time of a query with the default.
+pub const DEFAULT_CPU_SHARES: u64 = 1024;
+
+thread_local! {
+    // The executor worker running on this thread, a task woken from it is queued on it.
+    pub(crate) static CURRENT_WORKER: Cell<Option<usize>> = Cell::new(None);
+}
+
+/// The tasks of one query on the `PipelineExecutor`.
+/// Every worker has a run queue here, a worker without work takes the
+/// oldest tasks of the other workers' queues.
+pub struct ExecutorQuery {
+    shares: u64,
+    // The CPU time used, weighted by the shares. The workers run the
+    // runnable query with the least of it first.
+    vruntime: AtomicU64,
+    cpu_nanos: AtomicU64,
+    runnable: AtomicUsize,
+    next_task_id: AtomicUsize,
+    queues: Vec<Mutex<VecDeque<Arc<ExecutorTask>>>>,
+    executor: Arc<ExecutorShared>,
+}
+
+impl ExecutorQuery {
+    pub(crate) fn create(
+        executor: Arc<ExecutorShared>,
+        shares: u64,
+        vruntime: u64,
+    ) -> ExecutorQuery {
+        ExecutorQuery {
+            shares: shares.max(1),
+            vruntime: AtomicU64::new(vruntime),
+            cpu_nanos: AtomicU64::new(0),
+            runnable: AtomicUsize::new(0),
+            next_task_id: AtomicUsize::new(0),
+            queues: (0..executor.workers())
+                .map(|_| Mutex::new(VecDeque::new()))
+                .collect(),
+            executor,
+        }
+    }
+
+    /// Execute the processors as tasks on the executor workers and merge
+    /// their outputs into one stream, in no particular order.
+    pub fn execute(
+        self: &Arc<Self>,
+        processors: Vec<Arc<dyn Processor>>,
+    ) -> Result<SendableDataBlockStream> {
+        if processors.is_empty() {
+            return Err(ErrorCode::IllegalTransformConnectionState(
+                "Executor processors cannot be empty",
+            ));
+        }
+
+        let output = ExecutorOutput::create(processors.len(), processors.len());
+        let tasks = processors
+            .into_iter()
+            .map(|processor| {
+                let id = self.next_task_id.fetch_add(1, Ordering::Relaxed);
+                let task = ExecutorTask::create(id, self.clone(), processor, output.clone());
+                output.add_task(&task);
+                task
+            })
+            .collect::<Vec<_>>();
+
+        for task in &tasks {
+            ArcWake::wake_by_ref(task);
+        }
+        Ok(Box::pin(ExecutorStream::create(output)))
+    }
+
+    pub fn get_shares(&self) -> u64 {
+        self.shares
+    }
+
+    /// The CPU time used by the tasks of the query.
+    pub fn get_cpu_time(&self) -> Duration {
+        Duration::from_nanos(self.cpu_nanos.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn vruntime(&self) -> u64 {
+        self.vruntime.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn is_runnable(&self) -> bool {
+        self.runnable.load(Ordering::Acquire) > 0
+    }
+
+    pub(crate) fn schedule(&self, task: Arc<ExecutorTask>) {
+        let worker = CURRENT_WORKER
+            .with(|worker| worker.get())
+            .unwrap_or(task.id);
+
+        // Counted before it is queued, so that it is never popped before.
+        if self.runnable.fetch_add(1, Ordering::AcqRel) == 0 {
+            // Waiting for its inputs does not earn the query CPU time.
+            self.vruntime
+                .fetch_max(self.executor.min_vruntime(), Ordering::Relaxed);
+        }
+        self.queues[worker % self.queues.len()]
+            .lock()
+            .push_back(task);
+        self.executor.notify_worker();
+    }
+
+    /// Take the oldest task of the worker's own queue, or steal the newest
+    /// task of another worker.
+    pub(crate) fn pop(&self, worker: usize) -> Option<Arc<ExecutorTask>> {
+        let workers = self.queues.len();
+        let own = worker % workers;
+
+        let mut task = self.queues[own].lock().pop_front();
+        for other in (1..workers).map(|step| (own + step) % workers) {
+            if task.is_some() {
+                break;
+            }
+            task = self.queues[other].lock().pop_back();
+        }
+
+        if task.is_some() {
+            self.runnable.fetch_sub(1, Ordering::AcqRel);
+        }
+        task
+    }
+
+    pub(crate) fn account(&self, elapsed: Duration) {
+        let nanos = elapsed.as_nanos() as u64;
+        self.cpu_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.vruntime
+            .fetch_add(nanos * DEFAULT_CPU_SHARES / self.shares, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_streams::SendableDataBlockStream;
+use futures::future::BoxFuture;
+use futures::task::waker_ref;
+use futures::task::ArcWake;
+use futures::FutureExt;
+use futures::StreamExt;
+
+use crate::pipelines::executor::executor_output::ExecutorOutput;
+use crate::pipelines::executor::ExecutorQuery;
+use crate::pipelines::processors::Processor;
+
+// Waiting to be woken by its input or its output.
+const IDLE: u8 = 0;
+// In a run queue of its query.
+const SCHEDULED: u8 = 1;
+// Polled by a worker.
+const RUNNING: u8 = 2;
+// Woken while polled, it is queued again after the poll.
+const NOTIFIED: u8 = 3;
+const FINISHED: u8 = 4;
+
+enum TaskState {
+    Executing(BoxFuture<'static, Result<SendableDataBlockStream>>),
+    Pulling(SendableDataBlockStream),
+    Finished,
+}
+
+pub(crate) enum TaskStep {
+    /// Pushed a block, the task is queued again to run its next morsel.
+    Yield,
+    /// Waits for its input or for room in its output.
+    Pending,
+    Finished,
+}
+
+/// One processor of a pipe, executed a block at a time by the executor workers.
+/// The task is woken by its input stream or its output like a future, the
+/// waker queues it again on the worker it is woken from.
+pub(crate) struct ExecutorTask {
+    pub(crate) id: usize,
+    query: Arc<ExecutorQuery>,
+    status: AtomicU8,
+    state: Mutex<TaskState>,
+    output: Arc<ExecutorOutput>,
+}
+
+impl ExecutorTask {
+    pub fn create(
+        id: usize,
+        query: Arc<ExecutorQuery>,
+        processor: Arc<dyn Processor>,
+        output: Arc<ExecutorOutput>,
+    ) -> Arc<ExecutorTask> {
+        let execute = async move { processor.execute().await }.boxed();
+        Arc::new(ExecutorTask {
+            id,
+            query,
+            status: AtomicU8::new(IDLE),
+            state: Mutex::new(TaskState::Executing(execute)),
+            output,
+        })
+    }
+
+    /// Mark the task as queued, false if it is queued, finished, or will be
+    /// queued again once its current poll returns.
+    fn try_schedule(&self) -> bool {
+        loop {
+            let status = self.status.load(Ordering::Acquire);
+            let (next, schedule) = match status {
+                IDLE => (SCHEDULED, true),
+                RUNNING => (NOTIFIED, false),
+                _ => return false,
+            };
+
+            if self
+                .status
+                .compare_exchange(status, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return schedule;
+            }
+        }
+    }
+
+    /// Poll the task once, it pushes at most one block into its output.
+    /// On `TaskStep::Yield` the caller queues the task again.
+    pub fn run(self: &Arc<Self>) -> TaskStep {
+        self.status.store(RUNNING, Ordering::Release);
+
+        let waker = waker_ref(self);
+        let mut cx = Context::from_waker(&waker);
+        match self.poll_step(&mut cx) {
+            TaskStep::Yield => {
+                self.status.store(SCHEDULED, Ordering::Release);
+                TaskStep::Yield
+            }
+            TaskStep::Finished => {
+                self.status.store(FINISHED, Ordering::Release);
+                self.output.finish_producer();
+                TaskStep::Finished
+            }
+            TaskStep::Pending => {
+                match self.status.compare_exchange(
+                    RUNNING,
+                    IDLE,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => TaskStep::Pending,
+                    Err(_) => {
+                        // Woken during the poll.
+                        self.status.store(SCHEDULED, Ordering::Release);
+                        TaskStep::Yield
+                    }
+                }
+            }
+        }
+    }
+
+    fn poll_step(&self, cx: &mut Context<'_>) -> TaskStep {
+        let mut state = self.state.lock();
+        loop {
+            match self.output.poll_reserve(cx) {
+                Poll::Pending => return TaskStep::Pending,
+                Poll::Ready(false) => {
+                    *state = TaskState::Finished;
+                    return TaskStep::Finished;
+                }
+                Poll::Ready(true) => {}
+            }
+
+            match &mut *state {
+                TaskState::Executing(execute) => match execute.poll_unpin(cx) {
+                    Poll::Pending => return TaskStep::Pending,
+                    Poll::Ready(Ok(stream)) => *state = TaskState::Pulling(stream),
+                    Poll::Ready(Err(cause)) => {
+                        self.output.push(Err(cause));
+                        *state = TaskState::Finished;
+                        return TaskStep::Finished;
+                    }
+                },
+                TaskState::Pulling(stream) => match stream.poll_next_unpin(cx) {
+                    Poll::Pending => return TaskStep::Pending,
+                    Poll::Ready(Some(Ok(block))) => {
+                        self.output.push(Ok(block));
+                        return TaskStep::Yield;
+                    }
+                    Poll::Ready(Some(Err(cause))) => {
+                        // Stop pulling data
+                        self.output.push(Err(cause));
+                        *state = TaskState::Finished;
+                        return TaskStep::Finished;
+                    }
+                    Poll::Ready(None) => {
+                        *state = TaskState::Finished;
+                        return TaskStep::Finished;
+                    }
+                },
+                TaskState::Finished => return TaskStep::Finished,
+            }
+        }
+    }
+}
+
+impl ArcWake for ExecutorTask {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        if arc_self.try_schedule() {
+            arc_self.query.schedule(arc_self.clone());
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::executor::*;
+use crate::pipelines::processors::Processor;
+
+// Produces the numbers from `start`, a block of one row per number.
+struct NumbersProcessor {
+    start: u64,
+    blocks: u64,
+}
+
+#[async_trait::async_trait]
+impl Processor for NumbersProcessor {
+    fn name(&self) -> &str {
+        "NumbersProcessor"
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let schema =
+            DataSchemaRefExt::create(vec![DataField::new("number", DataType::UInt64, false)]);
+        let blocks = (self.start..self.start + self.blocks)
+            .map(|number| {
+                DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![number])])
+            })
+            .collect();
+        Ok(Box::pin(DataBlockStream::create(schema, None, blocks)))
+    }
+}
+
+struct ErrorProcessor;
+
+#[async_trait::async_trait]
+impl Processor for ErrorProcessor {
+    fn name(&self) -> &str {
+        "ErrorProcessor"
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        Err(ErrorCode::LogicalError("Cannot execute ErrorProcessor"))
+    }
+}
+
+fn numbers(start: u64, blocks: u64) -> Arc<dyn Processor> {
+    Arc::new(NumbersProcessor { start, blocks })
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_executor_execute() -> Result<()> {
+    let executor = PipelineExecutor::create(2);
+    let query = executor.create_query(DEFAULT_CPU_SHARES)?;
+
+    let stream = query.execute(vec![
+        numbers(0, 10),
+        numbers(10, 10),
+        numbers(20, 10),
+        numbers(30, 10),
+    ])?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 40);
+
+    let mut values = vec![];
+    for block in result {
+        for value in block.column(0).to_values()? {
+            if let DataValue::UInt64(Some(number)) = value {
+                values.push(number);
+            }
+        }
+    }
+    values.sort_unstable();
+    assert_eq!(values, (0..40).collect::<Vec<u64>>());
+
+    assert_eq!(query.get_shares(), DEFAULT_CPU_SHARES);
+    assert!(query.get_cpu_time() > Duration::from_nanos(0));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_executor_error() -> Result<()> {
+    let executor = PipelineExecutor::create(2);
+    let query = executor.create_query(DEFAULT_CPU_SHARES)?;
+
+    let stream = query.execute(vec![numbers(0, 10), Arc::new(ErrorProcessor)])?;
+    let result = stream.try_collect::<Vec<_>>().await;
+    let actual = result.err().map(|error| error.message());
+    let expect = Some("Cannot execute ErrorProcessor".to_string());
+    assert_eq!(actual, expect);
+
+    let result = query.execute(vec![]);
+    assert_eq!(
+        result.err().map(|error| error.code()),
+        Some(ErrorCode::IllegalTransformConnectionState("").code())
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_executor_drop_stream() -> Result<()> {
+    // One worker for two queries, a dropped stream must not hold it.
+    let executor = PipelineExecutor::create(1);
+    let query = executor.create_query(DEFAULT_CPU_SHARES)?;
+
+    let mut stream = query.execute(vec![numbers(0, 1000), numbers(1000, 1000)])?;
+    assert!(stream.next().await.is_some());
+    drop(stream);
+
+    let other = executor.create_query(DEFAULT_CPU_SHARES * 2)?;
+    let stream = other.execute(vec![numbers(0, 10), numbers(10, 10)])?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 20);
+    Ok(())
+}
@@ -26,6 +26,8 @@ mod processor_empty_test;
 mod processor_merge_test;
 #[cfg(test)]
 mod processor_mixed_test;
+#[cfg(test)]
+mod processor_profiling_test;
 
 mod pipe;
 mod pipeline;
@@ -36,6 +38,7 @@ mod processor;
 mod processor_empty;
 mod processor_merge;
 mod processor_mixed;
+mod processor_profiling;
 
 pub use pipe::Pipe;
 pub use pipeline::Pipeline;
@@ -45,3 +48,5 @@ pub use processor::Processor;
 pub use processor_empty::EmptyProcessor;
 pub use processor_merge::MergeProcessor;
 pub use processor_mixed::MixedProcessor;
+pub use processor_profiling::ProcessorProfileValues;
+pub use processor_profiling::ProfilingProcessor;
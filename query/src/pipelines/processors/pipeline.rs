@@ -22,6 +22,7 @@ use super::MixedProcessor;
 use crate::pipelines::processors::MergeProcessor;
 use crate::pipelines::processors::Pipe;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::processors::ProfilingProcessor;
 use crate::sessions::DatafuseQueryContextRef;
 
 pub struct Pipeline {
@@ -62,7 +63,16 @@ impl Pipeline {
             .ok_or_else(|| ErrorCode::IllegalPipelineState("Pipeline last pipe can not be none"))
     }
 
+    /// Wrap the processor to collect its runtime statistics if the query is profiled.
+    fn profile(&self, processor: Arc<dyn Processor>) -> Arc<dyn Processor> {
+        match self.ctx.is_processor_profiling() {
+            true => Arc::new(ProfilingProcessor::create(processor)),
+            false => processor,
+        }
+    }
+
     pub fn add_source(&mut self, source: Arc<dyn Processor>) -> Result<()> {
+        let source = self.profile(source);
         if self.pipes.first().is_none() {
             let mut first = Pipe::create();
             first.add(source);
@@ -90,7 +100,7 @@ impl Pipeline {
         for x in last_pipe.processors() {
             let mut p = f()?;
             p.connect_to(x.clone())?;
-            new_pipe.add(Arc::from(p));
+            new_pipe.add(self.profile(Arc::from(p)));
         }
         self.pipes.push(new_pipe);
         Ok(())
@@ -112,7 +122,7 @@ impl Pipeline {
                 merge.connect_to(x.clone())?;
             }
             let mut new_pipe = Pipe::create();
-            new_pipe.add(Arc::from(merge));
+            new_pipe.add(self.profile(Arc::from(merge)));
             self.pipes.push(new_pipe);
         }
         Ok(())
@@ -145,9 +155,9 @@ impl Pipeline {
         let mut new_pipe = Pipe::create();
         for _i in 0..n - 1 {
             let processor = processor.share()?;
-            new_pipe.add(Arc::from(processor));
+            new_pipe.add(self.profile(Arc::from(processor)));
         }
-        new_pipe.add(Arc::from(processor));
+        new_pipe.add(self.profile(Arc::from(processor)));
         self.pipes.push(new_pipe);
 
         Ok(())
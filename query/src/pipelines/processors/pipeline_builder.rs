@@ -31,6 +31,7 @@ use common_planners::SelectPlan;
 use common_planners::SortPlan;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::WindowPlan;
 use common_tracing::tracing;
 
 use crate::api::FlightTicket;
@@ -50,6 +51,7 @@ use crate::pipelines::transforms::SortMergeTransform;
 use crate::pipelines::transforms::SortPartialTransform;
 use crate::pipelines::transforms::SourceTransform;
 use crate::pipelines::transforms::SubQueriesPuller;
+use crate::pipelines::transforms::WindowTransform;
 use crate::sessions::DatafuseQueryContextRef;
 
 pub struct PipelineBuilder {
@@ -84,6 +86,7 @@ impl PipelineBuilder {
             PlanNode::Filter(node) => self.visit_filter(node),
             PlanNode::Having(node) => self.visit_having(node),
             PlanNode::Sort(node) => self.visit_sort(node),
+            PlanNode::Window(node) => self.visit_window(node),
             PlanNode::Limit(node) => self.visit_limit(node),
             PlanNode::LimitBy(node) => self.visit_limit_by(node),
             PlanNode::ReadSource(node) => self.visit_read_data_source(node),
@@ -272,6 +275,21 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn visit_window(&mut self, node: &WindowPlan) -> Result<Pipeline> {
+        // The window functions need all the rows, the limit can't be pushed down to the sort.
+        self.limit = None;
+
+        let mut pipeline = self.visit(&*node.input)?;
+        pipeline.merge_processor()?;
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(WindowTransform::try_create(
+                node.schema(),
+                node.window_exprs.clone(),
+            )?))
+        })?;
+        Ok(pipeline)
+    }
+
     fn visit_limit(&mut self, node: &LimitPlan) -> Result<Pipeline> {
         self.limit = node.n;
 
@@ -15,7 +15,11 @@
 use std::fmt;
 use std::fmt::Display;
 
+use crate::pipelines::processors::Pipe;
 use crate::pipelines::processors::Pipeline;
+use crate::pipelines::processors::ProcessorProfileValues;
+use crate::pipelines::processors::ProfilingProcessor;
+use crate::pipelines::transforms::RemoteTransform;
 
 impl Pipeline {
     pub fn display_indent(&self) -> impl fmt::Display + '_ {
@@ -48,7 +52,11 @@ impl Pipeline {
                             let mut pipes = self.0.pipes();
                             pipes.reverse();
 
-                            let prev_pipe = pipes[index - 1].clone();
+                            // The executed pipeline may end with a merge.
+                            let prev_pipe = match index {
+                                0 => pipe.clone(),
+                                _ => pipes[index - 1].clone(),
+                            };
                             let prev_name = prev_pipe.name().to_string();
                             let prev_ways = prev_pipe.nums();
 
@@ -103,13 +111,14 @@ impl Pipeline {
                         }
                         "RemoteTransform" => {
                             let name = processor.name();
-
-                            // TODO: We should output for every remote
-                            write!(
-                                f,
-                                "{} × {} processor(s)",
-                                name, ways /*, pipeline_display*/
-                            )?
+                            write!(f, "{} × {} processor(s)", name, ways)?;
+                            write_profile(f, pipe)?;
+
+                            // The profiled pipelines of the remote stages, reported over flight.
+                            for (node, remote_profile) in remote_profiles(pipe) {
+                                write_lines(f, index + 1, &format!("Remote {}:", node))?;
+                                write_lines(f, index + 2, &remote_profile)?;
+                            }
                         }
                         _ => {
                             write!(
@@ -122,6 +131,11 @@ impl Pipeline {
                         }
                     }
 
+                    match processor.name() {
+                        "EmptyProcessor" | "RemoteTransform" => {}
+                        _ => write_profile(f, pipe)?,
+                    }
+
                     index += 1;
                     Result::<bool, fmt::Error>::Ok(true)
                 })?;
@@ -150,6 +164,52 @@ impl Pipeline {
     }
 }
 
+fn write_profile(f: &mut fmt::Formatter, pipe: &Pipe) -> fmt::Result {
+    let mut values: Option<ProcessorProfileValues> = None;
+    for processor in pipe.processors() {
+        if let Some(profiling) = processor.as_any().downcast_ref::<ProfilingProcessor>() {
+            let processor_values = profiling.get_profile_values();
+            values = Some(match values {
+                None => processor_values,
+                Some(values) => values.merge(&processor_values),
+            });
+        }
+    }
+
+    match values {
+        None => Ok(()),
+        Some(values) => write!(f, " [{}]", values),
+    }
+}
+
+fn remote_profiles(pipe: &Pipe) -> Vec<(String, String)> {
+    let mut remote_profiles = vec![];
+    for processor in pipe.processors() {
+        let processor = match processor.as_any().downcast_ref::<ProfilingProcessor>() {
+            None => processor,
+            Some(profiling) => profiling.inner(),
+        };
+
+        if let Some(remote) = processor.as_any().downcast_ref::<RemoteTransform>() {
+            if let Some(remote_profile) = remote.get_remote_profile() {
+                remote_profiles.push((remote.get_fetch_node_name(), remote_profile));
+            }
+        }
+    }
+    remote_profiles
+}
+
+fn write_lines(f: &mut fmt::Formatter, indent: usize, text: &str) -> fmt::Result {
+    for line in text.lines() {
+        writeln!(f)?;
+        for _ in 0..indent {
+            write!(f, "  ")?;
+        }
+        write!(f, "{}", line)?;
+    }
+    Ok(())
+}
+
 impl fmt::Debug for Pipeline {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.display_indent().fmt(f)
@@ -15,14 +15,9 @@
 use std::any::Any;
 use std::sync::Arc;
 
-use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
-use common_runtime::tokio::sync::mpsc;
 use common_streams::SendableDataBlockStream;
-use log::error;
-use tokio_stream::wrappers::ReceiverStream;
-use tokio_stream::StreamExt;
 
 use crate::pipelines::processors::Processor;
 use crate::sessions::DatafuseQueryContextRef;
@@ -67,44 +62,10 @@ impl Processor for MergeProcessor {
                 "Merge processor inputs cannot be zero",
             )),
             1 => self.inputs[0].execute().await,
-            _ => {
-                let (sender, receiver) = mpsc::channel::<Result<DataBlock>>(inputs);
-                for i in 0..inputs {
-                    let input = self.inputs[i].clone();
-                    let sender = sender.clone();
-                    self.ctx.execute_task(async move {
-                        let mut stream = match input.execute().await {
-                            Err(e) => {
-                                if let Err(error) = sender.send(Result::Err(e)).await {
-                                    error!("Merge processor cannot push data: {}", error);
-                                }
-                                return;
-                            }
-                            Ok(stream) => stream,
-                        };
-
-                        while let Some(item) = stream.next().await {
-                            match item {
-                                Ok(item) => {
-                                    if let Err(error) = sender.send(Ok(item)).await {
-                                        // Stop pulling data
-                                        error!("Merge processor cannot push data: {}", error);
-                                        return;
-                                    }
-                                }
-                                Err(error) => {
-                                    // Stop pulling data
-                                    if let Err(error) = sender.send(Err(error)).await {
-                                        error!("Merge processor cannot push data: {}", error);
-                                    }
-                                    return;
-                                }
-                            }
-                        }
-                    })?;
-                }
-                Ok(Box::pin(ReceiverStream::new(receiver)))
-            }
+            _ => self
+                .ctx
+                .try_get_executor_query()?
+                .execute(self.inputs.clone()),
         }
     }
 }
@@ -0,0 +1,211 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::pipelines::processors::Processor;
+
+thread_local! {
+    // Time spent polling profiled streams nested in the poll running on this thread.
+    static NESTED_POLL_NANOS: Cell<u64> = Cell::new(0);
+}
+
+/// Runtime statistics of one processor, updated while its stream is polled.
+#[derive(Default)]
+pub struct ProcessorProfile {
+    rows: AtomicUsize,
+    blocks: AtomicUsize,
+    bytes: AtomicUsize,
+    wall_nanos: AtomicU64,
+    cpu_nanos: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProcessorProfileValues {
+    pub rows: usize,
+    pub blocks: usize,
+    pub bytes: usize,
+    /// From the start of `execute` until the end of the stream.
+    pub wall_time: Duration,
+    /// Time spent in the processor's own polls, excluding its profiled inputs.
+    pub cpu_time: Duration,
+}
+
+impl ProcessorProfile {
+    pub fn get_values(&self) -> ProcessorProfileValues {
+        ProcessorProfileValues {
+            rows: self.rows.load(Ordering::Relaxed),
+            blocks: self.blocks.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            wall_time: Duration::from_nanos(self.wall_nanos.load(Ordering::Relaxed)),
+            cpu_time: Duration::from_nanos(self.cpu_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl ProcessorProfileValues {
+    /// Sum the statistics of the processors in one pipe.
+    /// The wall time is the longest one, the processors run in parallel.
+    pub fn merge(&self, other: &ProcessorProfileValues) -> ProcessorProfileValues {
+        ProcessorProfileValues {
+            rows: self.rows + other.rows,
+            blocks: self.blocks + other.blocks,
+            bytes: self.bytes + other.bytes,
+            wall_time: self.wall_time.max(other.wall_time),
+            cpu_time: self.cpu_time + other.cpu_time,
+        }
+    }
+}
+
+impl fmt::Display for ProcessorProfileValues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "rows: {}, blocks: {}, bytes: {}, wall: {:?}, cpu: {:?}",
+            self.rows, self.blocks, self.bytes, self.wall_time, self.cpu_time
+        )
+    }
+}
+
+/// Wraps a connected processor and counts what its stream produces.
+/// The pipeline adds it around every processor when the query context has
+/// processor profiling enabled.
+pub struct ProfilingProcessor {
+    inner: Arc<dyn Processor>,
+    profile: Arc<ProcessorProfile>,
+}
+
+impl ProfilingProcessor {
+    pub fn create(inner: Arc<dyn Processor>) -> Self {
+        ProfilingProcessor {
+            inner,
+            profile: Arc::new(ProcessorProfile::default()),
+        }
+    }
+
+    pub fn inner(&self) -> Arc<dyn Processor> {
+        self.inner.clone()
+    }
+
+    pub fn get_profile_values(&self) -> ProcessorProfileValues {
+        self.profile.get_values()
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for ProfilingProcessor {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Result::Err(ErrorCode::IllegalTransformConnectionState(
+            "Cannot call ProfilingProcessor connect_to",
+        ))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        self.inner.inputs()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let start = Instant::now();
+        let input = self.inner.execute().await?;
+        Ok(Box::pin(ProfilingStream {
+            input,
+            start,
+            finished: false,
+            profile: self.profile.clone(),
+        }))
+    }
+}
+
+struct ProfilingStream {
+    input: SendableDataBlockStream,
+    start: Instant,
+    finished: bool,
+    profile: Arc<ProcessorProfile>,
+}
+
+impl ProfilingStream {
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            let elapsed = self.start.elapsed().as_nanos() as u64;
+            self.profile.wall_nanos.store(elapsed, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Stream for ProfilingStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // The inputs of a simple transform are polled inside its own poll,
+        // so their time is taken out to keep only the processor's own part.
+        let outer_nested = NESTED_POLL_NANOS.with(|nested| nested.replace(0));
+        let poll_start = Instant::now();
+        let poll = self.input.poll_next_unpin(ctx);
+        let elapsed = poll_start.elapsed().as_nanos() as u64;
+        let inner_nested = NESTED_POLL_NANOS.with(|nested| nested.replace(outer_nested + elapsed));
+
+        let cpu_nanos = elapsed.saturating_sub(inner_nested);
+        self.profile
+            .cpu_nanos
+            .fetch_add(cpu_nanos, Ordering::Relaxed);
+
+        match &poll {
+            Poll::Ready(Some(Ok(block))) => {
+                self.profile
+                    .rows
+                    .fetch_add(block.num_rows(), Ordering::Relaxed);
+                self.profile.blocks.fetch_add(1, Ordering::Relaxed);
+                self.profile
+                    .bytes
+                    .fetch_add(block.memory_size(), Ordering::Relaxed);
+            }
+            Poll::Ready(None) => self.finish(),
+            _ => {}
+        }
+        poll
+    }
+}
+
+impl Drop for ProfilingStream {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+use crate::tests;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_processor_profiling() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    ctx.enable_processor_profiling();
+    let test_source = tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(8)?;
+    pipeline.add_source(Arc::new(source))?;
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let processor = pipeline.last_pipe()?.first();
+    assert_eq!(processor.name(), "SourceTransform");
+
+    let profiling = processor.as_any().downcast_ref::<ProfilingProcessor>();
+    assert!(profiling.is_some());
+
+    let values = profiling.unwrap().get_profile_values();
+    assert_eq!(values.rows, 8);
+    assert_eq!(values.blocks, result.len());
+    assert_eq!(
+        values.bytes,
+        result
+            .iter()
+            .map(|block| block.memory_size())
+            .sum::<usize>()
+    );
+    assert!(values.wall_time >= values.cpu_time);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_processor_profiling_disabled() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(8)?;
+    pipeline.add_source(Arc::new(source))?;
+
+    let processor = pipeline.last_pipe()?.first();
+    let profiling = processor.as_any().downcast_ref::<ProfilingProcessor>();
+    assert!(profiling.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_processor_profile_values_merge() -> Result<()> {
+    let a = ProcessorProfileValues {
+        rows: 3,
+        blocks: 1,
+        bytes: 24,
+        wall_time: Duration::from_millis(10),
+        cpu_time: Duration::from_millis(2),
+    };
+    let b = ProcessorProfileValues {
+        rows: 5,
+        blocks: 2,
+        bytes: 40,
+        wall_time: Duration::from_millis(7),
+        cpu_time: Duration::from_millis(3),
+    };
+
+    let expect = ProcessorProfileValues {
+        rows: 8,
+        blocks: 3,
+        bytes: 64,
+        wall_time: Duration::from_millis(10),
+        cpu_time: Duration::from_millis(5),
+    };
+    assert_eq!(expect, a.merge(&b));
+    assert_eq!(
+        "rows: 8, blocks: 3, bytes: 64, wall: 10ms, cpu: 5ms",
+        format!("{}", expect)
+    );
+
+    Ok(())
+}
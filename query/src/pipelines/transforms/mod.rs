@@ -17,7 +17,6 @@ pub use transform_aggregator_partial::AggregatorPartialTransform;
 pub use transform_create_sets::CreateSetsTransform;
 pub use transform_create_sets::SubQueriesPuller;
 pub use transform_expression::ExpressionTransform;
-pub use transform_expression_executor::ExpressionExecutor;
 pub use transform_filter::FilterTransform;
 pub use transform_group_by_final::GroupByFinalTransform;
 pub use transform_group_by_partial::GroupByPartialTransform;
@@ -28,6 +27,7 @@ pub use transform_remote::RemoteTransform;
 pub use transform_sort_merge::SortMergeTransform;
 pub use transform_sort_partial::SortPartialTransform;
 pub use transform_source::SourceTransform;
+pub use transform_window::WindowTransform;
 
 #[cfg(test)]
 mod transform_aggregator_final_test;
@@ -51,12 +51,13 @@ mod transform_projection_test;
 mod transform_sort_test;
 #[cfg(test)]
 mod transform_source_test;
+#[cfg(test)]
+mod transform_window_test;
 
 mod transform_aggregator_final;
 mod transform_aggregator_partial;
 mod transform_create_sets;
 mod transform_expression;
-mod transform_expression_executor;
 mod transform_filter;
 mod transform_group_by_final;
 mod transform_group_by_partial;
@@ -67,5 +68,6 @@ mod transform_remote;
 mod transform_sort_merge;
 mod transform_sort_partial;
 mod transform_source;
+mod transform_window;
 
 mod aggregator;
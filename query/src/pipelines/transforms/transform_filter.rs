@@ -22,6 +22,7 @@ use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_planners::Expression;
+use common_planners::ExpressionExecutor;
 use common_streams::CorrectWithSchemaStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
@@ -29,7 +30,6 @@ use tokio_stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
-use crate::pipelines::transforms::ExpressionExecutor;
 
 pub struct FilterTransform {
     schema: DataSchemaRef,
@@ -20,13 +20,13 @@ use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_planners::Expression;
+use common_planners::ExpressionExecutor;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use tokio_stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
-use crate::pipelines::transforms::ExpressionExecutor;
 
 pub struct ProjectionTransform {
     executor: Arc<ExpressionExecutor>,
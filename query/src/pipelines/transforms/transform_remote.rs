@@ -18,6 +18,7 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_infallible::RwLock;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
@@ -31,6 +32,7 @@ pub struct RemoteTransform {
     ticket: FlightTicket,
     fetch_node_name: String,
     schema: DataSchemaRef,
+    remote_profile: Arc<RwLock<Option<String>>>,
     pub ctx: DatafuseQueryContextRef,
 }
 
@@ -45,10 +47,21 @@ impl RemoteTransform {
             ticket,
             fetch_node_name,
             schema,
+            remote_profile: Arc::new(RwLock::new(None)),
             ctx: context,
         })
     }
 
+    pub fn get_fetch_node_name(&self) -> String {
+        self.fetch_node_name.clone()
+    }
+
+    /// The profiled pipeline of the remote stage, sent at the end of the stream
+    /// when the query runs with processor profiling.
+    pub fn get_remote_profile(&self) -> Option<String> {
+        self.remote_profile.read().clone()
+    }
+
     async fn flight_client(&self) -> Result<FlightClient> {
         let context = self.ctx.clone();
         let cluster = context.try_get_cluster()?;
@@ -89,7 +102,9 @@ impl Processor for RemoteTransform {
 
         let fetch_ticket = self.ticket.clone();
         let mut flight_client = self.flight_client().await?;
-        let fetch_stream = flight_client.fetch_stream(fetch_ticket, data_schema, timeout);
+        let remote_profile = self.remote_profile.clone();
+        let fetch_stream =
+            flight_client.fetch_stream(fetch_ticket, data_schema, remote_profile, timeout);
         Ok(Box::pin(
             self.ctx.try_create_abortable(fetch_stream.await?)?,
         ))
@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Instant;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_runtime::tokio;
 use futures::TryStreamExt;
@@ -53,3 +55,20 @@ async fn transform_source_test() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn transform_source_with_deadline_test() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    ctx.set_deadline(Some(Instant::now()));
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx);
+    let a = test_source.number_source_transform_for_test(8)?;
+    pipeline.add_source(Arc::new(a))?;
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await;
+    assert_eq!(result.unwrap_err().code(), ErrorCode::Timeout("").code());
+
+    Ok(())
+}
@@ -0,0 +1,550 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+use common_planners::sort_to_inner_expr;
+use common_planners::Expression;
+use common_planners::WindowFrame;
+use common_planners::WindowFrameBound;
+use common_planners::WindowFrameUnits;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::transform_sort_partial::get_sort_descriptions;
+
+/// Evaluates window functions over all the input rows.
+/// For each window expression the rows are sorted by its partition and order keys,
+/// then the result column is computed partition by partition and appended to the block.
+pub struct WindowTransform {
+    schema: DataSchemaRef,
+    window_exprs: Vec<Expression>,
+    input: Arc<dyn Processor>,
+}
+
+impl WindowTransform {
+    pub fn try_create(schema: DataSchemaRef, window_exprs: Vec<Expression>) -> Result<Self> {
+        Ok(WindowTransform {
+            schema,
+            window_exprs,
+            input: Arc::new(EmptyProcessor::create()),
+        })
+    }
+
+    fn sort_block(block: &DataBlock, expr: &Expression) -> Result<DataBlock> {
+        let (partition_by, order_by) = match expr {
+            Expression::WindowFunction {
+                partition_by,
+                order_by,
+                ..
+            } => (partition_by, order_by),
+            _ => {
+                return Err(ErrorCode::BadTransformType(format!(
+                    "Window expression must be Expression::WindowFunction, but got: {:?}",
+                    expr
+                )))
+            }
+        };
+
+        let mut sort_columns_descriptions = partition_by
+            .iter()
+            .map(|expr| SortColumnDescription {
+                column_name: expr.column_name(),
+                asc: true,
+                nulls_first: true,
+            })
+            .collect::<Vec<_>>();
+        sort_columns_descriptions.extend(get_sort_descriptions(block.schema(), order_by)?);
+
+        match sort_columns_descriptions.is_empty() {
+            true => Ok(block.clone()),
+            false => DataBlock::sort_block(block, &sort_columns_descriptions, None),
+        }
+    }
+
+    fn evaluate(block: &DataBlock, expr: &Expression) -> Result<Series> {
+        let (op, args, partition_by, order_by, frame) = match expr {
+            Expression::WindowFunction {
+                op,
+                args,
+                partition_by,
+                order_by,
+                frame,
+                ..
+            } => (op.to_lowercase(), args, partition_by, order_by, frame),
+            _ => {
+                return Err(ErrorCode::BadTransformType(format!(
+                    "Window expression must be Expression::WindowFunction, but got: {:?}",
+                    expr
+                )))
+            }
+        };
+
+        let rows = block.num_rows();
+        let partition_columns = Self::columns_by_exprs(block, partition_by)?;
+        let order_exprs = order_by.iter().map(sort_to_inner_expr).collect::<Vec<_>>();
+        let order_columns = Self::columns_by_exprs(block, &order_exprs)?;
+        let arg_columns = Self::columns_by_exprs(block, args)?;
+
+        let range_keys = match frame {
+            Some(frame) if frame.units == WindowFrameUnits::Range => {
+                Self::range_keys(frame, order_by, &order_columns)?
+            }
+            _ => None,
+        };
+
+        let return_type = expr.to_data_type(block.schema())?;
+        let mut values = Vec::with_capacity(rows);
+
+        let mut partition_start = 0;
+        while partition_start < rows {
+            let mut partition_end = partition_start + 1;
+            while partition_end < rows
+                && Self::row_equals(&partition_columns, partition_start, partition_end)?
+            {
+                partition_end += 1;
+            }
+
+            let partition = WindowPartition::try_create(
+                partition_start,
+                partition_end,
+                &order_columns,
+                range_keys.as_deref(),
+            )?;
+
+            match op.as_str() {
+                "row_number" => {
+                    for row in partition.start..partition.end {
+                        let value = (row - partition.start + 1) as u64;
+                        values.push(DataValue::UInt64(Some(value)));
+                    }
+                }
+                "rank" => {
+                    for row in partition.start..partition.end {
+                        let value = (partition.peer_start(row) - partition.start + 1) as u64;
+                        values.push(DataValue::UInt64(Some(value)));
+                    }
+                }
+                "dense_rank" => {
+                    for row in partition.start..partition.end {
+                        let value = (partition.peer_index(row) + 1) as u64;
+                        values.push(DataValue::UInt64(Some(value)));
+                    }
+                }
+                "lag" | "lead" => {
+                    let (offset, default) = Self::offset_args(&op, args, &return_type)?;
+                    let column = Self::first_arg(&op, &arg_columns)?;
+                    for row in partition.start..partition.end {
+                        let target = match op.as_str() {
+                            "lag" => row.checked_sub(offset).filter(|v| *v >= partition.start),
+                            _ => Some(row.saturating_add(offset)).filter(|v| *v < partition.end),
+                        };
+                        match target {
+                            Some(target) => values.push(column.try_get(target)?),
+                            None => values.push(default.clone()),
+                        }
+                    }
+                }
+                "first_value" | "last_value" => {
+                    let column = Self::first_arg(&op, &arg_columns)?;
+                    for row in partition.start..partition.end {
+                        let (start, end) = partition.frame(frame, !order_by.is_empty(), row);
+                        match (start < end, op.as_str()) {
+                            (false, _) => values.push(DataValue::from(&return_type)),
+                            (true, "first_value") => values.push(column.try_get(start)?),
+                            (true, _) => values.push(column.try_get(end - 1)?),
+                        }
+                    }
+                }
+                _ => Self::evaluate_aggregate(
+                    block,
+                    expr,
+                    &partition,
+                    frame,
+                    !order_by.is_empty(),
+                    &arg_columns,
+                    &mut values,
+                )?,
+            }
+
+            partition_start = partition_end;
+        }
+
+        DataValue::try_into_data_array(&values, &return_type)
+    }
+
+    /// Evaluates an aggregate function over the frame of each row of the partition.
+    /// The state is accumulated incrementally when all the frames start at the partition start.
+    fn evaluate_aggregate(
+        block: &DataBlock,
+        expr: &Expression,
+        partition: &WindowPartition,
+        frame: &Option<WindowFrame>,
+        has_order: bool,
+        arg_columns: &[Series],
+        values: &mut Vec<DataValue>,
+    ) -> Result<()> {
+        let func = expr.to_aggregate_function(block.schema())?;
+        let arena = Bump::new();
+
+        let accumulate = |place: StateAddr, start: usize, end: usize| -> Result<()> {
+            if start < end {
+                let arrays = arg_columns
+                    .iter()
+                    .map(|column| column.slice(start, end - start))
+                    .collect::<Vec<_>>();
+                func.accumulate(place, &arrays, end - start)?;
+            }
+            Ok(())
+        };
+
+        let running = match frame {
+            None => true,
+            Some(frame) => frame.start == WindowFrameBound::UnboundedPreceding,
+        };
+
+        if running {
+            let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+            func.init_state(place);
+
+            let mut accumulated = partition.start;
+            for row in partition.start..partition.end {
+                let (_, end) = partition.frame(frame, has_order, row);
+                if end > accumulated {
+                    accumulate(place, accumulated, end)?;
+                    accumulated = end;
+                }
+                values.push(func.merge_result(place)?);
+            }
+        } else {
+            for row in partition.start..partition.end {
+                let (start, end) = partition.frame(frame, has_order, row);
+                let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+                func.init_state(place);
+                accumulate(place, start, end)?;
+                values.push(func.merge_result(place)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn columns_by_exprs(block: &DataBlock, exprs: &[Expression]) -> Result<Vec<Series>> {
+        exprs
+            .iter()
+            .map(|expr| block.try_array_by_name(&expr.column_name()))
+            .collect::<Result<Vec<_>>>()
+    }
+
+    fn row_equals(columns: &[Series], lhs: usize, rhs: usize) -> Result<bool> {
+        for column in columns {
+            if column.try_get(lhs)? != column.try_get(rhs)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn first_arg<'a>(op: &str, arg_columns: &'a [Series]) -> Result<&'a Series> {
+        arg_columns.first().ok_or_else(|| {
+            ErrorCode::NumberArgumentsNotMatch(format!(
+                "Window function {} expect to have at least one argument",
+                op
+            ))
+        })
+    }
+
+    /// Returns the offset and the default value of lag/lead, which must be constants.
+    fn offset_args(
+        op: &str,
+        args: &[Expression],
+        data_type: &DataType,
+    ) -> Result<(usize, DataValue)> {
+        let constant = |index: usize| -> Result<Option<DataValue>> {
+            match args.get(index) {
+                None => Ok(None),
+                Some(Expression::Literal { value, .. }) => Ok(Some(value.clone())),
+                Some(other) => Err(ErrorCode::BadArguments(format!(
+                    "The argument {:?} of {} must be a constant",
+                    other, op
+                ))),
+            }
+        };
+
+        let offset = match constant(1)? {
+            None => 1,
+            Some(value) => value.as_u64()? as usize,
+        };
+        let default = match constant(2)? {
+            None => DataValue::from(data_type),
+            Some(value) => value
+                .to_series_with_size(1)?
+                .cast_with_type(data_type)?
+                .try_get(0)?,
+        };
+        Ok((offset, default))
+    }
+
+    /// The sort keys of RANGE frames with offsets, which are normalized to ascending order.
+    fn range_keys(
+        frame: &WindowFrame,
+        order_by: &[Expression],
+        order_columns: &[Series],
+    ) -> Result<Option<Vec<Option<f64>>>> {
+        let has_offset = |bound: &WindowFrameBound| {
+            matches!(
+                bound,
+                WindowFrameBound::Preceding(_) | WindowFrameBound::Following(_)
+            )
+        };
+        if !has_offset(&frame.start) && !has_offset(&frame.end) {
+            return Ok(None);
+        }
+
+        if order_columns.len() != 1 || !is_numeric(order_columns[0].data_type()) {
+            return Err(ErrorCode::BadArguments(
+                "RANGE frame with offset requires exactly one numeric ORDER BY key",
+            ));
+        }
+
+        let asc = match &order_by[0] {
+            Expression::Sort { asc, .. } => *asc,
+            _ => true,
+        };
+        let keys = order_columns[0].cast_with_type(&DataType::Float64)?;
+        let keys = keys
+            .f64()?
+            .downcast_iter()
+            .map(|key| key.map(|v| if asc { *v } else { -*v }))
+            .collect::<Vec<_>>();
+        Ok(Some(keys))
+    }
+}
+
+/// The rows [start, end) of a window partition and their peer groups.
+struct WindowPartition<'a> {
+    start: usize,
+    end: usize,
+    // The peer group boundaries of each row, relative to the partition start.
+    peer_starts: Vec<usize>,
+    peer_ends: Vec<usize>,
+    peer_indexes: Vec<usize>,
+    range_keys: Option<&'a [Option<f64>]>,
+}
+
+impl<'a> WindowPartition<'a> {
+    fn try_create(
+        start: usize,
+        end: usize,
+        order_columns: &[Series],
+        range_keys: Option<&'a [Option<f64>]>,
+    ) -> Result<Self> {
+        let size = end - start;
+        let mut peer_starts = Vec::with_capacity(size);
+        let mut peer_ends = vec![0; size];
+        let mut peer_indexes = Vec::with_capacity(size);
+
+        let mut peer_start = start;
+        let mut peer_index = 0;
+        for row in start..end {
+            if row > peer_start && !WindowTransform::row_equals(order_columns, peer_start, row)? {
+                for peer_end in peer_ends
+                    .iter_mut()
+                    .take(row - start)
+                    .skip(peer_start - start)
+                {
+                    *peer_end = row;
+                }
+                peer_start = row;
+                peer_index += 1;
+            }
+            peer_starts.push(peer_start);
+            peer_indexes.push(peer_index);
+        }
+        for peer_end in peer_ends.iter_mut().skip(peer_start - start) {
+            *peer_end = end;
+        }
+
+        Ok(WindowPartition {
+            start,
+            end,
+            peer_starts,
+            peer_ends,
+            peer_indexes,
+            range_keys,
+        })
+    }
+
+    fn peer_start(&self, row: usize) -> usize {
+        self.peer_starts[row - self.start]
+    }
+
+    fn peer_end(&self, row: usize) -> usize {
+        self.peer_ends[row - self.start]
+    }
+
+    fn peer_index(&self, row: usize) -> usize {
+        self.peer_indexes[row - self.start]
+    }
+
+    /// Returns the frame [start, end) of the row. Without an explicit frame, the frame is
+    /// the whole partition if there is no ORDER BY, otherwise it ends at the last peer.
+    fn frame(&self, frame: &Option<WindowFrame>, has_order: bool, row: usize) -> (usize, usize) {
+        let frame = match frame {
+            Some(frame) => frame,
+            None if has_order => return (self.start, self.peer_end(row)),
+            None => return (self.start, self.end),
+        };
+
+        let rows = frame.units == WindowFrameUnits::Rows;
+        let start = match frame.start {
+            WindowFrameBound::UnboundedPreceding => self.start,
+            WindowFrameBound::UnboundedFollowing => self.end,
+            WindowFrameBound::CurrentRow if rows => row,
+            WindowFrameBound::CurrentRow => self.peer_start(row),
+            WindowFrameBound::Preceding(n) if rows => {
+                row.saturating_sub(n as usize).max(self.start)
+            }
+            WindowFrameBound::Preceding(n) => self.range_bound(row, -(n as f64), true),
+            WindowFrameBound::Following(n) if rows => row.saturating_add(n as usize).min(self.end),
+            WindowFrameBound::Following(n) => self.range_bound(row, n as f64, true),
+        };
+        let end = match frame.end {
+            WindowFrameBound::UnboundedPreceding => self.start,
+            WindowFrameBound::UnboundedFollowing => self.end,
+            WindowFrameBound::CurrentRow if rows => row + 1,
+            WindowFrameBound::CurrentRow => self.peer_end(row),
+            WindowFrameBound::Preceding(n) if rows => {
+                (row + 1).saturating_sub(n as usize).max(self.start)
+            }
+            WindowFrameBound::Preceding(n) => self.range_bound(row, -(n as f64), false),
+            WindowFrameBound::Following(n) if rows => {
+                (row + 1).saturating_add(n as usize).min(self.end)
+            }
+            WindowFrameBound::Following(n) => self.range_bound(row, n as f64, false),
+        };
+        (start, end.max(start))
+    }
+
+    /// Finds the boundary of the rows whose key is within `key + delta`,
+    /// the NULL keys are only peers of each other.
+    fn range_bound(&self, row: usize, delta: f64, is_start: bool) -> usize {
+        let keys = match self.range_keys {
+            Some(keys) => keys,
+            None if is_start => return self.peer_start(row),
+            None => return self.peer_end(row),
+        };
+
+        let target = match keys[row] {
+            Some(key) => key + delta,
+            None if is_start => return self.peer_start(row),
+            None => return self.peer_end(row),
+        };
+
+        // The NULL keys are sorted to one side of the partition.
+        let keys = &keys[self.start..self.end];
+        let non_null_start = keys.iter().position(|k| k.is_some()).unwrap_or(keys.len());
+        let non_null_end = keys.iter().rposition(|k| k.is_some()).map_or(0, |v| v + 1);
+        let non_null = &keys[non_null_start..non_null_end];
+
+        let position = match is_start {
+            true => non_null.partition_point(|k| k.map_or(false, |k| k < target)),
+            false => non_null.partition_point(|k| k.map_or(false, |k| k <= target)),
+        };
+        self.start + non_null_start + position
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for WindowTransform {
+    fn name(&self) -> &str {
+        "WindowTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let mut blocks = vec![];
+        let mut stream = self.input.execute().await?;
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            if !block.is_empty() {
+                blocks.push(block);
+            }
+        }
+
+        if blocks.is_empty() {
+            return Ok(Box::pin(DataBlockStream::create(
+                self.schema.clone(),
+                None,
+                vec![],
+            )));
+        }
+
+        let mut block = DataBlock::concat_blocks(&blocks)?;
+        for expr in self.window_exprs.iter() {
+            let name = expr.column_name();
+            if block.schema().field_with_name(&name).is_ok() {
+                continue;
+            }
+
+            block = Self::sort_block(&block, expr)?;
+            let column = Self::evaluate(&block, expr)?;
+
+            let mut fields = block.schema().fields().clone();
+            fields.push(DataField::new(&name, column.data_type(), true));
+            let mut columns = block.columns().to_vec();
+            columns.push(DataColumn::Array(column));
+            block = DataBlock::create(DataSchemaRefExt::create(fields), columns);
+        }
+
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| block.try_column_by_name(field.name()).map(|c| c.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        let block = DataBlock::create(self.schema.clone(), columns);
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            vec![block],
+        )))
+    }
+}
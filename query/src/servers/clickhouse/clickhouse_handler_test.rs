@@ -43,6 +43,27 @@ async fn test_clickhouse_handler_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_clickhouse_session_user() -> Result<()> {
+    let sessions = SessionManager::try_create(1)?;
+    let mut handler = ClickHouseHandler::create(sessions);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut handler = create_conn(listening.port()).await?;
+
+    execute(&mut handler, "SELECT 1").await?;
+    let query_str = "SELECT user FROM system.query_log WHERE query_text = 'SELECT 1'";
+    let block = query(&mut handler, query_str).await?;
+    assert_eq!(block.row_count(), 1);
+    let user: Option<String> = block
+        .get(0, "user")
+        .map_err(|error| ErrorCode::UnknownException(format!("Cannot get data {:?}", error)))?;
+    assert_eq!(user, Some("default".to_string()));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_clickhouse_insert_data() -> Result<()> {
     let sessions = SessionManager::try_create(1)?;
@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
 use clickhouse_srv::connection::Connection;
 use clickhouse_srv::CHContext;
 use clickhouse_srv::ClickHouseSession;
 use common_datavalues::prelude::Arc;
+use common_exception::Result;
 use metrics::histogram;
 
 use crate::servers::clickhouse::interactive_worker_base::InteractiveWorkerBase;
@@ -27,11 +30,33 @@ use crate::sessions::SessionRef;
 
 pub struct InteractiveWorker {
     session: SessionRef,
+    // The client hello is taken by clickhouse-srv, the user of it is set and its profile
+    // is applied at the first query.
+    logged_in: AtomicBool,
 }
 
 impl InteractiveWorker {
     pub fn create(session: SessionRef) -> Arc<InteractiveWorker> {
-        Arc::new(InteractiveWorker { session })
+        Arc::new(InteractiveWorker {
+            session,
+            logged_in: AtomicBool::new(false),
+        })
+    }
+
+    async fn login(&self, ctx: &CHContext) -> Result<()> {
+        if self.logged_in.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        if let Some(hello) = &ctx.hello {
+            let user = String::from_utf8_lossy(&hello.user).to_string();
+            if !user.is_empty() {
+                self.session.set_user(user);
+            }
+        }
+        self.session.apply_user_profile().await?;
+        self.logged_in.store(true, Ordering::SeqCst);
+        Ok(())
     }
 }
 
@@ -43,6 +68,7 @@ impl ClickHouseSession for InteractiveWorker {
         conn: &mut Connection,
     ) -> clickhouse_srv::errors::Result<()> {
         let start = Instant::now();
+        self.login(ctx).await.map_err(to_clickhouse_err)?;
 
         let context = self.session.create_context();
         context.attach_query_str(&ctx.state.query);
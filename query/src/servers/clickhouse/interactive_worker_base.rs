@@ -57,6 +57,7 @@ impl InteractiveWorkerBase {
         log::debug!("{}", query);
 
         let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        ctx.admit_query(&plan).await?;
 
         match plan {
             PlanNode::InsertInto(insert) => Self::process_insert_query(insert, ch_ctx, ctx).await,
@@ -65,6 +66,7 @@ impl InteractiveWorkerBase {
 
                 let async_data_stream = interpreter.execute();
                 let mut data_stream = async_data_stream.await?;
+                let mut result_limits = ctx.get_result_limits()?;
 
                 let mut interval_stream = IntervalStream::new(interval(Duration::from_millis(30)));
                 let cancel = Arc::new(AtomicBool::new(false));
@@ -82,7 +84,11 @@ impl InteractiveWorkerBase {
 
                 ctx.execute_task(async move {
                     while let Some(block) = data_stream.next().await {
-                        tx2.send(BlockItem::Block(block)).await.ok();
+                        let limited = block.and_then(|block| result_limits.limit_block(block));
+                        match limited.transpose() {
+                            None => break,
+                            Some(block) => tx2.send(BlockItem::Block(block)).await.ok(),
+                        };
                     }
 
                     cancel_clone.store(true, Ordering::Relaxed);
@@ -34,6 +34,8 @@ pub struct QueryWriter<'a> {
     client_version: u64,
     conn: &'a mut Connection,
     ctx: DatafuseQueryContextRef,
+    result_rows: usize,
+    error: Option<ErrorCode>,
 }
 
 impl<'a> QueryWriter<'a> {
@@ -46,17 +48,23 @@ impl<'a> QueryWriter<'a> {
             client_version: version,
             conn,
             ctx,
+            result_rows: 0,
+            error: None,
         }
     }
 
     pub async fn write(&mut self, receiver: Result<Receiver<BlockItem>>) -> Result<()> {
-        match receiver {
+        let written = match receiver {
             Err(error) => self.write_error(error).await,
             Ok(receiver) => {
                 let write_data = self.write_data(receiver);
                 write_data.await
             }
-        }
+        };
+
+        self.ctx
+            .log_query_finish(self.result_rows, self.error.as_ref());
+        written
     }
 
     async fn write_progress(&mut self) -> Result<()> {
@@ -79,6 +87,7 @@ impl<'a> QueryWriter<'a> {
 
     async fn write_error(&mut self, error: ErrorCode) -> Result<()> {
         log::error!("OnQuery Error: {:?}", error);
+        self.error = Some(error.clone());
         let clickhouse_err = to_clickhouse_err(error);
         match self.conn.write_error(&clickhouse_err).await {
             Ok(_) => Ok(()),
@@ -90,6 +99,7 @@ impl<'a> QueryWriter<'a> {
     }
 
     async fn write_block(&mut self, block: DataBlock) -> Result<()> {
+        self.result_rows += block.num_rows();
         let block = to_clickhouse_block(block)?;
 
         match self.conn.write_block(&block).await {
@@ -181,6 +191,27 @@ pub fn to_clickhouse_block(block: DataBlock) -> Result<Block> {
                 DataType::Date32 => result.column(name, column.date32()?.collect_values()),
                 DataType::Date64 => result.column(name, column.date64()?.collect_values()),
                 DataType::Utf8 => result.column(name, column.utf8()?.collect_values()),
+                // Decimals are sent as strings to keep them exact.
+                DataType::Decimal(_, scale) => {
+                    let v: Vec<Option<String>> = column
+                        .decimal()?
+                        .downcast_iter()
+                        .map(|f| f.map(|v| format_decimal(*v, scale)))
+                        .collect();
+                    result.column(name, v)
+                }
+                // Arrays are sent as strings in the form of [v1, v2, ...].
+                DataType::List(_) => {
+                    let v: Vec<Option<String>> = column
+                        .to_values()?
+                        .iter()
+                        .map(|v| match v.is_null() {
+                            true => None,
+                            false => Some(format!("{}", v)),
+                        })
+                        .collect();
+                    result.column(name, v)
+                }
                 DataType::Boolean => {
                     let v: Vec<Option<u8>> = column
                         .bool()?
@@ -251,6 +282,22 @@ pub fn to_clickhouse_block(block: DataBlock) -> Result<Block> {
                         column.utf8()?.downcast_iter().map(|c| c.unwrap()).collect();
                     result.column(name, vs)
                 }
+                DataType::Decimal(_, scale) => {
+                    let vs: Vec<String> = column
+                        .decimal()?
+                        .into_no_null_iter()
+                        .map(|v| format_decimal(v, scale))
+                        .collect();
+                    result.column(name, vs)
+                }
+                DataType::List(_) => {
+                    let vs: Vec<String> = column
+                        .to_values()?
+                        .iter()
+                        .map(|v| format!("{}", v))
+                        .collect();
+                    result.column(name, vs)
+                }
                 DataType::Boolean => {
                     let vs: Vec<u8> = column
                         .bool()?
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// The servers module used for external communication with user, such as MySQL wired protocol, etc.
+// The servers module used for external communication with user, such as MySQL and PostgreSQL wired protocol, etc.
 
 pub use clickhouse::ClickHouseHandler;
 pub use server::Server;
@@ -20,7 +20,10 @@ pub use server::ShutdownHandle;
 
 pub use self::mysql::MySQLConnection;
 pub use self::mysql::MySQLHandler;
+pub use self::postgres::PostgresConnection;
+pub use self::postgres::PostgresHandler;
 
 mod clickhouse;
 mod mysql;
+mod postgres;
 mod server;
@@ -0,0 +1,96 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+
+use crate::sessions::SessionRef;
+
+const CLIENT_PROTOCOL_41: u32 = 0x0200;
+// The handshake response is tiny, a larger one is not a handshake response.
+const MAX_HANDSHAKE_RESPONSE_LENGTH: usize = 64 * 1024;
+
+/// Reads the client stream for msql-srv, and takes the user name from the handshake response
+/// on its way: msql-srv accepts the client without telling the user.
+pub struct HandshakeReader<R: Read> {
+    inner: R,
+    session: SessionRef,
+    // The bytes of the handshake response read so far, None once it is parsed.
+    response: Option<Vec<u8>>,
+}
+
+impl<R: Read> HandshakeReader<R> {
+    pub fn create(session: SessionRef, inner: R) -> HandshakeReader<R> {
+        HandshakeReader {
+            inner,
+            session,
+            response: Some(vec![]),
+        }
+    }
+
+    // The user name of a complete handshake response packet, or None if the packet is incomplete.
+    fn parse_user(packet: &[u8]) -> Option<Option<String>> {
+        if packet.len() < 4 {
+            return None;
+        }
+
+        let length = u32::from_le_bytes([packet[0], packet[1], packet[2], 0]) as usize;
+        if length > MAX_HANDSHAKE_RESPONSE_LENGTH {
+            return Some(None);
+        }
+        if packet.len() < 4 + length {
+            return None;
+        }
+
+        let payload = &packet[4..4 + length];
+        let capabilities = match payload.len() >= 4 {
+            true => u32::from_le_bytes([payload[0], payload[1], payload[2], payload[3]]),
+            false => return Some(None),
+        };
+
+        // The user name is null terminated, after the capabilities, max packet size, charset
+        // and the reserved bytes, or after the capabilities and the max packet size of 3 bytes.
+        let offset = match capabilities & CLIENT_PROTOCOL_41 {
+            0 => 5,
+            _ => 32,
+        };
+        let user = payload
+            .get(offset..)
+            .and_then(|rest| rest.split(|b| *b == 0).next())
+            .filter(|user| !user.is_empty())
+            .map(|user| String::from_utf8_lossy(user).to_string());
+        Some(user)
+    }
+}
+
+impl<R: Read> Read for HandshakeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+
+        if let Some(response) = &mut self.response {
+            response.extend_from_slice(&buf[..read]);
+            match Self::parse_user(response) {
+                None if read > 0 => {}
+                None => self.response = None,
+                Some(user) => {
+                    if let Some(user) = user {
+                        self.session.set_user(user);
+                    }
+                    self.response = None;
+                }
+            }
+        }
+
+        Ok(read)
+    }
+}
@@ -18,6 +18,7 @@ pub use self::mysql_session::MySQLConnection;
 #[cfg(test)]
 mod mysql_handler_test;
 
+mod handshake_reader;
 mod mysql_handler;
 mod mysql_interactive_worker;
 mod mysql_metrics;
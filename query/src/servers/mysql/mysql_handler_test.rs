@@ -47,6 +47,27 @@ async fn test_use_database_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_session_user_from_handshake() -> Result<()> {
+    let mut handler = MySQLHandler::create(SessionManager::try_create(1)?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let uri = &format!("mysql://test_user@127.0.0.1:{}", runnable_server.port());
+    let opts = mysql::Opts::from_url(uri).unwrap();
+    let mut connection = mysql::Conn::new(opts)
+        .map_err_to_code(ErrorCode::UnknownException, || "Reject connection")?;
+
+    query::<EmptyRow>(&mut connection, "SELECT 1")?;
+    let received_data: Vec<Option<String>> = query(
+        &mut connection,
+        "SELECT user FROM system.query_log WHERE query_text = 'SELECT 1'",
+    )?;
+    assert_eq!(received_data, vec![Some("test_user".to_string())]);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_rejected_session_with_sequence() -> Result<()> {
     let mut handler = MySQLHandler::create(SessionManager::try_create(1)?);
@@ -26,7 +26,6 @@ use msql_srv::MysqlShim;
 use msql_srv::ParamParser;
 use msql_srv::QueryResultWriter;
 use msql_srv::StatementMetaWriter;
-use tokio_stream::StreamExt;
 
 use crate::interpreters::InterpreterFactory;
 use crate::servers::mysql::writers::DFInitResultWriter;
@@ -41,6 +40,7 @@ struct InteractiveWorkerBase<W: std::io::Write>(PhantomData<W>);
 pub struct InteractiveWorker<W: std::io::Write> {
     base: InteractiveWorkerBase<W>,
     session: SessionRef,
+    logged_in: bool,
 }
 
 impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
@@ -103,9 +103,18 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
         let context = self.session.create_context();
 
         context.attach_query_str(query);
-        if let Err(cause) =
-            DFQueryResultWriter::create(writer).write(self.base.do_query(query, context))
-        {
+        let query_result = match self.login() {
+            Ok(_) => self.base.do_query(query, context.clone()),
+            Err(cause) => Err(cause),
+        };
+        match &query_result {
+            Ok(blocks) => {
+                context.log_query_finish(blocks.iter().map(DataBlock::num_rows).sum(), None)
+            }
+            Err(error) => context.log_query_finish(0, Some(error)),
+        }
+
+        if let Err(cause) = DFQueryResultWriter::create(writer).write(query_result) {
             let new_error = cause.add_message(query);
             return Err(new_error);
         };
@@ -131,7 +140,11 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
         }
 
         let context = self.session.create_context();
-        DFInitResultWriter::create(writer).write(self.base.do_init(database_name, context))
+        let init_result = match self.login() {
+            Ok(_) => self.base.do_init(database_name, context),
+            Err(cause) => Err(cause),
+        };
+        DFInitResultWriter::create(writer).write(init_result)
     }
 }
 
@@ -176,10 +189,13 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
         let (plan, hints) = PlanParser::create(context.clone()).build_with_hint_from_sql(query);
 
         let fetch_query_blocks = || -> Result<Vec<DataBlock>> {
-            let interpreter = InterpreterFactory::get(context.clone(), plan?)?;
+            let plan = plan?;
+            runtime.block_on(context.admit_query(&plan))?;
+            let interpreter = InterpreterFactory::get(context.clone(), plan)?;
             let data_stream = runtime.block_on(interpreter.execute())?;
 
-            runtime.block_on(data_stream.collect::<Result<Vec<DataBlock>>>())
+            let result_limits = context.get_result_limits()?;
+            runtime.block_on(result_limits.collect(data_stream))
         };
         let blocks = fetch_query_blocks();
         match blocks {
@@ -225,6 +241,18 @@ impl<W: std::io::Write> InteractiveWorker<W> {
         InteractiveWorker::<W> {
             session,
             base: InteractiveWorkerBase::<W>(PhantomData::<W>),
+            logged_in: false,
         }
     }
+
+    // The user is taken from the handshake response by the HandshakeReader,
+    // its profile is applied before the first command of the client.
+    fn login(&mut self) -> Result<()> {
+        if !self.logged_in {
+            let runtime = InteractiveWorkerBase::<W>::build_runtime()?;
+            runtime.block_on(self.session.apply_user_profile())?;
+            self.logged_in = true;
+        }
+        Ok(())
+    }
 }
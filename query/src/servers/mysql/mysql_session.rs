@@ -21,6 +21,7 @@ use common_exception::ToErrorCode;
 use common_runtime::tokio::net::TcpStream;
 use msql_srv::MysqlIntermediary;
 
+use crate::servers::mysql::handshake_reader::HandshakeReader;
 use crate::servers::mysql::mysql_interactive_worker::InteractiveWorker;
 use crate::sessions::SessionRef;
 
@@ -38,8 +39,16 @@ impl MySQLConnection {
     }
 
     fn session_executor(session: SessionRef, blocking_stream: std::net::TcpStream) {
-        let interactive_worker = InteractiveWorker::create(session);
-        if let Err(error) = MysqlIntermediary::run_on_tcp(interactive_worker, blocking_stream) {
+        let interactive_worker = InteractiveWorker::create(session.clone());
+        let run_on_stream = blocking_stream
+            .try_clone()
+            .map_err(ErrorCode::from)
+            .and_then(|writer| {
+                let reader = HandshakeReader::create(session, blocking_stream);
+                MysqlIntermediary::run_on(interactive_worker, reader, writer)
+            });
+
+        if let Err(error) = run_on_stream {
             if error.code() != ABORT_SESSION {
                 log::error!(
                     "Unexpected error occurred during query execution: {:?}",
@@ -60,11 +60,13 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                 DataType::UInt64 => Ok(ColumnType::MYSQL_TYPE_LONG),
                 DataType::Float32 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
                 DataType::Float64 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
+                DataType::Decimal(_, _) => Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL),
                 DataType::Utf8 => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 DataType::Boolean => Ok(ColumnType::MYSQL_TYPE_SHORT),
                 DataType::Date32 => Ok(ColumnType::MYSQL_TYPE_TIMESTAMP),
                 DataType::Date64 => Ok(ColumnType::MYSQL_TYPE_TIMESTAMP),
                 DataType::Null => Ok(ColumnType::MYSQL_TYPE_NULL),
+                DataType::List(_) => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 _ => Err(ErrorCode::UnImplement(format!(
                     "Unsupported column type:{:?}",
                     field.data_type()
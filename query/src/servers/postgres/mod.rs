@@ -0,0 +1,30 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use self::postgres_handler::PostgresHandler;
+pub use self::postgres_session::PostgresConnection;
+
+#[cfg(test)]
+mod postgres_handler_test;
+#[cfg(test)]
+mod postgres_types_test;
+
+mod postgres_cancel;
+mod postgres_handler;
+mod postgres_interactive_worker;
+mod postgres_metrics;
+mod postgres_protocol;
+mod postgres_session;
+mod postgres_types;
+mod reject_connection;
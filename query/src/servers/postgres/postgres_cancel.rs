@@ -0,0 +1,92 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_infallible::RwLock;
+
+use crate::sessions::SessionManagerRef;
+
+/// The cancel keys of the PostgreSQL connections, the client sends a CancelRequest
+/// with the key on a new connection to cancel the running query of its session.
+pub struct CancelKeys {
+    next_process_id: AtomicI32,
+    keys: RwLock<HashMap<i32, (i32, String)>>,
+}
+
+pub type CancelKeysRef = Arc<CancelKeys>;
+
+impl CancelKeys {
+    pub fn create() -> CancelKeysRef {
+        Arc::new(CancelKeys {
+            next_process_id: AtomicI32::new(1),
+            keys: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub fn register(self: &Arc<Self>, session_id: String) -> BackendKey {
+        let process_id = self.next_process_id.fetch_add(1, Ordering::Relaxed);
+        let secret_key = rand::random::<i32>();
+        self.keys
+            .write()
+            .insert(process_id, (secret_key, session_id));
+
+        BackendKey {
+            process_id,
+            secret_key,
+            cancel_keys: self.clone(),
+        }
+    }
+
+    /// Kill the running query of the session through the SessionManager,
+    /// returns false if the key does not match any session.
+    pub fn cancel(&self, sessions: &SessionManagerRef, process_id: i32, secret_key: i32) -> bool {
+        let session_id = match self.keys.read().get(&process_id) {
+            Some((key, session_id)) if *key == secret_key => session_id.clone(),
+            _ => return false,
+        };
+
+        match sessions.get_session(&session_id) {
+            None => false,
+            Some(session) => {
+                log::info!(
+                    "Cancel the running query of PostgreSQL session {}",
+                    session_id
+                );
+                session.force_kill_query();
+                true
+            }
+        }
+    }
+
+    fn unregister(&self, process_id: i32) {
+        self.keys.write().remove(&process_id);
+    }
+}
+
+/// The key sent to the client in BackendKeyData, it's unregistered when the connection is closed.
+pub struct BackendKey {
+    pub process_id: i32,
+    pub secret_key: i32,
+    cancel_keys: CancelKeysRef,
+}
+
+impl Drop for BackendKey {
+    fn drop(&mut self) {
+        self.cancel_keys.unregister(self.process_id);
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio;
+use common_runtime::tokio::io::AsyncWriteExt;
+use common_runtime::tokio::net::TcpStream;
+use common_runtime::tokio::task::JoinHandle;
+use futures::future::AbortHandle;
+use futures::future::AbortRegistration;
+use futures::future::Abortable;
+use futures::StreamExt;
+use tokio_stream::wrappers::TcpListenerStream;
+
+use crate::servers::postgres::postgres_cancel::CancelKeys;
+use crate::servers::postgres::postgres_cancel::CancelKeysRef;
+use crate::servers::postgres::postgres_protocol::StartupPacket;
+use crate::servers::postgres::postgres_session::PostgresConnection;
+use crate::servers::postgres::reject_connection::RejectConnection;
+use crate::servers::server::ListeningStream;
+use crate::servers::server::Server;
+use crate::sessions::SessionManagerRef;
+
+pub struct PostgresHandler {
+    sessions: SessionManagerRef,
+    cancel_keys: CancelKeysRef,
+    abort_handle: AbortHandle,
+    abort_registration: Option<AbortRegistration>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl PostgresHandler {
+    pub fn create(sessions: SessionManagerRef) -> Box<dyn Server> {
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        Box::new(PostgresHandler {
+            sessions,
+            cancel_keys: CancelKeys::create(),
+            abort_handle,
+            abort_registration: Some(registration),
+            join_handle: None,
+        })
+    }
+
+    async fn listener_tcp(listening: SocketAddr) -> Result<(TcpListenerStream, SocketAddr)> {
+        let listener = tokio::net::TcpListener::bind(listening).await?;
+        let listener_addr = listener.local_addr()?;
+        Ok((TcpListenerStream::new(listener), listener_addr))
+    }
+
+    fn listen_loop(&self, stream: ListeningStream) -> impl Future<Output = ()> {
+        let sessions = self.sessions.clone();
+        let cancel_keys = self.cancel_keys.clone();
+        stream.for_each(move |accept_socket| {
+            let sessions = sessions.clone();
+            let cancel_keys = cancel_keys.clone();
+            async move {
+                match accept_socket {
+                    Err(error) => log::error!("Broken session connection: {}", error),
+                    Ok(socket) => {
+                        // The startup packets are read in the background, don't block the listener.
+                        tokio::spawn(async move {
+                            if let Err(error) =
+                                PostgresHandler::accept_socket(sessions, cancel_keys, socket).await
+                            {
+                                log::error!(
+                                    "Unexpected error occurred during startup: {:?}",
+                                    error
+                                );
+                            }
+                        });
+                    }
+                };
+            }
+        })
+    }
+
+    async fn accept_socket(
+        sessions: SessionManagerRef,
+        cancel_keys: CancelKeysRef,
+        mut socket: TcpStream,
+    ) -> Result<()> {
+        loop {
+            match StartupPacket::read(&mut socket).await? {
+                // We don't support SSL and GSSAPI encryption, the client may retry with plain text.
+                // The password is then sent in cleartext too, as the users only have a SHA-256 of
+                // their password stored, which MD5 and SCRAM authentication can't be checked
+                // against. Only listen on a trusted network, see postgres_handler_host.
+                StartupPacket::SSLRequest | StartupPacket::GSSENCRequest => {
+                    socket.write_all(b"N").await?;
+                    socket.flush().await?;
+                }
+                StartupPacket::CancelRequest {
+                    process_id,
+                    secret_key,
+                } => {
+                    // The cancel request is sent on a new connection, no response is expected.
+                    cancel_keys.cancel(&sessions, process_id, secret_key);
+                    return Ok(());
+                }
+                StartupPacket::Startup { params } => {
+                    return Self::accept_session(sessions, cancel_keys, socket, params).await;
+                }
+            }
+        }
+    }
+
+    async fn accept_session(
+        sessions: SessionManagerRef,
+        cancel_keys: CancelKeysRef,
+        socket: TcpStream,
+        params: HashMap<String, String>,
+    ) -> Result<()> {
+        match sessions.create_session("PostgreSQL") {
+            Err(error) => Self::reject_session(socket, error).await,
+            Ok(session) => {
+                log::info!("PostgreSQL connection coming: {:?}", socket.peer_addr());
+                let backend_key = cancel_keys.register(session.get_id());
+                PostgresConnection::run_on_stream(session, socket, params, backend_key)
+            }
+        }
+    }
+
+    async fn reject_session(stream: TcpStream, error: ErrorCode) -> Result<()> {
+        let code = match error.code() {
+            code if code == ErrorCode::TooManyUserConnections("").code() => "53300",
+            _ => "XX000",
+        };
+
+        if let Err(error) =
+            RejectConnection::reject_postgres_connection(stream, code, error.message()).await
+        {
+            log::error!(
+                "Unexpected error occurred during reject connection: {:?}",
+                error
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Server for PostgresHandler {
+    async fn shutdown(&mut self) {
+        self.abort_handle.abort();
+
+        if let Some(join_handle) = self.join_handle.take() {
+            if let Err(error) = join_handle.await {
+                log::error!(
+                    "Unexpected error during shutdown PostgresHandler. cause {}",
+                    error
+                );
+            }
+        }
+    }
+
+    async fn start(&mut self, listening: SocketAddr) -> Result<SocketAddr> {
+        match self.abort_registration.take() {
+            None => Err(ErrorCode::LogicalError("PostgresHandler already running.")),
+            Some(registration) => {
+                let (stream, listener) = Self::listener_tcp(listening).await?;
+                let stream = Abortable::new(stream, registration);
+                self.join_handle = Some(tokio::spawn(self.listen_loop(stream)));
+                Ok(listener)
+            }
+        }
+    }
+}
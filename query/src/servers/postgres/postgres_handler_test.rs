@@ -0,0 +1,388 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpStream;
+use std::time::Duration;
+
+use common_exception::Result;
+use common_runtime::tokio;
+use pretty_assertions::assert_eq;
+
+use crate::servers::postgres::postgres_cancel::CancelKeys;
+use crate::servers::postgres::postgres_protocol::FrontendMessage;
+use crate::servers::postgres::postgres_protocol::PROTOCOL_VERSION_3;
+use crate::servers::postgres::postgres_protocol::SSL_REQUEST_CODE;
+use crate::servers::PostgresHandler;
+use crate::sessions::SessionManager;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_simple_query() -> Result<()> {
+    let mut handler = PostgresHandler::create(SessionManager::try_create(1)?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut connection = create_connection(listening.port(), &[("user", "root")])?;
+    send_password(&mut connection, "root")?;
+
+    let startup = read_until_ready(&mut connection)?;
+    assert_eq!(startup[0], (b'R', 0_i32.to_be_bytes().to_vec()));
+    assert!(startup.iter().any(|(tag, _)| *tag == b'K'));
+
+    send_message(
+        &mut connection,
+        b'Q',
+        &cstr("SELECT number, number * 2 AS d FROM numbers(3)"),
+    )?;
+    let messages = read_until_ready(&mut connection)?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, b"TDDDCZ".to_vec());
+    assert_eq!(data_row(&messages[1].1), vec![
+        Some("0".to_string()),
+        Some("0".to_string())
+    ]);
+    assert_eq!(data_row(&messages[3].1), vec![
+        Some("2".to_string()),
+        Some("4".to_string())
+    ]);
+    assert_eq!(messages[4].1, cstr("SELECT 3"));
+
+    // Multiple statements and the empty query.
+    send_message(&mut connection, b'Q', &cstr("SELECT 1; SELECT 'a;b'"))?;
+    let tags = read_until_ready(&mut connection)?
+        .iter()
+        .map(|(tag, _)| *tag)
+        .collect::<Vec<_>>();
+    assert_eq!(tags, b"TDCTDCZ".to_vec());
+
+    send_message(&mut connection, b'Q', &cstr(" ; "))?;
+    let tags = read_until_ready(&mut connection)?
+        .iter()
+        .map(|(tag, _)| *tag)
+        .collect::<Vec<_>>();
+    assert_eq!(tags, b"IZ".to_vec());
+
+    // The error doesn't break the connection.
+    send_message(
+        &mut connection,
+        b'Q',
+        &cstr("SELECT * FROM system.not_exists_table"),
+    )?;
+    let messages = read_until_ready(&mut connection)?;
+    assert_eq!(messages[0].0, b'E');
+    assert!(error_field(&messages[0].1, b'C') == "42P01");
+
+    send_message(&mut connection, b'Q', &cstr("SELECT database()"))?;
+    let messages = read_until_ready(&mut connection)?;
+    assert_eq!(data_row(&messages[1].1), vec![Some("default".to_string())]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_use_database_with_startup() -> Result<()> {
+    let mut handler = PostgresHandler::create(SessionManager::try_create(1)?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    // The client asks for SSL firstly, we refuse it and it continues in plain text.
+    let mut connection = TcpStream::connect(("127.0.0.1", listening.port()))?;
+    connection.write_all(&8_i32.to_be_bytes())?;
+    connection.write_all(&SSL_REQUEST_CODE.to_be_bytes())?;
+    let mut response = [0_u8; 1];
+    connection.read_exact(&mut response)?;
+    assert_eq!(&response, b"N");
+
+    send_startup(&mut connection, &[("user", "root"), ("database", "system")])?;
+    send_password(&mut connection, "root")?;
+    read_until_ready(&mut connection)?;
+
+    send_message(&mut connection, b'Q', &cstr("SELECT database()"))?;
+    let messages = read_until_ready(&mut connection)?;
+    assert_eq!(data_row(&messages[1].1), vec![Some("system".to_string())]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_authentication() -> Result<()> {
+    let mut handler = PostgresHandler::create(SessionManager::try_create(3)?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut no_user = create_connection(listening.port(), &[("database", "system")])?;
+    let (tag, body) = read_message(&mut no_user)?;
+    assert_eq!(tag, b'E');
+    assert_eq!(error_field(&body, b'C'), "28P01");
+
+    let mut wrong_password = create_connection(listening.port(), &[("user", "root")])?;
+    send_password(&mut wrong_password, "not_the_password")?;
+    let (tag, body) = read_message(&mut wrong_password)?;
+    assert_eq!(tag, b'E');
+    assert_eq!(error_field(&body, b'C'), "28P01");
+
+    // The database is not a part of any query.
+    let params = [
+        ("user", "root"),
+        ("database", "system; DROP DATABASE default"),
+    ];
+    let mut unknown_database = create_connection(listening.port(), &params)?;
+    send_password(&mut unknown_database, "root")?;
+    let (tag, _) = read_message(&mut unknown_database)?;
+    assert_eq!(tag, b'R');
+    let (tag, body) = read_message(&mut unknown_database)?;
+    assert_eq!(tag, b'E');
+    assert_eq!(error_field(&body, b'C'), "3D000");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_extended_query() -> Result<()> {
+    let mut handler = PostgresHandler::create(SessionManager::try_create(1)?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut connection = create_connection(listening.port(), &[("user", "root")])?;
+    send_password(&mut connection, "root")?;
+    read_until_ready(&mut connection)?;
+
+    // Parse: unnamed statement with an int4 parameter.
+    let mut parse = cstr("");
+    parse.extend(cstr(
+        "SELECT number + $1 AS n FROM numbers(5) WHERE number > $1",
+    ));
+    parse.extend(1_i16.to_be_bytes());
+    parse.extend(23_i32.to_be_bytes());
+    send_message(&mut connection, b'P', &parse)?;
+
+    // Describe statement.
+    let mut describe = vec![b'S'];
+    describe.extend(cstr(""));
+    send_message(&mut connection, b'D', &describe)?;
+
+    // Bind with text format parameter 2.
+    let mut bind = cstr("");
+    bind.extend(cstr(""));
+    bind.extend(0_i16.to_be_bytes());
+    bind.extend(1_i16.to_be_bytes());
+    bind.extend(1_i32.to_be_bytes());
+    bind.extend(b"2");
+    bind.extend(0_i16.to_be_bytes());
+    send_message(&mut connection, b'B', &bind)?;
+
+    // Execute with max rows 1, then fetch the rest.
+    let mut execute = cstr("");
+    execute.extend(1_i32.to_be_bytes());
+    send_message(&mut connection, b'E', &execute)?;
+    let mut execute = cstr("");
+    execute.extend(0_i32.to_be_bytes());
+    send_message(&mut connection, b'E', &execute)?;
+    send_message(&mut connection, b'S', &[])?;
+
+    let messages = read_until_ready(&mut connection)?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, b"1tT2DsDCZ".to_vec());
+    assert_eq!(data_row(&messages[4].1), vec![Some("5".to_string())]);
+    assert_eq!(data_row(&messages[6].1), vec![Some("6".to_string())]);
+    assert_eq!(messages[7].1, cstr("SELECT 1"));
+
+    // The messages after an error are discarded until Sync.
+    let mut bind = cstr("");
+    bind.extend(cstr("not_exists_statement"));
+    bind.extend([0, 0, 0, 0, 0, 0]);
+    send_message(&mut connection, b'B', &bind)?;
+    send_message(&mut connection, b'E', &execute)?;
+    send_message(&mut connection, b'S', &[])?;
+
+    let tags = read_until_ready(&mut connection)?
+        .iter()
+        .map(|(tag, _)| *tag)
+        .collect::<Vec<_>>();
+    assert_eq!(tags, b"EZ".to_vec());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_rejected_session() -> Result<()> {
+    let mut handler = PostgresHandler::create(SessionManager::try_create(1)?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    {
+        // Accepted connection
+        let mut accepted = create_connection(listening.port(), &[("user", "root")])?;
+        send_password(&mut accepted, "root")?;
+        read_until_ready(&mut accepted)?;
+
+        // Rejected connection
+        let mut rejected = create_connection(listening.port(), &[("user", "root")])?;
+        let (tag, body) = read_message(&mut rejected)?;
+        assert_eq!(tag, b'E');
+        assert_eq!(error_field(&body, b'C'), "53300");
+    }
+
+    // Wait for the connection to be destroyed
+    std::thread::sleep(Duration::from_secs(5));
+    let mut accepted = create_connection(listening.port(), &[("user", "root")])?;
+    send_password(&mut accepted, "root")?;
+    read_until_ready(&mut accepted)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cancel_keys() -> Result<()> {
+    let sessions = SessionManager::try_create(1)?;
+    let session = sessions.create_session("PostgreSQL")?;
+
+    let cancel_keys = CancelKeys::create();
+    let backend_key = cancel_keys.register(session.get_id());
+    let (process_id, secret_key) = (backend_key.process_id, backend_key.secret_key);
+
+    assert!(!cancel_keys.cancel(&sessions, process_id, secret_key.wrapping_add(1)));
+    assert!(cancel_keys.cancel(&sessions, process_id, secret_key));
+
+    // The key is unregistered when the connection is closed.
+    drop(backend_key);
+    assert!(!cancel_keys.cancel(&sessions, process_id, secret_key));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_message_length() -> Result<()> {
+    let mut query = vec![b'Q'];
+    query.extend(8_i32.to_be_bytes());
+    query.extend(cstr("abc"));
+    assert_eq!(
+        FrontendMessage::read(&mut Cursor::new(query))?,
+        Some(FrontendMessage::Query("abc".to_string()))
+    );
+
+    for length in [i32::MIN, -1, 0, 3, i32::MAX] {
+        let mut message = vec![b'Q'];
+        message.extend(length.to_be_bytes());
+        let error = FrontendMessage::read(&mut Cursor::new(message)).unwrap_err();
+        assert_eq!(
+            error.message(),
+            format!("Invalid PostgreSQL message length: {}", length)
+        );
+    }
+
+    Ok(())
+}
+
+fn cstr(value: &str) -> Vec<u8> {
+    let mut bytes = value.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+fn send_startup(connection: &mut TcpStream, params: &[(&str, &str)]) -> Result<()> {
+    let mut body = PROTOCOL_VERSION_3.to_be_bytes().to_vec();
+    for (name, value) in params {
+        body.extend(cstr(name));
+        body.extend(cstr(value));
+    }
+    body.push(0);
+
+    connection.write_all(&(body.len() as i32 + 4).to_be_bytes())?;
+    connection.write_all(&body)?;
+    Ok(())
+}
+
+fn create_connection(port: u16, params: &[(&str, &str)]) -> Result<TcpStream> {
+    let mut connection = TcpStream::connect(("127.0.0.1", port))?;
+    connection.set_read_timeout(Some(Duration::from_secs(30)))?;
+    send_startup(&mut connection, params)?;
+    Ok(connection)
+}
+
+fn send_password(connection: &mut TcpStream, password: &str) -> Result<()> {
+    let (tag, body) = read_message(connection)?;
+    assert_eq!((tag, body), (b'R', 3_i32.to_be_bytes().to_vec()));
+    send_message(connection, b'p', &cstr(password))
+}
+
+fn send_message(connection: &mut TcpStream, tag: u8, body: &[u8]) -> Result<()> {
+    connection.write_all(&[tag])?;
+    connection.write_all(&(body.len() as i32 + 4).to_be_bytes())?;
+    connection.write_all(body)?;
+    Ok(())
+}
+
+fn read_message(connection: &mut TcpStream) -> Result<(u8, Vec<u8>)> {
+    let mut header = [0_u8; 5];
+    connection.read_exact(&mut header)?;
+    let length = i32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+    let mut body = vec![0; length as usize - 4];
+    connection.read_exact(&mut body)?;
+    Ok((header[0], body))
+}
+
+fn read_until_ready(connection: &mut TcpStream) -> Result<Vec<(u8, Vec<u8>)>> {
+    let mut messages = vec![];
+    loop {
+        let message = read_message(connection)?;
+        let is_ready = message.0 == b'Z';
+        messages.push(message);
+        if is_ready {
+            return Ok(messages);
+        }
+    }
+}
+
+fn data_row(body: &[u8]) -> Vec<Option<String>> {
+    let columns = i16::from_be_bytes([body[0], body[1]]);
+    let mut position = 2;
+    let mut values = vec![];
+    for _ in 0..columns {
+        let length = i32::from_be_bytes([
+            body[position],
+            body[position + 1],
+            body[position + 2],
+            body[position + 3],
+        ]);
+        position += 4;
+        match length {
+            -1 => values.push(None),
+            length => {
+                let value = &body[position..position + length as usize];
+                values.push(Some(String::from_utf8(value.to_vec()).unwrap()));
+                position += length as usize;
+            }
+        }
+    }
+    values
+}
+
+fn error_field(body: &[u8], field: u8) -> String {
+    let mut position = 0;
+    while body[position] != 0 {
+        let end = position + 1 + body[position + 1..].iter().position(|c| *c == 0).unwrap();
+        if body[position] == field {
+            return String::from_utf8(body[position + 1..end].to_vec()).unwrap();
+        }
+        position = end + 1;
+    }
+    String::new()
+}
@@ -0,0 +1,568 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::time::Instant;
+
+use common_exception::exception::ABORT_QUERY;
+use common_exception::exception::ABORT_SESSION;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::PlanNode;
+use common_runtime::tokio;
+use metrics::histogram;
+
+use crate::interpreters::InterpreterFactory;
+use crate::servers::postgres::postgres_cancel::BackendKey;
+use crate::servers::postgres::postgres_protocol::BackendMessage;
+use crate::servers::postgres::postgres_protocol::FieldDescription;
+use crate::servers::postgres::postgres_protocol::FrontendMessage;
+use crate::servers::postgres::postgres_protocol::TargetKind;
+use crate::servers::postgres::postgres_types::bind_query_params;
+use crate::servers::postgres::postgres_types::count_query_params;
+use crate::servers::postgres::postgres_types::param_to_sql_literal;
+use crate::servers::postgres::postgres_types::split_statements;
+use crate::servers::postgres::postgres_types::to_field_description;
+use crate::servers::postgres::postgres_types::to_postgres_text;
+use crate::servers::postgres::postgres_types::TEXT_FORMAT;
+use crate::servers::postgres::postgres_types::TEXT_OID;
+use crate::sessions::DatafuseQueryContextRef;
+use crate::sessions::SessionRef;
+use crate::sql::PlanParser;
+
+const SERVER_VERSION: &str = "9.6.0";
+
+struct PreparedStatement {
+    query: String,
+    param_types: Vec<i32>,
+}
+
+/// The bound statement, it is executed by the first Execute and may be fetched in several times.
+struct Portal {
+    query: String,
+    result: Option<QueryResult>,
+}
+
+struct QueryResult {
+    fields: Vec<FieldDescription>,
+    rows: VecDeque<Vec<Option<String>>>,
+    command: String,
+}
+
+impl QueryResult {
+    fn command_tag(&self, rows: usize) -> String {
+        match self.fields.is_empty() {
+            true => self.command.clone(),
+            false => format!("{} {}", self.command, rows),
+        }
+    }
+}
+
+struct MessageWriter<W: Write> {
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> MessageWriter<W> {
+    fn create(inner: W) -> MessageWriter<W> {
+        MessageWriter {
+            inner,
+            buffer: vec![],
+        }
+    }
+
+    fn write(&mut self, message: BackendMessage) {
+        message.encode(&mut self.buffer);
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.write_all(&self.buffer)?;
+        self.inner.flush()?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+pub struct InteractiveWorker {
+    session: SessionRef,
+    backend_key: BackendKey,
+    statements: HashMap<String, PreparedStatement>,
+    portals: HashMap<String, Portal>,
+}
+
+impl InteractiveWorker {
+    pub fn create(session: SessionRef, backend_key: BackendKey) -> InteractiveWorker {
+        InteractiveWorker {
+            session,
+            backend_key,
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+        }
+    }
+
+    pub fn run_on_tcp(
+        &mut self,
+        stream: std::net::TcpStream,
+        params: HashMap<String, String>,
+    ) -> Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = MessageWriter::create(stream);
+        self.on_startup(&params, &mut reader, &mut writer)?;
+
+        // After an error in the extended query, the messages are discarded until Sync.
+        let mut skip_until_sync = false;
+        while let Some(message) = FrontendMessage::read(&mut reader)? {
+            if self.session.is_aborting() {
+                let error = ErrorCode::AbortedSession(
+                    "Aborting this connection. because we are try aborting server.",
+                );
+                writer.write(Self::error_response(&error));
+                writer.flush()?;
+                return Err(error);
+            }
+
+            match message {
+                FrontendMessage::Terminate => return Ok(()),
+                FrontendMessage::Flush => writer.flush()?,
+                FrontendMessage::Sync => {
+                    skip_until_sync = false;
+                    writer.write(BackendMessage::ReadyForQuery);
+                    writer.flush()?;
+                }
+                FrontendMessage::Query(query) => {
+                    self.on_query(&query, &mut writer);
+                    writer.write(BackendMessage::ReadyForQuery);
+                    writer.flush()?;
+                }
+                _ if skip_until_sync => {}
+                message => {
+                    if let Err(error) = self.on_extended_message(message, &mut writer) {
+                        writer.write(Self::error_response(&error));
+                        skip_until_sync = true;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_startup<R: Read, W: Write>(
+        &mut self,
+        params: &HashMap<String, String>,
+        reader: &mut R,
+        writer: &mut MessageWriter<W>,
+    ) -> Result<()> {
+        if let Err(error) = self.login(params, reader, writer) {
+            writer.write(Self::error_response(&error));
+            writer.flush()?;
+            return Err(error);
+        }
+
+        let mut parameters = vec![
+            ("server_version", SERVER_VERSION),
+            ("server_encoding", "UTF8"),
+            ("client_encoding", "UTF8"),
+            ("DateStyle", "ISO, MDY"),
+            ("TimeZone", "UTC"),
+            ("integer_datetimes", "on"),
+            ("standard_conforming_strings", "on"),
+        ];
+        if let Some(application_name) = params.get("application_name") {
+            parameters.push(("application_name", application_name.as_str()));
+        }
+
+        for (name, value) in parameters {
+            writer.write(BackendMessage::ParameterStatus(
+                name.to_string(),
+                value.to_string(),
+            ));
+        }
+
+        writer.write(BackendMessage::BackendKeyData {
+            process_id: self.backend_key.process_id,
+            secret_key: self.backend_key.secret_key,
+        });
+        writer.write(BackendMessage::ReadyForQuery);
+        writer.flush()
+    }
+
+    /// Ask the client for the cleartext password of the user, then switch to the database
+    /// of the startup parameters. There is no SSL, the password is sent as is on the wire.
+    fn login<R: Read, W: Write>(
+        &mut self,
+        params: &HashMap<String, String>,
+        reader: &mut R,
+        writer: &mut MessageWriter<W>,
+    ) -> Result<()> {
+        let user = match params.get("user").filter(|user| !user.is_empty()) {
+            Some(user) => user.clone(),
+            None => {
+                return Err(ErrorCode::AuthenticateFailure(
+                    "No PostgreSQL user name specified in startup packet",
+                ))
+            }
+        };
+
+        writer.write(BackendMessage::AuthenticationCleartextPassword);
+        writer.flush()?;
+        let password = match FrontendMessage::read(reader)? {
+            Some(FrontendMessage::Password(password)) => password,
+            _ => {
+                return Err(ErrorCode::AuthenticateFailure(format!(
+                    "Expected the password of user \"{}\"",
+                    user
+                )))
+            }
+        };
+
+        let runtime = Self::build_runtime()?;
+        runtime.block_on(self.session.authenticate(&user, &password))?;
+        self.session.set_user(user);
+        runtime.block_on(self.session.apply_user_profile())?;
+        writer.write(BackendMessage::AuthenticationOk);
+
+        if let Some(database) = params.get("database").filter(|name| !name.is_empty()) {
+            let context = self.session.create_context();
+            context.set_current_database(database.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn on_query<W: Write>(&mut self, query: &str, writer: &mut MessageWriter<W>) {
+        let start = Instant::now();
+        let statements = split_statements(query);
+        if statements.is_empty() {
+            writer.write(BackendMessage::EmptyQueryResponse);
+            return;
+        }
+
+        // The statements are executed in order, stop at the first error.
+        for statement in statements {
+            match self.do_query(&statement) {
+                Err(error) => {
+                    writer.write(Self::error_response(&error.add_message(&statement)));
+                    break;
+                }
+                Ok(mut result) => {
+                    if !result.fields.is_empty() {
+                        writer.write(BackendMessage::RowDescription(result.fields.clone()));
+                    }
+
+                    let rows = result.rows.len();
+                    for row in result.rows.drain(..) {
+                        writer.write(BackendMessage::DataRow(row));
+                    }
+                    writer.write(BackendMessage::CommandComplete(result.command_tag(rows)));
+                }
+            }
+        }
+
+        histogram!(
+            super::postgres_metrics::METRIC_POSTGRES_PROCESSOR_REQUEST_DURATION,
+            start.elapsed()
+        );
+    }
+
+    fn on_extended_message<W: Write>(
+        &mut self,
+        message: FrontendMessage,
+        writer: &mut MessageWriter<W>,
+    ) -> Result<()> {
+        match message {
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            } => {
+                // The unnamed statement is replaced by the next Parse.
+                if !name.is_empty() && self.statements.contains_key(&name) {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Prepared statement \"{}\" already exists",
+                        name
+                    )));
+                }
+
+                let statement = PreparedStatement { query, param_types };
+                self.statements.insert(name, statement);
+                writer.write(BackendMessage::ParseComplete);
+            }
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            } => {
+                let prepared = self.get_statement(&statement)?;
+                if result_formats.iter().any(|format| *format != TEXT_FORMAT) {
+                    return Err(ErrorCode::UnImplement(
+                        "Binary result format is not supported in PostgreSQL handler",
+                    ));
+                }
+
+                // No format codes means all text, one format code applies to all the params.
+                let literals = params
+                    .iter()
+                    .enumerate()
+                    .map(|(index, param)| {
+                        let format = match param_formats.len() {
+                            0 => TEXT_FORMAT,
+                            1 => param_formats[0],
+                            _ => param_formats.get(index).copied().unwrap_or(TEXT_FORMAT),
+                        };
+                        let type_oid = prepared.param_types.get(index).copied().unwrap_or(0);
+                        param_to_sql_literal(type_oid, format, param)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let query = bind_query_params(&prepared.query, &literals)?;
+                self.portals.insert(portal, Portal {
+                    query,
+                    result: None,
+                });
+                writer.write(BackendMessage::BindComplete);
+            }
+            FrontendMessage::Describe {
+                kind: TargetKind::Statement,
+                name,
+            } => {
+                let prepared = self.get_statement(&name)?;
+                let params_count = prepared
+                    .param_types
+                    .len()
+                    .max(count_query_params(&prepared.query));
+
+                // The unspecified parameter types are described as text.
+                let param_types = (0..params_count)
+                    .map(|index| match prepared.param_types.get(index) {
+                        Some(type_oid) if *type_oid != 0 => *type_oid,
+                        _ => TEXT_OID,
+                    })
+                    .collect::<Vec<_>>();
+
+                let nulls = vec![String::from("NULL"); params_count];
+                let query = bind_query_params(&prepared.query, &nulls)?;
+                let fields = self.describe_query(&query)?;
+
+                writer.write(BackendMessage::ParameterDescription(param_types));
+                writer.write(Self::row_description(fields));
+            }
+            FrontendMessage::Describe {
+                kind: TargetKind::Portal,
+                name,
+            } => {
+                let fields = match self.get_portal(&name)? {
+                    Portal {
+                        result: Some(result),
+                        ..
+                    } => result.fields.clone(),
+                    Portal { query, .. } => self.describe_query(query)?,
+                };
+                writer.write(Self::row_description(fields));
+            }
+            FrontendMessage::Execute { portal, max_rows } => {
+                if self.get_portal(&portal)?.result.is_none() {
+                    let query = self.get_portal(&portal)?.query.clone();
+                    let result = self.do_query(&query)?;
+                    if let Some(portal) = self.portals.get_mut(&portal) {
+                        portal.result = Some(result);
+                    }
+                }
+
+                if let Some(Portal {
+                    result: Some(result),
+                    ..
+                }) = self.portals.get_mut(&portal)
+                {
+                    // Zero max rows means no limit.
+                    let max_rows = match max_rows {
+                        max_rows if max_rows <= 0 => result.rows.len(),
+                        max_rows => (max_rows as usize).min(result.rows.len()),
+                    };
+
+                    for row in result.rows.drain(..max_rows) {
+                        writer.write(BackendMessage::DataRow(row));
+                    }
+
+                    match result.rows.is_empty() {
+                        true => writer.write(BackendMessage::CommandComplete(
+                            result.command_tag(max_rows),
+                        )),
+                        false => writer.write(BackendMessage::PortalSuspended),
+                    }
+                }
+            }
+            FrontendMessage::Close {
+                kind: TargetKind::Statement,
+                name,
+            } => {
+                self.statements.remove(&name);
+                writer.write(BackendMessage::CloseComplete);
+            }
+            FrontendMessage::Close {
+                kind: TargetKind::Portal,
+                name,
+            } => {
+                self.portals.remove(&name);
+                writer.write(BackendMessage::CloseComplete);
+            }
+            message => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Unexpected PostgreSQL extended query message: {:?}",
+                    message
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_statement(&self, name: &str) -> Result<&PreparedStatement> {
+        self.statements.get(name).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("Prepared statement \"{}\" does not exist", name))
+        })
+    }
+
+    fn get_portal(&self, name: &str) -> Result<&Portal> {
+        self.portals
+            .get(name)
+            .ok_or_else(|| ErrorCode::BadArguments(format!("Portal \"{}\" does not exist", name)))
+    }
+
+    fn row_description(fields: Vec<FieldDescription>) -> BackendMessage {
+        match fields.is_empty() {
+            true => BackendMessage::NoData,
+            false => BackendMessage::RowDescription(fields),
+        }
+    }
+
+    fn describe_query(&self, query: &str) -> Result<Vec<FieldDescription>> {
+        let context = self.session.create_context();
+        let plan = PlanParser::create(context).build_from_sql(query)?;
+        Ok(Self::plan_fields(&plan))
+    }
+
+    fn plan_fields(plan: &PlanNode) -> Vec<FieldDescription> {
+        plan.schema()
+            .fields()
+            .iter()
+            .map(to_field_description)
+            .collect()
+    }
+
+    fn plan_command(plan: &PlanNode) -> &'static str {
+        match plan {
+            PlanNode::CreateDatabase(_) => "CREATE DATABASE",
+            PlanNode::DropDatabase(_) => "DROP DATABASE",
+            PlanNode::CreateTable(_) => "CREATE TABLE",
+            PlanNode::DropTable(_) => "DROP TABLE",
+            PlanNode::TruncateTable(_) => "TRUNCATE TABLE",
+            PlanNode::AnalyzeTable(_) => "ANALYZE",
+            PlanNode::UseDatabase(_) => "USE",
+            PlanNode::SetVariable(_) => "SET",
+            PlanNode::InsertInto(_) => "INSERT 0 0",
+            PlanNode::Kill(_) => "KILL",
+            _ => "SELECT",
+        }
+    }
+
+    fn do_query(&self, query: &str) -> Result<QueryResult> {
+        log::debug!("{}", query);
+
+        let context = self.session.create_context();
+        context.attach_query_str(query);
+
+        let query_result = Self::execute_query(context.clone(), query);
+        match &query_result {
+            Ok(result) => context.log_query_finish(result.rows.len(), None),
+            Err(error) => context.log_query_finish(0, Some(error)),
+        }
+        query_result
+    }
+
+    fn execute_query(context: DatafuseQueryContextRef, query: &str) -> Result<QueryResult> {
+        let plan = PlanParser::create(context.clone()).build_from_sql(query)?;
+        let fields = Self::plan_fields(&plan);
+        let command = Self::plan_command(&plan).to_string();
+
+        let runtime = Self::build_runtime()?;
+        runtime.block_on(context.admit_query(&plan))?;
+        let interpreter = InterpreterFactory::get(context.clone(), plan)?;
+        let data_stream = runtime.block_on(interpreter.execute())?;
+        let result_limits = context.get_result_limits()?;
+        let blocks = runtime.block_on(result_limits.collect(data_stream))?;
+
+        let mut rows = VecDeque::new();
+        for block in blocks.iter().filter(|block| block.num_columns() > 0) {
+            let columns = block
+                .columns()
+                .iter()
+                .map(|column| column.to_array())
+                .collect::<Result<Vec<_>>>()?;
+
+            for row_index in 0..block.num_rows() {
+                let row = columns
+                    .iter()
+                    .map(|column| column.try_get(row_index).map(|v| to_postgres_text(&v)))
+                    .collect::<Result<Vec<_>>>()?;
+                rows.push_back(row);
+            }
+        }
+
+        Ok(QueryResult {
+            fields,
+            rows,
+            command,
+        })
+    }
+
+    fn error_response(error: &ErrorCode) -> BackendMessage {
+        // The SQLSTATE codes, see: https://www.postgresql.org/docs/current/errcodes-appendix.html
+        let code = match error.code() {
+            ABORT_QUERY => "57014",
+            ABORT_SESSION => "57P01",
+            code if code == ErrorCode::UnImplement("").code() => "0A000",
+            code if code == ErrorCode::AuthenticateFailure("").code() => "28P01",
+            code if code == ErrorCode::UnknownDatabase("").code() => "3D000",
+            code if code == ErrorCode::SyntaxException("").code() => "42601",
+            code if code == ErrorCode::UnknownFunction("").code() => "42883",
+            code if code == ErrorCode::UnknownAggregateFunction("").code() => "42883",
+            code if code == ErrorCode::UnknownTable("").code() => "42P01",
+            code if code == ErrorCode::TooManyUserConnections("").code() => "53300",
+            _ => "XX000",
+        };
+
+        if error.code() != ABORT_QUERY && error.code() != ABORT_SESSION {
+            log::error!("OnQuery Error: {:?}", error);
+        }
+
+        BackendMessage::ErrorResponse {
+            code: code.to_string(),
+            message: format!("{}", error),
+        }
+    }
+
+    fn build_runtime() -> Result<tokio::runtime::Runtime> {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|tokio_error| ErrorCode::TokioError(format!("{}", tokio_error)))
+    }
+}
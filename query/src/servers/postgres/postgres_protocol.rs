@@ -0,0 +1,413 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The PostgreSQL frontend/backend protocol(version 3.0) messages.
+// See: https://www.postgresql.org/docs/current/protocol-message-formats.html
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::io::Read;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio::io::AsyncReadExt;
+use common_runtime::tokio::net::TcpStream;
+
+pub const PROTOCOL_VERSION_3: i32 = 196608;
+pub const CANCEL_REQUEST_CODE: i32 = 80877102;
+pub const SSL_REQUEST_CODE: i32 = 80877103;
+pub const GSSENC_REQUEST_CODE: i32 = 80877104;
+
+const MAX_STARTUP_PACKET_LENGTH: usize = 10000;
+const MAX_MESSAGE_LENGTH: usize = 64 * 1024 * 1024;
+
+/// The first packets of a connection, they have no message type byte.
+#[derive(Debug, PartialEq)]
+pub enum StartupPacket {
+    SSLRequest,
+    GSSENCRequest,
+    CancelRequest { process_id: i32, secret_key: i32 },
+    Startup { params: HashMap<String, String> },
+}
+
+impl StartupPacket {
+    pub async fn read(stream: &mut TcpStream) -> Result<StartupPacket> {
+        let length = stream.read_i32().await? as usize;
+        if !(8..=MAX_STARTUP_PACKET_LENGTH).contains(&length) {
+            return Err(ErrorCode::BadBytes(format!(
+                "Invalid PostgreSQL startup packet length: {}",
+                length
+            )));
+        }
+
+        let mut body = vec![0; length - 4];
+        stream.read_exact(&mut body).await?;
+        StartupPacket::decode(&body)
+    }
+
+    /// Decode the startup packet body without the length prefix.
+    pub fn decode(body: &[u8]) -> Result<StartupPacket> {
+        let mut reader = MessageReader::create(body);
+        match reader.read_i32()? {
+            SSL_REQUEST_CODE => Ok(StartupPacket::SSLRequest),
+            GSSENC_REQUEST_CODE => Ok(StartupPacket::GSSENCRequest),
+            CANCEL_REQUEST_CODE => Ok(StartupPacket::CancelRequest {
+                process_id: reader.read_i32()?,
+                secret_key: reader.read_i32()?,
+            }),
+            PROTOCOL_VERSION_3 => {
+                let mut params = HashMap::new();
+                loop {
+                    let name = reader.read_cstr()?;
+                    if name.is_empty() {
+                        break;
+                    }
+                    params.insert(name, reader.read_cstr()?);
+                }
+                Ok(StartupPacket::Startup { params })
+            }
+            version => Err(ErrorCode::UnImplement(format!(
+                "Unsupported PostgreSQL frontend protocol {}.{}",
+                version >> 16,
+                version & 0xFFFF
+            ))),
+        }
+    }
+}
+
+/// The target of Describe and Close messages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetKind {
+    Statement,
+    Portal,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FrontendMessage {
+    Query(String),
+    Parse {
+        name: String,
+        query: String,
+        param_types: Vec<i32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Vec<u8>>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        kind: TargetKind,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        kind: TargetKind,
+        name: String,
+    },
+    Password(String),
+    Sync,
+    Flush,
+    Terminate,
+}
+
+impl FrontendMessage {
+    /// Read the next message, returns None if the client has closed the connection.
+    pub fn read<R: Read>(reader: &mut R) -> Result<Option<FrontendMessage>> {
+        let mut tag = [0_u8; 1];
+        if let Err(error) = reader.read_exact(&mut tag) {
+            return match error.kind() {
+                ErrorKind::UnexpectedEof => Ok(None),
+                _ => Err(error.into()),
+            };
+        }
+
+        let mut length = [0_u8; 4];
+        reader.read_exact(&mut length)?;
+        let length = i32::from_be_bytes(length);
+        if length < 4 || length as usize > MAX_MESSAGE_LENGTH {
+            return Err(ErrorCode::BadBytes(format!(
+                "Invalid PostgreSQL message length: {}",
+                length
+            )));
+        }
+
+        let mut body = vec![0; length as usize - 4];
+        reader.read_exact(&mut body)?;
+        FrontendMessage::decode(tag[0], &body).map(Some)
+    }
+
+    pub fn decode(tag: u8, body: &[u8]) -> Result<FrontendMessage> {
+        let mut reader = MessageReader::create(body);
+        match tag {
+            b'Q' => Ok(FrontendMessage::Query(reader.read_cstr()?)),
+            b'P' => {
+                let name = reader.read_cstr()?;
+                let query = reader.read_cstr()?;
+                let param_types = (0..reader.read_i16()?)
+                    .map(|_| reader.read_i32())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(FrontendMessage::Parse {
+                    name,
+                    query,
+                    param_types,
+                })
+            }
+            b'B' => {
+                let portal = reader.read_cstr()?;
+                let statement = reader.read_cstr()?;
+                let param_formats = (0..reader.read_i16()?)
+                    .map(|_| reader.read_i16())
+                    .collect::<Result<Vec<_>>>()?;
+                let params = (0..reader.read_i16()?)
+                    .map(|_| match reader.read_i32()? {
+                        length if length < 0 => Ok(None),
+                        length => reader.read_bytes(length as usize).map(Some),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let result_formats = (0..reader.read_i16()?)
+                    .map(|_| reader.read_i16())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(FrontendMessage::Bind {
+                    portal,
+                    statement,
+                    param_formats,
+                    params,
+                    result_formats,
+                })
+            }
+            b'D' => Ok(FrontendMessage::Describe {
+                kind: reader.read_target_kind()?,
+                name: reader.read_cstr()?,
+            }),
+            b'E' => Ok(FrontendMessage::Execute {
+                portal: reader.read_cstr()?,
+                max_rows: reader.read_i32()?,
+            }),
+            b'C' => Ok(FrontendMessage::Close {
+                kind: reader.read_target_kind()?,
+                name: reader.read_cstr()?,
+            }),
+            b'p' => Ok(FrontendMessage::Password(reader.read_cstr()?)),
+            b'S' => Ok(FrontendMessage::Sync),
+            b'H' => Ok(FrontendMessage::Flush),
+            b'X' => Ok(FrontendMessage::Terminate),
+            tag => Err(ErrorCode::UnImplement(format!(
+                "Unsupported PostgreSQL message type: '{}'",
+                tag as char
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDescription {
+    pub name: String,
+    pub type_oid: i32,
+    pub type_len: i16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackendMessage {
+    AuthenticationOk,
+    AuthenticationCleartextPassword,
+    ParameterStatus(String, String),
+    BackendKeyData { process_id: i32, secret_key: i32 },
+    // We have no transaction, the backend is always idle.
+    ReadyForQuery,
+    RowDescription(Vec<FieldDescription>),
+    DataRow(Vec<Option<String>>),
+    CommandComplete(String),
+    EmptyQueryResponse,
+    ErrorResponse { code: String, message: String },
+    ParseComplete,
+    BindComplete,
+    CloseComplete,
+    NoData,
+    PortalSuspended,
+    ParameterDescription(Vec<i32>),
+}
+
+impl BackendMessage {
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        match self {
+            BackendMessage::AuthenticationOk => {
+                Self::write_message(buffer, b'R', |body| put_i32(body, 0))
+            }
+            BackendMessage::AuthenticationCleartextPassword => {
+                Self::write_message(buffer, b'R', |body| put_i32(body, 3))
+            }
+            BackendMessage::ParameterStatus(name, value) => {
+                Self::write_message(buffer, b'S', |body| {
+                    put_cstr(body, name);
+                    put_cstr(body, value);
+                })
+            }
+            BackendMessage::BackendKeyData {
+                process_id,
+                secret_key,
+            } => Self::write_message(buffer, b'K', |body| {
+                put_i32(body, *process_id);
+                put_i32(body, *secret_key);
+            }),
+            BackendMessage::ReadyForQuery => {
+                Self::write_message(buffer, b'Z', |body| body.push(b'I'))
+            }
+            BackendMessage::RowDescription(fields) => Self::write_message(buffer, b'T', |body| {
+                put_i16(body, fields.len() as i16);
+                for field in fields {
+                    put_cstr(body, &field.name);
+                    // table oid and column attribute number
+                    put_i32(body, 0);
+                    put_i16(body, 0);
+                    put_i32(body, field.type_oid);
+                    put_i16(body, field.type_len);
+                    // type modifier and text format
+                    put_i32(body, -1);
+                    put_i16(body, 0);
+                }
+            }),
+            BackendMessage::DataRow(values) => Self::write_message(buffer, b'D', |body| {
+                put_i16(body, values.len() as i16);
+                for value in values {
+                    match value {
+                        None => put_i32(body, -1),
+                        Some(value) => {
+                            put_i32(body, value.len() as i32);
+                            body.extend_from_slice(value.as_bytes());
+                        }
+                    }
+                }
+            }),
+            BackendMessage::CommandComplete(tag) => {
+                Self::write_message(buffer, b'C', |body| put_cstr(body, tag))
+            }
+            BackendMessage::EmptyQueryResponse => Self::write_message(buffer, b'I', |_| {}),
+            BackendMessage::ErrorResponse { code, message } => {
+                Self::write_message(buffer, b'E', |body| {
+                    for (field, value) in [(b'S', "ERROR"), (b'V', "ERROR"), (b'C', code.as_str())]
+                    {
+                        body.push(field);
+                        put_cstr(body, value);
+                    }
+                    body.push(b'M');
+                    put_cstr(body, message);
+                    body.push(0);
+                })
+            }
+            BackendMessage::ParseComplete => Self::write_message(buffer, b'1', |_| {}),
+            BackendMessage::BindComplete => Self::write_message(buffer, b'2', |_| {}),
+            BackendMessage::CloseComplete => Self::write_message(buffer, b'3', |_| {}),
+            BackendMessage::NoData => Self::write_message(buffer, b'n', |_| {}),
+            BackendMessage::PortalSuspended => Self::write_message(buffer, b's', |_| {}),
+            BackendMessage::ParameterDescription(types) => {
+                Self::write_message(buffer, b't', |body| {
+                    put_i16(body, types.len() as i16);
+                    for type_oid in types {
+                        put_i32(body, *type_oid);
+                    }
+                })
+            }
+        }
+    }
+
+    fn write_message(buffer: &mut Vec<u8>, tag: u8, write_body: impl FnOnce(&mut Vec<u8>)) {
+        buffer.push(tag);
+        let length_pos = buffer.len();
+        put_i32(buffer, 0);
+        write_body(buffer);
+
+        // The length includes itself but not the message type byte.
+        let length = (buffer.len() - length_pos) as i32;
+        buffer[length_pos..length_pos + 4].copy_from_slice(&length.to_be_bytes());
+    }
+}
+
+fn put_i16(buffer: &mut Vec<u8>, value: i16) {
+    buffer.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_i32(buffer: &mut Vec<u8>, value: i32) {
+    buffer.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_cstr(buffer: &mut Vec<u8>, value: &str) {
+    buffer.extend_from_slice(value.as_bytes());
+    buffer.push(0);
+}
+
+struct MessageReader<'a> {
+    buffer: &'a [u8],
+    position: usize,
+}
+
+impl<'a> MessageReader<'a> {
+    fn create(buffer: &'a [u8]) -> MessageReader<'a> {
+        MessageReader {
+            buffer,
+            position: 0,
+        }
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<Vec<u8>> {
+        match self.position + length <= self.buffer.len() {
+            true => {
+                let bytes = &self.buffer[self.position..self.position + length];
+                self.position += length;
+                Ok(bytes.to_vec())
+            }
+            false => Err(ErrorCode::BadBytes("Unexpected end of PostgreSQL message")),
+        }
+    }
+
+    fn read_i16(&mut self) -> Result<i16> {
+        let bytes = self.read_bytes(2)?;
+        Ok(i16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_cstr(&mut self) -> Result<String> {
+        let remaining = &self.buffer[self.position..];
+        match remaining.iter().position(|c| *c == 0) {
+            None => Err(ErrorCode::BadBytes(
+                "Unterminated string in PostgreSQL message",
+            )),
+            Some(end) => {
+                let value = String::from_utf8(remaining[..end].to_vec())
+                    .map_err(|error| ErrorCode::BadBytes(format!("{}", error)))?;
+                self.position += end + 1;
+                Ok(value)
+            }
+        }
+    }
+
+    fn read_target_kind(&mut self) -> Result<TargetKind> {
+        match self.read_bytes(1)?[0] {
+            b'S' => Ok(TargetKind::Statement),
+            b'P' => Ok(TargetKind::Portal),
+            kind => Err(ErrorCode::BadBytes(format!(
+                "Invalid describe or close target: '{}'",
+                kind as char
+            ))),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::net::Shutdown;
+
+use common_exception::exception::ABORT_SESSION;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_runtime::tokio::net::TcpStream;
+
+use crate::servers::postgres::postgres_cancel::BackendKey;
+use crate::servers::postgres::postgres_interactive_worker::InteractiveWorker;
+use crate::sessions::SessionRef;
+
+pub struct PostgresConnection;
+
+impl PostgresConnection {
+    pub fn run_on_stream(
+        session: SessionRef,
+        stream: TcpStream,
+        params: HashMap<String, String>,
+        backend_key: BackendKey,
+    ) -> Result<()> {
+        let blocking_stream = Self::convert_stream(stream)?;
+        PostgresConnection::attach_session(&session, &blocking_stream)?;
+        std::thread::spawn(move || {
+            PostgresConnection::session_executor(session, blocking_stream, params, backend_key);
+        });
+
+        Ok(())
+    }
+
+    fn session_executor(
+        session: SessionRef,
+        blocking_stream: std::net::TcpStream,
+        params: HashMap<String, String>,
+        backend_key: BackendKey,
+    ) {
+        let mut interactive_worker = InteractiveWorker::create(session, backend_key);
+        if let Err(error) = interactive_worker.run_on_tcp(blocking_stream, params) {
+            if error.code() != ABORT_SESSION {
+                log::error!(
+                    "Unexpected error occurred during query execution: {:?}",
+                    error
+                );
+            }
+        };
+    }
+
+    fn attach_session(session: &SessionRef, blocking_stream: &std::net::TcpStream) -> Result<()> {
+        let host = blocking_stream.peer_addr().ok();
+        let blocking_stream_ref = blocking_stream.try_clone()?;
+        session.attach(host, move || {
+            if let Err(error) = blocking_stream_ref.shutdown(Shutdown::Both) {
+                log::error!("Cannot shutdown PostgreSQL session io {}", error);
+            }
+        });
+
+        Ok(())
+    }
+
+    fn convert_stream(stream: TcpStream) -> Result<std::net::TcpStream> {
+        let stream = stream
+            .into_std()
+            .map_err_to_code(ErrorCode::TokioError, || {
+                "Cannot to convert Tokio TcpStream to Std TcpStream"
+            })?;
+        stream
+            .set_nonblocking(false)
+            .map_err_to_code(ErrorCode::TokioError, || {
+                "Cannot to convert Tokio TcpStream to Std TcpStream"
+            })?;
+
+        Ok(stream)
+    }
+}
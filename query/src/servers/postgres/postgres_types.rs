@@ -0,0 +1,266 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataField;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::servers::postgres::postgres_protocol::FieldDescription;
+
+// The type oids in pg_catalog.pg_type
+pub const BOOL_OID: i32 = 16;
+pub const BYTEA_OID: i32 = 17;
+pub const INT8_OID: i32 = 20;
+pub const INT2_OID: i32 = 21;
+pub const INT4_OID: i32 = 23;
+pub const TEXT_OID: i32 = 25;
+pub const FLOAT4_OID: i32 = 700;
+pub const FLOAT8_OID: i32 = 701;
+pub const VARCHAR_OID: i32 = 1043;
+pub const NUMERIC_OID: i32 = 1700;
+
+pub const TEXT_FORMAT: i16 = 0;
+pub const BINARY_FORMAT: i16 = 1;
+
+/// Map the DataType to the pg type oid and type length(-1 means variable length).
+/// Unsigned integers are widened to the next signed type because pg has no unsigned types,
+/// the dates and intervals are sent as their integer representation.
+pub fn to_postgres_type(data_type: &DataType) -> (i32, i16) {
+    match data_type {
+        DataType::Boolean => (BOOL_OID, 1),
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => (INT2_OID, 2),
+        DataType::Int32 | DataType::UInt16 | DataType::Date32 => (INT4_OID, 4),
+        DataType::Int64 | DataType::UInt32 | DataType::Date64 => (INT8_OID, 8),
+        DataType::Timestamp(_, _) | DataType::Interval(_) => (INT8_OID, 8),
+        DataType::UInt64 | DataType::Decimal(_, _) => (NUMERIC_OID, -1),
+        DataType::Float32 => (FLOAT4_OID, 4),
+        DataType::Float64 => (FLOAT8_OID, 8),
+        DataType::Binary => (BYTEA_OID, -1),
+        DataType::Null | DataType::Utf8 | DataType::List(_) | DataType::Struct(_) => (TEXT_OID, -1),
+    }
+}
+
+pub fn to_field_description(field: &DataField) -> FieldDescription {
+    let (type_oid, type_len) = to_postgres_type(field.data_type());
+    FieldDescription {
+        name: field.name().clone(),
+        type_oid,
+        type_len,
+    }
+}
+
+/// Encode the value in pg text format, None is NULL.
+pub fn to_postgres_text(value: &DataValue) -> Option<String> {
+    match value {
+        value if value.is_null() => None,
+        DataValue::Boolean(Some(v)) => Some(String::from(if *v { "t" } else { "f" })),
+        DataValue::Binary(Some(_)) => Some(format!("\\x{}", value)),
+        value => Some(format!("{}", value)),
+    }
+}
+
+/// Convert a bound parameter to the sql literal, so that the query can be planned as text.
+pub fn param_to_sql_literal(type_oid: i32, format: i16, param: &Option<Vec<u8>>) -> Result<String> {
+    let bytes = match param {
+        None => return Ok(String::from("NULL")),
+        Some(bytes) => bytes,
+    };
+
+    if format == BINARY_FORMAT {
+        return binary_param_to_sql_literal(type_oid, bytes);
+    }
+
+    let text = String::from_utf8(bytes.clone())
+        .map_err(|error| ErrorCode::BadBytes(format!("Invalid parameter: {}", error)))?;
+    match type_oid {
+        BOOL_OID => match text.to_lowercase().as_str() {
+            "t" | "true" | "1" | "on" | "yes" | "y" => Ok(String::from("true")),
+            "f" | "false" | "0" | "off" | "no" | "n" => Ok(String::from("false")),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Invalid boolean parameter: {}",
+                text
+            ))),
+        },
+        INT2_OID | INT4_OID | INT8_OID | FLOAT4_OID | FLOAT8_OID | NUMERIC_OID => {
+            match text.parse::<f64>() {
+                Ok(_) => Ok(text),
+                Err(_) => Err(ErrorCode::BadArguments(format!(
+                    "Invalid numeric parameter: {}",
+                    text
+                ))),
+            }
+        }
+        // The unspecified type, we inline the numbers as it is.
+        0 if text.parse::<f64>().is_ok() => Ok(text),
+        _ => Ok(quote_string(&text)),
+    }
+}
+
+fn binary_param_to_sql_literal(type_oid: i32, bytes: &[u8]) -> Result<String> {
+    fn fixed<const N: usize>(bytes: &[u8]) -> Result<[u8; N]> {
+        let mut fixed = [0_u8; N];
+        match bytes.len() == N {
+            true => {
+                fixed.copy_from_slice(bytes);
+                Ok(fixed)
+            }
+            false => Err(ErrorCode::BadBytes(format!(
+                "Invalid binary parameter length: expect {}, but got {}",
+                N,
+                bytes.len()
+            ))),
+        }
+    }
+
+    match type_oid {
+        BOOL_OID => Ok(String::from(match fixed::<1>(bytes)?[0] {
+            0 => "false",
+            _ => "true",
+        })),
+        INT2_OID => Ok(i16::from_be_bytes(fixed(bytes)?).to_string()),
+        INT4_OID => Ok(i32::from_be_bytes(fixed(bytes)?).to_string()),
+        INT8_OID => Ok(i64::from_be_bytes(fixed(bytes)?).to_string()),
+        FLOAT4_OID => Ok(f32::from_be_bytes(fixed(bytes)?).to_string()),
+        FLOAT8_OID => Ok(f64::from_be_bytes(fixed(bytes)?).to_string()),
+        TEXT_OID | VARCHAR_OID => String::from_utf8(bytes.to_vec())
+            .map(|text| quote_string(&text))
+            .map_err(|error| ErrorCode::BadBytes(format!("Invalid parameter: {}", error))),
+        _ => Err(ErrorCode::UnImplement(format!(
+            "Unsupported binary format parameter of type oid {}",
+            type_oid
+        ))),
+    }
+}
+
+fn quote_string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+enum QuerySegment<'a> {
+    Text(&'a str),
+    /// The digits of a $n placeholder.
+    Param(&'a str),
+}
+
+/// Split the query into the text and the $n placeholders, the placeholders inside
+/// the quoted strings, the quoted identifiers and the comments are text.
+fn query_segments(query: &str) -> Vec<QuerySegment> {
+    // The delimiters are ASCII, so every index stopped at is a char boundary.
+    let bytes = query.as_bytes();
+    let mut segments = vec![];
+    let mut text_start = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            quote @ (b'\'' | b'"') => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != quote {
+                    index += 1;
+                }
+                index = (index + 1).min(bytes.len());
+            }
+            b'-' if bytes.get(index + 1) == Some(&b'-') => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+                index = (index + 1).min(bytes.len());
+            }
+            b'$' if bytes.get(index + 1).map_or(false, |c| c.is_ascii_digit()) => {
+                segments.push(QuerySegment::Text(&query[text_start..index]));
+                let digits_start = index + 1;
+                index = digits_start;
+                while index < bytes.len() && bytes[index].is_ascii_digit() {
+                    index += 1;
+                }
+                segments.push(QuerySegment::Param(&query[digits_start..index]));
+                text_start = index;
+            }
+            _ => index += 1,
+        }
+    }
+
+    segments.push(QuerySegment::Text(&query[text_start..]));
+    segments
+}
+
+/// Replace the $n placeholders with the params, the placeholders inside
+/// the quoted strings, the quoted identifiers and the comments are skipped.
+pub fn bind_query_params(query: &str, params: &[String]) -> Result<String> {
+    let mut bound = String::with_capacity(query.len());
+
+    for segment in query_segments(query) {
+        match segment {
+            QuerySegment::Text(text) => bound.push_str(text),
+            QuerySegment::Param(index) => match index.parse::<usize>() {
+                Ok(index) if index >= 1 && index <= params.len() => {
+                    bound.push_str(&params[index - 1])
+                }
+                _ => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Parameter ${} is not bound, there are {} parameters",
+                        index,
+                        params.len()
+                    )))
+                }
+            },
+        }
+    }
+
+    Ok(bound)
+}
+
+/// The number of the parameters in the query, which is the max $n placeholder.
+pub fn count_query_params(query: &str) -> usize {
+    query_segments(query)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            QuerySegment::Param(index) => index.parse::<usize>().ok(),
+            QuerySegment::Text(_) => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Split the simple query into statements by the semicolons outside of the quotes.
+pub fn split_statements(query: &str) -> Vec<String> {
+    let mut statements = vec![];
+    let mut current = String::new();
+    let mut chars = query.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                current.push(c);
+                for quoted in chars.by_ref() {
+                    current.push(quoted);
+                    if quoted == c {
+                        break;
+                    }
+                }
+            }
+            ';' => statements.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    statements.push(current);
+
+    statements
+        .into_iter()
+        .map(|statement| statement.trim().to_string())
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
@@ -0,0 +1,118 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::servers::postgres::postgres_types::*;
+
+#[test]
+fn test_to_postgres_type() -> Result<()> {
+    let tests = vec![
+        (DataType::Boolean, (BOOL_OID, 1)),
+        (DataType::UInt8, (INT2_OID, 2)),
+        (DataType::Int32, (INT4_OID, 4)),
+        (DataType::UInt32, (INT8_OID, 8)),
+        (DataType::UInt64, (NUMERIC_OID, -1)),
+        (DataType::Float64, (FLOAT8_OID, 8)),
+        (DataType::Utf8, (TEXT_OID, -1)),
+        (DataType::Binary, (BYTEA_OID, -1)),
+    ];
+
+    for (data_type, expect) in tests {
+        assert_eq!(to_postgres_type(&data_type), expect, "{:?}", data_type);
+    }
+
+    assert_eq!(
+        to_postgres_text(&DataValue::Boolean(Some(true))),
+        Some("t".to_string())
+    );
+    assert_eq!(
+        to_postgres_text(&DataValue::UInt64(Some(3))),
+        Some("3".to_string())
+    );
+    assert_eq!(to_postgres_text(&DataValue::UInt64(None)), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_param_to_sql_literal() -> Result<()> {
+    let text = |value: &str| Some(value.as_bytes().to_vec());
+
+    assert_eq!(
+        param_to_sql_literal(INT4_OID, TEXT_FORMAT, &text("42"))?,
+        "42"
+    );
+    assert_eq!(
+        param_to_sql_literal(BOOL_OID, TEXT_FORMAT, &text("t"))?,
+        "true"
+    );
+    assert_eq!(
+        param_to_sql_literal(TEXT_OID, TEXT_FORMAT, &text("it's"))?,
+        "'it''s'"
+    );
+    assert_eq!(param_to_sql_literal(0, TEXT_FORMAT, &text("1.5"))?, "1.5");
+    assert_eq!(param_to_sql_literal(0, TEXT_FORMAT, &text("abc"))?, "'abc'");
+    assert_eq!(param_to_sql_literal(TEXT_OID, TEXT_FORMAT, &None)?, "NULL");
+
+    let binary = Some(7_i32.to_be_bytes().to_vec());
+    assert_eq!(param_to_sql_literal(INT4_OID, BINARY_FORMAT, &binary)?, "7");
+
+    let result = param_to_sql_literal(INT4_OID, TEXT_FORMAT, &text("1; DROP TABLE t"));
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Invalid numeric parameter: 1; DROP TABLE t"
+    );
+
+    let result = param_to_sql_literal(INT8_OID, BINARY_FORMAT, &binary);
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Invalid binary parameter length: expect 8, but got 4"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_bind_query_params() -> Result<()> {
+    let params = vec!["1".to_string(), "'a'".to_string()];
+
+    let bound = bind_query_params("SELECT $1, $2, '$1', \"$2\" -- $1\n, $1", &params)?;
+    assert_eq!(bound, "SELECT 1, 'a', '$1', \"$2\" -- $1\n, 1");
+    assert_eq!(count_query_params("SELECT $1, $12, '$20'"), 12);
+    assert_eq!(count_query_params("SELECT $1, $2 -- $3\n, \"$4\""), 2);
+    assert_eq!(count_query_params("SELECT '$1"), 0);
+
+    let result = bind_query_params("SELECT $3", &params);
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Parameter $3 is not bound, there are 2 parameters"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_split_statements() -> Result<()> {
+    assert_eq!(split_statements("SELECT 1; SELECT 'a;b' ;"), vec![
+        "SELECT 1".to_string(),
+        "SELECT 'a;b'".to_string()
+    ]);
+    assert!(split_statements(" ; ").is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_runtime::tokio::io::AsyncWriteExt;
+use common_runtime::tokio::net::TcpStream;
+
+use crate::servers::postgres::postgres_protocol::BackendMessage;
+
+pub struct RejectConnection;
+
+impl RejectConnection {
+    /// The startup packet has been received, so we only need to send the error and close.
+    pub async fn reject_postgres_connection(
+        mut stream: TcpStream,
+        code: &str,
+        error_message: impl Into<String>,
+    ) -> Result<()> {
+        let mut buffer = vec![];
+        BackendMessage::ErrorResponse {
+            code: code.to_string(),
+            message: error_message.into(),
+        }
+        .encode(&mut buffer);
+
+        stream.write_all(&buffer).await?;
+        stream.flush().await?;
+
+        Ok(())
+    }
+}
@@ -17,6 +17,7 @@ use std::future::Future;
 use std::sync::atomic::Ordering;
 use std::sync::atomic::Ordering::Acquire;
 use std::sync::Arc;
+use std::time::Instant;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -31,6 +32,8 @@ use common_progress::ProgressCallback;
 use common_progress::ProgressValues;
 use common_runtime::tokio::task::JoinHandle;
 use common_streams::AbortStream;
+use common_streams::ReadLimits;
+use common_streams::ResultLimits;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::catalog::Catalog;
@@ -39,6 +42,7 @@ use crate::catalogs::utils::TableMeta;
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
 use crate::datasources::DatabaseCatalog;
+use crate::pipelines::executor::ExecutorQuery;
 use crate::sessions::context_shared::DatafuseQueryContextShared;
 use crate::sessions::SessionManagerRef;
 use crate::sessions::Settings;
@@ -83,16 +87,43 @@ impl DatafuseQueryContext {
         Ok(self.shared.try_get_runtime()?.spawn(task))
     }
 
+    /// The tasks of the query on the executor shared by all the queries.
+    pub fn try_get_executor_query(&self) -> Result<Arc<ExecutorQuery>> {
+        self.shared.try_get_executor_query()
+    }
+
     /// Set progress callback to context.
     /// By default, it is called for leaf sources, after each block
     /// Note that the callback can be called from different threads.
     pub fn progress_callback(&self) -> Result<ProgressCallback> {
         let current_progress = self.shared.progress.clone();
+        let total_progress = self.shared.total_progress.clone();
         Ok(Box::new(move |value: &ProgressValues| {
             current_progress.incr(value);
+            total_progress.incr(value);
         }))
     }
 
+    /// The limits on the rows and bytes read by the query, checked against all its sources.
+    pub fn get_read_limits(&self) -> Result<ReadLimits> {
+        let settings = self.get_settings();
+        Ok(ReadLimits {
+            max_rows: settings.get_max_rows_to_read()? as usize,
+            max_bytes: settings.get_max_bytes_to_read()? as usize,
+            overflow_mode: settings.get_read_overflow_mode()?.parse()?,
+            reserved: self.shared.read_reservation.clone(),
+        })
+    }
+
+    pub fn get_result_limits(&self) -> Result<ResultLimits> {
+        let settings = self.get_settings();
+        Ok(ResultLimits::create(
+            settings.get_max_result_rows()? as usize,
+            settings.get_max_result_bytes()? as usize,
+            settings.get_result_overflow_mode()?.parse()?,
+        ))
+    }
+
     pub fn get_progress_value(&self) -> ProgressValues {
         self.shared.progress.as_ref().get_values()
     }
@@ -172,12 +203,54 @@ impl DatafuseQueryContext {
         self.shared.init_query_id.as_ref().read().clone()
     }
 
+    /// Collect the runtime statistics of every processor in the pipelines built
+    /// after this call, used by EXPLAIN ANALYZE.
+    pub fn enable_processor_profiling(&self) {
+        self.shared
+            .processor_profiling
+            .store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_processor_profiling(&self) -> bool {
+        self.shared.processor_profiling.load(Ordering::Relaxed)
+    }
+
     pub fn try_create_abortable(&self, input: SendableDataBlockStream) -> Result<AbortStream> {
-        let (abort_handle, abort_stream) = AbortStream::try_create(input)?;
-        self.shared.add_source_abort_handle(abort_handle);
+        let deadline = self.shared.get_deadline();
+        let deadline_exceeded = self.shared.deadline_exceeded.clone();
+        let (abort_handle, abort_stream) =
+            AbortStream::try_create_with_deadline(input, deadline, deadline_exceeded)?;
+        self.shared.add_source_abort_handle(abort_handle)?;
         Ok(abort_stream)
     }
 
+    /// Wait for the workload group of the user to admit the query before it is executed,
+    /// the query is shown as queued in system.processes meanwhile. KILL is never queued,
+    /// so that a stuck group can still be drained.
+    pub async fn admit_query(&self, plan: &PlanNode) -> Result<()> {
+        match plan {
+            PlanNode::Kill(_) => Ok(()),
+            _ => self.shared.admit_query().await,
+        }
+    }
+
+    /// Overrides the deadline derived from max_execution_time, e.g. for the stages
+    /// scheduled by another node, which share the deadline of the whole query.
+    pub fn set_deadline(&self, deadline: Option<Instant>) {
+        self.shared.set_deadline(deadline);
+    }
+
+    /// The execution time left for the query in milliseconds, 0 means no limit.
+    pub fn get_remaining_execution_millis(&self) -> u64 {
+        match self.shared.get_deadline() {
+            None => 0,
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                std::cmp::max(remaining.as_millis() as u64, 1)
+            }
+        }
+    }
+
     pub fn get_current_database(&self) -> String {
         self.shared.get_current_database()
     }
@@ -207,6 +280,11 @@ impl DatafuseQueryContext {
         self.shared.get_settings()
     }
 
+    /// Check a SET of the setting against the limit of the session's user profile.
+    pub fn check_profile_limit(&self, name: &str, value: &str) -> Result<()> {
+        self.shared.session.check_profile_limit(name, value)
+    }
+
     pub fn get_config(&self) -> Config {
         self.shared.conf.clone()
     }
@@ -220,6 +298,10 @@ impl DatafuseQueryContext {
         self.shared.attach_query_str(query);
     }
 
+    pub fn log_query_finish(&self, result_rows: usize, error: Option<&ErrorCode>) {
+        self.shared.log_query_finish(result_rows, error);
+    }
+
     pub fn attach_query_plan(&self, query_plan: &PlanNode) {
         self.shared.attach_query_plan(query_plan);
     }
@@ -12,20 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_infallible::Mutex;
 use common_infallible::RwLock;
 use common_planners::PlanNode;
 use common_progress::Progress;
+use common_runtime::tokio;
 use common_runtime::Runtime;
+use common_streams::ReadReservation;
 use futures::future::AbortHandle;
+use futures::future::Abortable;
 use uuid::Uuid;
 
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
+use crate::datasources::remote::RemoteFactory;
 use crate::datasources::DatabaseCatalog;
+use crate::pipelines::executor::ExecutorQuery;
+use crate::sessions::workload::WorkloadPermit;
+use crate::sessions::QueryLogEntry;
 use crate::sessions::Session;
 use crate::sessions::Settings;
 
@@ -41,31 +56,60 @@ use crate::sessions::Settings;
 pub struct DatafuseQueryContextShared {
     pub(in crate::sessions) conf: Config,
     pub(in crate::sessions) progress: Arc<Progress>,
+    // Never reset while the query runs, unlike the progress sent to the client.
+    pub(in crate::sessions) total_progress: Arc<Progress>,
+    // The rows and bytes reserved by the sources against the read limits.
+    pub(in crate::sessions) read_reservation: Arc<Mutex<ReadReservation>>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
+    pub(in crate::sessions) executor_query: Arc<RwLock<Option<Arc<ExecutorQuery>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
     pub(in crate::sessions) cluster_cache: Arc<RwLock<Option<ClusterRef>>>,
     pub(in crate::sessions) sources_abort_handle: Arc<RwLock<Vec<AbortHandle>>>,
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
     pub(in crate::sessions) subquery_index: Arc<AtomicUsize>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
+    pub(in crate::sessions) running_query_start: Arc<RwLock<Option<SystemTime>>>,
     pub(in crate::sessions) running_plan: Arc<RwLock<Option<PlanNode>>>,
+    pub(in crate::sessions) processor_profiling: Arc<AtomicBool>,
+    pub(in crate::sessions) deadline: Arc<RwLock<Option<Instant>>>,
+    pub(in crate::sessions) deadline_watchdog: Arc<AtomicBool>,
+    // Set by the watchdog, the sources it aborts fail with a timeout instead.
+    pub(in crate::sessions) deadline_exceeded: Arc<AtomicBool>,
+    pub(in crate::sessions) queued: Arc<AtomicBool>,
+    pub(in crate::sessions) workload_permit: Arc<RwLock<Option<WorkloadPermit>>>,
 }
 
 impl DatafuseQueryContextShared {
     pub fn try_create(conf: Config, session: Arc<Session>) -> Arc<DatafuseQueryContextShared> {
+        let max_execution_time = session.get_settings().get_max_execution_time().unwrap_or(0);
+        let deadline = match max_execution_time {
+            0 => None,
+            seconds => Some(Instant::now() + Duration::from_secs(seconds)),
+        };
+
         Arc::new(DatafuseQueryContextShared {
             conf,
             init_query_id: Arc::new(RwLock::new(Uuid::new_v4().to_string())),
             progress: Arc::new(Progress::create()),
+            total_progress: Arc::new(Progress::create()),
+            read_reservation: Arc::new(Mutex::new(ReadReservation::default())),
             session,
             runtime: Arc::new(RwLock::new(None)),
+            executor_query: Arc::new(RwLock::new(None)),
             cluster_cache: Arc::new(RwLock::new(None)),
             sources_abort_handle: Arc::new(RwLock::new(Vec::new())),
             ref_count: Arc::new(AtomicUsize::new(0)),
             subquery_index: Arc::new(AtomicUsize::new(1)),
             running_query: Arc::new(RwLock::new(None)),
+            running_query_start: Arc::new(RwLock::new(None)),
             running_plan: Arc::new(RwLock::new(None)),
+            processor_profiling: Arc::new(AtomicBool::new(false)),
+            deadline: Arc::new(RwLock::new(deadline)),
+            deadline_watchdog: Arc::new(AtomicBool::new(false)),
+            deadline_exceeded: Arc::new(AtomicBool::new(false)),
+            queued: Arc::new(AtomicBool::new(false)),
+            workload_permit: Arc::new(RwLock::new(None)),
         })
     }
 
@@ -125,9 +169,64 @@ impl DatafuseQueryContextShared {
         }
     }
 
+    /// Register the query on the executor when first get.
+    pub fn try_get_executor_query(&self) -> Result<Arc<ExecutorQuery>> {
+        let mut executor_query = self.executor_query.write();
+
+        match &*executor_query {
+            Some(executor_query) => Ok(executor_query.clone()),
+            None => {
+                let cpu_shares = self.get_settings().get_cpu_shares()?;
+                let executor = self.session.get_sessions_manager().get_executor();
+                let query = executor.create_query(cpu_shares)?;
+                *executor_query = Some(query.clone());
+                Ok(query)
+            }
+        }
+    }
+
     pub fn attach_query_str(&self, query: &str) {
         let mut running_query = self.running_query.write();
         *running_query = Some(query.to_string());
+        *self.running_query_start.write() = Some(SystemTime::now());
+    }
+
+    /// Record the running query into system.query_log once it finished or failed.
+    pub fn log_query_finish(&self, result_rows: usize, error: Option<&ErrorCode>) {
+        let query_text = match &*self.running_query.read() {
+            None => return,
+            Some(query_text) => query_text.clone(),
+        };
+
+        let unix_millis = |time: SystemTime| {
+            time.duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or(0)
+        };
+
+        let query_end_time = SystemTime::now();
+        let query_start_time = self.running_query_start.read().unwrap_or(query_end_time);
+        let progress_values = self.total_progress.get_values();
+        let (user, client_address) = {
+            let session_state = self.session.mutable_state.lock();
+            let client_address = session_state.client_host.map(|host| host.to_string());
+            (session_state.user.clone(), client_address)
+        };
+
+        let query_log = self.session.get_sessions_manager().get_query_log();
+        query_log.append(QueryLogEntry {
+            query_id: self.init_query_id.read().clone(),
+            user,
+            query_text,
+            query_start_time: unix_millis(query_start_time),
+            query_end_time: unix_millis(query_end_time),
+            read_rows: progress_values.read_rows as u64,
+            read_bytes: progress_values.read_bytes as u64,
+            result_rows: result_rows as u64,
+            error_code: error.map(ErrorCode::code),
+            error_message: error.map(ErrorCode::message),
+            client_address,
+        });
     }
 
     pub fn attach_query_plan(&self, plan: &PlanNode) {
@@ -135,9 +234,95 @@ impl DatafuseQueryContextShared {
         *running_plan = Some(plan.clone());
     }
 
-    pub fn add_source_abort_handle(&self, handle: AbortHandle) {
+    /// Wait for the workload group of the session user to admit the query. The wait
+    /// is aborted like the sources when the query is killed or exceeds its deadline.
+    pub async fn admit_query(&self) -> Result<()> {
+        let user = self.session.mutable_state.lock().user.clone();
+        let workload = self.session.get_sessions_manager().get_workload();
+
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        self.add_source_abort_handle(abort_handle)?;
+
+        let queued = self.queued.clone();
+        let on_queued = move || queued.store(true, Ordering::Relaxed);
+        let admission = workload.admit(user.as_deref(), on_queued);
+        let admitted = Abortable::new(admission, abort_registration).await;
+        self.queued.store(false, Ordering::Relaxed);
+
+        match admitted {
+            Ok(permit) => {
+                *self.workload_permit.write() = permit?;
+                Ok(())
+            }
+            Err(_) if self.deadline_exceeded.load(Ordering::SeqCst) => Err(ErrorCode::Timeout(
+                "Aborted query, because it exceeded the max execution time",
+            )),
+            Err(_) => Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed",
+            )),
+        }
+    }
+
+    pub fn is_queued(&self) -> bool {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    pub fn get_deadline(&self) -> Option<Instant> {
+        *self.deadline.read()
+    }
+
+    pub fn set_deadline(&self, deadline: Option<Instant>) {
+        *self.deadline.write() = deadline;
+    }
+
+    pub fn add_source_abort_handle(&self, handle: AbortHandle) -> Result<()> {
         let mut sources_abort_handle = self.sources_abort_handle.write();
         sources_abort_handle.push(handle);
+        drop(sources_abort_handle);
+
+        match self.get_deadline() {
+            None => Ok(()),
+            Some(deadline) => self.start_deadline_watchdog(deadline),
+        }
+    }
+
+    /// The sources only see the deadline when they are polled, so a query waiting
+    /// on a slow source or a remote stage is aborted by the watchdog instead.
+    fn start_deadline_watchdog(&self, deadline: Instant) -> Result<()> {
+        if self.deadline_watchdog.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let conf = self.conf.clone();
+        let query_id = self.init_query_id.read().clone();
+        let sources_abort_handle = Arc::downgrade(&self.sources_abort_handle);
+        let deadline_exceeded = self.deadline_exceeded.clone();
+        self.try_get_runtime()?.spawn(async move {
+            tokio::time::sleep_until(deadline.into()).await;
+
+            // The query is finished once the handles are gone.
+            let sources_abort_handle = match sources_abort_handle.upgrade() {
+                None => return,
+                Some(sources_abort_handle) => sources_abort_handle,
+            };
+
+            let handles = std::mem::take(&mut *sources_abort_handle.write());
+            if handles.is_empty() {
+                return;
+            }
+
+            log::warn!("Query {} exceeded the max execution time", query_id);
+            deadline_exceeded.store(true, Ordering::SeqCst);
+            for handle in handles {
+                handle.abort();
+            }
+
+            if !conf.disable_remote_catalog {
+                RemoteFactory::new(&conf).kill_store_query(query_id).await;
+            }
+        });
+
+        Ok(())
     }
 }
 
@@ -12,12 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod query_log_test;
+#[cfg(test)]
+mod workload_test;
+
 #[macro_use]
 mod macros;
 
 mod context;
 mod context_shared;
 mod metrics;
+mod query_log;
 mod session;
 mod session_info;
 mod session_ref;
@@ -25,12 +31,17 @@ mod session_ref;
 mod sessions;
 mod sessions_info;
 mod settings;
+mod workload;
 
 pub use context::DatafuseQueryContext;
 pub use context::DatafuseQueryContextRef;
+pub use query_log::QueryLog;
+pub use query_log::QueryLogEntry;
 pub use session::Session;
 pub use session_info::ProcessInfo;
 pub use session_ref::SessionRef;
 pub use sessions::SessionManager;
 pub use sessions::SessionManagerRef;
 pub use settings::Settings;
+pub use workload::WorkloadGroupConfig;
+pub use workload::WorkloadManager;
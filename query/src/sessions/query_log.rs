@@ -0,0 +1,172 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_infallible::Mutex;
+use common_infallible::RwLock;
+
+/// A finished or failed query, as shown in system.query_log.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct QueryLogEntry {
+    pub query_id: String,
+    pub user: Option<String>,
+    pub query_text: String,
+    /// Milliseconds since the unix epoch.
+    pub query_start_time: u64,
+    /// Milliseconds since the unix epoch.
+    pub query_end_time: u64,
+    pub read_rows: u64,
+    pub read_bytes: u64,
+    pub result_rows: u64,
+    pub error_code: Option<u16>,
+    pub error_message: Option<String>,
+    pub client_address: Option<String>,
+}
+
+/// The latest finished queries of this node in a bounded ring, the oldest is
+/// dropped first. With a file, every entry is also appended to it as a json
+/// line and the ring is reloaded from it on restart.
+pub struct QueryLog {
+    max_entries: usize,
+    entries: RwLock<VecDeque<QueryLogEntry>>,
+    file: Option<Mutex<QueryLogFile>>,
+}
+
+struct QueryLogFile {
+    path: PathBuf,
+    file: File,
+    // The lines in the file, it is rewritten with the ring once they are twice the ring size.
+    lines: usize,
+}
+
+impl QueryLog {
+    pub fn try_create(max_entries: usize, file: &str) -> Result<QueryLog> {
+        let mut entries = VecDeque::with_capacity(max_entries);
+        let file = match file.is_empty() {
+            true => None,
+            false => {
+                let path = PathBuf::from(file);
+                let file = Self::open_file(&path, max_entries, &mut entries)?;
+                Some(Mutex::new(QueryLogFile {
+                    path,
+                    file,
+                    lines: entries.len(),
+                }))
+            }
+        };
+
+        Ok(QueryLog {
+            max_entries,
+            entries: RwLock::new(entries),
+            file,
+        })
+    }
+
+    pub fn append(&self, entry: QueryLogEntry) {
+        // Held across the ring update, so that the file has the entries in the same order.
+        let file = self.file.as_ref().map(|file| file.lock());
+
+        let mut entries = self.entries.write();
+        if entries.len() == self.max_entries {
+            entries.pop_front();
+        }
+        if self.max_entries > 0 {
+            entries.push_back(entry.clone());
+        }
+
+        if let Some(mut file) = file {
+            let written = match file.lines >= self.max_entries.max(1) * 2 {
+                true => Self::write_file(&file.path, entries.iter()).map(|rewritten| {
+                    file.file = rewritten;
+                    file.lines = entries.len();
+                }),
+                false => serde_json::to_string(&entry)
+                    .map_err(std::io::Error::from)
+                    .and_then(|line| writeln!(file.file, "{}", line))
+                    .map(|_| file.lines += 1)
+                    .map_err(ErrorCode::from),
+            };
+
+            if let Err(cause) = written {
+                log::error!("Cannot append to the query log file, cause: {}", cause);
+            }
+        }
+    }
+
+    /// The entries from the oldest to the latest.
+    pub fn entries(&self) -> Vec<QueryLogEntry> {
+        self.entries.read().iter().cloned().collect()
+    }
+
+    // Load the latest entries and rewrite the file with them only, so that
+    // it does not grow without bound across restarts. At runtime, it is rewritten
+    // by `append` the same way.
+    fn open_file(
+        path: &Path,
+        max_entries: usize,
+        entries: &mut VecDeque<QueryLogEntry>,
+    ) -> Result<File> {
+        if path.exists() {
+            let file = File::open(path).map_err_to_code(ErrorCode::CannotReadFile, || {
+                format!("Cannot open query log file {:?}", path)
+            })?;
+
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err_to_code(ErrorCode::CannotReadFile, || {
+                    format!("Cannot read query log file {:?}", path)
+                })?;
+
+                match serde_json::from_str::<QueryLogEntry>(&line) {
+                    Err(cause) => log::warn!("Skip bad query log line {:?}: {}", line, cause),
+                    Ok(entry) => {
+                        if entries.len() == max_entries {
+                            entries.pop_front();
+                        }
+                        if max_entries > 0 {
+                            entries.push_back(entry);
+                        }
+                    }
+                }
+            }
+        }
+
+        Self::write_file(path, entries.iter())
+    }
+
+    fn write_file<'a>(
+        path: &Path,
+        entries: impl Iterator<Item = &'a QueryLogEntry>,
+    ) -> Result<File> {
+        let mut file = File::create(path).map_err_to_code(ErrorCode::CannotReadFile, || {
+            format!("Cannot create query log file {:?}", path)
+        })?;
+        for entry in entries {
+            let line = serde_json::to_string(entry)?;
+            writeln!(file, "{}", line).map_err_to_code(ErrorCode::CannotReadFile, || {
+                format!("Cannot write query log file {:?}", path)
+            })?;
+        }
+        Ok(file)
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::sessions::QueryLog;
+use crate::sessions::QueryLogEntry;
+
+fn entry(query_id: &str) -> QueryLogEntry {
+    QueryLogEntry {
+        query_id: query_id.to_string(),
+        user: None,
+        query_text: format!("SELECT '{}'", query_id),
+        query_start_time: 1,
+        query_end_time: 2,
+        read_rows: 0,
+        read_bytes: 0,
+        result_rows: 1,
+        error_code: None,
+        error_message: None,
+        client_address: Some("127.0.0.1:3307".to_string()),
+    }
+}
+
+fn query_ids(query_log: &QueryLog) -> Vec<String> {
+    let entries = query_log.entries();
+    entries.into_iter().map(|entry| entry.query_id).collect()
+}
+
+#[test]
+fn test_query_log_ring() -> Result<()> {
+    let query_log = QueryLog::try_create(2, "")?;
+    query_log.append(entry("1"));
+    query_log.append(entry("2"));
+    query_log.append(entry("3"));
+
+    assert_eq!(vec!["2", "3"], query_ids(&query_log));
+    assert_eq!(entry("3"), query_log.entries()[1]);
+    Ok(())
+}
+
+#[test]
+fn test_query_log_file() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("query_log_{}.json", uuid::Uuid::new_v4()));
+    let file = path.display().to_string();
+
+    {
+        let query_log = QueryLog::try_create(3, &file)?;
+        for query_id in &["1", "2", "3", "4"] {
+            query_log.append(entry(query_id));
+        }
+    }
+
+    // Reloaded on restart, with the oldest ones dropped from the file too.
+    let query_log = QueryLog::try_create(2, &file)?;
+    assert_eq!(vec!["3", "4"], query_ids(&query_log));
+    assert_eq!(2, std::fs::read_to_string(&path)?.lines().count());
+
+    query_log.append(entry("5"));
+    let query_log = QueryLog::try_create(2, &file)?;
+    assert_eq!(vec!["4", "5"], query_ids(&query_log));
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_query_log_file_rewritten() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("query_log_{}.json", uuid::Uuid::new_v4()));
+    let file = path.display().to_string();
+
+    // The file is rewritten with the ring once it has twice as many lines.
+    let query_log = QueryLog::try_create(2, &file)?;
+    for query_id in &["1", "2", "3", "4"] {
+        query_log.append(entry(query_id));
+    }
+    assert_eq!(4, std::fs::read_to_string(&path)?.lines().count());
+
+    query_log.append(entry("5"));
+    assert_eq!(2, std::fs::read_to_string(&path)?.lines().count());
+    query_log.append(entry("6"));
+    assert_eq!(3, std::fs::read_to_string(&path)?.lines().count());
+
+    let query_log = QueryLog::try_create(2, &file)?;
+    assert_eq!(vec!["5", "6"], query_ids(&query_log));
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
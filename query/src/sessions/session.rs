@@ -12,17 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
+use common_management::UserMgr;
+use common_management::UserMgrApi;
+use common_management::UserProfile;
 use futures::channel::oneshot::Sender;
 use futures::channel::*;
+use sha2::Digest;
 
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
+use crate::datasources::remote::RemoteFactory;
 use crate::datasources::DatabaseCatalog;
 use crate::sessions::context_shared::DatafuseQueryContextShared;
 use crate::sessions::DatafuseQueryContext;
@@ -33,13 +40,27 @@ use crate::sessions::Settings;
 pub(in crate::sessions) struct MutableStatus {
     pub(in crate::sessions) abort: bool,
     pub(in crate::sessions) current_database: String,
+    pub(in crate::sessions) user: Option<String>,
+    // The limits of the user profile by setting name, a SET cannot raise them.
+    pub(in crate::sessions) profile_limits: HashMap<String, u64>,
     pub(in crate::sessions) session_settings: Arc<Settings>,
-    #[allow(unused)]
     pub(in crate::sessions) client_host: Option<SocketAddr>,
     pub(in crate::sessions) io_shutdown_tx: Option<Sender<Sender<()>>>,
     pub(in crate::sessions) context_shared: Option<Arc<DatafuseQueryContextShared>>,
 }
 
+/// The settings of a user profile that limit the queries, 0 means no limit.
+const PROFILE_LIMITS: [&str; 5] = [
+    "max_execution_time",
+    "max_rows_to_read",
+    "max_bytes_to_read",
+    "max_result_rows",
+    "max_result_bytes",
+];
+
+/// The user of which the profile is applied to the users unknown to the store.
+const DEFAULT_PROFILE_USER: &str = "default";
+
 #[derive(Clone)]
 pub struct Session {
     pub(in crate::sessions) id: String,
@@ -66,6 +87,8 @@ impl Session {
             mutable_state: Arc::new(Mutex::new(MutableStatus {
                 abort: false,
                 current_database: String::from("default"),
+                user: None,
+                profile_limits: HashMap::new(),
                 session_settings: Settings::try_create()?,
                 client_host: None,
                 io_shutdown_tx: None,
@@ -114,6 +137,15 @@ impl Session {
         }
     }
 
+    /// The id of the query running in the session, if any.
+    pub fn get_running_query_id(self: &Arc<Self>) -> Option<String> {
+        let mutable_state = self.mutable_state.lock();
+        mutable_state
+            .context_shared
+            .as_ref()
+            .map(|shared| shared.init_query_id.read().clone())
+    }
+
     pub fn create_context(self: &Arc<Self>) -> DatafuseQueryContextRef {
         let mut state_guard = self.mutable_state.lock();
 
@@ -149,6 +181,129 @@ impl Session {
         inner.current_database = database_name;
     }
 
+    /// The user name given by the client.
+    pub fn set_user(self: &Arc<Self>, user: String) {
+        let mut inner = self.mutable_state.lock();
+        inner.user = Some(user);
+    }
+
+    /// Check the password of a user, the users are the store api user of the config and
+    /// the users stored in the store, like the store checks its flight clients.
+    pub async fn authenticate(self: &Arc<Self>, user: &str, password: &str) -> Result<()> {
+        let failure = || {
+            ErrorCode::AuthenticateFailure(format!(
+                "Password authentication failed for user \"{}\"",
+                user
+            ))
+        };
+
+        if user == self.config.store_api_username.as_ref().as_str() {
+            return match password == self.config.store_api_password.as_ref().as_str() {
+                true => Ok(()),
+                false => Err(failure()),
+            };
+        }
+
+        if self.config.disable_remote_catalog {
+            return Err(failure());
+        }
+
+        let provider = RemoteFactory::new(&self.config).store_client_provider();
+        let client = provider.try_get_client().await?;
+        let password_sha256: [u8; 32] = sha2::Sha256::digest(password.as_bytes()).into();
+        match UserMgr::new(client).get_user(user, None).await {
+            Ok((_, user_info)) if user_info.password_sha256 == password_sha256 => Ok(()),
+            Ok(_) => Err(failure()),
+            Err(cause) if cause.code() == ErrorCode::UnknownUser("").code() => Err(failure()),
+            Err(cause) => Err(cause),
+        }
+    }
+
+    /// Apply the profile of the session user, as stored with the user in the store, to
+    /// the session settings. The users unknown to the store get the profile of the
+    /// `default` user, and are rejected if there is no such user. The login is rejected
+    /// as well if the profile cannot be read, instead of letting the user go unlimited.
+    pub async fn apply_user_profile(self: &Arc<Self>) -> Result<()> {
+        let user = self.mutable_state.lock().user.clone();
+        if self.config.disable_remote_catalog
+            || user.as_deref() == Some(self.config.store_api_username.as_ref().as_str())
+        {
+            return Ok(());
+        }
+
+        let provider = RemoteFactory::new(&self.config).store_client_provider();
+        let mut user_mgr = UserMgr::new(provider.try_get_client().await?);
+        let unknown = |cause: &ErrorCode| cause.code() == ErrorCode::UnknownUser("").code();
+
+        let user_info = match &user {
+            Some(user) => user_mgr.get_user(user, None).await,
+            None => Err(ErrorCode::UnknownUser("")),
+        };
+        let profile = match user_info {
+            Ok((_, user_info)) => user_info.profile,
+            Err(cause) if unknown(&cause) => {
+                match user_mgr.get_user(DEFAULT_PROFILE_USER, None).await {
+                    Ok((_, default_user)) => default_user.profile,
+                    Err(cause) if unknown(&cause) => {
+                        return Err(ErrorCode::AuthenticateFailure(format!(
+                            "Unknown user \"{}\", and there is no user \"{}\" whose profile applies to it",
+                            user.unwrap_or_default(),
+                            DEFAULT_PROFILE_USER
+                        )))
+                    }
+                    Err(cause) => return Err(cause),
+                }
+            }
+            Err(cause) => return Err(cause),
+        };
+
+        self.apply_profile(&profile)
+    }
+
+    /// Apply the settings of a profile. The limits in it, except 0 for no limit,
+    /// are the upper bounds of the settings for the rest of the session.
+    pub fn apply_profile(self: &Arc<Self>, profile: &UserProfile) -> Result<()> {
+        let settings = self.get_settings();
+        let mut limits = HashMap::new();
+        for (name, value) in &profile.settings {
+            settings.update_settings(name, value.clone())?;
+            if PROFILE_LIMITS.contains(&name.as_str()) {
+                match value.parse::<u64>() {
+                    Ok(0) => {}
+                    Ok(limit) => {
+                        limits.insert(name.clone(), limit);
+                    }
+                    Err(_) => {
+                        return Err(ErrorCode::BadArguments(format!(
+                            "Bad value {:?} of the profile limit {}",
+                            value, name
+                        )))
+                    }
+                }
+            }
+        }
+
+        self.mutable_state.lock().profile_limits = limits;
+        Ok(())
+    }
+
+    /// Check a SET of the setting against the limit of the user profile:
+    /// it may lower the limit, but not raise or remove it.
+    pub fn check_profile_limit(self: &Arc<Self>, name: &str, value: &str) -> Result<()> {
+        let limit = match self.mutable_state.lock().profile_limits.get(name) {
+            None => return Ok(()),
+            Some(limit) => *limit,
+        };
+
+        match value.trim_matches('\'').parse::<u64>() {
+            Ok(value) if value > 0 && value <= limit => Ok(()),
+            _ => Err(ErrorCode::PermissionDenied(format!(
+                "Cannot set {} to {}, it is limited to {} by the user profile",
+                name, value, limit
+            ))),
+        }
+    }
+
     pub fn get_current_database(self: &Arc<Self>) -> String {
         let inner = self.mutable_state.lock();
         inner.current_database.clone()
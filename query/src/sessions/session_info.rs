@@ -52,6 +52,7 @@ impl Session {
         match status.context_shared {
             _ if status.abort => String::from("Aborting"),
             None => String::from("Idle"),
+            Some(ref shared) if shared.is_queued() => String::from("Queued"),
             Some(_) => String::from("Query"),
         }
     }
@@ -33,8 +33,12 @@ use crate::clusters::ClusterRef;
 use crate::configs::Config;
 use crate::datasources::remote::RemoteFactory;
 use crate::datasources::DatabaseCatalog;
+use crate::interpreters::QueryResultCache;
+use crate::pipelines::executor::PipelineExecutor;
+use crate::sessions::query_log::QueryLog;
 use crate::sessions::session::Session;
 use crate::sessions::session_ref::SessionRef;
+use crate::sessions::WorkloadManager;
 
 pub struct SessionManager {
     pub(in crate::sessions) conf: Config,
@@ -43,14 +47,29 @@ pub struct SessionManager {
 
     pub(in crate::sessions) max_sessions: usize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
+    pub(in crate::sessions) query_log: Arc<QueryLog>,
+    pub(in crate::sessions) executor: Arc<PipelineExecutor>,
+    pub(in crate::sessions) query_cache: Arc<QueryResultCache>,
+    pub(in crate::sessions) workload: Arc<WorkloadManager>,
 }
 
 pub type SessionManagerRef = Arc<SessionManager>;
 
 impl SessionManager {
     pub fn try_create(max_mysql_sessions: u64) -> Result<SessionManagerRef> {
+        // No store is reachable, thus no user profile either.
+        let conf = Config {
+            disable_remote_catalog: true,
+            ..Config::default()
+        };
+        let num_cpus = conf.num_cpus as usize;
+        let query_cache_max_bytes = conf.query_cache_max_bytes as usize;
+        let query_log =
+            QueryLog::try_create(conf.query_log_max_entries as usize, &conf.query_log_file)?;
+        let workload = WorkloadManager::try_create(&conf.workload_groups)?;
+
         Ok(Arc::new(SessionManager {
-            conf: Config::default(),
+            conf,
             cluster: Cluster::empty(),
             datasource: Arc::new(DatabaseCatalog::try_create()?),
 
@@ -58,11 +77,20 @@ impl SessionManager {
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(
                 max_mysql_sessions as usize,
             ))),
+            query_log: Arc::new(query_log),
+            executor: PipelineExecutor::create(num_cpus),
+            query_cache: Arc::new(QueryResultCache::create(query_cache_max_bytes)),
+            workload: Arc::new(workload),
         }))
     }
 
     pub fn from_conf(conf: Config, cluster: ClusterRef) -> Result<SessionManagerRef> {
         let max_active_sessions = conf.max_active_sessions as usize;
+        let num_cpus = conf.num_cpus as usize;
+        let query_cache_max_bytes = conf.query_cache_max_bytes as usize;
+        let query_log =
+            QueryLog::try_create(conf.query_log_max_entries as usize, &conf.query_log_file)?;
+        let workload = WorkloadManager::try_create(&conf.workload_groups)?;
         let meta_store_cli = Arc::new(RemoteMetaStoreClient::create(Arc::new(
             RemoteFactory::new(&conf).store_client_provider(),
         )));
@@ -75,6 +103,10 @@ impl SessionManager {
             cluster,
             max_sessions: max_active_sessions,
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_active_sessions))),
+            query_log: Arc::new(query_log),
+            executor: PipelineExecutor::create(num_cpus),
+            query_cache: Arc::new(QueryResultCache::create(query_cache_max_bytes)),
+            workload: Arc::new(workload),
         }))
     }
 
@@ -90,6 +122,22 @@ impl SessionManager {
         self.datasource.clone()
     }
 
+    pub fn get_query_log(self: &Arc<Self>) -> Arc<QueryLog> {
+        self.query_log.clone()
+    }
+
+    pub fn get_executor(self: &Arc<Self>) -> Arc<PipelineExecutor> {
+        self.executor.clone()
+    }
+
+    pub fn get_query_cache(self: &Arc<Self>) -> Arc<QueryResultCache> {
+        self.query_cache.clone()
+    }
+
+    pub fn get_workload(self: &Arc<Self>) -> Arc<WorkloadManager> {
+        self.workload.clone()
+    }
+
     pub fn create_session(self: &Arc<Self>, typ: impl Into<String>) -> Result<SessionRef> {
         counter!(super::metrics::METRIC_SESSION_CONNECT_NUMBERS, 1);
 
@@ -31,7 +31,17 @@ impl Settings {
         ("max_threads", u64, 16, "The maximum number of threads to execute the request. By default, it is determined automatically.".to_string()),
         ("flight_client_timeout", u64, 60, "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds".to_string()),
         ("min_distributed_rows", u64, 100000000, "Minimum distributed read rows. In cluster mode, when read rows exceeds this value, the local table converted to distributed query.".to_string()),
-        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.".to_string())
+        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.".to_string()),
+        ("cpu_shares", u64, 1024, "The relative CPU weight of the query on the executor workers. When the workers are busy, a query with 2048 gets twice the CPU time of a query with 1024.".to_string()),
+        ("use_query_cache", u64, 0, "Reuse the cached result of the same SELECT if the tables it reads are at the same versions, 1 to enable. Only the SELECTs reading remote tables alone are cached, by the table versions taken when the query is planned. Not used with max_rows_to_read or max_bytes_to_read.".to_string()),
+        ("query_cache_ttl", u64, 60, "Max age of a cached query result in seconds. By default, it is 60 seconds".to_string()),
+        ("max_execution_time", u64, 0, "Max duration the query is allowed to run in seconds, the query is aborted once it is exceeded. 0 means no limit.".to_string()),
+        ("max_rows_to_read", u64, 0, "Max rows the query is allowed to read from the tables. 0 means no limit.".to_string()),
+        ("max_bytes_to_read", u64, 0, "Max uncompressed bytes the query is allowed to read from the tables. 0 means no limit.".to_string()),
+        ("read_overflow_mode", String, "throw".to_string(), "What to do once max_rows_to_read or max_bytes_to_read is exceeded: throw to fail the query, truncate to return what was read.".to_string()),
+        ("max_result_rows", u64, 0, "Max rows of the query result. 0 means no limit.".to_string()),
+        ("max_result_bytes", u64, 0, "Max uncompressed bytes of the query result. 0 means no limit.".to_string()),
+        ("result_overflow_mode", String, "throw".to_string(), "What to do once max_result_rows or max_result_bytes is exceeded: throw to fail the query, truncate to return the result so far.".to_string())
     }
 
     pub fn try_create() -> Result<Arc<Settings>> {
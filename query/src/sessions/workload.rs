@@ -0,0 +1,207 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio;
+use common_runtime::tokio::sync::OwnedSemaphorePermit;
+use common_runtime::tokio::sync::Semaphore;
+
+/// The users matched by '*' are those without a group of their own.
+const ANY_USER: &str = "*";
+
+/// A workload group, as configured by `workload_groups`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkloadGroupConfig {
+    pub name: String,
+    pub users: Vec<String>,
+    pub max_concurrent_queries: usize,
+    /// The queries waiting over this length are rejected, 0 means no queueing.
+    pub max_queue_length: usize,
+    /// How long a query waits in the queue before it fails, 0 means no limit.
+    pub queue_timeout: Duration,
+}
+
+impl WorkloadGroupConfig {
+    /// Parse the groups separated by ';', each one is its name and its options:
+    ///     ingest:users=loader|etl,max_concurrent_queries=8,max_queue_length=64,queue_timeout=60
+    pub fn parse_groups(groups: &str) -> Result<Vec<WorkloadGroupConfig>> {
+        groups
+            .split(';')
+            .map(str::trim)
+            .filter(|group| !group.is_empty())
+            .map(Self::parse_group)
+            .collect()
+    }
+
+    fn parse_group(group: &str) -> Result<WorkloadGroupConfig> {
+        let bad_group = |reason: &str| {
+            ErrorCode::BadArguments(format!("Bad workload group '{}': {}", group, reason))
+        };
+
+        let (name, options) = match group.split_once(':') {
+            Some((name, options)) if !name.trim().is_empty() => (name.trim(), options),
+            _ => return Err(bad_group("expected <name>:<options>")),
+        };
+
+        let mut config = WorkloadGroupConfig {
+            name: name.to_string(),
+            users: vec![],
+            max_concurrent_queries: 0,
+            max_queue_length: 0,
+            queue_timeout: Duration::from_secs(0),
+        };
+
+        for option in options.split(',').map(str::trim) {
+            let (key, value) = option
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or_else(|| bad_group("expected <option>=<value>"))?;
+
+            let number = || {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| bad_group(&format!("{} must be a number", key)))
+            };
+
+            match key {
+                "users" => config.users = value.split('|').map(str::to_string).collect(),
+                "max_concurrent_queries" => config.max_concurrent_queries = number()? as usize,
+                "max_queue_length" => config.max_queue_length = number()? as usize,
+                "queue_timeout" => config.queue_timeout = Duration::from_secs(number()?),
+                _ => return Err(bad_group(&format!("unknown option {}", key))),
+            }
+        }
+
+        if config.users.is_empty() {
+            return Err(bad_group("users is required"));
+        }
+
+        if config.max_concurrent_queries == 0 {
+            return Err(bad_group("max_concurrent_queries must be greater than 0"));
+        }
+
+        Ok(config)
+    }
+}
+
+struct WorkloadGroup {
+    config: WorkloadGroupConfig,
+    running: Arc<Semaphore>,
+    queued: AtomicUsize,
+}
+
+/// Held by a query while it runs, the next queued query of the group starts once it is dropped.
+pub struct WorkloadPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+/// Admits the queries of the users in a workload group up to its max concurrent
+/// queries, the others wait in the group queue in arrival order. The queries of
+/// the users without a group are admitted at once.
+pub struct WorkloadManager {
+    groups: Vec<Arc<WorkloadGroup>>,
+}
+
+impl WorkloadManager {
+    pub fn try_create(workload_groups: &str) -> Result<WorkloadManager> {
+        let groups = WorkloadGroupConfig::parse_groups(workload_groups)?;
+        Ok(WorkloadManager {
+            groups: groups
+                .into_iter()
+                .map(|config| {
+                    Arc::new(WorkloadGroup {
+                        running: Arc::new(Semaphore::new(config.max_concurrent_queries)),
+                        queued: AtomicUsize::new(0),
+                        config,
+                    })
+                })
+                .collect(),
+        })
+    }
+
+    fn find_group(&self, user: Option<&str>) -> Option<Arc<WorkloadGroup>> {
+        let is_member = |group: &&Arc<WorkloadGroup>, user: &str| {
+            group.config.users.iter().any(|member| member == user)
+        };
+
+        let group = match user {
+            None => None,
+            Some(user) => self.groups.iter().find(|group| is_member(group, user)),
+        };
+
+        group
+            .or_else(|| self.groups.iter().find(|group| is_member(group, ANY_USER)))
+            .cloned()
+    }
+
+    /// Wait for a permit of the workload group of the user, `on_queued` is called
+    /// if the query has to wait for it. Without a group, no permit is needed.
+    pub async fn admit(
+        &self,
+        user: Option<&str>,
+        on_queued: impl FnOnce(),
+    ) -> Result<Option<WorkloadPermit>> {
+        let group = match self.find_group(user) {
+            None => return Ok(None),
+            Some(group) => group,
+        };
+
+        // The permits are handed to the waiting queries first, so it never jumps the queue.
+        if let Ok(permit) = group.running.clone().try_acquire_owned() {
+            return Ok(Some(WorkloadPermit { _permit: permit }));
+        }
+
+        let config = &group.config;
+        if group.queued.fetch_add(1, Ordering::SeqCst) >= config.max_queue_length {
+            group.queued.fetch_sub(1, Ordering::SeqCst);
+            return Err(ErrorCode::TooManyQueuedQueries(format!(
+                "The queue of workload group {} is full, it has {} queries waiting",
+                config.name, config.max_queue_length
+            )));
+        }
+
+        // Leaves the queue even if the query is killed while waiting.
+        let _queued = QueuedGuard(&group.queued);
+        on_queued();
+
+        let acquire = group.running.clone().acquire_owned();
+        let permit = match config.queue_timeout.as_secs() {
+            0 => Ok(acquire.await),
+            _ => tokio::time::timeout(config.queue_timeout, acquire).await,
+        };
+
+        match permit {
+            Ok(Ok(permit)) => Ok(Some(WorkloadPermit { _permit: permit })),
+            Ok(Err(cause)) => Err(ErrorCode::LogicalError(cause.to_string())),
+            Err(_) => Err(ErrorCode::Timeout(format!(
+                "Query waited more than {} seconds in the queue of workload group {}",
+                config.queue_timeout.as_secs(),
+                config.name
+            ))),
+        }
+    }
+}
+
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
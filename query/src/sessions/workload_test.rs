@@ -0,0 +1,103 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::Duration;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio;
+use pretty_assertions::assert_eq;
+
+use crate::sessions::WorkloadGroupConfig;
+use crate::sessions::WorkloadManager;
+
+#[test]
+fn test_workload_group_config() -> Result<()> {
+    let groups = WorkloadGroupConfig::parse_groups(
+        "ingest:users=loader|etl,max_concurrent_queries=8,max_queue_length=64,queue_timeout=60; \
+         adhoc:users=*,max_concurrent_queries=2",
+    )?;
+    assert_eq!(groups, vec![
+        WorkloadGroupConfig {
+            name: "ingest".to_string(),
+            users: vec!["loader".to_string(), "etl".to_string()],
+            max_concurrent_queries: 8,
+            max_queue_length: 64,
+            queue_timeout: Duration::from_secs(60),
+        },
+        WorkloadGroupConfig {
+            name: "adhoc".to_string(),
+            users: vec!["*".to_string()],
+            max_concurrent_queries: 2,
+            max_queue_length: 0,
+            queue_timeout: Duration::from_secs(0),
+        },
+    ]);
+
+    assert!(WorkloadGroupConfig::parse_groups("")?.is_empty());
+
+    let bad_groups = vec![
+        "adhoc",
+        "adhoc:max_concurrent_queries=2",
+        "adhoc:users=alice",
+        "adhoc:users=alice,max_concurrent_queries=x",
+        "adhoc:users=alice,max_concurrent_queries=2,priority=1",
+    ];
+    for bad_group in bad_groups {
+        let error = WorkloadGroupConfig::parse_groups(bad_group).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::BadArguments("").code());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_workload_admission() -> Result<()> {
+    let workload = WorkloadManager::try_create(
+        "adhoc:users=alice|bob,max_concurrent_queries=1,max_queue_length=1,queue_timeout=1",
+    )?;
+
+    // Without a group.
+    assert!(workload.admit(Some("carol"), || {}).await?.is_none());
+    assert!(workload.admit(None, || {}).await?.is_none());
+
+    let running = workload.admit(Some("alice"), || {}).await?;
+    assert!(running.is_some());
+
+    // The queue is full while bob waits.
+    let mut queued = false;
+    let waiting = workload.admit(Some("bob"), || queued = true);
+    let rejected = async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        workload.admit(Some("alice"), || {}).await
+    };
+    let (waiting, rejected) = futures::join!(waiting, rejected);
+    let error = rejected.err().unwrap();
+    assert_eq!(error.code(), ErrorCode::TooManyQueuedQueries("").code());
+
+    // Bob times out in the queue because alice is still running.
+    let error = waiting.err().unwrap();
+    assert_eq!(error.code(), ErrorCode::Timeout("").code());
+    assert!(queued);
+
+    // The queued query starts once the running one is finished.
+    let waiting = workload.admit(Some("bob"), || {});
+    let finished = async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(running);
+    };
+    let (admitted, _) = futures::join!(waiting, finished);
+    assert!(admitted?.is_some());
+
+    Ok(())
+}
@@ -21,17 +21,21 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
 use common_infallible::Mutex;
+use common_metatypes::TableVersionAt;
 use common_planners::expand_aggregate_arg_exprs;
 use common_planners::expand_wildcard;
+use common_planners::expand_window_arg_exprs;
 use common_planners::expr_as_column_expr;
 use common_planners::extract_aliases;
 use common_planners::find_aggregate_exprs;
 use common_planners::find_columns_not_satisfy_exprs;
+use common_planners::find_window_exprs;
 use common_planners::rebase_expr;
 use common_planners::rebase_expr_from_input;
 use common_planners::resolve_aliases_to_exprs;
 use common_planners::sort_to_inner_expr;
 use common_planners::unwrap_alias_exprs;
+use common_planners::AnalyzeTablePlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DescribeTablePlan;
@@ -50,6 +54,9 @@ use common_planners::TableScanInfo;
 use common_planners::TruncateTablePlan;
 use common_planners::UseDatabasePlan;
 use common_planners::VarValue;
+use common_planners::WindowFrame;
+use common_planners::WindowFrameBound;
+use common_planners::WindowFrameUnits;
 use common_streams::Source;
 use common_streams::ValueSource;
 use common_tracing::tracing;
@@ -68,6 +75,7 @@ use crate::sessions::DatafuseQueryContextRef;
 use crate::sql::sql_statement::DfCreateTable;
 use crate::sql::sql_statement::DfDropDatabase;
 use crate::sql::sql_statement::DfUseDatabase;
+use crate::sql::DfAnalyzeTable;
 use crate::sql::DfCreateDatabase;
 use crate::sql::DfDescribeTable;
 use crate::sql::DfDropTable;
@@ -130,6 +138,7 @@ impl PlanParser {
             DfStatement::DescribeTable(v) => self.sql_describe_table_to_plan(v),
             DfStatement::DropTable(v) => self.sql_drop_table_to_plan(v),
             DfStatement::TruncateTable(v) => self.sql_truncate_table_to_plan(v),
+            DfStatement::AnalyzeTable(v) => self.sql_analyze_table_to_plan(v),
             DfStatement::UseDatabase(v) => self.sql_use_database_to_plan(v),
             DfStatement::ShowCreateTable(v) => self.sql_show_create_table_to_plan(v),
             DfStatement::ShowTables(df) => {
@@ -219,11 +228,22 @@ impl PlanParser {
             options.insert(p.name.value.to_lowercase(), p.value.to_string());
         }
 
+        let clone_from = match &create.clone_from {
+            None => None,
+            Some(src) => {
+                let src = src.0[0].value.clone();
+                // The source database has to exist.
+                self.ctx.get_datasource().get_database(&src)?;
+                Some(src)
+            }
+        };
+
         Ok(PlanNode::CreateDatabase(CreateDatabasePlan {
             if_not_exists: create.if_not_exists,
             db: name,
             engine: create.engine,
             options,
+            clone_from,
         }))
     }
 
@@ -297,7 +317,24 @@ impl PlanParser {
             );
         }
 
-        let schema = DataSchemaRefExt::create(fields);
+        let mut schema = DataSchemaRefExt::create(fields);
+        let mut clone_from = None;
+        if let Some(src) = &create.clone_from {
+            let mut src_db = self.ctx.get_current_database();
+            let mut src_table = src.0[0].value.clone();
+            if src.0.len() > 1 {
+                src_db = src_table;
+                src_table = src.0[1].value.clone();
+            }
+            // The clone has the schema of the source table.
+            schema = self
+                .ctx
+                .get_table(&src_db, &src_table)?
+                .datasource()
+                .schema()?;
+            clone_from = Some((src_db, src_table));
+        }
+
         Ok(PlanNode::CreateTable(CreateTablePlan {
             if_not_exists: create.if_not_exists,
             db,
@@ -305,6 +342,7 @@ impl PlanParser {
             schema,
             engine: create.engine,
             options,
+            clone_from,
         }))
     }
 
@@ -401,6 +439,24 @@ impl PlanParser {
         Ok(PlanNode::TruncateTable(TruncateTablePlan { db, table }))
     }
 
+    // DfAnalyzeTable to plan.
+    #[tracing::instrument(level = "info", skip(self, analyze), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_analyze_table_to_plan(&self, analyze: &DfAnalyzeTable) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if analyze.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(
+                "AnalyzeTable table name is empty",
+            ));
+        }
+        let mut table = analyze.name.0[0].value.clone();
+        if analyze.name.0.len() > 1 {
+            db = table;
+            table = analyze.name.0[1].value.clone();
+        }
+
+        Ok(PlanNode::AnalyzeTable(AnalyzeTablePlan { db, table }))
+    }
+
     #[tracing::instrument(level = "info", skip(self, table_name, columns, source), fields(ctx.id = self.ctx.get_id().as_str()))]
     fn insert_to_plan(
         &self,
@@ -632,10 +688,21 @@ impl PlanParser {
             "Before OrderBy"
         };
 
-        let plan = self.expression(&plan, &expression_with_sort, stage_phase)?;
+        // All of the window function expressions (deduplicated).
+        // Window functions are evaluated after the having, so we apply it before them.
+        let window_exprs = find_window_exprs(&expression_with_sort);
+        let plan = if window_exprs.is_empty() {
+            let plan = self.expression(&plan, &expression_with_sort, stage_phase)?;
+            self.having(&plan, having_expr_post_aggr_opt)?
+        } else {
+            let before_window_exprs = expand_window_arg_exprs(&window_exprs);
+            let plan = self
+                .expression(&plan, &before_window_exprs, "Before Window")
+                .and_then(|input| self.having(&input, having_expr_post_aggr_opt))
+                .and_then(|input| self.window(&input, &window_exprs))?;
+            self.expression(&plan, &expression_with_sort, stage_phase)?
+        };
 
-        // Having.
-        let plan = self.having(&plan, having_expr_post_aggr_opt)?;
         // Order by
         let plan = self.sort(&plan, &order_by_exprs)?;
         // Projection
@@ -721,7 +788,12 @@ impl PlanParser {
 
     fn create_relation(&self, relation: &sqlparser::ast::TableFactor) -> Result<PlanNode> {
         match relation {
-            TableFactor::Table { name, args, .. } => {
+            TableFactor::Table {
+                name,
+                args,
+                with_hints,
+                ..
+            } => {
                 let mut db_name = self.ctx.get_current_database();
                 let mut table_name = name.to_string();
                 if name.0.len() == 2 {
@@ -751,6 +823,12 @@ impl PlanParser {
                         }
                     }
 
+                    if !with_hints.is_empty() {
+                        return Result::Err(ErrorCode::BadArguments(
+                            "Table function can't be read at a version",
+                        ));
+                    }
+
                     let func_meta = self.ctx.get_table_function(&table_name)?;
                     meta_id = func_meta.meta_id();
                     meta_version = func_meta.meta_ver();
@@ -760,8 +838,19 @@ impl PlanParser {
                 } else {
                     let table_meta = self.ctx.get_table(&db_name, &table_name)?;
                     meta_id = table_meta.meta_id();
-                    meta_version = table_meta.meta_ver();
                     table = table_meta.datasource().clone();
+                    meta_version = match Self::table_version_at(with_hints)? {
+                        // The read is pinned to the version of now, thus its result can be cached by the version.
+                        // The version is not looked up unless the query cache is used.
+                        None => match table_meta.meta_ver() {
+                            Some(version) => Some(version),
+                            None if self.ctx.get_settings().get_use_query_cache()? != 0 => {
+                                table.current_version(self.ctx.clone())?
+                            }
+                            None => None,
+                        },
+                        Some(at) => Some(table.resolve_version(self.ctx.clone(), at)?),
+                    };
                 }
 
                 let scan = {
@@ -796,6 +885,48 @@ impl PlanParser {
             }
         }
     }
+
+    /// The time travel hint `at_version(n)` or `at_timestamp(ts)`, see `DfParser`.
+    fn table_version_at(hints: &[sqlparser::ast::Expr]) -> Result<Option<TableVersionAt>> {
+        let hint = match hints {
+            [] => return Ok(None),
+            [sqlparser::ast::Expr::Function(hint)] if hint.args.len() == 1 => hint,
+            _ => {
+                return Result::Err(ErrorCode::SyntaxException(format!(
+                    "Unsupported table hints: {:?}",
+                    hints
+                )))
+            }
+        };
+
+        let invalid = || {
+            ErrorCode::BadArguments(format!(
+                "Invalid time travel clause {}, expected AT (VERSION => n) or AT (TIMESTAMP => ts)",
+                hint
+            ))
+        };
+        let value = match &hint.args[0] {
+            FunctionArg::Unnamed(sqlparser::ast::Expr::Value(value)) => value,
+            _ => return Result::Err(invalid()),
+        };
+
+        match (hint.name.to_string().as_str(), value) {
+            ("at_version", sqlparser::ast::Value::Number(n, _)) => n
+                .parse::<u64>()
+                .map(|v| Some(TableVersionAt::Version(v)))
+                .map_err(|_| invalid()),
+            // Seconds since 1970.
+            ("at_timestamp", sqlparser::ast::Value::Number(n, _)) => n
+                .parse::<u64>()
+                .map(|v| Some(TableVersionAt::Timestamp(v.saturating_mul(1000))))
+                .map_err(|_| invalid()),
+            ("at_timestamp", sqlparser::ast::Value::SingleQuotedString(ts)) => {
+                SQLCommon::make_timestamp_millis(ts).map(|v| Some(TableVersionAt::Timestamp(v)))
+            }
+            _ => Result::Err(invalid()),
+        }
+    }
+
     fn process_compound_ident(
         &self,
         ids: &[Ident],
@@ -873,6 +1004,137 @@ impl PlanParser {
         }
     }
 
+    fn function_params_to_values(e: &sqlparser::ast::Function) -> Result<Vec<DataValue>> {
+        e.params
+            .iter()
+            .map(|v| {
+                let expr = Self::value_to_rex(v);
+                if let Ok(Expression::Literal { value, .. }) = expr {
+                    Ok(value)
+                } else {
+                    Result::Err(ErrorCode::SyntaxException(format!(
+                        "Unsupported value expression: {:?}, must be datavalue",
+                        expr
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+    }
+
+    /// Generate a window function expression from `func(args) OVER (PARTITION BY .. ORDER BY .. frame)`
+    fn window_function_to_rex(
+        &self,
+        e: &sqlparser::ast::Function,
+        op: String,
+        args: Vec<Expression>,
+        over: &sqlparser::ast::WindowSpec,
+        schema: &DataSchema,
+        select: Option<&sqlparser::ast::Select>,
+    ) -> Result<Expression> {
+        if e.distinct {
+            return Result::Err(ErrorCode::SyntaxException(format!(
+                "DISTINCT is not supported in window function: {}",
+                op
+            )));
+        }
+
+        let name = op.to_lowercase();
+        let is_window_only = matches!(
+            name.as_str(),
+            "row_number" | "rank" | "dense_rank" | "lag" | "lead" | "first_value" | "last_value"
+        );
+        if !is_window_only && !AggregateFunctionFactory::check(&op) {
+            return Result::Err(ErrorCode::UnknownAggregateFunction(format!(
+                "Unsupported window function: {}",
+                op
+            )));
+        }
+
+        let args = match name.as_str() {
+            "count" => args
+                .iter()
+                .map(|c| match c {
+                    Expression::Wildcard => common_planners::lit(0i64),
+                    _ => c.clone(),
+                })
+                .collect(),
+            _ => args,
+        };
+
+        let partition_by = over
+            .partition_by
+            .iter()
+            .map(|e| self.sql_to_rex(e, schema, select))
+            .collect::<Result<Vec<_>>>()?;
+
+        let order_by = over
+            .order_by
+            .iter()
+            .map(|e| -> Result<Expression> {
+                Ok(Expression::Sort {
+                    expr: Box::new(self.sql_to_rex(&e.expr, schema, select)?),
+                    asc: e.asc.unwrap_or(true),
+                    nulls_first: e.nulls_first.unwrap_or(true),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let frame = over
+            .window_frame
+            .as_ref()
+            .map(Self::window_frame_to_plan)
+            .transpose()?;
+
+        Ok(Expression::WindowFunction {
+            op,
+            params: Self::function_params_to_values(e)?,
+            args,
+            partition_by,
+            order_by,
+            frame,
+        })
+    }
+
+    fn window_frame_to_plan(frame: &sqlparser::ast::WindowFrame) -> Result<WindowFrame> {
+        let units = match frame.units {
+            sqlparser::ast::WindowFrameUnits::Rows => WindowFrameUnits::Rows,
+            sqlparser::ast::WindowFrameUnits::Range => WindowFrameUnits::Range,
+            sqlparser::ast::WindowFrameUnits::Groups => {
+                return Result::Err(ErrorCode::SyntaxException(
+                    "GROUPS window frame is not supported",
+                ))
+            }
+        };
+
+        let bound = |bound: &sqlparser::ast::WindowFrameBound| match bound {
+            sqlparser::ast::WindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+            sqlparser::ast::WindowFrameBound::Preceding(None) => {
+                WindowFrameBound::UnboundedPreceding
+            }
+            sqlparser::ast::WindowFrameBound::Preceding(Some(n)) => WindowFrameBound::Preceding(*n),
+            sqlparser::ast::WindowFrameBound::Following(None) => {
+                WindowFrameBound::UnboundedFollowing
+            }
+            sqlparser::ast::WindowFrameBound::Following(Some(n)) => WindowFrameBound::Following(*n),
+        };
+
+        let start = bound(&frame.start_bound);
+        let end = frame
+            .end_bound
+            .as_ref()
+            .map(bound)
+            .unwrap_or(WindowFrameBound::CurrentRow);
+
+        let window_frame = WindowFrame { units, start, end };
+        match (start, end) {
+            (WindowFrameBound::UnboundedFollowing, _)
+            | (_, WindowFrameBound::UnboundedPreceding) => Result::Err(ErrorCode::SyntaxException(
+                format!("Invalid window frame: {}", window_frame),
+            )),
+            _ => Ok(window_frame),
+        }
+    }
+
     fn value_to_rex(value: &sqlparser::ast::Value) -> Result<Expression> {
         match value {
             sqlparser::ast::Value::Number(ref n, _) => {
@@ -961,6 +1223,10 @@ impl PlanParser {
                 }
 
                 let op = e.name.to_string();
+                if let Some(over) = &e.over {
+                    return self.window_function_to_rex(e, op, args, over, schema, select);
+                }
+
                 if AggregateFunctionFactory::check(&op) {
                     let args = match op.to_lowercase().as_str() {
                         "count" => args
@@ -973,22 +1239,7 @@ impl PlanParser {
                         _ => args,
                     };
 
-                    let params = e
-                        .params
-                        .iter()
-                        .map(|v| {
-                            let expr = Self::value_to_rex(v);
-                            if let Ok(Expression::Literal { value, .. }) = expr {
-                                Ok(value)
-                            } else {
-                                Result::Err(ErrorCode::SyntaxException(format!(
-                                    "Unsupported value expression: {:?}, must be datavalue",
-                                    expr
-                                )))
-                            }
-                        })
-                        .collect::<Result<Vec<_>>>()?;
-
+                    let params = Self::function_params_to_values(e)?;
                     return Ok(Expression::AggregateFunction {
                         op,
                         distinct: e.distinct,
@@ -1116,6 +1367,18 @@ impl PlanParser {
         }
     }
 
+    /// Apply the window functions to the plan
+    fn window(&self, input: &PlanNode, exprs: &[Expression]) -> Result<PlanNode> {
+        let window_exprs = exprs
+            .iter()
+            .map(|expr| rebase_expr_from_input(expr, &input.schema()))
+            .collect::<Result<Vec<_>>>()?;
+
+        PlanBuilder::from(input)
+            .window(&window_exprs)
+            .and_then(|builder| builder.build())
+    }
+
     /// Apply a having to the plan
     fn having(&self, plan: &PlanNode, expr: Option<Expression>) -> Result<PlanNode> {
         if let Some(expr) = expr {
@@ -99,6 +99,12 @@ fn test_plan_parser() -> Result<()> {
             expect: "",
             error: "",
         },
+        Test {
+            name: "analyze-table-passed",
+            sql: "ANALYZE TABLE db1.t1",
+            expect: "",
+            error: "",
+        },
         Test {
             name: "cast-passed",
             sql: "select cast('1' as int)",
@@ -175,6 +181,21 @@ fn test_plan_parser() -> Result<()> {
             \n                  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
             error: "",
         },
+        Test {
+            name: "window-passed",
+            sql: "select number, row_number() over (order by number desc) as rn from numbers(10)",
+            expect: "\
+            Projection: number:UInt64, row_number() OVER (ORDER BY number DESC) as rn:UInt64\
+            \n  Window: [[row_number() OVER (ORDER BY number DESC)]]\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "window-invalid-frame",
+            sql: "select sum(number) over (order by number rows between unbounded following and current row) from numbers(10)",
+            expect: "",
+            error: "Code: 5, displayText = Invalid window frame: ROWS BETWEEN UNBOUNDED FOLLOWING AND CURRENT ROW.",
+        },
 
         Test {
             name: "unimplemented-cte",
@@ -182,6 +203,18 @@ fn test_plan_parser() -> Result<()> {
             expect: "",
             error: "Code: 2, displayText = CTE is not yet implement.",
         },
+        Test {
+            name: "time-travel-unsupported-engine",
+            sql: "select * from system.one at (version => 1)",
+            expect: "",
+            error: "Code: 2, displayText = reading table one at version 1 is not supported by engine SystemOne.",
+        },
+        Test {
+            name: "time-travel-invalid-version",
+            sql: "select * from system.one at (version => 'v1')",
+            expect: "",
+            error: "Code: 6, displayText = Invalid time travel clause at_version('v1'), expected AT (VERSION => n) or AT (TIMESTAMP => ts).",
+        },
         Test {
             name: "kleene-logic-null",
             sql: "select * from numbers(10) where null",
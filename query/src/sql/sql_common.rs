@@ -35,13 +35,25 @@ impl SQLCommon {
             SQLDataType::Varchar(_) => Ok(DataType::Utf8),
             SQLDataType::String => Ok(DataType::Utf8),
             SQLDataType::Text => Ok(DataType::Utf8),
-            SQLDataType::Decimal(_, _) => Ok(DataType::Float64),
+            SQLDataType::Decimal(precision, scale) => {
+                // Same as MySQL, DECIMAL means DECIMAL(10, 0).
+                let precision = precision.unwrap_or(10) as usize;
+                let scale = scale.unwrap_or(0) as usize;
+                check_decimal_type(precision, scale)?;
+                Ok(DataType::Decimal(precision, scale))
+            }
             SQLDataType::Float(_) => Ok(DataType::Float32),
             SQLDataType::Real | SQLDataType::Double => Ok(DataType::Float64),
             SQLDataType::Boolean => Ok(DataType::Boolean),
             SQLDataType::Date => Ok(DataType::Date32),
             SQLDataType::Time => Ok(DataType::Timestamp(TimeUnit::Millisecond, None)),
             SQLDataType::Timestamp => Ok(DataType::Date64),
+            // Array(T) is parsed into T[] by DfParser.
+            SQLDataType::Array(item_type) => Ok(DataType::List(Box::new(DataField::new(
+                "item",
+                Self::make_data_type(item_type)?,
+                true,
+            )))),
 
             //custom types for datafuse
             // Custom(ObjectName([Ident { value: "uint8", quote_style: None }])
@@ -242,4 +254,54 @@ impl SQLCommon {
             Some(result),
         )))
     }
+
+    /// '2021-08-01 12:30:00' or '2021-08-01' in UTC
+    /// to milliseconds since 1970
+    pub fn make_timestamp_millis(value: &str) -> Result<u64> {
+        let invalid = || {
+            ErrorCode::SyntaxException(format!(
+                "Invalid timestamp {:?}, expected 'YYYY-MM-DD HH:MM:SS'",
+                value
+            ))
+        };
+        let parse =
+            |part: Option<&str>| part.and_then(|v| u64::from_str(v).ok()).ok_or_else(invalid);
+
+        let mut parts = value.trim().splitn(2, ' ');
+        let mut date = parts.next().unwrap_or_default().split('-');
+        let (year, month, day) = (
+            parse(date.next())?,
+            parse(date.next())?,
+            parse(date.next())?,
+        );
+        let (hour, minute, second) = match parts.next() {
+            None => (0, 0, 0),
+            Some(time) => {
+                let mut time = time.trim().split(':');
+                (
+                    parse(time.next())?,
+                    parse(time.next())?,
+                    parse(time.next())?,
+                )
+            }
+        };
+        if year < 1970 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(invalid());
+        }
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(invalid());
+        }
+
+        // Days since 1970-01-01 of the proleptic Gregorian calendar, the year starts in March.
+        let (y, m) = if month <= 2 {
+            (year - 1, month + 9)
+        } else {
+            (year, month - 3)
+        };
+        let era_year = y % 400;
+        let era_day = era_year * 365 + era_year / 4 - era_year / 100 + (153 * m + 2) / 5 + day - 1;
+        let days = (y / 400) * 146_097 + era_day - 719_468;
+
+        Ok(((days * 24 + hour) * 60 + minute) * 60_000 + second * 1000)
+    }
 }
@@ -21,7 +21,9 @@ use common_planners::ExplainType;
 use common_planners::TableEngineType;
 use sqlparser::ast::ColumnDef;
 use sqlparser::ast::ColumnOptionDef;
+use sqlparser::ast::DataType;
 use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
 use sqlparser::ast::SqlOption;
 use sqlparser::ast::TableConstraint;
 use sqlparser::ast::Value;
@@ -34,6 +36,7 @@ use sqlparser::tokenizer::Token;
 use sqlparser::tokenizer::Tokenizer;
 use sqlparser::tokenizer::Whitespace;
 
+use crate::sql::DfAnalyzeTable;
 use crate::sql::DfCreateDatabase;
 use crate::sql::DfCreateTable;
 use crate::sql::DfDescribeTable;
@@ -73,7 +76,9 @@ impl<'a> DfParser<'a> {
     /// Parse the specified tokens with dialect
     pub fn new_with_dialect(sql: &str, dialect: &'a dyn Dialect) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let tokens = tokenizer.tokenize()?;
+        let tokens = rewrite_cast_array_types(tokenizer.tokenize()?)?;
+        let tokens = rewrite_array_syntax(tokens)?;
+        let tokens = rewrite_time_travel_syntax(tokens)?;
 
         Ok(DfParser {
             parser: Parser::new(tokens, dialect),
@@ -197,6 +202,10 @@ impl<'a> DfParser<'a> {
                         self.parser.next_token();
                         self.parse_truncate()
                     }
+                    _ if w.value.to_uppercase() == "ANALYZE" => {
+                        self.parser.next_token();
+                        self.parse_analyze()
+                    }
                     Keyword::NoKeyword => match w.value.to_uppercase().as_str() {
                         // Use database
                         "USE" => self.parse_use_database(),
@@ -230,6 +239,10 @@ impl<'a> DfParser<'a> {
                     self.parser.next_token();
                     ExplainType::Graph
                 }
+                "ANALYZE" => {
+                    self.parser.next_token();
+                    ExplainType::Analyze
+                }
                 _ => ExplainType::Syntax,
             },
             _ => ExplainType::Syntax,
@@ -304,9 +317,25 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    /// Parse a data type, including the array data type `Array(T)` which sqlparser only knows as `T[]`.
+    fn parse_data_type(&mut self) -> Result<DataType, ParserError> {
+        if let Token::Word(w) = self.parser.peek_token() {
+            if w.value.eq_ignore_ascii_case("array") && w.quote_style.is_none() {
+                self.parser.next_token();
+                if self.parser.consume_token(&Token::LParen) {
+                    let item_type = self.parse_data_type()?;
+                    self.parser.expect_token(&Token::RParen)?;
+                    return Ok(DataType::Array(Box::new(item_type)));
+                }
+                self.parser.prev_token();
+            }
+        }
+        self.parser.parse_data_type()
+    }
+
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
         let name = self.parser.parse_identifier()?;
-        let data_type = self.parser.parse_data_type()?;
+        let data_type = self.parse_data_type()?;
         let collation = if self.parser.parse_keyword(Keyword::COLLATE) {
             Some(self.parser.parse_object_name()?)
         } else {
@@ -354,6 +383,7 @@ impl<'a> DfParser<'a> {
             self.parser
                 .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let db_name = self.parser.parse_object_name()?;
+        let clone_from = self.parse_clone_from()?;
         let engine = self.parse_database_engine()?;
 
         let create = DfCreateDatabase {
@@ -361,6 +391,7 @@ impl<'a> DfParser<'a> {
             name: db_name,
             engine,
             options: vec![],
+            clone_from,
         };
 
         Ok(DfStatement::CreateDatabase(create))
@@ -461,6 +492,20 @@ impl<'a> DfParser<'a> {
             self.parser
                 .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let table_name = self.parser.parse_object_name()?;
+
+        // The clone has the same columns and engine as the source table.
+        let clone_from = self.parse_clone_from()?;
+        if clone_from.is_some() {
+            return Ok(DfStatement::CreateTable(DfCreateTable {
+                if_not_exists,
+                name: table_name,
+                columns: vec![],
+                engine: TableEngineType::Null,
+                options: vec![],
+                clone_from,
+            }));
+        }
+
         let (columns, _) = self.parse_columns()?;
         let engine = self.parse_table_engine()?;
 
@@ -482,11 +527,21 @@ impl<'a> DfParser<'a> {
             columns,
             engine,
             options: table_properties,
+            clone_from: None,
         };
 
         Ok(DfStatement::CreateTable(create))
     }
 
+    /// Parses the optional `CLONE name` of a create statement.
+    fn parse_clone_from(&mut self) -> Result<Option<ObjectName>, ParserError> {
+        if self.consume_token("CLONE") {
+            Ok(Some(self.parser.parse_object_name()?))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Parses the set of valid formats
     fn parse_table_engine(&mut self) -> Result<TableEngineType, ParserError> {
         // TODO make ENGINE as a keyword
@@ -544,6 +599,20 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    fn parse_analyze(&mut self) -> Result<DfStatement, ParserError> {
+        match self.parser.next_token() {
+            Token::Word(w) => match w.keyword {
+                Keyword::TABLE => {
+                    let table_name = self.parser.parse_object_name()?;
+                    let analyze = DfAnalyzeTable { name: table_name };
+                    Ok(DfStatement::AnalyzeTable(analyze))
+                }
+                _ => self.expected("analyze statement", Token::Word(w)),
+            },
+            unexpected => self.expected("analyze statement", unexpected),
+        }
+    }
+
     fn consume_token(&mut self, expected: &str) -> bool {
         if self.parser.peek_token().to_string().to_uppercase() == *expected.to_uppercase() {
             self.parser.next_token();
@@ -553,3 +622,330 @@ impl<'a> DfParser<'a> {
         }
     }
 }
+
+/// Rewrite the array syntax which sqlparser does not understand into plain function calls:
+/// - array literal `[1, 2]` into `array(1, 2)`
+/// - subscript `arr[1]` into `arrayElement(arr, 1)`
+fn rewrite_array_syntax(tokens: Vec<Token>) -> Result<Vec<Token>, ParserError> {
+    let mut output: Vec<Token> = Vec::with_capacity(tokens.len());
+    // Both subscripts and array literals are closed by `)` after rewriting.
+    let mut open_brackets = 0;
+
+    let mut index = 0;
+    while index < tokens.len() {
+        match &tokens[index] {
+            Token::LBracket => {
+                let prev = last_non_whitespace(&output, output.len());
+                let empty_end = next_non_whitespace(&tokens, index + 1)
+                    .filter(|i| tokens[*i] == Token::RBracket);
+                match (prev, empty_end) {
+                    // `T[]`, the postgres style array data type, is kept as is.
+                    (Some(prev), Some(end))
+                        if matches!(output[prev], Token::Word(_))
+                            && is_operand_end(&output[prev]) =>
+                    {
+                        output.push(Token::LBracket);
+                        output.push(Token::RBracket);
+                        index = end;
+                    }
+                    (Some(prev), _) if is_operand_end(&output[prev]) => {
+                        let start = operand_start(&output, prev);
+                        output.insert(start, Token::LParen);
+                        output.insert(start, Token::make_word("arrayElement", None));
+                        output.push(Token::Comma);
+                        open_brackets += 1;
+                    }
+                    _ => {
+                        output.push(Token::make_word("array", None));
+                        output.push(Token::LParen);
+                        open_brackets += 1;
+                    }
+                }
+            }
+            Token::RBracket if open_brackets > 0 => {
+                open_brackets -= 1;
+                output.push(Token::RParen);
+            }
+            Token::RBracket => return parser_err!("Unexpected token: ]"),
+            token => output.push(token.clone()),
+        }
+        index += 1;
+    }
+
+    match open_brackets {
+        0 => Ok(output),
+        _ => parser_err!("Expected ], found: EOF"),
+    }
+}
+
+/// Rewrite the array data type `Array(T)` in the type of CAST into `T[]`, as sqlparser parses
+/// CAST by itself and only knows the latter. Nothing but the type after the AS of a CAST is
+/// rewritten, `array(x)` is still a function call anywhere else.
+fn rewrite_cast_array_types(tokens: Vec<Token>) -> Result<Vec<Token>, ParserError> {
+    let mut output: Vec<Token> = Vec::with_capacity(tokens.len());
+    // Whether each open parenthesis is the one of a CAST.
+    let mut parens: Vec<bool> = vec![];
+
+    let mut index = 0;
+    while index < tokens.len() {
+        match &tokens[index] {
+            Token::LParen => {
+                let is_cast = match last_non_whitespace(&tokens, index).map(|i| &tokens[i]) {
+                    Some(Token::Word(w)) => w.keyword == Keyword::CAST,
+                    _ => false,
+                };
+                parens.push(is_cast);
+            }
+            Token::RParen => {
+                parens.pop();
+            }
+            Token::Word(w) if w.keyword == Keyword::AS && parens.last() == Some(&true) => {
+                if let Some(type_start) = next_non_whitespace(&tokens, index + 1) {
+                    if let Some((type_tokens, next)) = parse_array_data_type(&tokens, type_start)? {
+                        output.extend_from_slice(&tokens[index..type_start]);
+                        output.extend(type_tokens);
+                        index = next;
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        output.push(tokens[index].clone());
+        index += 1;
+    }
+    Ok(output)
+}
+
+/// Rewrite the time travel clause `t AT (VERSION => n | TIMESTAMP => ts) [AS alias]`
+/// into the table hint `t [AS alias] WITH (at_version(n) | at_timestamp(ts))`,
+/// as sqlparser only understands table hints after the alias.
+fn rewrite_time_travel_syntax(tokens: Vec<Token>) -> Result<Vec<Token>, ParserError> {
+    let mut output: Vec<Token> = Vec::with_capacity(tokens.len());
+
+    let mut index = 0;
+    while index < tokens.len() {
+        match parse_time_travel_clause(&tokens, index)? {
+            Some((hint, next)) => {
+                let (alias, next) = parse_table_alias(&tokens, next);
+                output.extend(alias);
+                output.push(Token::make_keyword("WITH"));
+                output.push(Token::LParen);
+                output.extend(hint);
+                output.push(Token::RParen);
+                index = next;
+            }
+            None => {
+                output.push(tokens[index].clone());
+                index += 1;
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Parse `AT (VERSION => n)` or `AT (TIMESTAMP => ts)` at `index` following a table name,
+/// returns the tokens of the hint function call and the index after the closing parenthesis.
+fn parse_time_travel_clause(
+    tokens: &[Token],
+    index: usize,
+) -> Result<Option<(Vec<Token>, usize)>, ParserError> {
+    match &tokens[index] {
+        Token::Word(w) if w.value.eq_ignore_ascii_case("at") && w.quote_style.is_none() => {}
+        _ => return Ok(None),
+    }
+    match last_non_whitespace(tokens, index) {
+        Some(i) if matches!(tokens[i], Token::Word(_)) => {}
+        _ => return Ok(None),
+    }
+
+    let lparen = match next_non_whitespace(tokens, index + 1) {
+        Some(i) if tokens[i] == Token::LParen => i,
+        _ => return Ok(None),
+    };
+    let (kind, hint) = match next_non_whitespace(tokens, lparen + 1) {
+        Some(i) => match &tokens[i] {
+            Token::Word(w) if w.value.eq_ignore_ascii_case("version") => (i, "at_version"),
+            Token::Word(w) if w.value.eq_ignore_ascii_case("timestamp") => (i, "at_timestamp"),
+            _ => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+    let arrow = match next_non_whitespace(tokens, kind + 1) {
+        Some(i) if tokens[i] == Token::RArrow => i,
+        _ => return Ok(None),
+    };
+
+    let mut depth = 0;
+    let mut rparen = None;
+    for (i, token) in tokens.iter().enumerate().skip(arrow + 1) {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen if depth == 0 => {
+                rparen = Some(i);
+                break;
+            }
+            Token::RParen => depth -= 1,
+            _ => {}
+        }
+    }
+    let rparen = match rparen {
+        Some(i) => i,
+        None => return parser_err!("Expected ), found: EOF"),
+    };
+    if next_non_whitespace(tokens, arrow + 1) == Some(rparen) {
+        return parser_err!(format!("Expected the value of {}, found: )", tokens[kind]));
+    }
+
+    let mut hint_tokens = vec![Token::make_word(hint, None), Token::LParen];
+    hint_tokens.extend_from_slice(&tokens[arrow + 1..rparen]);
+    hint_tokens.push(Token::RParen);
+    Ok(Some((hint_tokens, rparen + 1)))
+}
+
+/// Parse the optional table alias `[AS] alias` from `index`,
+/// returns the tokens of the alias and the index after it.
+fn parse_table_alias(tokens: &[Token], index: usize) -> (Vec<Token>, usize) {
+    let alias_end = next_non_whitespace(tokens, index).and_then(|i| match &tokens[i] {
+        Token::Word(w) if w.keyword == Keyword::AS => next_non_whitespace(tokens, i + 1)
+            .filter(|name| matches!(tokens[*name], Token::Word(_))),
+        Token::Word(w) if w.quote_style.is_some() || w.keyword == Keyword::NoKeyword => Some(i),
+        _ => None,
+    });
+
+    match alias_end {
+        Some(end) => (tokens[index..=end].to_vec(), end + 1),
+        None => (vec![], index),
+    }
+}
+
+/// Parse `Array(T)` at `index` where T is a data type (maybe another array type),
+/// returns the tokens of `T[]` and the index after the closing parenthesis.
+fn parse_array_data_type(
+    tokens: &[Token],
+    index: usize,
+) -> Result<Option<(Vec<Token>, usize)>, ParserError> {
+    match &tokens[index] {
+        Token::Word(w) if w.value.eq_ignore_ascii_case("array") && w.quote_style.is_none() => {}
+        _ => return Ok(None),
+    }
+    let lparen = match next_non_whitespace(tokens, index + 1) {
+        Some(i) if tokens[i] == Token::LParen => i,
+        _ => return Ok(None),
+    };
+
+    let mut depth = 0;
+    let mut rparen = None;
+    for (i, token) in tokens.iter().enumerate().skip(lparen + 1) {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen if depth == 0 => {
+                rparen = Some(i);
+                break;
+            }
+            Token::RParen => depth -= 1,
+            _ => {}
+        }
+    }
+    let rparen = match rparen {
+        Some(i) => i,
+        None => return parser_err!("Expected ), found: EOF"),
+    };
+
+    let item_start = match next_non_whitespace(tokens, lparen + 1) {
+        Some(i) if i < rparen => i,
+        _ => return parser_err!("Expected the item type of Array, found: )"),
+    };
+    let mut type_tokens = match parse_array_data_type(&tokens[..rparen], item_start)? {
+        Some((nested, end)) if next_non_whitespace(tokens, end) == Some(rparen) => nested,
+        _ => tokens[item_start..rparen].to_vec(),
+    };
+    type_tokens.push(Token::LBracket);
+    type_tokens.push(Token::RBracket);
+    Ok(Some((type_tokens, rparen + 1)))
+}
+
+/// Whether the token can end an operand, then a following `[` is a subscript.
+fn is_operand_end(token: &Token) -> bool {
+    match token {
+        Token::RParen
+        | Token::Number(_, _)
+        | Token::SingleQuotedString(_)
+        | Token::NationalStringLiteral(_)
+        | Token::HexStringLiteral(_) => true,
+        Token::Word(w) => {
+            w.quote_style.is_some()
+                || !matches!(
+                    w.keyword,
+                    Keyword::SELECT
+                        | Keyword::WHERE
+                        | Keyword::AND
+                        | Keyword::OR
+                        | Keyword::NOT
+                        | Keyword::IN
+                        | Keyword::CASE
+                        | Keyword::WHEN
+                        | Keyword::THEN
+                        | Keyword::ELSE
+                        | Keyword::ON
+                        | Keyword::HAVING
+                        | Keyword::IS
+                        | Keyword::LIKE
+                        | Keyword::BETWEEN
+                        | Keyword::BY
+                        | Keyword::DISTINCT
+                        | Keyword::SET
+                        | Keyword::LIMIT
+                        | Keyword::OFFSET
+                        | Keyword::VALUES
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Find the start of the operand ending at `end`, such as `db.t.c`, `f(x)` or `(a)`.
+fn operand_start(tokens: &[Token], end: usize) -> usize {
+    let mut start = end;
+    if tokens[end] == Token::RParen {
+        let mut depth = 0;
+        for i in (0..=end).rev() {
+            match tokens[i] {
+                Token::RParen => depth += 1,
+                Token::LParen => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                start = i;
+                break;
+            }
+        }
+        // Function call, the name is a part of the operand.
+        match last_non_whitespace(tokens, start) {
+            Some(i) if matches!(tokens[i], Token::Word(_)) && is_operand_end(&tokens[i]) => i,
+            _ => start,
+        }
+    } else {
+        // Compound identifier.
+        while let Some(period) = last_non_whitespace(tokens, start) {
+            match (
+                tokens[period] == Token::Period,
+                last_non_whitespace(tokens, period),
+            ) {
+                (true, Some(i)) if matches!(tokens[i], Token::Word(_)) => start = i,
+                _ => break,
+            }
+        }
+        start
+    }
+}
+
+fn last_non_whitespace(tokens: &[Token], before: usize) -> Option<usize> {
+    (0..before)
+        .rev()
+        .find(|i| !matches!(tokens[*i], Token::Whitespace(_)))
+}
+
+fn next_non_whitespace(tokens: &[Token], from: usize) -> Option<usize> {
+    (from..tokens.len()).find(|i| !matches!(tokens[*i], Token::Whitespace(_)))
+}
@@ -77,6 +77,7 @@ mod tests {
                 name: ObjectName(vec![Ident::new("db1")]),
                 engine: DatabaseEngineType::Remote,
                 options: vec![],
+                clone_from: None,
             });
             expect_parse_ok(sql, expected)?;
         }
@@ -88,6 +89,7 @@ mod tests {
                 name: ObjectName(vec![Ident::new("db1")]),
                 engine: DatabaseEngineType::Remote,
                 options: vec![],
+                clone_from: None,
             });
             expect_parse_ok(sql, expected)?;
         }
@@ -99,6 +101,19 @@ mod tests {
                 name: ObjectName(vec![Ident::new("db1")]),
                 engine: DatabaseEngineType::Local,
                 options: vec![],
+                clone_from: None,
+            });
+            expect_parse_ok(sql, expected)?;
+        }
+
+        {
+            let sql = "CREATE DATABASE db2 CLONE db1";
+            let expected = DfStatement::CreateDatabase(DfCreateDatabase {
+                if_not_exists: false,
+                name: ObjectName(vec![Ident::new("db2")]),
+                engine: DatabaseEngineType::Remote,
+                options: vec![],
+                clone_from: Some(ObjectName(vec![Ident::new("db1")])),
             });
             expect_parse_ok(sql, expected)?;
         }
@@ -147,6 +162,7 @@ mod tests {
                 name: Ident::new("LOCATION".to_string()),
                 value: Value::SingleQuotedString("/data/33.csv".into()),
             }],
+            clone_from: None,
         });
         expect_parse_ok(sql, expected)?;
 
@@ -165,6 +181,18 @@ mod tests {
                 name: Ident::new("LOCATION".to_string()),
                 value: Value::SingleQuotedString("foo.parquet".into()),
             }],
+            clone_from: None,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "CREATE TABLE IF NOT EXISTS t2 CLONE db1.t1";
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: true,
+            name: ObjectName(vec![Ident::new("t2")]),
+            columns: vec![],
+            engine: TableEngineType::Null,
+            options: vec![],
+            clone_from: Some(ObjectName(vec![Ident::new("db1"), Ident::new("t1")])),
         });
         expect_parse_ok(sql, expected)?;
 
@@ -323,6 +351,147 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn analyze_table() -> Result<()> {
+        {
+            let sql = "ANALYZE TABLE t1";
+            let expected = DfStatement::AnalyzeTable(DfAnalyzeTable {
+                name: ObjectName(vec![Ident::new("t1")]),
+            });
+            expect_parse_ok(sql, expected)?;
+        }
+
+        {
+            let sql = "analyze table db1.t1";
+            let expected = DfStatement::AnalyzeTable(DfAnalyzeTable {
+                name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            });
+            expect_parse_ok(sql, expected)?;
+        }
+
+        expect_parse_error("ANALYZE t1", "Expected analyze statement")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn array_syntax_test() -> Result<()> {
+        let expect_same = |sql: &str, rewritten: &str| -> Result<()> {
+            let (statements, _) = DfParser::parse_sql(sql)?;
+            let (expected, _) = DfParser::parse_sql(rewritten)?;
+            assert_eq!(statements, expected, "{}", sql);
+            Ok(())
+        };
+
+        expect_same("SELECT [1, 2, 3]", "SELECT array(1, 2, 3)")?;
+        expect_same(
+            "SELECT [[1], [2, 3]][2][1]",
+            "SELECT arrayElement(arrayElement(array(array(1), array(2, 3)), 2), 1)",
+        )?;
+        expect_same(
+            "SELECT a[1], t.b[2], f(a)[3] FROM t WHERE a[1] IN [1, 2]",
+            "SELECT arrayElement(a, 1), arrayElement(t.b, 2), arrayElement(f(a), 3) FROM t WHERE arrayElement(a, 1) IN array(1, 2)",
+        )?;
+        expect_same(
+            "CREATE TABLE t(c1 Array(Int32), c2 Array(Array(Decimal(10, 2)))) ENGINE = Null",
+            "CREATE TABLE t(c1 Int32[], c2 Decimal(10, 2)[][]) ENGINE = Null",
+        )?;
+        expect_same(
+            "SELECT CAST(a AS Array(UInt8))",
+            "SELECT CAST(a AS UInt8[])",
+        )?;
+
+        expect_same(
+            "SELECT CAST(a AS Array(Array(Decimal(10, 2)))), CAST(array(b) AS Array(String))",
+            "SELECT CAST(a AS Decimal(10, 2)[][]), CAST(array(b) AS String[])",
+        )?;
+        expect_same(
+            "SELECT 'abc'[1], 1[2]",
+            "SELECT arrayElement('abc', 1), arrayElement(1, 2)",
+        )?;
+
+        expect_parse_error("SELECT [1, 2", "Expected ], found: EOF")?;
+        expect_parse_error("SELECT 1]", "Unexpected token: ]")?;
+        expect_parse_error(
+            "SELECT CAST(a AS Array())",
+            "Expected the item type of Array",
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn array_syntax_with_type_named_columns_test() -> Result<()> {
+        let expect_projection = |sql: &str, expected: Vec<&str>| -> Result<()> {
+            let (statements, _) = DfParser::parse_sql(sql)?;
+            let projection = match &statements[0] {
+                DfStatement::Statement(Statement::Query(query)) => match &query.body {
+                    SetExpr::Select(select) => select.projection.clone(),
+                    other => panic!("Expected a select, found: {}", other),
+                },
+                other => panic!("Expected a query, found: {:?}", other),
+            };
+            let projection: Vec<String> = projection.iter().map(|x| x.to_string()).collect();
+            assert_eq!(projection, expected, "{}", sql);
+            Ok(())
+        };
+
+        // Only the type of CAST is a data type, the columns named after types are kept.
+        expect_projection("SELECT array(date), array(int32, string) FROM t", vec![
+            "array(date)",
+            "array(int32, string)",
+        ])?;
+        expect_projection("SELECT CAST(array(date) AS Array(Date)) FROM t", vec![
+            "CAST(array(date) AS DATE[])",
+        ])?;
+        expect_projection("SELECT date[1], array(date)[1] FROM t", vec![
+            "arrayElement(date, 1)",
+            "arrayElement(array(date), 1)",
+        ])?;
+
+        expect_parse_ok(
+            "CREATE TABLE t(date Date, array Array(Date), string Array(String)) ENGINE = Null",
+            DfStatement::CreateTable(DfCreateTable {
+                if_not_exists: false,
+                name: ObjectName(vec![Ident::new("t")]),
+                columns: vec![
+                    make_column_def("date", DataType::Date),
+                    make_column_def("array", DataType::Array(Box::new(DataType::Date))),
+                    make_column_def("string", DataType::Array(Box::new(DataType::String))),
+                ],
+                engine: TableEngineType::Null,
+                options: vec![],
+                clone_from: None,
+            }),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn time_travel_syntax_test() -> Result<()> {
+        let expect_same = |sql: &str, rewritten: &str| -> Result<()> {
+            let (statements, _) = DfParser::parse_sql(sql)?;
+            let (expected, _) = DfParser::parse_sql(rewritten)?;
+            assert_eq!(statements, expected, "{}", sql);
+            Ok(())
+        };
+
+        expect_same(
+            "SELECT * FROM db.t AT (VERSION => 3)",
+            "SELECT * FROM db.t WITH (at_version(3))",
+        )?;
+        expect_same(
+            "SELECT * FROM t AT(TIMESTAMP => '2021-08-01 12:00:00') AS x WHERE x.a > 1",
+            "SELECT * FROM t AS x WITH (at_timestamp('2021-08-01 12:00:00')) WHERE x.a > 1",
+        )?;
+        expect_same(
+            "SELECT * FROM t1 AT (version => 1) x JOIN t2 AT (VERSION => 2) ON x.a = t2.a",
+            "SELECT * FROM t1 x WITH (at_version(1)) JOIN t2 WITH (at_version(2)) ON x.a = t2.a",
+        )?;
+
+        expect_parse_error("SELECT * FROM t AT (VERSION => 1", "Expected ), found: EOF")?;
+        Ok(())
+    }
+
     #[test]
     fn hint_test() -> Result<()> {
         {
@@ -64,6 +64,8 @@ pub struct DfCreateTable {
     pub columns: Vec<ColumnDef>,
     pub engine: TableEngineType,
     pub options: Vec<SqlOption>,
+    /// The source table of `CREATE TABLE t2 CLONE t1`.
+    pub clone_from: Option<ObjectName>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -82,12 +84,19 @@ pub struct DfTruncateTable {
     pub name: ObjectName,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfAnalyzeTable {
+    pub name: ObjectName,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateDatabase {
     pub if_not_exists: bool,
     pub name: ObjectName,
     pub engine: DatabaseEngineType,
     pub options: Vec<SqlOption>,
+    /// The source database of `CREATE DATABASE d2 CLONE d1`.
+    pub clone_from: Option<ObjectName>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -126,6 +135,7 @@ pub enum DfStatement {
     DescribeTable(DfDescribeTable),
     DropTable(DfDropTable),
     TruncateTable(DfTruncateTable),
+    AnalyzeTable(DfAnalyzeTable),
 
     // Settings.
     ShowSettings(DfShowSettings),
@@ -12,24 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_exception::Result;
 use warp::Filter;
 
 use crate::configs::Config;
+use crate::meta_service::MetaNode;
 
 pub struct Router {
     cfg: Config,
+    meta_node: Arc<MetaNode>,
 }
 
 impl Router {
-    pub fn create(cfg: Config) -> Self {
-        Router { cfg }
+    pub fn create(cfg: Config, meta_node: Arc<MetaNode>) -> Self {
+        Router { cfg, meta_node }
     }
 
     pub fn router(
         &self,
     ) -> Result<impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone> {
         let v1 = super::v1::config::config_handler(self.cfg.clone())
+            .or(super::v1::cluster::cluster_handler(
+                self.cfg.clone(),
+                self.meta_node.clone(),
+            ))
             .or(super::debug::home::debug_handler(self.cfg.clone()));
         let routes = v1.with(warp::log("v1"));
         Ok(routes)
@@ -0,0 +1,233 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use serde::de::DeserializeOwned;
+use sha2::Digest;
+use warp::reject::Reject;
+use warp::Filter;
+
+use crate::configs::Config;
+use crate::meta_service::MetaNode;
+
+/// The membership changes are reserved for the built-in user, like in the flight api.
+/// The user is given with the basic http authentication.
+pub fn cluster_handler(
+    conf: Config,
+    meta_node: Arc<MetaNode>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    cluster_membership(meta_node.clone())
+        .or(cluster_add_learner(conf.clone(), meta_node.clone()))
+        .or(cluster_change_membership(conf.clone(), meta_node.clone()))
+        .or(cluster_remove_node(conf, meta_node))
+        .recover(handlers::unauthorized)
+}
+
+/// GET /v1/cluster/membership
+fn cluster_membership(
+    meta_node: Arc<MetaNode>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "cluster" / "membership")
+        .and(warp::get())
+        .and(with_meta_node(meta_node))
+        .and_then(handlers::get_membership)
+}
+
+/// POST /v1/cluster/add_learner
+fn cluster_add_learner(
+    conf: Config,
+    meta_node: Arc<MetaNode>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "cluster" / "add_learner")
+        .and(warp::post())
+        .and(with_built_in_user(conf))
+        .and(json_body())
+        .and(with_meta_node(meta_node))
+        .and_then(handlers::add_learner)
+}
+
+/// POST /v1/cluster/change_membership
+fn cluster_change_membership(
+    conf: Config,
+    meta_node: Arc<MetaNode>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "cluster" / "change_membership")
+        .and(warp::post())
+        .and(with_built_in_user(conf))
+        .and(json_body())
+        .and(with_meta_node(meta_node))
+        .and_then(handlers::change_membership)
+}
+
+/// POST /v1/cluster/remove_node
+fn cluster_remove_node(
+    conf: Config,
+    meta_node: Arc<MetaNode>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "cluster" / "remove_node")
+        .and(warp::post())
+        .and(with_built_in_user(conf))
+        .and(json_body())
+        .and(with_meta_node(meta_node))
+        .and_then(handlers::remove_node)
+}
+
+fn with_built_in_user(conf: Config) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    let username = conf.flight_api_username.clone();
+    let password_sha256: [u8; 32] =
+        sha2::Sha256::digest(conf.flight_api_password.as_ref().as_bytes()).into();
+
+    warp::header::optional::<String>("authorization")
+        .and_then(move |authorization: Option<String>| {
+            let is_built_in_user = match authorization.as_deref().and_then(basic_credentials) {
+                None => false,
+                Some((user, password)) => {
+                    let sha256: [u8; 32] = sha2::Sha256::digest(password.as_bytes()).into();
+                    user == username && sha256 == password_sha256
+                }
+            };
+
+            async move {
+                match is_built_in_user {
+                    true => Ok(()),
+                    false => Err(warp::reject::custom(Unauthorized)),
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// The user and password of a `Basic <base64(user:password)>` authorization header.
+fn basic_credentials(authorization: &str) -> Option<(String, String)> {
+    let encoded = authorization.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(base64::decode(encoded.trim()).ok()?).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+fn with_meta_node(
+    meta_node: Arc<MetaNode>,
+) -> impl Filter<Extract = (Arc<MetaNode>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || meta_node.clone())
+}
+
+fn json_body<T: DeserializeOwned + Send>(
+) -> impl Filter<Extract = (T,), Error = warp::Rejection> + Clone {
+    warp::body::content_length_limit(1024 * 16).and(warp::body::json())
+}
+
+mod handlers {
+    use std::sync::Arc;
+
+    use common_flights::cluster_api_impl::AddLearnerReq;
+    use common_flights::cluster_api_impl::ChangeMembershipReq;
+    use common_flights::cluster_api_impl::RemoveNodeReq;
+    use log::info;
+    use warp::http::StatusCode;
+
+    use crate::api::http::v1::cluster::NoBacktraceErrorCode;
+    use crate::api::http::v1::cluster::Unauthorized;
+    use crate::meta_service::MembershipChange;
+    use crate::meta_service::MetaNode;
+
+    pub async fn get_membership(
+        meta_node: Arc<MetaNode>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        match meta_node.get_membership().await {
+            Ok(membership) => Ok(warp::reply::json(&membership)),
+            Err(error_code) => Err(warp::reject::custom(NoBacktraceErrorCode(error_code))),
+        }
+    }
+
+    pub async fn add_learner(
+        req: AddLearnerReq,
+        meta_node: Arc<MetaNode>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        info!("Cluster add learner: {:?}", req);
+        change(meta_node, MembershipChange::AddLearner {
+            node_id: req.node_id,
+            address: req.address,
+        })
+        .await
+    }
+
+    pub async fn change_membership(
+        req: ChangeMembershipReq,
+        meta_node: Arc<MetaNode>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        info!("Cluster change membership: {:?}", req);
+        change(meta_node, MembershipChange::ChangeMembership {
+            voters: req.voters,
+        })
+        .await
+    }
+
+    pub async fn remove_node(
+        req: RemoveNodeReq,
+        meta_node: Arc<MetaNode>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        info!("Cluster remove node: {:?}", req);
+        change(meta_node, MembershipChange::RemoveNode {
+            node_id: req.node_id,
+        })
+        .await
+    }
+
+    /// Asks for the credentials of the built-in user, the other rejections are passed on.
+    pub async fn unauthorized(
+        rejection: warp::Rejection,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        match rejection.find::<Unauthorized>() {
+            None => Err(rejection),
+            Some(_) => Ok(warp::reply::with_header(
+                warp::reply::with_status(
+                    "Only the built-in user can change the cluster membership",
+                    StatusCode::UNAUTHORIZED,
+                ),
+                "www-authenticate",
+                "Basic realm=\"datafuse-store\"",
+            )),
+        }
+    }
+
+    async fn change(
+        meta_node: Arc<MetaNode>,
+        change: MembershipChange,
+    ) -> Result<warp::reply::Json, warp::Rejection> {
+        match meta_node.change_membership(change).await {
+            Ok(membership) => Ok(warp::reply::json(&membership)),
+            Err(error_code) => Err(warp::reject::custom(NoBacktraceErrorCode(error_code))),
+        }
+    }
+}
+
+struct NoBacktraceErrorCode(ErrorCode);
+
+impl Debug for NoBacktraceErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Reject for NoBacktraceErrorCode {}
+
+#[derive(Debug)]
+struct Unauthorized;
+
+impl Reject for Unauthorized {}
@@ -0,0 +1,112 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use common_flights::cluster_api_impl::ChangeMembershipReq;
+use common_flights::cluster_api_impl::ClusterMembership;
+use common_flights::cluster_api_impl::RemoveNodeReq;
+use common_runtime::tokio;
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::api::http::v1::cluster::cluster_handler;
+use crate::meta_service::MetaNode;
+use crate::tests::service::new_test_context;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cluster() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_test_context();
+    let mn = MetaNode::boot(0, &tc.config).await?;
+    let filter = cluster_handler(tc.config.clone(), mn.clone());
+    let built_in_user = format!(
+        "Basic {}",
+        base64::encode(format!(
+            "{}:{}",
+            tc.config.flight_api_username,
+            tc.config.flight_api_password.as_ref()
+        ))
+    );
+
+    // Membership.
+    {
+        let res = warp::test::request()
+            .path("/v1/cluster/membership")
+            .reply(&filter)
+            .await;
+        assert_eq!(200, res.status());
+
+        let got: ClusterMembership = serde_json::from_slice(res.body())?;
+        assert_eq!(btreeset![0], got.voters);
+        assert_eq!(
+            Some(&tc.config.meta_api_addr()),
+            got.nodes.get(&0),
+            "node-0 address"
+        );
+    }
+
+    // The membership changes are reserved for the built-in user.
+    {
+        let res = warp::test::request()
+            .method("POST")
+            .path("/v1/cluster/remove_node")
+            .json(&RemoveNodeReq { node_id: 0 })
+            .reply(&filter)
+            .await;
+        assert_eq!(401, res.status());
+
+        let res = warp::test::request()
+            .method("POST")
+            .path("/v1/cluster/remove_node")
+            .header(
+                "authorization",
+                format!("Basic {}", base64::encode("root:wrong")),
+            )
+            .json(&RemoveNodeReq { node_id: 0 })
+            .reply(&filter)
+            .await;
+        assert_eq!(401, res.status());
+    }
+
+    // A voter must be added as a learner first.
+    {
+        let res = warp::test::request()
+            .method("POST")
+            .path("/v1/cluster/change_membership")
+            .header("authorization", &built_in_user)
+            .json(&ChangeMembershipReq {
+                voters: btreeset![0, 1],
+            })
+            .reply(&filter)
+            .await;
+        assert_eq!(500, res.status());
+    }
+
+    // The last voter can not be removed.
+    {
+        let res = warp::test::request()
+            .method("POST")
+            .path("/v1/cluster/remove_node")
+            .header("authorization", &built_in_user)
+            .json(&RemoveNodeReq { node_id: 0 })
+            .reply(&filter)
+            .await;
+        assert_eq!(500, res.status());
+    }
+
+    mn.stop().await?;
+    Ok(())
+}
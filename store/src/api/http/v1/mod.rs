@@ -12,4 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod cluster;
+#[cfg(test)]
+mod cluster_test;
 pub mod config;
@@ -12,22 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_exception::Result;
 
 use crate::api::http::router::Router;
 use crate::configs::Config;
+use crate::meta_service::MetaNode;
 
 pub struct HttpService {
     cfg: Config,
+    meta_node: Arc<MetaNode>,
 }
 
 impl HttpService {
-    pub fn create(cfg: Config) -> Self {
-        HttpService { cfg }
+    pub fn create(cfg: Config, meta_node: Arc<MetaNode>) -> Self {
+        HttpService { cfg, meta_node }
     }
 
     pub async fn start(&mut self) -> Result<()> {
-        let router = Router::create(self.cfg.clone());
+        let router = Router::create(self.cfg.clone(), self.meta_node.clone());
         let server = warp::serve(router.router()?);
 
         let conf = self.cfg.clone();
@@ -0,0 +1,111 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_flights::FlightClaim;
+use common_flights::FlightToken;
+use common_management::UserInfo;
+use common_management::USER_API_KEY_PREFIX;
+use common_metatypes::SeqValue;
+use sha2::Digest;
+
+use crate::configs::Config;
+use crate::meta_service::MetaNode;
+
+/// Authenticates the flight clients and verifies their tokens.
+///
+/// A client is authenticated either as the built-in user in the store config,
+/// or as a user stored in meta, i.e., the ones managed by `UserMgr`.
+/// The token of a meta user is bound to the seq of its record,
+/// thus updating the password or dropping the user revokes the tokens issued before.
+pub struct FlightAuth {
+    username: String,
+    password_sha256: [u8; 32],
+    token: FlightToken,
+    meta_node: Arc<MetaNode>,
+}
+
+impl FlightAuth {
+    pub fn create(conf: &Config, meta_node: Arc<MetaNode>) -> Self {
+        FlightAuth {
+            username: conf.flight_api_username.clone(),
+            password_sha256: sha2::Sha256::digest(conf.flight_api_password.as_ref().as_bytes())
+                .into(),
+            token: FlightToken::create(),
+            meta_node,
+        }
+    }
+
+    /// Checks the password and returns a token for the user.
+    pub async fn authenticate(&self, username: &str, password: &str) -> Result<String> {
+        let password_sha256: [u8; 32] = sha2::Sha256::digest(password.as_bytes()).into();
+
+        let user_seq = if username == self.username {
+            if password_sha256 != self.password_sha256 {
+                return Err(Self::failure(username));
+            }
+            None
+        } else {
+            match self.get_user(username).await? {
+                Some((seq, user)) if user.password_sha256 == password_sha256 => Some(seq),
+                _ => return Err(Self::failure(username)),
+            }
+        };
+
+        self.token.try_create_token(FlightClaim {
+            username: username.to_string(),
+            user_seq,
+        })
+    }
+
+    /// Verifies the token and returns the claim in it.
+    /// A token of a meta user is rejected if the user record has been changed since it is issued.
+    pub async fn verify(&self, token: String) -> Result<FlightClaim> {
+        let claim = self.token.try_verify_token(token)?;
+
+        if let Some(user_seq) = claim.user_seq {
+            match self.get_user(&claim.username).await? {
+                Some((seq, _)) if seq == user_seq => {}
+                _ => {
+                    return Err(ErrorCode::AuthenticateFailure(format!(
+                        "The token of user {} is expired, please authenticate again",
+                        claim.username
+                    )))
+                }
+            }
+        }
+
+        Ok(claim)
+    }
+
+    async fn get_user(&self, username: &str) -> Result<Option<SeqValue<UserInfo>>> {
+        let key = format!("{}{}", USER_API_KEY_PREFIX, username);
+        match self.meta_node.get_kv(&key).await? {
+            None => Ok(None),
+            Some((seq, kv_value)) => Ok(Some((seq, UserInfo::try_from(kv_value.value)?))),
+        }
+    }
+
+    /// The same error for an unknown user and a wrong password, not to reveal which users exist.
+    fn failure(username: &str) -> ErrorCode {
+        ErrorCode::AuthenticateFailure(format!(
+            "Authenticate failure for user {}: unknown user or wrong password",
+            username
+        ))
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_flights::KVApi;
+use common_flights::StoreClient;
+use common_management::UserMgr;
+use common_management::UserMgrApi;
+use common_metatypes::MatchSeq;
+use common_runtime::tokio;
+use common_tracing::tracing;
+use pretty_assertions::assert_eq;
+
+use crate::tests::service::new_test_context;
+use crate::tests::start_store_server_with_context;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_auth_builtin_user() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let mut tc = new_test_context();
+    tc.config.flight_api_username = "admin".to_string();
+    tc.config.flight_api_password = "secret".parse()?;
+    start_store_server_with_context(&mut tc).await?;
+    let addr = tc.config.flight_api_address.clone();
+
+    tracing::info!("--- wrong password or unknown user");
+    {
+        let auth_failure = ErrorCode::AuthenticateFailure("").code();
+
+        let r = StoreClient::try_create(addr.as_str(), "admin", "root").await;
+        assert_eq!(Some(auth_failure), r.err().map(|e| e.code()));
+
+        let r = StoreClient::try_create(addr.as_str(), "root", "root").await;
+        assert_eq!(Some(auth_failure), r.err().map(|e| e.code()));
+    }
+
+    tracing::info!("--- the built-in user has all the permissions");
+    {
+        let mut client = StoreClient::try_create(addr.as_str(), "admin", "secret").await?;
+        let mut user_mgr = UserMgr::new(client.clone());
+        user_mgr.add_user("u1", "p1", "s1").await?;
+
+        let res = client.get_kv("__fd_users/u1").await?;
+        assert!(res.result.is_some());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_auth_meta_user() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let mut tc = new_test_context();
+    start_store_server_with_context(&mut tc).await?;
+    let addr = tc.config.flight_api_address.clone();
+
+    let admin = StoreClient::try_create(addr.as_str(), "root", "root").await?;
+    let mut user_mgr = UserMgr::new(admin);
+    user_mgr.add_user("u1", "p1", "s1").await?;
+
+    tracing::info!("--- a meta user can not access the user records");
+    let mut client = StoreClient::try_create(addr.as_str(), "u1", "p1").await?;
+    {
+        let res = client
+            .upsert_kv("k1", MatchSeq::Any, Some(b"v1".to_vec()), None)
+            .await?;
+        assert!(res.result.is_some());
+
+        let permission_denied = ErrorCode::PermissionDenied("").code();
+
+        let r = client.get_kv("__fd_users/u1").await;
+        assert_eq!(permission_denied, r.unwrap_err().code());
+
+        let r = client
+            .upsert_kv("__fd_users/root", MatchSeq::Any, Some(vec![]), None)
+            .await;
+        assert_eq!(permission_denied, r.unwrap_err().code());
+
+        let r = client.prefix_list_kv("__fd").await;
+        assert_eq!(permission_denied, r.unwrap_err().code());
+    }
+
+    tracing::info!("--- rotating the password revokes the tokens issued before");
+    {
+        user_mgr.update_user("u1", Some("p2"), None, None).await?;
+
+        let auth_failure = ErrorCode::AuthenticateFailure("").code();
+
+        let r = client.get_kv("k1").await;
+        assert_eq!(auth_failure, r.unwrap_err().code());
+
+        let r = StoreClient::try_create(addr.as_str(), "u1", "p1").await;
+        assert_eq!(Some(auth_failure), r.err().map(|e| e.code()));
+
+        let mut client = StoreClient::try_create(addr.as_str(), "u1", "p2").await?;
+        let res = client.get_kv("k1").await?;
+        assert_eq!(b"v1".to_vec(), res.result.unwrap().1.value);
+    }
+
+    tracing::info!("--- dropping the user revokes the tokens");
+    {
+        let mut client = StoreClient::try_create(addr.as_str(), "u1", "p2").await?;
+        user_mgr.drop_user("u1", None).await?;
+
+        let r = client.get_kv("k1").await;
+        assert_eq!(
+            ErrorCode::AuthenticateFailure("").code(),
+            r.unwrap_err().code()
+        );
+    }
+
+    Ok(())
+}
@@ -32,7 +32,6 @@ use common_arrow::arrow_flight::PutResult;
 use common_arrow::arrow_flight::SchemaResult;
 use common_arrow::arrow_flight::Ticket;
 use common_flights::FlightClaim;
-use common_flights::FlightToken;
 use common_flights::StoreDoAction;
 use common_flights::StoreDoGet;
 use common_runtime::tokio;
@@ -51,6 +50,7 @@ use tonic::Response;
 use tonic::Status;
 use tonic::Streaming;
 
+use crate::api::rpc::flight_auth::FlightAuth;
 use crate::configs::Config;
 use crate::executor::ActionHandler;
 use crate::executor::ReplySerializer;
@@ -62,30 +62,27 @@ pub type FlightStream<T> =
 
 /// StoreFlightImpl provides data access API-s for DatafuseQuery, in arrow-flight protocol.
 pub struct StoreFlightImpl {
-    token: FlightToken,
+    auth: FlightAuth,
     action_handler: ActionHandler,
 }
 
 impl StoreFlightImpl {
-    pub fn create(_conf: Config, fs: Arc<dyn FileSystem>, meta_node: Arc<MetaNode>) -> Self {
+    pub fn create(conf: Config, fs: Arc<dyn FileSystem>, meta_node: Arc<MetaNode>) -> Self {
         Self {
-            token: FlightToken::create(),
+            auth: FlightAuth::create(&conf, meta_node.clone()),
             // TODO pass in action handler
             action_handler: ActionHandler::create(fs, meta_node),
         }
     }
 
-    fn check_token(&self, metadata: &MetadataMap) -> Result<FlightClaim, Status> {
+    async fn check_token(&self, metadata: &MetadataMap) -> Result<FlightClaim, Status> {
         let token = metadata
             .get_bin("auth-token-bin")
             .and_then(|v| v.to_bytes().ok())
             .and_then(|b| String::from_utf8(b.to_vec()).ok())
             .ok_or_else(|| Status::internal("Error auth-token-bin is empty"))?;
 
-        let claim = self
-            .token
-            .try_verify_token(token)
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let claim = self.auth.verify(token).await?;
         Ok(claim)
     }
 }
@@ -107,28 +104,17 @@ impl FlightService for StoreFlightImpl {
         let auth = BasicAuth::decode(&*payload).map_err(|e| Status::internal(e.to_string()))?;
 
         // Check auth and create token.
-        let user = "root";
-        if auth.username == user {
-            let claim = FlightClaim {
-                username: user.to_string(),
-            };
-            let token = self
-                .token
-                .try_create_token(claim)
-                .map_err(|e| Status::internal(e.to_string()))?;
-
-            let resp = HandshakeResponse {
-                payload: token.into_bytes(),
-                ..HandshakeResponse::default()
-            };
-            let output = futures::stream::once(async { Ok(resp) });
-            Ok(Response::new(Box::pin(output)))
-        } else {
-            Err(Status::unauthenticated(format!(
-                "Don't know user {}",
-                auth.username
-            )))
-        }
+        let token = self
+            .auth
+            .authenticate(&auth.username, &auth.password)
+            .await?;
+
+        let resp = HandshakeResponse {
+            payload: token.into_bytes(),
+            ..HandshakeResponse::default()
+        };
+        let output = futures::stream::once(async { Ok(resp) });
+        Ok(Response::new(Box::pin(output)))
     }
 
     type ListFlightsStream = FlightStream<FlightInfo>;
@@ -160,7 +146,7 @@ impl FlightService for StoreFlightImpl {
         request: Request<Ticket>,
     ) -> Result<Response<Self::DoGetStream>, Status> {
         // Check token.
-        let _claim = self.check_token(request.metadata())?;
+        let _claim = self.check_token(request.metadata()).await?;
 
         // Action.
         let action: StoreDoGet = request.try_into()?;
@@ -194,17 +180,17 @@ impl FlightService for StoreFlightImpl {
         &self,
         request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoPutStream>, Status> {
-        let _claim = self.check_token(request.metadata())?;
+        let _claim = self.check_token(request.metadata()).await?;
         let meta = request.metadata();
 
         let (db_name, tbl_name) = common_flights::storage_api_impl::get_meta(meta)
             .map_err(|e| Status::internal(e.to_string()))?;
+        let query_id = common_flights::storage_api_impl::get_query_id(meta)?;
 
         let append_res = self
             .action_handler
-            .do_put(db_name, tbl_name, request.into_inner())
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .do_put(query_id, db_name, tbl_name, request.into_inner())
+            .await?;
 
         let bytes = serde_json::to_vec(&append_res).map_err(|e| Status::internal(e.to_string()))?;
         let put_res = PutResult {
@@ -232,7 +218,7 @@ impl FlightService for StoreFlightImpl {
         request: Request<Action>,
     ) -> Result<Response<Self::DoActionStream>, Status> {
         // Check token.
-        let _claim = self.check_token(request.metadata())?;
+        let claim = self.check_token(request.metadata()).await?;
 
         common_tracing::extract_remote_span_as_parent(&request);
 
@@ -240,7 +226,7 @@ impl FlightService for StoreFlightImpl {
         info!("Receive do_action: {:?}", action);
 
         let s = JsonSer;
-        let body = self.action_handler.execute(action, s).await?;
+        let body = self.action_handler.execute(&claim, action, s).await?;
         let arrow = arrow_flight::Result { body };
         let output = futures::stream::once(async { Ok(arrow) });
         Ok(Response::new(Box::pin(output)))
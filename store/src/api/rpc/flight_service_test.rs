@@ -17,8 +17,10 @@ use std::time::UNIX_EPOCH;
 
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_flights::meta_api_impl::DropTableActionResult;
 use common_flights::meta_api_impl::GetTableActionResult;
+use common_flights::session_api_impl::SessionApi;
 use common_flights::KVApi;
 use common_flights::MetaApi;
 use common_flights::StorageApi;
@@ -50,7 +52,7 @@ async fn test_flight_restart() -> anyhow::Result<()> {
 
     let (mut tc, addr) = crate::tests::start_store_server().await?;
 
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     let db_name = "db1";
     let table_name = "table1";
@@ -62,6 +64,7 @@ async fn test_flight_restart() -> anyhow::Result<()> {
             db: db_name.to_string(),
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
 
         let res = client.create_database(plan.clone()).await;
@@ -93,6 +96,7 @@ async fn test_flight_restart() -> anyhow::Result<()> {
             schema: schema.clone(),
             options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
             engine: TableEngineType::JSONEachRow,
+            clone_from: None,
         };
 
         {
@@ -131,7 +135,7 @@ async fn test_flight_restart() -> anyhow::Result<()> {
     tokio::time::sleep(tokio::time::Duration::from_millis(10_000)).await;
 
     // try to reconnect the restarted server.
-    let mut _client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut _client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     // TODO(xp): db and table are still in pure memory store. the following test will no pass.
 
@@ -170,7 +174,7 @@ async fn test_flight_create_database() -> anyhow::Result<()> {
     // 1. Service starts.
     let (_tc, addr) = crate::tests::start_store_server().await?;
 
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     // 2. Create database.
 
@@ -183,6 +187,7 @@ async fn test_flight_create_database() -> anyhow::Result<()> {
             db: "db1".to_string(),
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
 
         let res = client.create_database(plan.clone()).await;
@@ -197,6 +202,7 @@ async fn test_flight_create_database() -> anyhow::Result<()> {
             db: "db2".to_string(),
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
 
         let res = client.create_database(plan.clone()).await;
@@ -248,7 +254,7 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
     // 1. Service starts.
     let (_tc, addr) = crate::tests::start_store_server().await?;
 
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     let db_name = "db1";
     let tbl_name = "tb2";
@@ -260,6 +266,7 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
             db: db_name.to_string(),
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
 
         let res = client.create_database(plan.clone()).await;
@@ -289,6 +296,7 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
             options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
             // TODO
             engine: TableEngineType::JSONEachRow,
+            clone_from: None,
         };
 
         {
@@ -376,7 +384,7 @@ async fn test_flight_drop_table() -> anyhow::Result<()> {
     // 1. Service starts.
     let (_tc, addr) = crate::tests::start_store_server().await?;
 
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     let db_name = "db1";
     let tbl_name = "tb2";
@@ -388,6 +396,7 @@ async fn test_flight_drop_table() -> anyhow::Result<()> {
             db: db_name.to_string(),
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
 
         let res = client.create_database(plan.clone()).await;
@@ -417,6 +426,7 @@ async fn test_flight_drop_table() -> anyhow::Result<()> {
             options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
             // TODO
             engine: TableEngineType::JSONEachRow,
+            clone_from: None,
         };
 
         {
@@ -497,13 +507,14 @@ async fn test_do_append() -> anyhow::Result<()> {
     let num_batch = batches.len();
     let stream = futures::stream::iter(batches);
 
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
     {
         let plan = CreateDatabasePlan {
             if_not_exists: false,
             db: db_name.to_string(),
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
         let res = client.create_database(plan.clone()).await;
         let res = res.unwrap();
@@ -515,14 +526,16 @@ async fn test_do_append() -> anyhow::Result<()> {
             schema: schema.clone(),
             options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
             engine: TableEngineType::Parquet,
+            clone_from: None,
         };
         client.create_table(plan.clone()).await.unwrap();
     }
     let res = client
         .append_data(
+            "".to_string(),
             db_name.to_string(),
             tbl_name.to_string(),
-            schema,
+            schema.clone(),
             Box::pin(stream),
         )
         .await
@@ -535,6 +548,88 @@ async fn test_do_append() -> anyhow::Result<()> {
         assert_eq!(p.rows, expected_rows / num_batch);
         assert_eq!(p.cols, expected_cols);
     });
+
+    tracing::info!("--- the data of a mismatched schema is rejected");
+    {
+        let other_schema =
+            DataSchemaRefExt::create(vec![DataField::new("col_i", DataType::Int64, false)]);
+        let block = DataBlock::create_by_array(other_schema.clone(), vec![Series::new(vec![0i64])]);
+        let res = client
+            .append_data(
+                "".to_string(),
+                db_name.to_string(),
+                tbl_name.to_string(),
+                other_schema,
+                Box::pin(futures::stream::iter(vec![block])),
+            )
+            .await;
+        assert_eq!(ErrorCode::IllegalSchema("").code(), res.unwrap_err().code());
+
+        let parts = client
+            .read_plan(db_name.to_string(), tbl_name.to_string(), &ScanPlan {
+                schema_name: tbl_name.to_string(),
+                ..ScanPlan::empty()
+            })
+            .await?;
+        assert_eq!(num_batch, parts.unwrap().len(), "no part is published");
+    }
+
+    tracing::info!("--- append to an unknown table");
+    {
+        let res = client
+            .append_data(
+                "".to_string(),
+                db_name.to_string(),
+                "not_exist".to_string(),
+                schema.clone(),
+                Box::pin(futures::stream::iter(Vec::<DataBlock>::new())),
+            )
+            .await;
+        assert_eq!(ErrorCode::UnknownTable("").code(), res.unwrap_err().code());
+    }
+
+    tracing::info!("--- killing the query aborts the append in progress");
+    {
+        use futures::StreamExt;
+
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![0i64]),
+            Series::new(vec!["str1"]),
+        ]);
+        // The input never ends until the query is killed.
+        let stream = futures::stream::iter(vec![block]).chain(futures::stream::pending());
+
+        let mut append_client = client.clone();
+        let mut append = tokio::spawn(async move {
+            append_client
+                .append_data(
+                    "query-1".to_string(),
+                    db_name.to_string(),
+                    tbl_name.to_string(),
+                    schema,
+                    Box::pin(stream),
+                )
+                .await
+        });
+
+        // The append may not be registered in the store yet, kill it until it is aborted.
+        let res = loop {
+            client.kill_query("query-1".to_string()).await?;
+            tokio::select! {
+                res = &mut append => break res?,
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {}
+            }
+        };
+        assert_eq!(ErrorCode::AbortedQuery("").code(), res.unwrap_err().code());
+
+        let parts = client
+            .read_plan(db_name.to_string(), tbl_name.to_string(), &ScanPlan {
+                schema_name: tbl_name.to_string(),
+                ..ScanPlan::empty()
+            })
+            .await?;
+        assert_eq!(num_batch, parts.unwrap().len(), "no part is published");
+    }
     Ok(())
 }
 
@@ -576,13 +671,14 @@ async fn test_scan_partition() -> anyhow::Result<()> {
     let num_batch = batches.len();
     let stream = futures::stream::iter(batches);
 
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
     {
         let plan = CreateDatabasePlan {
             if_not_exists: false,
             db: db_name.to_string(),
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
         client.create_database(plan.clone()).await?;
         let plan = CreateTablePlan {
@@ -592,11 +688,13 @@ async fn test_scan_partition() -> anyhow::Result<()> {
             schema: schema.clone(),
             options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
             engine: TableEngineType::Parquet,
+            clone_from: None,
         };
         client.create_table(plan.clone()).await?;
     }
     let res = client
         .append_data(
+            "".to_string(),
             db_name.to_string(),
             tbl_name.to_string(),
             schema,
@@ -643,7 +741,7 @@ async fn test_flight_generic_kv_mget() -> anyhow::Result<()> {
 
         let (_tc, addr) = crate::tests::start_store_server().await?;
 
-        let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+        let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
         client
             .upsert_kv("k1", MatchSeq::Any, Some(b"v1".to_vec()), None)
@@ -691,7 +789,7 @@ async fn test_flight_generic_kv_list() -> anyhow::Result<()> {
 
         let (_tc, addr) = crate::tests::start_store_server().await?;
 
-        let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+        let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
         let mut values = vec![];
         {
@@ -739,7 +837,7 @@ async fn test_flight_generic_kv_delete() -> anyhow::Result<()> {
 
         let (_tc, addr) = crate::tests::start_store_server().await?;
 
-        let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+        let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
         let test_key = "test_key";
         client
@@ -807,7 +905,7 @@ async fn test_flight_generic_kv_update() -> anyhow::Result<()> {
 
         let (_tc, addr) = crate::tests::start_store_server().await?;
 
-        let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+        let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
         let test_key = "test_key_for_update";
 
@@ -916,7 +1014,7 @@ async fn test_flight_generic_kv_timeout() -> anyhow::Result<()> {
 
         let (_tc, addr) = crate::tests::start_store_server().await?;
 
-        let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+        let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -1030,7 +1128,7 @@ async fn test_flight_generic_kv() -> anyhow::Result<()> {
 
         let (_tc, addr) = crate::tests::start_store_server().await?;
 
-        let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+        let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
         {
             // write
@@ -1100,7 +1198,7 @@ async fn test_flight_get_database_meta_empty_db() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_store_ut!();
     let _ent = ut_span.enter();
     let (_tc, addr) = crate::tests::start_store_server().await?;
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     // Empty Database
     let res = client.get_database_meta(None).await?;
@@ -1114,7 +1212,7 @@ async fn test_flight_get_database_meta_ddl_db() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_store_ut!();
     let _ent = ut_span.enter();
     let (_tc, addr) = crate::tests::start_store_server().await?;
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     // create-db operation will increases meta_version
     let plan = CreateDatabasePlan {
@@ -1122,6 +1220,7 @@ async fn test_flight_get_database_meta_ddl_db() -> anyhow::Result<()> {
         db: "db1".to_string(),
         engine: DatabaseEngineType::Local,
         options: Default::default(),
+        clone_from: None,
     };
     client.create_database(plan).await?;
 
@@ -1148,6 +1247,7 @@ async fn test_flight_get_database_meta_ddl_db() -> anyhow::Result<()> {
         db: "db1".to_string(),
         engine: DatabaseEngineType::Local, // accepts a Local engine?
         options: Default::default(),
+        clone_from: None,
     };
 
     client.create_database(plan).await?;
@@ -1176,7 +1276,7 @@ async fn test_flight_get_database_meta_ddl_table() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_store_ut!();
     let _ent = ut_span.enter();
     let (_tc, addr) = crate::tests::start_store_server().await?;
-    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
 
     let test_db = "db1";
     let plan = CreateDatabasePlan {
@@ -1184,6 +1284,7 @@ async fn test_flight_get_database_meta_ddl_table() -> anyhow::Result<()> {
         db: test_db.to_string(),
         engine: DatabaseEngineType::Local,
         options: Default::default(),
+        clone_from: None,
     };
     client.create_database(plan).await?;
 
@@ -1203,6 +1304,7 @@ async fn test_flight_get_database_meta_ddl_table() -> anyhow::Result<()> {
         schema: schema.clone(),
         options: Default::default(),
         engine: TableEngineType::JSONEachRow,
+        clone_from: None,
     };
 
     client.create_table(plan.clone()).await?;
@@ -1248,3 +1350,180 @@ async fn test_flight_get_database_meta_ddl_table() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_backup_restore() -> anyhow::Result<()> {
+    // - Start a store server, create a db, a table with data, a clone of the table and a kv record.
+    // - Back up the store.
+    // - Restore the backup to another fresh store and check the catalog, the parts and the kv.
+
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    use std::sync::Arc;
+
+    use common_flights::BackupApi;
+
+    let (_tc, addr) = crate::tests::start_store_server().await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
+
+    let schema = Arc::new(DataSchema::new(vec![DataField::new(
+        "col_i",
+        DataType::Int64,
+        false,
+    )]));
+    let db_name = "db1";
+    let tbl_name = "tbl1";
+
+    tracing::info!("--- prepare the data to back up");
+    {
+        client
+            .create_database(CreateDatabasePlan {
+                if_not_exists: false,
+                db: db_name.to_string(),
+                engine: DatabaseEngineType::Local,
+                options: Default::default(),
+                clone_from: None,
+            })
+            .await?;
+        client
+            .create_table(CreateTablePlan {
+                if_not_exists: false,
+                db: db_name.to_string(),
+                table: tbl_name.to_string(),
+                schema: schema.clone(),
+                options: Default::default(),
+                engine: TableEngineType::Parquet,
+                clone_from: None,
+            })
+            .await?;
+
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![0i64, 1, 2])]);
+        client
+            .append_data(
+                "".to_string(),
+                db_name.to_string(),
+                tbl_name.to_string(),
+                schema.clone(),
+                Box::pin(futures::stream::iter(vec![block.clone(), block])),
+            )
+            .await?;
+
+        client
+            .create_table(CreateTablePlan {
+                if_not_exists: false,
+                db: db_name.to_string(),
+                table: "tbl1_clone".to_string(),
+                schema: schema.clone(),
+                options: Default::default(),
+                engine: TableEngineType::Parquet,
+                clone_from: Some((db_name.to_string(), tbl_name.to_string())),
+            })
+            .await?;
+
+        client
+            .upsert_kv("foo", MatchSeq::Any, Some(b"bar".to_vec()), None)
+            .await?;
+    }
+
+    let tmp_dir = tempfile::tempdir()?;
+    let backup_dir = tmp_dir.path().to_str().unwrap().to_string();
+
+    tracing::info!("--- back up");
+    {
+        let res = client.backup(backup_dir.clone()).await?;
+        assert_eq!(
+            (1, 2, 2),
+            (res.databases, res.tables, res.parts),
+            "the parts shared by the clone are backed up once"
+        );
+
+        let res = client.backup(backup_dir.clone()).await;
+        assert!(res.is_err(), "a dir with a backup can not be reused");
+    }
+
+    let scan_plan = |table: &str| ScanPlan {
+        schema_name: table.to_string(),
+        ..ScanPlan::empty()
+    };
+
+    tracing::info!("--- restore to a fresh store");
+    let (_tc2, addr2) = crate::tests::start_store_server().await?;
+    let mut client2 = StoreClient::try_create(addr2.as_str(), "root", "root").await?;
+    {
+        let res = client2.restore(backup_dir.clone()).await?;
+        assert_eq!((1, 2, 2), (res.databases, res.tables, res.parts));
+
+        let want = client
+            .read_plan(
+                db_name.to_string(),
+                tbl_name.to_string(),
+                &scan_plan(tbl_name),
+            )
+            .await?;
+        for table in [tbl_name, "tbl1_clone"] {
+            let got = client2
+                .read_plan(db_name.to_string(), table.to_string(), &scan_plan(table))
+                .await?;
+            assert_eq!(want, got, "the latest parts of {} are restored", table);
+
+            let got = client2
+                .get_table(db_name.to_string(), table.to_string())
+                .await?;
+            assert_eq!(schema, got.schema);
+        }
+
+        let got = client2.get_kv("foo").await?;
+        assert_eq!(b"bar".to_vec(), got.result.unwrap().1.value);
+    }
+
+    tracing::info!("--- restore to a store that is not fresh");
+    {
+        let res = client2.restore(backup_dir.clone()).await;
+        assert_eq!(
+            ErrorCode::IllegalMetaState("").code(),
+            res.unwrap_err().code()
+        );
+    }
+
+    tracing::info!("--- a damaged backup is rejected");
+    {
+        let snapshot_path = std::path::Path::new(&backup_dir).join("state_machine.snapshot");
+        std::fs::write(snapshot_path, b"damaged")?;
+        let res = client2.restore(backup_dir.clone()).await;
+        assert_eq!(ErrorCode::FileDamaged("").code(), res.unwrap_err().code());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_cluster_membership() -> anyhow::Result<()> {
+    // - Start a single node store.
+    // - Read the membership and check an unsafe change is rejected.
+
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    use common_flights::ClusterApi;
+    use maplit::btreeset;
+
+    let (_tc, addr) = crate::tests::start_store_server().await?;
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "root").await?;
+
+    let got = client.get_membership().await?;
+    assert_eq!(Some(0), got.leader);
+    assert_eq!(btreeset![0], got.voters);
+    assert!(got.learners.is_empty());
+
+    let res = client.remove_node(0).await;
+    assert_eq!(
+        ErrorCode::InvalidMembership("").code(),
+        res.unwrap_err().code()
+    );
+
+    let res = client.change_membership(btreeset![0, 1]).await;
+    assert_eq!(ErrorCode::UnknownNode("").code(), res.unwrap_err().code());
+
+    Ok(())
+}
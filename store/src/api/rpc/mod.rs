@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod flight_auth_test;
 #[cfg(test)]
 mod flight_service_test;
 #[cfg(test)]
 mod tls_flight_service_test;
 
+mod flight_auth;
 mod flight_service;
 mod metrics;
 
@@ -48,7 +48,7 @@ async fn test_flight_tls() -> anyhow::Result<()> {
     };
 
     let mut client =
-        StoreClient::with_tls_conf(addr.as_str(), "root", "xxx", Some(tls_conf)).await?;
+        StoreClient::with_tls_conf(addr.as_str(), "root", "root", Some(tls_conf)).await?;
 
     let r = client
         .get_table("do not care".to_owned(), "do not care".to_owned())
@@ -84,7 +84,7 @@ async fn test_flight_tls_client_config_failure() -> anyhow::Result<()> {
         domain_name: TEST_CN_NAME.to_string(),
     };
 
-    let r = StoreClient::with_tls_conf("addr", "root", "xxx", Some(tls_conf)).await;
+    let r = StoreClient::with_tls_conf("addr", "root", "root", Some(tls_conf)).await;
 
     assert!(r.is_err());
     if let Err(e) = r {
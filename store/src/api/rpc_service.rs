@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_arrow::arrow_flight::flight_service_server::FlightServiceServer;
 use common_exception::ErrorCode;
@@ -30,17 +31,19 @@ use transport::ServerTlsConfig;
 
 use crate::api::rpc::StoreFlightImpl;
 use crate::configs::Config;
+use crate::data_part::part_gc::PartGc;
 use crate::dfs::Dfs;
 use crate::localfs::LocalFS;
 use crate::meta_service::MetaNode;
 
 pub struct StoreServer {
     conf: Config,
+    meta_node: Arc<MetaNode>,
 }
 
 impl StoreServer {
-    pub fn create(conf: Config) -> Self {
-        Self { conf }
+    pub fn create(conf: Config, meta_node: Arc<MetaNode>) -> Self {
+        Self { conf, meta_node }
     }
 
     /// Start store server and returns two channel to send shutdown signal and receive signal when shutdown finished.
@@ -88,29 +91,13 @@ impl StoreServer {
 
         let fs = LocalFS::try_create(self.conf.local_fs_dir.clone())?;
 
-        // - boot mode: create the first node in a new cluster.
-        // - TODO(xp): join mode: create a new node to join a cluster.
-        // - open mode: open an existent node.
-        tracing::info!(
-            "Starting MetaNode boot:{} single: {} with config: {:?}",
-            self.conf.boot,
-            self.conf.single,
-            self.conf
-        );
-
-        let mn = if self.conf.boot {
-            MetaNode::boot(0, &self.conf).await?
-        } else if self.conf.single {
-            let (mn, _is_open) =
-                MetaNode::open_create_boot(&self.conf, Some(()), Some(()), Some(())).await?;
-            mn
-        } else {
-            MetaNode::open(&self.conf).await?
-        };
-        tracing::info!("Done starting MetaNode: {:?}", self.conf);
+        let mn = self.meta_node.clone();
 
         let dfs = Dfs::create(fs, mn.clone());
 
+        let gc_interval = Duration::from_secs(self.conf.part_gc_interval);
+        let gc_handle = PartGc::create(&self.conf, mn.clone()).spawn(gc_interval);
+
         let flight_impl = StoreFlightImpl::create(self.conf.clone(), Arc::new(dfs), mn.clone());
         let flight_srv = FlightServiceServer::new(flight_impl);
 
@@ -138,6 +125,7 @@ impl StoreServer {
             })
             .await;
 
+        gc_handle.abort();
         let _ = mn.stop().await;
         let s = fin_tx.send(());
         tracing::info!(
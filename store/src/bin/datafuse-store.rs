@@ -12,12 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
+use common_flights::BackupApi;
+use common_flights::StoreClient;
 use common_runtime::tokio;
 use common_tracing::init_tracing_with_file;
 use datafuse_store::api::HttpService;
 use datafuse_store::api::StoreServer;
 use datafuse_store::configs::Config;
 use datafuse_store::meta_service::raft_db::init_sled_db;
+use datafuse_store::meta_service::MetaNode;
 use datafuse_store::metrics::MetricService;
 use log::info;
 use structopt::StructOpt;
@@ -42,8 +47,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         *datafuse_store::configs::config::FUSE_COMMIT_VERSION
     );
 
+    conf.check()?;
+    if !conf.command.is_empty() {
+        return run_command(&conf).await;
+    }
+
     init_sled_db(conf.meta_dir.clone());
 
+    let mn = MetaNode::start(&conf).await?;
+
     // Metric API service.
     {
         let srv = MetricService::create(conf.clone());
@@ -55,7 +67,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // HTTP API service.
     {
-        let mut srv = HttpService::create(conf.clone());
+        let mut srv = HttpService::create(conf.clone(), mn.clone());
         info!("HTTP API server listening on {}", conf.http_api_address);
         tokio::spawn(async move {
             srv.start().await.expect("HTTP: admin api error");
@@ -64,7 +76,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // RPC API service.
     {
-        let srv = StoreServer::create(conf.clone());
+        let srv = StoreServer::create(conf.clone(), mn);
         info!(
             "DatafuseStore API server listening on {}",
             conf.flight_api_address
@@ -75,3 +87,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Runs a backup or a restore on the store serving at the flight api address.
+async fn run_command(conf: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let mut client = StoreClient::try_create(
+        conf.flight_api_address.as_str(),
+        conf.flight_api_username.as_str(),
+        conf.flight_api_password.as_ref(),
+    )
+    .await?;
+    // Copying all the part files takes much longer than a meta request.
+    client.set_timeout(Duration::from_secs(3600));
+
+    let res = match conf.command.as_str() {
+        "backup" => client.backup(conf.backup_dir.clone()).await?,
+        _ => client.restore(conf.backup_dir.clone()).await?,
+    };
+    println!("{} done, {}", conf.command, res);
+    Ok(())
+}
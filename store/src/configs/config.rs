@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
+use std::str::FromStr;
+
 use common_exception::ErrorCode;
 use lazy_static::lazy_static;
 use structopt::StructOpt;
@@ -74,6 +77,23 @@ pub struct Config {
     )]
     pub flight_api_address: String,
 
+    #[structopt(
+        long,
+        env = "STORE_FLIGHT_API_USERNAME",
+        default_value = "root",
+        help = concat!("The built-in user to access the flight api, which has all the permissions.",
+                      " The other users are stored in meta.")
+    )]
+    pub flight_api_username: String,
+
+    #[structopt(
+        long,
+        env = "STORE_FLIGHT_API_PASSWORD",
+        default_value = "root",
+        help = "The password of the built-in flight api user"
+    )]
+    pub flight_api_password: Password,
+
     #[structopt(
         long,
         env = "STORE_META_API_HOST",
@@ -134,6 +154,14 @@ pub struct Config {
     )]
     pub install_snapshot_timeout: u64,
 
+    #[structopt(
+        long,
+        env = "STORE_SNAPSHOT_CHUNK_SIZE",
+        default_value = "4194304",
+        help = "The max size in bytes of a chunk, in which a leader sends a snapshot to a follower or non-voter."
+    )]
+    pub snapshot_chunk_size: u64,
+
     #[structopt(
         long,
         env = "STORE_BOOT",
@@ -161,12 +189,20 @@ pub struct Config {
     )]
     pub single: bool,
 
+    #[structopt(
+        long,
+        env = "STORE_JOIN",
+        help = concat!("Create a node that is going to be added into a cluster as a learner, if meta data is not initialized.",
+                      " Otherwise it opens the previous one.")
+    )]
+    pub join: bool,
+
     #[structopt(
         long,
         env = "STORE_ID",
         default_value = "0",
         help = concat!("The node id. Only used when this server is not initialized,",
-                      " e.g. --boot or --single for the first time.",
+                      " e.g. --boot, --single or --join for the first time.",
                       " Otherwise this argument is ignored.")
     )]
     pub id: NodeId,
@@ -179,6 +215,49 @@ pub struct Config {
     )]
     pub local_fs_dir: String,
 
+    #[structopt(
+        long,
+        env = "STORE_TABLE_HISTORY_RETENTION",
+        default_value = "86400",
+        help = concat!("The seconds to keep a version of the table parts after it is replaced,",
+                      " within which the table can be read as of the version.")
+    )]
+    pub table_history_retention: u64,
+
+    #[structopt(
+        long,
+        env = "STORE_PART_GC_INTERVAL",
+        default_value = "60",
+        help = "The seconds between two scans of --local-fs-dir for the part files no table version references."
+    )]
+    pub part_gc_interval: u64,
+
+    #[structopt(
+        long,
+        env = "STORE_PART_GC_GRACE",
+        default_value = "3600",
+        help = concat!("The seconds a part file is kept before it can be collected,",
+                      " an appended part is written before it is published to the table.")
+    )]
+    pub part_gc_grace: u64,
+
+    #[structopt(
+        default_value = "",
+        help = concat!("Run a command against the store serving at --flight-api-address, instead of starting a store:",
+                      " `backup` writes a consistent backup of the meta and the data parts into --backup-dir,",
+                      " `restore` rebuilds a fresh cluster from the backup in --backup-dir.",
+                      " The dir is on the host of the store.")
+    )]
+    pub command: String,
+
+    #[structopt(
+        long,
+        env = "STORE_BACKUP_DIR",
+        default_value = "",
+        help = "The dir to write a backup into, or to restore a backup from"
+    )]
+    pub backup_dir: String,
+
     #[structopt(
         long,
         default_value = "",
@@ -187,6 +266,30 @@ pub struct Config {
     pub sled_tree_prefix: String,
 }
 
+/// A password that is masked in the debug output, e.g., in logs.
+#[derive(Clone, serde::Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct Password(String);
+
+impl AsRef<str> for Password {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Password {
+    type Err = ErrorCode;
+    fn from_str(s: &str) -> common_exception::Result<Self> {
+        Ok(Password(s.to_string()))
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "******")
+    }
+}
+
 impl Config {
     /// StructOptToml provides a default Default impl that loads config from cli args,
     /// which conflicts with unit test if case-filter arguments passed, e.g.:
@@ -207,12 +310,35 @@ impl Config {
     }
 
     pub fn check(&self) -> common_exception::Result<()> {
-        if self.boot && self.single {
+        if [self.boot, self.single, self.join]
+            .iter()
+            .filter(|x| **x)
+            .count()
+            > 1
+        {
             return Err(ErrorCode::InvalidConfig(
-                "--boot and --single can not be both set",
+                "only one of --boot, --single and --join can be set",
             ));
         }
 
+        match self.command.as_str() {
+            "" => {}
+            "backup" | "restore" => {
+                if self.backup_dir.is_empty() {
+                    return Err(ErrorCode::InvalidConfig(format!(
+                        "--backup-dir is required by {}",
+                        self.command
+                    )));
+                }
+            }
+            cmd => {
+                return Err(ErrorCode::InvalidConfig(format!(
+                    "unknown command: {}, expect backup or restore",
+                    cmd
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -31,3 +31,41 @@ fn test_tls_rpc_enabled() -> anyhow::Result<()> {
     assert_eq!(true, conf.tls_rpc_server_enabled());
     Ok(())
 }
+
+#[test]
+fn test_flight_api_password_masked() -> anyhow::Result<()> {
+    let mut conf = Config::empty();
+    assert_eq!("root", conf.flight_api_password.as_ref());
+
+    conf.flight_api_password = "secret".parse()?;
+    assert_eq!("secret", conf.flight_api_password.as_ref());
+    assert!(!format!("{:?}", conf).contains("secret"));
+    Ok(())
+}
+
+#[test]
+fn test_check_command() -> anyhow::Result<()> {
+    let mut conf = Config::empty();
+    assert!(conf.check().is_ok(), "serve by default");
+
+    conf.command = "backup".to_string();
+    assert!(conf.check().is_err(), "backup requires --backup-dir");
+
+    conf.backup_dir = "./_backup".to_string();
+    assert!(conf.check().is_ok());
+
+    conf.command = "foo".to_string();
+    assert!(conf.check().is_err(), "unknown command");
+    Ok(())
+}
+
+#[test]
+fn test_check_start_mode() -> anyhow::Result<()> {
+    let mut conf = Config::empty();
+    conf.join = true;
+    assert!(conf.check().is_ok());
+
+    conf.boot = true;
+    assert!(conf.check().is_err(), "--boot conflicts with --join");
+    Ok(())
+}
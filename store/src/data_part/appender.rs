@@ -27,6 +27,9 @@ use common_arrow::arrow_flight::FlightData;
 // use common_arrow::parquet::arrow::ArrowWriter;
 // use common_arrow::parquet::file::writer::InMemoryWriteableCursor;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
 use common_flights::storage_api_impl::AppendResult;
 use futures::StreamExt;
 use uuid::Uuid;
@@ -37,41 +40,112 @@ pub(crate) struct Appender {
     fs: Arc<dyn FileSystem>,
 }
 
-pub type InputData = std::pin::Pin<Box<dyn futures::Stream<Item = FlightData> + Send>>;
+pub type InputData =
+    std::pin::Pin<Box<dyn futures::Stream<Item = common_exception::Result<FlightData>> + Send>>;
 
 impl Appender {
     pub fn new(fs: Arc<dyn FileSystem>) -> Self {
         Appender { fs }
     }
 
+    /// Writes the parts of an append, which are invisible until they are published to the table meta.
+    /// If any error occurs, the parts written are removed.
+    ///
     /// Assumes
     /// - upstream caller has properly batched data
-    /// - first element of the incoming stream is a properly serialized schema
-    pub async fn append_data(&self, path: String, mut stream: InputData) -> Result<AppendResult> {
-        if let Some(flight_data) = stream.next().await {
-            let arrow_schema = ArrowSchema::try_from(&flight_data)?;
-            let arrow_schema_ref = Arc::new(arrow_schema);
-
-            let mut result = AppendResult::default();
-            while let Some(flight_data) = stream.next().await {
-                let batch =
-                    flight_data_to_arrow_batch(&flight_data, arrow_schema_ref.clone(), true, &[])?;
-                let block = DataBlock::try_from(batch)?;
-                let (rows, cols, wire_bytes) =
-                    (block.num_rows(), block.num_columns(), block.memory_size());
-                let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
-                let location = format!("{}/{}", path, part_uuid);
-                let buffer = write_in_memory(block)?;
-
-                result.append_part(&location, rows, cols, wire_bytes, buffer.len());
-
-                self.fs.add(&location, &buffer).await?;
+    /// - first element of the incoming stream is a properly serialized schema,
+    ///   which must be the same as the table schema
+    pub async fn append_data(
+        &self,
+        path: String,
+        table_schema: &DataSchemaRef,
+        stream: InputData,
+    ) -> common_exception::Result<AppendResult> {
+        let mut result = AppendResult {
+            tx_id: Uuid::new_v4().to_simple().to_string(),
+            ..AppendResult::default()
+        };
+
+        let appended = self
+            .write_parts(&path, table_schema, stream, &mut result)
+            .await;
+        if let Err(e) = appended {
+            self.remove_parts(&result).await;
+            return Err(e);
+        }
+        Ok(result)
+    }
+
+    /// Removes the parts written by an append, e.g., which fails to be published.
+    pub async fn remove_parts(&self, result: &AppendResult) {
+        for part in &result.parts {
+            if let Err(e) = self.fs.delete(&part.location).await {
+                log::warn!(
+                    "fail to remove part {} of append {}: {}",
+                    part.location,
+                    result.tx_id,
+                    e
+                );
+            }
+        }
+    }
+
+    async fn write_parts(
+        &self,
+        path: &str,
+        table_schema: &DataSchemaRef,
+        mut stream: InputData,
+        result: &mut AppendResult,
+    ) -> common_exception::Result<()> {
+        let flight_data = match stream.next().await {
+            None => {
+                return Err(ErrorCode::EmptyData(
+                    "Schema of input data must be provided",
+                ))
             }
-            Ok(result)
-        } else {
-            anyhow::bail!("Schema of input data must be provided")
+            Some(flight_data) => flight_data?,
+        };
+        let arrow_schema = ArrowSchema::try_from(&flight_data)?;
+        check_schema(table_schema, &DataSchema::from(arrow_schema.clone()))?;
+
+        let arrow_schema_ref = Arc::new(arrow_schema);
+        while let Some(flight_data) = stream.next().await {
+            let batch =
+                flight_data_to_arrow_batch(&flight_data?, arrow_schema_ref.clone(), true, &[])?;
+            let block = DataBlock::try_from(batch)?;
+            let (rows, cols, wire_bytes) =
+                (block.num_rows(), block.num_columns(), block.memory_size());
+            let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
+            let location = format!("{}/{}", path, part_uuid);
+            let buffer = write_in_memory(block)?;
+
+            self.fs.add(&location, &buffer).await?;
+            result.append_part(&location, rows, cols, wire_bytes, buffer.len());
         }
+        Ok(())
+    }
+}
+
+/// The input data must have the same columns as the table, in the same order.
+/// The nullability is not checked, since it does not change how the data is stored.
+fn check_schema(
+    table_schema: &DataSchema,
+    input_schema: &DataSchema,
+) -> common_exception::Result<()> {
+    let same = table_schema.fields().len() == input_schema.fields().len()
+        && table_schema
+            .fields()
+            .iter()
+            .zip(input_schema.fields())
+            .all(|(t, i)| t.name() == i.name() && t.data_type() == i.data_type());
+
+    if !same {
+        return Err(ErrorCode::IllegalSchema(format!(
+            "The schema of the input data {:?} does not match the table schema {:?}",
+            input_schema, table_schema
+        )));
     }
+    Ok(())
 }
 
 pub(crate) fn write_in_memory(block: DataBlock) -> Result<Vec<u8>> {
@@ -28,6 +28,7 @@ mod test {
     use common_arrow::arrow_flight::utils::flight_data_from_arrow_schema;
     use common_datablocks::DataBlock;
     use common_datavalues::prelude::*;
+    use common_exception::ErrorCode;
     use common_runtime::tokio;
 
     use crate::data_part::appender::*;
@@ -69,6 +70,10 @@ mod test {
 
         let batch = RecordBatch::try_from_iter(vec![("col0", col0), ("col1", col1)])?;
         let schema = batch.schema();
+        let table_schema = DataSchemaRefExt::create(vec![
+            DataField::new("col0", DataType::Int64, false),
+            DataField::new("col1", DataType::Utf8, false),
+        ]);
 
         let p = tempfile::tempdir()?;
         let fs = LocalFS::try_create(p.path().to_str().unwrap().to_string())?;
@@ -77,15 +82,51 @@ mod test {
 
         let default_ipc_write_opt = IpcWriteOptions::default();
         let flight_schema = flight_data_from_arrow_schema(&schema, &default_ipc_write_opt);
+        let flight_batch = flight_data_from_arrow_batch(&batch, &default_ipc_write_opt).1; // ignore dict
 
         let req = futures::stream::iter(vec![
-            flight_schema,
-            flight_data_from_arrow_batch(&batch, &default_ipc_write_opt).1, // ignore dict
+            Ok::<_, ErrorCode>(flight_schema.clone()),
+            Ok(flight_batch.clone()),
+            Ok(flight_batch.clone()),
+        ]);
+        let r = appender
+            .append_data("test_tbl".to_string(), &table_schema, Box::pin(req))
+            .await?;
+        assert_eq!(2, r.parts.len());
+        assert_eq!(6, r.summary.rows);
+        assert!(!r.tx_id.is_empty());
+        for part in &r.parts {
+            assert!(p.path().join(&part.location).exists());
+        }
+
+        // The input data does not match the table schema.
+        let other_schema = DataSchemaRefExt::create(vec![
+            DataField::new("col0", DataType::Int32, false),
+            DataField::new("col1", DataType::Utf8, false),
+        ]);
+        let req = futures::stream::iter(vec![
+            Ok::<_, ErrorCode>(flight_schema.clone()),
+            Ok(flight_batch.clone()),
         ]);
         let r = appender
-            .append_data("test_tbl".to_string(), Box::pin(req))
+            .append_data("test_tbl_2".to_string(), &other_schema, Box::pin(req))
             .await;
-        assert!(r.is_ok());
+        assert_eq!(ErrorCode::IllegalSchema("").code(), r.unwrap_err().code());
+        assert!(!p.path().join("test_tbl_2").exists());
+
+        // The parts written are removed if the input stream breaks.
+        let req = futures::stream::iter(vec![
+            Ok(flight_schema),
+            Ok(flight_batch),
+            Err(ErrorCode::BrokenChannel("broken input")),
+        ]);
+        let r = appender
+            .append_data("test_tbl_3".to_string(), &table_schema, Box::pin(req))
+            .await;
+        assert_eq!(ErrorCode::BrokenChannel("").code(), r.unwrap_err().code());
+        let dir = p.path().join("test_tbl_3");
+        assert!(!dir.exists() || std::fs::read_dir(dir)?.next().is_none());
+
         Ok(())
     }
 }
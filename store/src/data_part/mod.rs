@@ -14,6 +14,13 @@
 //
 
 pub(crate) mod appender;
+pub(crate) mod part_gc;
+pub(crate) mod pruner;
+pub(crate) mod reader;
 
 #[cfg(test)]
 mod appender_test;
+#[cfg(test)]
+mod part_gc_test;
+#[cfg(test)]
+mod reader_test;
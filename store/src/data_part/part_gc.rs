@@ -0,0 +1,130 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use common_exception::Result;
+use common_runtime::tokio;
+use common_runtime::tokio::task::JoinHandle;
+use common_tracing::tracing;
+
+use crate::configs::Config;
+use crate::meta_service::MetaNode;
+
+const PART_SUFFIX: &str = ".parquet";
+
+/// Deletes the part files in the local fs that no table version references.
+///
+/// The references are counted by the state machine, which every node applies in the same order,
+/// thus every node collects its own copies of the parts without any coordination.
+pub struct PartGc {
+    root: PathBuf,
+    meta_node: Arc<MetaNode>,
+    /// A part file is written before the append publishing it is applied,
+    /// thus a file younger than it is kept even if it is not referenced yet.
+    grace: Duration,
+}
+
+impl PartGc {
+    pub fn create(conf: &Config, meta_node: Arc<MetaNode>) -> Self {
+        PartGc {
+            root: PathBuf::from(&conf.local_fs_dir),
+            meta_node,
+            grace: Duration::from_secs(conf.part_gc_grace),
+        }
+    }
+
+    /// Runs a collection every `interval`, until the returned handle is aborted.
+    pub fn spawn(self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.collect().await {
+                    Ok(removed) if !removed.is_empty() => {
+                        tracing::info!("removed {} unused parts", removed.len())
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("fail to collect unused parts: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Deletes the part files that are not referenced and older than the grace period.
+    /// Returns the names of the parts deleted.
+    pub async fn collect(&self) -> Result<Vec<String>> {
+        let mut candidates = vec![];
+        if self.root.exists() {
+            self.list_parts(&self.root, "", &mut candidates)?;
+        }
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let unused = match self.meta_node.get_unreferenced_parts(candidates).await? {
+            Some(unused) => unused,
+            // Every part would look unused, keep them all.
+            None => {
+                tracing::warn!("part references are not tracked by the state machine, skip gc");
+                return Ok(vec![]);
+            }
+        };
+        let mut removed = vec![];
+        for name in unused {
+            match std::fs::remove_file(self.root.join(&name)) {
+                Ok(_) => removed.push(name),
+                Err(e) => tracing::warn!("fail to remove unused part {}: {}", name, e),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Lists the names of the part files under `dir` that are older than the grace period.
+    /// A name is the path relative to the root, the same as the part location.
+    fn list_parts(&self, dir: &Path, prefix: &str, names: &mut Vec<String>) -> Result<()> {
+        let entries = std::fs::read_dir(dir)
+            .with_context(|| format!("PartGc: fail to list {}", dir.display()))?;
+
+        for ent in entries {
+            let ent = ent?;
+            let file_name = match ent.file_name().into_string() {
+                Ok(x) => x,
+                // Not written by an appender.
+                Err(_) => continue,
+            };
+            let name = if prefix.is_empty() {
+                file_name
+            } else {
+                format!("{}/{}", prefix, file_name)
+            };
+
+            let meta = ent.metadata()?;
+            if meta.is_dir() {
+                self.list_parts(&ent.path(), &name, names)?;
+            } else if name.ends_with(PART_SUFFIX) {
+                let age = meta.modified()?.elapsed().unwrap_or_default();
+                if age >= self.grace {
+                    names.push(name);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::path::PathBuf;
+
+use common_flights::storage_api_impl::AppendResult;
+use common_metatypes::Database;
+use common_metatypes::Table;
+use common_runtime::tokio;
+use pretty_assertions::assert_eq;
+
+use crate::data_part::part_gc::PartGc;
+use crate::meta_service::Cmd;
+use crate::meta_service::LogEntry;
+use crate::meta_service::MetaNode;
+use crate::tests::service::new_test_context;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_part_gc_collect() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let mut tc = new_test_context();
+    tc.config.part_gc_grace = 0;
+    let mn = MetaNode::boot(0, &tc.config).await?;
+    tc.meta_nodes.push(mn.clone());
+
+    let root = PathBuf::from(&tc.config.local_fs_dir);
+    let write_file = |name: &str| -> anyhow::Result<()> {
+        let path = root.join(name);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, "data")?;
+        Ok(())
+    };
+    for name in ["db1/tb1/p1.parquet", "db1/tb1/p2.parquet", "db1/tb1/other"] {
+        write_file(name)?;
+    }
+
+    let s = |x: &str| x.to_string();
+    let write = |cmd: Cmd| mn.write(LogEntry { txid: None, cmd });
+
+    write(Cmd::CreateDatabase {
+        name: s("db1"),
+        if_not_exists: false,
+        db: Database::default(),
+    })
+    .await?;
+    write(Cmd::CreateTable {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        if_not_exists: false,
+        table: Table::default(),
+    })
+    .await?;
+    let table_id = mn.get_database("db1").await.unwrap().tables["tb1"];
+
+    let mut append_res = AppendResult::default();
+    append_res.append_part("db1/tb1/p1.parquet", 1, 1, 1, 1);
+    write(Cmd::AppendDataParts {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        table_id,
+        append_res,
+        time_ms: 1,
+    })
+    .await?;
+
+    // Only the part files not referenced are removed.
+    let gc = PartGc::create(&tc.config, mn.clone());
+    assert_eq!(vec![s("db1/tb1/p2.parquet")], gc.collect().await?);
+    assert!(root.join("db1/tb1/p1.parquet").exists());
+    assert!(!root.join("db1/tb1/p2.parquet").exists());
+    assert!(root.join("db1/tb1/other").exists());
+
+    // The parts of a table are unreferenced once it is dropped.
+    write(Cmd::DropTable {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        if_exists: false,
+    })
+    .await?;
+    assert_eq!(vec![s("db1/tb1/p1.parquet")], gc.collect().await?);
+    assert!(gc.collect().await?.is_empty());
+
+    // A part file within the grace period is kept, it may be published by an append later.
+    tc.config.part_gc_grace = 3600;
+    write_file("db1/tb1/p3.parquet")?;
+    let gc = PartGc::create(&tc.config, mn.clone());
+    assert!(gc.collect().await?.is_empty());
+    assert!(root.join("db1/tb1/p3.parquet").exists());
+
+    Ok(())
+}
@@ -0,0 +1,281 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use common_arrow::parquet::metadata::RowGroupMetaData;
+use common_arrow::parquet::statistics::BinaryStatistics;
+use common_arrow::parquet::statistics::BooleanStatistics;
+use common_arrow::parquet::statistics::PrimitiveStatistics;
+use common_arrow::parquet::types::NativeType;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::Expression;
+use common_planners::ExpressionExecutor;
+
+/// Prunes the row groups of a part by the min/max statistics.
+///
+/// The filter is rewritten to a predicate over the statistics, e.g. `a > 1` becomes `max(a) > 1`,
+/// which is evaluated by the `ExpressionExecutor` over a one-row block of the statistics.
+/// The sub-expressions that can't be rewritten are regarded as true, so that a row group is
+/// skipped only if none of its rows can match the filter.
+pub(crate) struct RowGroupPruner {
+    /// The part columns referenced by the predicate, with their column index in the part.
+    columns: Vec<(usize, DataField)>,
+    stats_schema: DataSchemaRef,
+    predicate: Expression,
+    executor: ExpressionExecutor,
+}
+
+impl RowGroupPruner {
+    /// Returns None if the filter can't be used to prune the row groups.
+    pub fn try_create(part_schema: &DataSchemaRef, filter: &Expression) -> Result<Option<Self>> {
+        let mut column_names = vec![];
+        let predicate = rewrite_filter(part_schema, filter, &mut column_names);
+        if column_names.is_empty() {
+            return Ok(None);
+        }
+
+        let mut columns = vec![];
+        let mut stats_fields = vec![];
+        for (index, field) in part_schema.fields().iter().enumerate() {
+            if column_names.contains(field.name()) {
+                let data_type = field.data_type();
+                columns.push((index, field.clone()));
+                stats_fields.push(DataField::new(
+                    &min_name(field.name()),
+                    data_type.clone(),
+                    true,
+                ));
+                stats_fields.push(DataField::new(
+                    &max_name(field.name()),
+                    data_type.clone(),
+                    true,
+                ));
+            }
+        }
+
+        let stats_schema = DataSchemaRefExt::create(stats_fields);
+        let executor = ExpressionExecutor::try_create(
+            "row group pruner executor",
+            stats_schema.clone(),
+            DataSchemaRefExt::create(vec![predicate.to_data_field(&stats_schema)?]),
+            vec![predicate.clone()],
+            false,
+        )?;
+        executor.validate()?;
+
+        Ok(Some(RowGroupPruner {
+            columns,
+            stats_schema,
+            predicate,
+            executor,
+        }))
+    }
+
+    /// Returns false only if no row of the row group can match the filter.
+    pub fn may_match(&self, row_group: &RowGroupMetaData) -> Result<bool> {
+        let mut stats_columns = Vec::with_capacity(self.columns.len() * 2);
+        for (index, field) in &self.columns {
+            match column_min_max(row_group, *index, field.data_type()) {
+                // The statistics is missing or the column is not comparable, read it anyway.
+                None => return Ok(true),
+                Some((min, max)) => {
+                    stats_columns.push(DataColumn::Constant(min, 1));
+                    stats_columns.push(DataColumn::Constant(max, 1));
+                }
+            }
+        }
+
+        let stats_block = DataBlock::create(self.stats_schema.clone(), stats_columns);
+        let result_block = self.executor.execute(&stats_block)?;
+        let result = result_block
+            .try_column_by_name(&self.predicate.column_name())?
+            .try_get(0)?;
+        Ok(!matches!(result, DataValue::Boolean(Some(false))))
+    }
+}
+
+fn min_name(column_name: &str) -> String {
+    format!("min({})", column_name)
+}
+
+fn max_name(column_name: &str) -> String {
+    format!("max({})", column_name)
+}
+
+fn rewrite_filter(
+    part_schema: &DataSchemaRef,
+    filter: &Expression,
+    column_names: &mut Vec<String>,
+) -> Expression {
+    match filter {
+        Expression::BinaryExpression { left, op, right } => {
+            match (op.to_lowercase().as_str(), left.as_ref(), right.as_ref()) {
+                ("and", left, right) => rewrite_filter(part_schema, left, column_names)
+                    .and(rewrite_filter(part_schema, right, column_names)),
+                ("or", left, right) => rewrite_filter(part_schema, left, column_names)
+                    .or(rewrite_filter(part_schema, right, column_names)),
+                (op, Expression::Column(name), value @ Expression::Literal { .. }) => {
+                    rewrite_comparison(part_schema, name, op, value, column_names)
+                }
+                (op, value @ Expression::Literal { .. }, Expression::Column(name)) => {
+                    let flipped_op = match op {
+                        "<" => ">",
+                        "<=" => ">=",
+                        ">" => "<",
+                        ">=" => "<=",
+                        op => op,
+                    };
+                    rewrite_comparison(part_schema, name, flipped_op, value, column_names)
+                }
+                _ => lit(true),
+            }
+        }
+        _ => lit(true),
+    }
+}
+
+fn rewrite_comparison(
+    part_schema: &DataSchemaRef,
+    name: &str,
+    op: &str,
+    value: &Expression,
+    column_names: &mut Vec<String>,
+) -> Expression {
+    let comparable = part_schema
+        .field_with_name(name)
+        .map(|field| is_comparable(field.data_type()))
+        .unwrap_or(false);
+
+    if !comparable {
+        return lit(true);
+    }
+
+    let (min, max) = (col(&min_name(name)), col(&max_name(name)));
+    let predicate = match op {
+        "=" => min.lt_eq(value.clone()).and(max.gt_eq(value.clone())),
+        "<" => min.lt(value.clone()),
+        "<=" => min.lt_eq(value.clone()),
+        ">" => max.gt(value.clone()),
+        ">=" => max.gt_eq(value.clone()),
+        _ => return lit(true),
+    };
+
+    if !column_names.iter().any(|column_name| column_name == name) {
+        column_names.push(name.to_string());
+    }
+    predicate
+}
+
+fn is_comparable(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Boolean
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Utf8
+    )
+}
+
+fn primitive_min_max<T: NativeType + 'static>(statistics: &dyn Any) -> Option<(T, T)> {
+    let statistics = statistics.downcast_ref::<PrimitiveStatistics<T>>()?;
+    Some((statistics.min_value?, statistics.max_value?))
+}
+
+/// The min and max value of the column chunk, None if they are not available.
+fn column_min_max(
+    row_group: &RowGroupMetaData,
+    index: usize,
+    data_type: &DataType,
+) -> Option<(DataValue, DataValue)> {
+    let statistics = row_group.columns().get(index)?.statistics()?.ok()?;
+    let statistics = statistics.as_any();
+
+    // The integers are stored with the physical type int32 or int64.
+    // The unsigned ones are only comparable if all of them fit in the signed type.
+    match data_type {
+        DataType::Boolean => {
+            let statistics = statistics.downcast_ref::<BooleanStatistics>()?;
+            let (min, max) = (statistics.min_value?, statistics.max_value?);
+            Some((DataValue::Boolean(Some(min)), DataValue::Boolean(Some(max))))
+        }
+        DataType::Int8 => primitive_min_max::<i32>(statistics).map(|(min, max)| {
+            (
+                DataValue::Int8(Some(min as i8)),
+                DataValue::Int8(Some(max as i8)),
+            )
+        }),
+        DataType::Int16 => primitive_min_max::<i32>(statistics).map(|(min, max)| {
+            (
+                DataValue::Int16(Some(min as i16)),
+                DataValue::Int16(Some(max as i16)),
+            )
+        }),
+        DataType::Int32 => primitive_min_max::<i32>(statistics)
+            .map(|(min, max)| (DataValue::Int32(Some(min)), DataValue::Int32(Some(max)))),
+        DataType::Int64 => primitive_min_max::<i64>(statistics)
+            .map(|(min, max)| (DataValue::Int64(Some(min)), DataValue::Int64(Some(max)))),
+        DataType::UInt8 => primitive_min_max::<i32>(statistics).map(|(min, max)| {
+            (
+                DataValue::UInt8(Some(min as u8)),
+                DataValue::UInt8(Some(max as u8)),
+            )
+        }),
+        DataType::UInt16 => primitive_min_max::<i32>(statistics).map(|(min, max)| {
+            (
+                DataValue::UInt16(Some(min as u16)),
+                DataValue::UInt16(Some(max as u16)),
+            )
+        }),
+        DataType::UInt32 => primitive_min_max::<i32>(statistics)
+            .filter(|(min, _)| *min >= 0)
+            .map(|(min, max)| {
+                (
+                    DataValue::UInt32(Some(min as u32)),
+                    DataValue::UInt32(Some(max as u32)),
+                )
+            }),
+        DataType::UInt64 => primitive_min_max::<i64>(statistics)
+            .filter(|(min, _)| *min >= 0)
+            .map(|(min, max)| {
+                (
+                    DataValue::UInt64(Some(min as u64)),
+                    DataValue::UInt64(Some(max as u64)),
+                )
+            }),
+        DataType::Float32 => primitive_min_max::<f32>(statistics)
+            .map(|(min, max)| (DataValue::Float32(Some(min)), DataValue::Float32(Some(max)))),
+        DataType::Float64 => primitive_min_max::<f64>(statistics)
+            .map(|(min, max)| (DataValue::Float64(Some(min)), DataValue::Float64(Some(max)))),
+        DataType::Utf8 => {
+            let statistics = statistics.downcast_ref::<BinaryStatistics>()?;
+            let min = String::from_utf8(statistics.min_value.clone()?).ok()?;
+            let max = String::from_utf8(statistics.max_value.clone()?).ok()?;
+            Some((DataValue::Utf8(Some(min)), DataValue::Utf8(Some(max))))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow;
+use common_arrow::arrow::io::parquet::read;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::parquet::metadata::FileMetaData;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::find_column_exprs;
+use common_planners::Expression;
+use common_planners::ExpressionExecutor;
+use common_planners::ReadDataSourcePlan;
+
+use crate::data_part::pruner::RowGroupPruner;
+use crate::fs::FileSystem;
+
+/// The footer of a parquet file: the length of the file meta data and the magic number.
+const FOOTER_SIZE: u64 = 8;
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Reads a data part with the push downs of the `ReadDataSourcePlan` applied:
+/// - only the projected columns and the columns required by the filters are decoded
+/// - the row groups are pruned by the filters against the row group statistics
+/// - the rows of the remaining row groups are filtered by the `ExpressionExecutor`
+///
+/// Only the footer and the column chunks to decode are fetched from the file system.
+pub(crate) struct PartReader {
+    schema: DataSchemaRef,
+    filter: Option<Expression>,
+}
+
+impl PartReader {
+    pub fn create(plan: &ReadDataSourcePlan) -> Self {
+        // Multiple filters are conjunctive.
+        let filter = plan
+            .get_push_downs()
+            .filters
+            .into_iter()
+            .reduce(|left, right| left.and(right));
+
+        PartReader {
+            schema: plan.schema.clone(),
+            filter,
+        }
+    }
+
+    pub async fn read(&self, fs: &dyn FileSystem, path: &str) -> Result<Vec<RecordBatch>> {
+        let mut reader = Self::fetch_footer(fs, path).await?;
+        let metadata = read::read_metadata(&mut reader)?;
+        let part_schema = DataSchemaRef::new(DataSchema::from(read::get_schema(&metadata)?));
+
+        let projection = self.projection(&part_schema)?;
+        let read_schema = DataSchemaRefExt::create(
+            projection
+                .iter()
+                .map(|index| part_schema.field(*index).clone())
+                .collect(),
+        );
+
+        let row_groups = match &self.filter {
+            None => vec![true; metadata.row_groups.len()],
+            Some(filter) => match RowGroupPruner::try_create(&part_schema, filter)? {
+                None => vec![true; metadata.row_groups.len()],
+                Some(pruner) => metadata
+                    .row_groups
+                    .iter()
+                    .map(|row_group| pruner.may_match(row_group))
+                    .collect::<Result<Vec<_>>>()?,
+            },
+        };
+
+        if !row_groups.iter().any(|need_read| *need_read) {
+            return Ok(vec![]);
+        }
+
+        for (start, length) in column_chunk_ranges(&metadata, &row_groups, &projection) {
+            reader.add_range(start, fs.read(path, start, length).await?);
+        }
+
+        let executor = match &self.filter {
+            None => None,
+            Some(filter) => Some(ExpressionExecutor::try_create(
+                "part filter executor",
+                read_schema.clone(),
+                DataSchemaRefExt::create(vec![filter.to_data_field(&read_schema)?]),
+                vec![filter.clone()],
+                false,
+            )?),
+        };
+
+        let reader = read::RecordReader::try_new(
+            reader,
+            Some(projection),
+            None,
+            Arc::new(move |index, _| row_groups[index]),
+        )?;
+
+        let mut batches = vec![];
+        for batch in reader {
+            let batch = match &executor {
+                None => batch?,
+                Some(executor) => self.filter_batch(executor, batch?)?,
+            };
+
+            if batch.num_rows() > 0 {
+                batches.push(self.project_batch(batch)?);
+            }
+        }
+        Ok(batches)
+    }
+
+    async fn fetch_footer(fs: &dyn FileSystem, path: &str) -> Result<RangesReader> {
+        let size = fs.size(path).await?;
+        if size < FOOTER_SIZE + PARQUET_MAGIC.len() as u64 {
+            return Err(ErrorCode::FileDamaged(format!(
+                "Invalid parquet file {}: the file size {} is too small",
+                path, size
+            )));
+        }
+
+        let tail = fs.read(path, size - FOOTER_SIZE, FOOTER_SIZE).await?;
+        if tail.len() as u64 != FOOTER_SIZE || &tail[4..] != PARQUET_MAGIC {
+            return Err(ErrorCode::FileDamaged(format!(
+                "Invalid parquet file {}: the magic number is not found",
+                path
+            )));
+        }
+
+        let metadata_len = u32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]) as u64;
+        let footer_start = size
+            .checked_sub(FOOTER_SIZE + metadata_len)
+            .ok_or_else(|| {
+                ErrorCode::FileDamaged(format!(
+                    "Invalid parquet file {}: the meta data length {} exceeds the file size {}",
+                    path, metadata_len, size
+                ))
+            })?;
+
+        let mut reader = RangesReader::create(size);
+        let footer = fs
+            .read(path, footer_start, metadata_len + FOOTER_SIZE)
+            .await?;
+        reader.add_range(footer_start, footer);
+        Ok(reader)
+    }
+
+    /// The indices of the columns to decode, which are in the order of the part schema.
+    fn projection(&self, part_schema: &DataSchemaRef) -> Result<Vec<usize>> {
+        let mut column_names = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>();
+
+        if let Some(filter) = &self.filter {
+            for expr in find_column_exprs(&[filter.clone()]) {
+                if let Expression::Column(name) = expr {
+                    if !column_names.contains(&name) {
+                        column_names.push(name);
+                    }
+                }
+            }
+        }
+
+        let mut projection = column_names
+            .iter()
+            .map(|name| part_schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        projection.sort_unstable();
+        Ok(projection)
+    }
+
+    fn filter_batch(
+        &self,
+        executor: &ExpressionExecutor,
+        batch: RecordBatch,
+    ) -> Result<RecordBatch> {
+        let block = DataBlock::try_from(batch.clone())?;
+        let filter_block = executor.execute(&block)?;
+        let filter_column_name = filter_block.schema().field(0).name().clone();
+        let filter_array = filter_block
+            .try_column_by_name(&filter_column_name)?
+            .to_array()?;
+        let filter_array = filter_array.cast_with_type(&DataType::Boolean)?;
+        let filter_array = filter_array.bool()?.downcast_ref();
+        Ok(arrow::compute::filter::filter_record_batch(
+            &batch,
+            filter_array,
+        )?)
+    }
+
+    /// Remove the columns only required by the filters, in the order of the projected schema.
+    fn project_batch(&self, batch: RecordBatch) -> Result<RecordBatch> {
+        let block = DataBlock::try_from(batch)?;
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| block.try_column_by_name(field.name()).cloned())
+            .collect::<Result<Vec<_>>>()?;
+        RecordBatch::try_from(DataBlock::create(self.schema.clone(), columns))
+    }
+}
+
+/// The byte ranges of the column chunks to decode, the adjacent ones are merged.
+fn column_chunk_ranges(
+    metadata: &FileMetaData,
+    row_groups: &[bool],
+    projection: &[usize],
+) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = vec![];
+    for (row_group, _) in metadata
+        .row_groups
+        .iter()
+        .zip(row_groups)
+        .filter(|(_, need_read)| **need_read)
+    {
+        for index in projection {
+            let column = &row_group.columns()[*index];
+            let start = column
+                .dictionary_page_offset()
+                .unwrap_or_else(|| column.data_page_offset()) as u64;
+            let length = column.compressed_size() as u64;
+
+            match ranges.last_mut() {
+                Some((last_start, last_length)) if *last_start + *last_length == start => {
+                    *last_length += length
+                }
+                _ => ranges.push((start, length)),
+            }
+        }
+    }
+    ranges
+}
+
+/// A `Read + Seek` view of a file of which only some byte ranges are fetched.
+/// Reading the bytes not fetched is an error.
+struct RangesReader {
+    size: u64,
+    position: u64,
+    ranges: BTreeMap<u64, Vec<u8>>,
+}
+
+impl RangesReader {
+    fn create(size: u64) -> Self {
+        RangesReader {
+            size,
+            position: 0,
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    fn add_range(&mut self, start: u64, data: Vec<u8>) {
+        self.ranges.insert(start, data);
+    }
+}
+
+impl Read for RangesReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.size || buf.is_empty() {
+            return Ok(0);
+        }
+
+        let position = self.position;
+        let (start, data) = self
+            .ranges
+            .range(..=position)
+            .next_back()
+            .filter(|(start, data)| position < *start + data.len() as u64)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("The byte at {} is not fetched", position),
+                )
+            })?;
+
+        let offset = (position - start) as usize;
+        let n = buf.len().min(data.len() - offset);
+        buf[..n].copy_from_slice(&data[offset..offset + n]);
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for RangesReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::End(offset) => self.size as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+
+        if position < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek to a negative position",
+            ));
+        }
+        self.position = position as u64;
+        Ok(self.position)
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    use common_arrow::arrow::io::parquet::read;
+    use common_datablocks::DataBlock;
+    use common_datavalues::prelude::*;
+    use common_planners::col;
+    use common_planners::lit;
+    use common_planners::Expression;
+    use common_planners::ReadDataSourcePlan;
+    use common_planners::ScanPlan;
+    use common_runtime::tokio;
+
+    use crate::data_part::appender::write_in_memory;
+    use crate::data_part::pruner::RowGroupPruner;
+    use crate::data_part::reader::PartReader;
+    use crate::fs::FileSystem;
+    use crate::localfs::LocalFS;
+
+    fn test_part() -> anyhow::Result<(DataSchemaRef, Vec<u8>)> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("a", DataType::Int64, false),
+            DataField::new("b", DataType::Utf8, false),
+            DataField::new("c", DataType::Float64, false),
+        ]);
+
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![1_i64, 2, 3]),
+            Series::new(vec!["x", "y", "z"]),
+            Series::new(vec![1.5_f64, 2.5, 3.5]),
+        ]);
+        Ok((schema, write_in_memory(block)?))
+    }
+
+    fn test_plan(schema: DataSchemaRef, filters: Vec<Expression>) -> ReadDataSourcePlan {
+        let mut scan_plan = ScanPlan::with_table_id(0, None);
+        scan_plan.push_downs.filters = filters;
+
+        let mut plan = ReadDataSourcePlan::empty(0, None);
+        plan.schema = schema;
+        plan.scan_plan = Arc::new(scan_plan);
+        plan
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_read_with_push_downs() -> anyhow::Result<()> {
+        let (schema, part) = test_part()?;
+        let dir = tempfile::tempdir()?;
+        let fs = LocalFS::try_create(dir.path().to_str().unwrap().to_string())?;
+        fs.add("part.parquet", &part).await?;
+        let projected_schema =
+            DataSchemaRefExt::create(vec![schema.field(2).clone(), schema.field(1).clone()]);
+
+        // Projection only.
+        let plan = test_plan(projected_schema.clone(), vec![]);
+        let batches = PartReader::create(&plan).read(&fs, "part.parquet").await?;
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].schema().as_ref(), &projected_schema.to_arrow());
+        assert_eq!(batches[0].num_rows(), 3);
+
+        // The filter column is not in the projection.
+        let plan = test_plan(projected_schema.clone(), vec![col("a").gt(lit(1))]);
+        let batches = PartReader::create(&plan).read(&fs, "part.parquet").await?;
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].schema().as_ref(), &projected_schema.to_arrow());
+        assert_eq!(
+            batches[0].column(1),
+            &Series::new(vec!["y", "z"]).get_array_ref()
+        );
+
+        // Multiple filters are conjunctive.
+        let plan = test_plan(projected_schema.clone(), vec![
+            col("a").gt(lit(1)),
+            col("b").not_eq(lit("z")),
+        ]);
+        let batches = PartReader::create(&plan).read(&fs, "part.parquet").await?;
+        assert_eq!(
+            batches[0].column(1),
+            &Series::new(vec!["y"]).get_array_ref()
+        );
+
+        // No rows match.
+        let plan = test_plan(projected_schema, vec![col("b").eq(lit("w"))]);
+        let batches = PartReader::create(&plan).read(&fs, "part.parquet").await?;
+        assert!(batches.is_empty());
+
+        // Not a parquet file.
+        fs.add("bad.parquet", "PAR1".as_bytes()).await?;
+        let got = PartReader::create(&plan).read(&fs, "bad.parquet").await;
+        assert_eq!(
+            "Invalid parquet file bad.parquet: the file size 4 is too small",
+            got.unwrap_err().message()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_group_pruner() -> anyhow::Result<()> {
+        let (schema, part) = test_part()?;
+        let metadata = read::read_metadata(&mut Cursor::new(part))?;
+        let row_group = &metadata.row_groups[0];
+
+        let tests = vec![
+            (col("a").gt(lit(3)), false),
+            (col("a").gt_eq(lit(3)), true),
+            (lit(0).gt(col("a")), false),
+            (col("a").eq(lit(2)), true),
+            (col("c").lt(lit(1.5)), false),
+            (col("b").eq(lit("w")), false),
+            (col("b").gt(lit("x")), true),
+            (col("a").gt(lit(3)).or(col("b").eq(lit("y"))), true),
+            (col("a").gt(lit(3)).and(col("b").eq(lit("y"))), false),
+            // The filter can't be pruned by the statistics.
+            (col("a").not_eq(lit(2)), true),
+        ];
+
+        for (filter, expect) in tests {
+            let may_match = match RowGroupPruner::try_create(&schema, &filter)? {
+                None => true,
+                Some(pruner) => pruner.may_match(row_group)?,
+            };
+            assert_eq!(may_match, expect, "{:?}", filter);
+        }
+
+        Ok(())
+    }
+}
@@ -19,6 +19,7 @@ use common_exception::exception;
 use common_exception::ErrorCode;
 use common_tracing::tracing;
 
+use crate::fs::FileReadStream;
 use crate::fs::FileSystem;
 use crate::fs::ListResult;
 use crate::localfs::LocalFS;
@@ -48,7 +49,19 @@ impl Dfs {
     }
 }
 
-impl Dfs {}
+impl Dfs {
+    /// Ensures the file is present in the meta data.
+    async fn check_file_meta(&self, key: &str) -> exception::Result<()> {
+        // TODO read from remote if file is not in local fs
+        // TODO(xp): week consistency, meta may not have been replicated to this node.
+
+        // meanwhile, file meta is empty string
+        let _file_meta = self.meta_node.get_file(key).await?.ok_or_else(|| {
+            ErrorCode::FileMetaNotFound(format!("dfs/meta: key not found: {:?}", key))
+        })?;
+        Ok(())
+    }
+}
 
 #[async_trait]
 impl FileSystem for Dfs {
@@ -73,15 +86,43 @@ impl FileSystem for Dfs {
 
     #[tracing::instrument(level = "debug", skip(self))]
     async fn read_all(&self, key: &str) -> exception::Result<Vec<u8>> {
-        // TODO read from remote if file is not in local fs
-        // TODO(xp): week consistency, meta may not have been replicated to this node.
+        self.check_file_meta(key).await?;
+        self.local_fs.read_all(key).await
+    }
 
-        // meanwhile, file meta is empty string
-        let _file_meta = self.meta_node.get_file(key).await?.ok_or_else(|| {
-            ErrorCode::FileMetaNotFound(format!("dfs/meta: key not found: {:?}", key))
-        })?;
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn read(&self, key: &str, offset: u64, length: u64) -> exception::Result<Vec<u8>> {
+        self.check_file_meta(key).await?;
+        self.local_fs.read(key, offset, length).await
+    }
 
-        self.local_fs.read_all(key).await
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn read_stream(&self, key: &str) -> exception::Result<FileReadStream> {
+        self.check_file_meta(key).await?;
+        self.local_fs.read_stream(key).await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn size(&self, key: &str) -> exception::Result<u64> {
+        self.check_file_meta(key).await?;
+        self.local_fs.size(key).await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete(&self, key: &str) -> common_exception::Result<()> {
+        self.check_file_meta(key).await?;
+
+        // remove the meta first, so that the file is invisible before the local copy is removed.
+
+        let req = LogEntry {
+            txid: None,
+            cmd: Cmd::DeleteFile {
+                key: key.to_string(),
+            },
+        };
+        let _resp = self.meta_node.write(req).await?;
+
+        self.local_fs.delete(key).await
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
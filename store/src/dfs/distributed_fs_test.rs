@@ -119,6 +119,42 @@ async fn test_distributed_fs_single_node_list() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_distributed_fs_single_node_read_and_delete() -> anyhow::Result<()> {
+    // - Brings a single node dfs online.
+    // - Write several files.
+    // - Test read(), size() and delete()
+
+    let files = hashmap! {
+        "foo" => "bar",
+        "ping" => "pong",
+    };
+    let dir = tempdir()?;
+    let (_tc, dfs) = bring_up_dfs(&dir, files.clone()).await?;
+
+    assert_eq!(4, dfs.size("ping").await?);
+    assert_eq!("on".as_bytes(), dfs.read("ping", 1, 2).await?);
+
+    dfs.delete("ping").await?;
+
+    let got = dfs.list("").await?;
+    assert_eq!(vec!["foo".to_string()], got.files);
+
+    let got = dfs.read("ping", 1, 2).await;
+    assert_eq!(
+        "dfs/meta: key not found: \"ping\"",
+        got.unwrap_err().message()
+    );
+
+    let got = dfs.delete("ping").await;
+    assert_eq!(
+        "dfs/meta: key not found: \"ping\"",
+        got.unwrap_err().message()
+    );
+
+    Ok(())
+}
+
 // Start an dfs.
 // And feed files into dfs.
 async fn bring_up_dfs(
@@ -12,29 +12,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Cursor;
 use std::pin::Pin;
 use std::sync::Arc;
 
 use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
-use common_arrow::arrow::io::parquet::read;
 use common_arrow::arrow_flight::utils::flight_data_from_arrow_batch;
 use common_arrow::arrow_flight::FlightData;
 use common_exception::ErrorCode;
+use common_flights::meta_api_impl::GetTableAction;
 use common_flights::storage_api_impl::AppendResult;
 use common_flights::storage_api_impl::ReadAction;
+use common_flights::FlightClaim;
 use common_flights::RequestFor;
 use common_flights::StoreDoAction;
+use common_management::USER_API_KEY_PREFIX;
 use common_planners::PlanNode;
+use common_runtime::tokio;
 use common_runtime::tokio::sync::mpsc::Sender;
 use futures::Stream;
 use serde::Serialize;
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
 use tonic::Status;
 use tonic::Streaming;
 
 use crate::data_part::appender::Appender;
+use crate::data_part::reader::PartReader;
+use crate::executor::query_streams::QueryStreams;
 use crate::fs::FileSystem;
+use crate::meta_service::table_history;
+use crate::meta_service::AppliedState;
+use crate::meta_service::Cmd;
+use crate::meta_service::LogEntry;
 use crate::meta_service::MetaNode;
 
 pub trait ReplySerializer {
@@ -48,7 +57,9 @@ pub struct ActionHandler {
     /// In our design meta serves for both the distributed file system and the catalogs storage such as db,tabel etc.
     /// Thus in case the `fs` is a Dfs impl, `meta_node` is just a reference to the `Dfs.meta_node`.
     pub(crate) meta_node: Arc<MetaNode>,
-    fs: Arc<dyn FileSystem>,
+    pub(crate) fs: Arc<dyn FileSystem>,
+    /// The reads and appends in progress, to be cancelled when their query is killed.
+    pub(crate) query_streams: QueryStreams,
 }
 
 // TODO did this already defined somewhere?
@@ -64,7 +75,11 @@ where T: RequestFor
 
 impl ActionHandler {
     pub fn create(fs: Arc<dyn FileSystem>, meta_node: Arc<MetaNode>) -> Self {
-        ActionHandler { meta_node, fs }
+        ActionHandler {
+            meta_node,
+            fs,
+            query_streams: QueryStreams::default(),
+        }
     }
 
     /// Handle pull-file request, which is used internally for replicating data copies.
@@ -89,8 +104,17 @@ impl ActionHandler {
         .map_err(|e| Status::internal(format!("{:?}", e)))
     }
 
-    pub async fn execute<S, R>(&self, action: StoreDoAction, s: S) -> common_exception::Result<R>
-    where S: ReplySerializer<Output = R> {
+    pub async fn execute<S, R>(
+        &self,
+        claim: &FlightClaim,
+        action: StoreDoAction,
+        s: S,
+    ) -> common_exception::Result<R>
+    where
+        S: ReplySerializer<Output = R>,
+    {
+        check_permission(claim, &action)?;
+
         // To keep the code IDE-friendly, we manually expand the enum variants and dispatch them one by one
 
         match action {
@@ -105,7 +129,10 @@ impl ActionHandler {
             StoreDoAction::DropTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTableExt(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::GetTableVersion(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::TruncateTable(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::SetTableStatistics(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::GetTableStatistics(a) => s.serialize(self.handle(a).await?),
 
             // part
             StoreDoAction::ReadPlan(a) => s.serialize(self.handle(a).await?),
@@ -118,34 +145,73 @@ impl ActionHandler {
 
             // session
             StoreDoAction::KillQuery(a) => s.serialize(self.handle(a).await?),
+
+            // backup
+            StoreDoAction::Backup(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::Restore(a) => s.serialize(self.handle(a).await?),
+
+            // cluster membership
+            StoreDoAction::GetMembership(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::AddLearner(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::ChangeMembership(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::RemoveNode(a) => s.serialize(self.handle(a).await?),
         }
     }
 
+    /// Appends the data to a table, all or nothing:
+    /// the parts are written first, then published to the table meta by a single raft log.
+    /// If the query is killed before the parts are published, the parts written are removed.
     pub(crate) async fn do_put(
         &self,
+        query_id: String,
         db_name: String,
         table_name: String,
         parts: Streaming<FlightData>,
     ) -> common_exception::Result<AppendResult> {
-        {
-            // TODO:  Validates the schema of input stream:
-            // The schema of `parts` should be a subset of
-            // table's current schema (or following the evolution rules of table schema)
-        }
+        let cancel = self.query_streams.register(&query_id);
 
-        let appender = Appender::new(self.fs.clone());
-        let parts = parts
-            .take_while(|item| item.is_ok())
-            .map(|item| item.unwrap());
+        let table = self
+            .handle(GetTableAction {
+                db: db_name.clone(),
+                table: table_name.clone(),
+            })
+            .await?;
 
+        let appender = Appender::new(self.fs.clone());
+        let parts = cancel.wrap_stream(parts.map(|item| item.map_err(ErrorCode::from)));
         let res = appender
-            .append_data(format!("{}/{}", &db_name, &table_name), Box::pin(parts))
+            .append_data(
+                format!("{}/{}", &db_name, &table_name),
+                &table.schema,
+                Box::pin(parts),
+            )
             .await?;
 
-        self.meta_node
-            .append_data_parts(&db_name, &table_name, &res)
-            .await;
-        Ok(res)
+        let cmd = LogEntry {
+            txid: None,
+            cmd: Cmd::AppendDataParts {
+                db_name: db_name.clone(),
+                table_name: table_name.clone(),
+                table_id: table.table_id,
+                append_res: res.clone(),
+                time_ms: table_history::now_ms(),
+            },
+        };
+
+        // If the raft write fails, it is unknown whether the parts are published, thus they are kept.
+        match self.meta_node.write(cmd).await? {
+            AppliedState::DataParts {
+                result: Some(_), ..
+            } => Ok(res),
+            AppliedState::DataParts { result: None, .. } => {
+                appender.remove_parts(&res).await;
+                Err(ErrorCode::UnknownTable(format!(
+                    "table {}.{} has been dropped during the append",
+                    db_name, table_name
+                )))
+            }
+            _ => Err(ErrorCode::MetaNodeInternalError("not a DataParts result")),
+        }
     }
 
     pub async fn read_partition(
@@ -153,6 +219,7 @@ impl ActionHandler {
         action: ReadAction,
     ) -> common_exception::Result<DoGetStream> {
         log::info!("entering read");
+        let mut cancel = self.query_streams.register(&action.query_id);
         let part_file = action.part.name;
 
         let plan = if let PlanNode::ReadSource(read_source_plan) = action.push_down {
@@ -161,34 +228,81 @@ impl ActionHandler {
             return Err(ErrorCode::IllegalScanPlan("invalid PlanNode passed in"));
         };
 
-        // before push_down is passed in, we returns all the columns
-        let schema = plan.schema;
-        let projection = (0..schema.fields().len()).collect::<Vec<_>>();
-
-        // TODO expose a reader from fs
-        let content = self.fs.read_all(&part_file).await?;
-        let reader = Cursor::new(content);
-
-        let reader = read::RecordReader::try_new(
-            reader,
-            Some(projection.to_vec()),
-            None,
-            Arc::new(|_, _| true),
-        )?;
+        // Only the projected columns and the rows matching the pushed down filters are sent back
+        let reader = PartReader::create(&plan);
+        let batches = tokio::select! {
+            _ = &mut cancel => return Err(cancel.aborted()),
+            batches = reader.read(self.fs.as_ref(), &part_file) => batches?,
+        };
 
         // For simplicity, we do the conversion in-memory, to be optimized later
         // TODO consider using `parquet_table` and `stream_parquet`
         let write_opt = IpcWriteOptions::default();
-        let flights =
-            reader
-                .into_iter()
-                .map(|batch| {
-                    batch.map(
-                    |b| flight_data_from_arrow_batch(&b, &write_opt).1, /*dictionary ignored*/
-                ).map_err(|arrow_err| Status::internal(arrow_err.to_string()))
-                })
-                .collect::<Vec<_>>();
-        let stream = futures::stream::iter(flights);
-        Ok(Box::pin(stream))
+        let flights = batches
+            .iter()
+            // dictionary ignored
+            .map(|b| flight_data_from_arrow_batch(b, &write_opt).1)
+            .collect::<Vec<_>>();
+
+        // The stream stays registered until all the data is sent or the client goes away.
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        tokio::spawn(async move {
+            for flight in flights {
+                tokio::select! {
+                    _ = &mut cancel => {
+                        // Do not wait for a client that does not receive any more.
+                        let _ = tx.try_send(Err(Status::from(cancel.aborted())));
+                        return;
+                    }
+                    sent = tx.send(Ok(flight)) => {
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+}
+
+/// Only the built-in user, i.e., the one without a user record, is allowed to access the user records,
+/// which contain the password hashes of all the users.
+/// A backup or a restore also accesses the user records, along with all the other kv records.
+/// Changing the cluster membership is also reserved for the built-in user.
+fn check_permission(claim: &FlightClaim, action: &StoreDoAction) -> common_exception::Result<()> {
+    if claim.user_seq.is_none() {
+        return Ok(());
+    }
+
+    if let StoreDoAction::AddLearner(_)
+    | StoreDoAction::ChangeMembership(_)
+    | StoreDoAction::RemoveNode(_) = action
+    {
+        return Err(ErrorCode::PermissionDenied(format!(
+            "Permission denied: user {} can not change the cluster membership",
+            claim.username
+        )));
+    }
+
+    let is_user_key = |key: &String| key.starts_with(USER_API_KEY_PREFIX);
+    let access_users = match action {
+        StoreDoAction::UpsertKV(a) => is_user_key(&a.key),
+        StoreDoAction::GetKV(a) => is_user_key(&a.key),
+        StoreDoAction::MGetKV(a) => a.keys.iter().any(is_user_key),
+        // A shorter prefix also lists the user records.
+        StoreDoAction::PrefixListKV(a) => {
+            is_user_key(&a.0) || USER_API_KEY_PREFIX.starts_with(&a.0)
+        }
+        StoreDoAction::Backup(_) | StoreDoAction::Restore(_) => true,
+        _ => false,
+    };
+
+    if access_users {
+        return Err(ErrorCode::PermissionDenied(format!(
+            "Permission denied: user {} can not access the user records",
+            claim.username
+        )));
     }
+    Ok(())
 }
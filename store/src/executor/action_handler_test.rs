@@ -53,6 +53,8 @@ use crate::executor::action_handler::RequestHandler;
 use crate::executor::ActionHandler;
 use crate::fs::FileSystem;
 use crate::localfs::LocalFS;
+use crate::meta_service::Cmd;
+use crate::meta_service::LogEntry;
 use crate::meta_service::MetaNode;
 use crate::tests::service::new_test_context;
 use crate::tests::service::StoreTestContext;
@@ -110,6 +112,7 @@ async fn test_action_handler_add_database() -> anyhow::Result<()> {
             if_not_exists,
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
         let want = match want {
             Ok(want_db_id) => Ok(CreateDatabaseActionResult {
@@ -196,6 +199,7 @@ async fn test_action_handler_get_database() -> anyhow::Result<()> {
                 if_not_exists: false,
                 engine: DatabaseEngineType::Local,
                 options: Default::default(),
+                clone_from: None,
             };
             let cba = CreateDatabaseAction { plan };
             hdlr.handle(cba).await?;
@@ -274,6 +278,7 @@ async fn test_action_handler_drop_database() -> anyhow::Result<()> {
                 if_not_exists: false,
                 engine: DatabaseEngineType::Local,
                 options: Default::default(),
+                clone_from: None,
             };
             let cba = CreateDatabaseAction { plan };
             hdlr.handle(cba).await?;
@@ -329,6 +334,7 @@ async fn test_action_handler_create_table() -> anyhow::Result<()> {
             if_not_exists,
             engine: DatabaseEngineType::Local,
             options: Default::default(),
+            clone_from: None,
         };
         let want = match want {
             Ok(want_db_id) => Ok(CreateDatabaseActionResult {
@@ -364,6 +370,7 @@ async fn test_action_handler_create_table() -> anyhow::Result<()> {
             schema: schema.clone(),
             engine: TableEngineType::JSONEachRow,
             options: Default::default(),
+            clone_from: None,
         };
         let want = match want {
             Ok(want_table_id) => Ok(CreateTableActionResult {
@@ -486,6 +493,7 @@ async fn test_action_handler_get_table() -> anyhow::Result<()> {
                 if_not_exists: false,
                 engine: DatabaseEngineType::Local,
                 options: Default::default(),
+                clone_from: None,
             };
             let cba = CreateDatabaseAction { plan };
             hdlr.handle(cba).await?;
@@ -506,6 +514,7 @@ async fn test_action_handler_get_table() -> anyhow::Result<()> {
                 schema: schema.clone(),
                 engine: TableEngineType::JSONEachRow,
                 options: Default::default(),
+                clone_from: None,
             };
             let cta = CreateTableAction { plan };
             hdlr.handle(cta).await?;
@@ -595,6 +604,7 @@ async fn test_action_handler_drop_table() -> anyhow::Result<()> {
                 if_not_exists: false,
                 engine: DatabaseEngineType::Local,
                 options: Default::default(),
+                clone_from: None,
             };
             let cba = CreateDatabaseAction { plan };
             hdlr.handle(cba).await?;
@@ -615,6 +625,7 @@ async fn test_action_handler_drop_table() -> anyhow::Result<()> {
                 schema: schema.clone(),
                 engine: TableEngineType::JSONEachRow,
                 options: Default::default(),
+                clone_from: None,
             };
             let cta = CreateTableAction { plan };
             hdlr.handle(cta).await?;
@@ -699,6 +710,7 @@ async fn test_action_handler_trancate_table() -> anyhow::Result<()> {
                 if_not_exists: false,
                 engine: DatabaseEngineType::Local,
                 options: Default::default(),
+                clone_from: None,
             };
             let cba = CreateDatabaseAction { plan };
             hdlr.handle(cba).await?;
@@ -719,6 +731,7 @@ async fn test_action_handler_trancate_table() -> anyhow::Result<()> {
                 schema: schema.clone(),
                 engine: TableEngineType::JSONEachRow,
                 options: Default::default(),
+                clone_from: None,
             };
             let cta = CreateTableAction { plan };
             hdlr.handle(cta).await?;
@@ -728,9 +741,19 @@ async fn test_action_handler_trancate_table() -> anyhow::Result<()> {
         let mut append_result = AppendResult::default();
         let location = format!("{}/{}", "path", "part_uuid");
         append_result.append_part(&location, 1, 1, 1, 1);
+        let table_id = hdlr.meta_node.get_database("foo").await.unwrap().tables["foo_t1"];
         hdlr.meta_node
-            .append_data_parts("foo", "foo_t1", &append_result)
-            .await;
+            .write(LogEntry {
+                txid: None,
+                cmd: Cmd::AppendDataParts {
+                    db_name: "foo".to_string(),
+                    table_name: "foo_t1".to_string(),
+                    table_id,
+                    append_res: append_result,
+                    time_ms: 1,
+                },
+            })
+            .await?;
         let mut before_parts_len: usize = 0;
         let before_parts = hdlr.meta_node.get_data_parts("foo", "foo_t1").await;
         if let Some(before_parts) = before_parts {
@@ -0,0 +1,302 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::fs::OpenOptions;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use common_exception::ErrorCode;
+use common_flights::backup_api_impl::BackupReq;
+use common_flights::backup_api_impl::BackupResult;
+use common_flights::backup_api_impl::RestoreReq;
+use common_metatypes::Database;
+use common_metatypes::MatchSeq;
+use common_metatypes::Table;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sled::IVec;
+
+use crate::executor::action_handler::RequestHandler;
+use crate::executor::ActionHandler;
+use crate::fs::FileSystem;
+use crate::localfs::LocalFS;
+use crate::meta_service::backup;
+use crate::meta_service::backup::DatabaseBackup;
+use crate::meta_service::snapshot::write_snapshot_data;
+use crate::meta_service::table_history;
+use crate::meta_service::AppliedState;
+use crate::meta_service::Cmd;
+use crate::meta_service::LogEntry;
+use crate::meta_service::SnapshotReader;
+
+/// The manifest of a backup. It is written last, thus a backup without it is incomplete.
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// The state machine snapshot written by `write_snapshot_data()`.
+pub const SNAPSHOT_FILE: &str = "state_machine.snapshot";
+/// The dir of the part files, in which a part has the same path as in the store.
+pub const PARTS_DIR: &str = "parts";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BackupManifest {
+    pub snapshot_id: String,
+    pub databases: Vec<DatabaseBackup>,
+    /// Every file in the backup except the manifest.
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BackupFile {
+    /// The path relative to the backup dir.
+    pub path: String,
+    pub size: u64,
+    /// The hex encoded sha256 of the content.
+    pub sha256: String,
+}
+
+impl BackupFile {
+    pub fn create(path: &str, data: &[u8]) -> Self {
+        BackupFile {
+            path: path.to_string(),
+            size: data.len() as u64,
+            sha256: format!("{:x}", sha2::Sha256::digest(data)),
+        }
+    }
+
+    /// Checks the file in the backup dir chunk by chunk.
+    pub async fn check(&self, dir: &LocalFS) -> common_exception::Result<()> {
+        let mut hasher = sha2::Sha256::new();
+        let mut size = 0;
+        let mut chunks = dir.read_stream(&self.path).await?;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            hasher.update(&chunk);
+            size += chunk.len() as u64;
+        }
+
+        if size != self.size || format!("{:x}", hasher.finalize()) != self.sha256 {
+            return Err(ErrorCode::FileDamaged(format!(
+                "backup file {} is damaged: the size or the checksum mismatches",
+                self.path
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn part_path(part_name: &str) -> String {
+    format!("{}/{}", PARTS_DIR, part_name)
+}
+
+/// Writes the state machine data into the snapshot file of a backup, without holding it in memory.
+fn write_snapshot(
+    dir: &Path,
+    view: impl Iterator<Item = sled::Result<(IVec, IVec)>>,
+) -> common_exception::Result<BackupFile> {
+    std::fs::create_dir_all(dir)?;
+    let f = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dir.join(SNAPSHOT_FILE))?;
+
+    let mut w = BufWriter::new(HashWriter {
+        inner: f,
+        hasher: sha2::Sha256::new(),
+        size: 0,
+    });
+    write_snapshot_data(&mut w, view)?;
+    let w = w.into_inner().map_err(std::io::Error::from)?;
+    w.inner.sync_all()?;
+
+    Ok(BackupFile {
+        path: SNAPSHOT_FILE.to_string(),
+        size: w.size,
+        sha256: format!("{:x}", w.hasher.finalize()),
+    })
+}
+
+/// Counts the size and computes the checksum of the data written through it.
+struct HashWriter<W: Write> {
+    inner: W,
+    hasher: sha2::Sha256,
+    size: u64,
+}
+
+impl<W: Write> Write for HashWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Writes the state machine snapshot, the catalog and the files of the parts referenced by
+/// the latest version of every table into a dir, which must not contain a backup already.
+#[async_trait::async_trait]
+impl RequestHandler<BackupReq> for ActionHandler {
+    async fn handle(&self, req: BackupReq) -> common_exception::Result<BackupResult> {
+        let meta = self.meta_node.backup().await?;
+        let mut files = vec![write_snapshot(Path::new(&req.dir), meta.snapshot)?];
+        let dir = LocalFS::try_create(req.dir)?;
+
+        let part_names = backup::part_names(&meta.databases);
+        for name in &part_names {
+            // The parts are read after the meta is taken,
+            // a part is gone only if all the tables referencing it are dropped meanwhile.
+            let data =
+                self.fs.read_all(name).await.map_err(|e| {
+                    e.add_message(format!("fail to back up part {}, try again", name))
+                })?;
+            let path = part_path(name);
+            dir.add(&path, &data).await?;
+            files.push(BackupFile::create(&path, &data));
+        }
+
+        let res = BackupResult {
+            snapshot_id: meta.snapshot_id.clone(),
+            databases: meta.databases.len(),
+            tables: meta.databases.iter().map(|db| db.tables.len()).sum(),
+            parts: part_names.len(),
+        };
+
+        let manifest = BackupManifest {
+            snapshot_id: meta.snapshot_id,
+            databases: meta.databases,
+            files,
+        };
+        dir.add(MANIFEST_FILE, &serde_json::to_vec_pretty(&manifest)?)
+            .await?;
+
+        Ok(res)
+    }
+}
+
+/// Rebuilds a fresh cluster from a backup: the general purpose kv records,
+/// the databases and the tables with their parts.
+/// The ids and the seq numbers are newly assigned, and every table starts a new history.
+#[async_trait::async_trait]
+impl RequestHandler<RestoreReq> for ActionHandler {
+    async fn handle(&self, req: RestoreReq) -> common_exception::Result<BackupResult> {
+        let dir = LocalFS::try_create(req.dir.clone())?;
+        let manifest: BackupManifest = serde_json::from_slice(&dir.read_all(MANIFEST_FILE).await?)?;
+
+        // Nothing is changed unless every file of the backup is intact.
+        for file in &manifest.files {
+            file.check(&dir).await?;
+        }
+
+        let has_databases = matches!(
+            self.meta_node.get_database_meta(None).await?,
+            Some((_, dbs, _)) if !dbs.is_empty()
+        );
+        let has_kvs = !self.meta_node.prefix_list_kv("").await?.is_empty();
+        if has_databases || has_kvs {
+            return Err(ErrorCode::IllegalMetaState(
+                "can only restore a backup to a fresh cluster without any database or kv record",
+            ));
+        }
+
+        let snapshot = SnapshotReader::open(&Path::new(&req.dir).join(SNAPSHOT_FILE))?;
+        for (key, (_seq, kv)) in backup::snapshot_generic_kvs(snapshot)? {
+            self.restore_write(Cmd::UpsertKV {
+                key,
+                seq: MatchSeq::Exact(0),
+                value: Some(kv.value),
+                value_meta: kv.meta,
+            })
+            .await?;
+        }
+
+        // The parts are in place before any table references them.
+        let part_names = backup::part_names(&manifest.databases);
+        for name in &part_names {
+            let data = dir.read_all(&part_path(name)).await?;
+            self.fs.add(name, &data).await?;
+        }
+
+        for db in &manifest.databases {
+            self.restore_write(Cmd::CreateDatabase {
+                name: db.name.clone(),
+                if_not_exists: false,
+                db: Database {
+                    database_id: 0,
+                    tables: Default::default(),
+                },
+            })
+            .await?;
+
+            for table in &db.tables {
+                let created = self
+                    .restore_write(Cmd::CreateTable {
+                        db_name: db.name.clone(),
+                        table_name: table.name.clone(),
+                        if_not_exists: false,
+                        table: Table {
+                            table_id: 0,
+                            schema: table.schema.clone(),
+                            parts: Default::default(),
+                        },
+                    })
+                    .await?;
+                let table_id = match created {
+                    AppliedState::Table {
+                        result: Some(t), ..
+                    } => t.table_id,
+                    _ => {
+                        return Err(ErrorCode::MetaNodeInternalError(format!(
+                            "fail to restore table {}.{}",
+                            db.name, table.name
+                        )))
+                    }
+                };
+
+                if table.parts.is_empty() {
+                    continue;
+                }
+                self.restore_write(Cmd::RestoreDataParts {
+                    db_name: db.name.clone(),
+                    table_name: table.name.clone(),
+                    table_id,
+                    parts: table.parts.clone(),
+                    time_ms: table_history::now_ms(),
+                })
+                .await?;
+            }
+        }
+
+        Ok(BackupResult {
+            snapshot_id: manifest.snapshot_id,
+            databases: manifest.databases.len(),
+            tables: manifest.databases.iter().map(|db| db.tables.len()).sum(),
+            parts: part_names.len(),
+        })
+    }
+}
+
+impl ActionHandler {
+    async fn restore_write(&self, cmd: Cmd) -> common_exception::Result<AppliedState> {
+        self.meta_node
+            .write(LogEntry { txid: None, cmd })
+            .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))
+    }
+}
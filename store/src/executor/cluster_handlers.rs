@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use common_flights::cluster_api_impl::AddLearnerReq;
+use common_flights::cluster_api_impl::ChangeMembershipReq;
+use common_flights::cluster_api_impl::ClusterMembership;
+use common_flights::cluster_api_impl::GetMembershipReq;
+use common_flights::cluster_api_impl::RemoveNodeReq;
+
+use crate::executor::action_handler::RequestHandler;
+use crate::executor::ActionHandler;
+use crate::meta_service::MembershipChange;
+
+#[async_trait::async_trait]
+impl RequestHandler<GetMembershipReq> for ActionHandler {
+    async fn handle(&self, _act: GetMembershipReq) -> common_exception::Result<ClusterMembership> {
+        self.meta_node.get_membership().await
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<AddLearnerReq> for ActionHandler {
+    async fn handle(&self, act: AddLearnerReq) -> common_exception::Result<ClusterMembership> {
+        self.meta_node
+            .change_membership(MembershipChange::AddLearner {
+                node_id: act.node_id,
+                address: act.address,
+            })
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<ChangeMembershipReq> for ActionHandler {
+    async fn handle(
+        &self,
+        act: ChangeMembershipReq,
+    ) -> common_exception::Result<ClusterMembership> {
+        self.meta_node
+            .change_membership(MembershipChange::ChangeMembership { voters: act.voters })
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<RemoveNodeReq> for ActionHandler {
+    async fn handle(&self, act: RemoveNodeReq) -> common_exception::Result<ClusterMembership> {
+        self.meta_node
+            .change_membership(MembershipChange::RemoveNode {
+                node_id: act.node_id,
+            })
+            .await
+    }
+}
@@ -38,16 +38,27 @@ use common_flights::meta_api_impl::GetDatabaseMetaAction;
 use common_flights::meta_api_impl::GetTableAction;
 use common_flights::meta_api_impl::GetTableActionResult;
 use common_flights::meta_api_impl::GetTableExtReq;
+use common_flights::meta_api_impl::GetTableStatisticsAction;
+use common_flights::meta_api_impl::GetTableVersionAction;
+use common_flights::meta_api_impl::SetTableStatisticsAction;
+use common_flights::meta_api_impl::SetTableStatisticsActionResult;
+use common_flights::meta_api_impl::TableStatisticsReply;
 use common_metatypes::Database;
+use common_metatypes::MetaVersion;
 use common_metatypes::Table;
+use common_metatypes::TableVersionAt;
 use log::info;
 
 use crate::executor::action_handler::RequestHandler;
 use crate::executor::ActionHandler;
+use crate::meta_service::cmd::Cmd::CloneDatabase;
+use crate::meta_service::cmd::Cmd::CloneTable;
 use crate::meta_service::cmd::Cmd::CreateDatabase;
 use crate::meta_service::cmd::Cmd::CreateTable;
 use crate::meta_service::cmd::Cmd::DropDatabase;
 use crate::meta_service::cmd::Cmd::DropTable;
+use crate::meta_service::cmd::Cmd::SetTableStatistics;
+use crate::meta_service::table_history;
 use crate::meta_service::AppliedState;
 use crate::meta_service::LogEntry;
 
@@ -62,9 +73,8 @@ impl RequestHandler<CreateDatabaseAction> for ActionHandler {
         let db_name = &plan.db;
         let if_not_exists = plan.if_not_exists;
 
-        let cr = LogEntry {
-            txid: None,
-            cmd: CreateDatabase {
+        let cmd = match &plan.clone_from {
+            None => CreateDatabase {
                 name: db_name.clone(),
                 if_not_exists,
                 db: Database {
@@ -72,7 +82,14 @@ impl RequestHandler<CreateDatabaseAction> for ActionHandler {
                     tables: HashMap::new(),
                 },
             },
+            Some(src_name) => CloneDatabase {
+                name: db_name.clone(),
+                src_name: src_name.clone(),
+                if_not_exists,
+                time_ms: table_history::now_ms(),
+            },
         };
+        let cr = LogEntry { txid: None, cmd };
 
         let rst = self
             .meta_node
@@ -94,9 +111,16 @@ impl RequestHandler<CreateDatabaseAction> for ActionHandler {
                         )))
                     }
                 } else {
-                    Ok(CreateDatabaseActionResult {
-                        database_id: result.unwrap().database_id,
-                    })
+                    match result {
+                        Some(db) => Ok(CreateDatabaseActionResult {
+                            database_id: db.database_id,
+                        }),
+                        // Only a clone fails to create, when the source is absent.
+                        None => Err(ErrorCode::UnknownDatabase(format!(
+                            "database not found: {:}",
+                            plan.clone_from.clone().unwrap_or_default()
+                        ))),
+                    }
                 }
             }
 
@@ -187,15 +211,23 @@ impl RequestHandler<CreateTableAction> for ActionHandler {
             parts: Default::default(),
         };
 
-        let cr = LogEntry {
-            txid: None,
-            cmd: CreateTable {
+        let cmd = match &plan.clone_from {
+            None => CreateTable {
                 db_name: db_name.clone(),
                 table_name: table_name.clone(),
                 if_not_exists,
                 table,
             },
+            Some((src_db_name, src_table_name)) => CloneTable {
+                db_name: db_name.clone(),
+                table_name: table_name.clone(),
+                src_db_name: src_db_name.clone(),
+                src_table_name: src_table_name.clone(),
+                if_not_exists,
+                time_ms: table_history::now_ms(),
+            },
         };
+        let cr = LogEntry { txid: None, cmd };
 
         let rst = self
             .meta_node
@@ -217,9 +249,20 @@ impl RequestHandler<CreateTableAction> for ActionHandler {
                         )))
                     }
                 } else {
-                    Ok(CreateTableActionResult {
-                        table_id: result.unwrap().table_id,
-                    })
+                    match result {
+                        Some(table) => Ok(CreateTableActionResult {
+                            table_id: table.table_id,
+                        }),
+                        // Only a clone fails to create, when the source or the database is absent.
+                        None => {
+                            let (src_db_name, src_table_name) =
+                                plan.clone_from.clone().unwrap_or_default();
+                            Err(ErrorCode::UnknownTable(format!(
+                                "table not found: {:}.{:}, or database not found: {:}",
+                                src_db_name, src_table_name, db_name
+                            )))
+                        }
+                    }
                 }
             }
             _ => Err(ErrorCode::MetaNodeInternalError("not a Table result")),
@@ -315,6 +358,13 @@ impl RequestHandler<GetTableExtReq> for ActionHandler {
         let result = self.meta_node.get_table(&table_id).await;
         match result {
             Some(table) => {
+                // The schema is the same in all the versions, but the version has to exist.
+                if let Some(version) = act.tbl_ver {
+                    self.meta_node
+                        .get_table_version(table_id, &TableVersionAt::Version(version))
+                        .await?;
+                }
+
                 let arrow_schema = ArrowSchema::try_from(&FlightData {
                     data_header: table.schema,
                     ..Default::default()
@@ -339,6 +389,21 @@ impl RequestHandler<GetTableExtReq> for ActionHandler {
     }
 }
 
+#[async_trait::async_trait]
+impl RequestHandler<GetTableVersionAction> for ActionHandler {
+    async fn handle(&self, act: GetTableVersionAction) -> common_exception::Result<MetaVersion> {
+        let db = self.meta_node.get_database(&act.db).await.ok_or_else(|| {
+            ErrorCode::UnknownDatabase(format!("database not found: {:}", act.db))
+        })?;
+        let table_id = db
+            .tables
+            .get(&act.table)
+            .ok_or_else(|| ErrorCode::UnknownTable(format!("table not found: {:}", act.table)))?;
+
+        self.meta_node.get_table_version(*table_id, &act.at).await
+    }
+}
+
 #[async_trait::async_trait]
 impl RequestHandler<GetDatabaseMetaAction> for ActionHandler {
     async fn handle(
@@ -357,3 +422,59 @@ impl RequestHandler<GetDatabaseMetaAction> for ActionHandler {
         }))
     }
 }
+
+#[async_trait::async_trait]
+impl RequestHandler<SetTableStatisticsAction> for ActionHandler {
+    async fn handle(
+        &self,
+        act: SetTableStatisticsAction,
+    ) -> common_exception::Result<SetTableStatisticsActionResult> {
+        let cr = LogEntry {
+            txid: None,
+            cmd: SetTableStatistics {
+                db_name: act.db.clone(),
+                table_name: act.table.clone(),
+                statistics: act.statistics,
+            },
+        };
+
+        let rst = self
+            .meta_node
+            .write(cr)
+            .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))?;
+
+        match rst {
+            AppliedState::TableStatistics {
+                result: Some(_), ..
+            } => Ok(SetTableStatisticsActionResult {}),
+            _ => Err(ErrorCode::UnknownTable(format!(
+                "table not found: {}.{}",
+                act.db, act.table
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<GetTableStatisticsAction> for ActionHandler {
+    async fn handle(
+        &self,
+        act: GetTableStatisticsAction,
+    ) -> common_exception::Result<TableStatisticsReply> {
+        let db = self.meta_node.get_database(&act.db).await.ok_or_else(|| {
+            ErrorCode::UnknownDatabase(format!("database not found: {:}", act.db))
+        })?;
+        if !db.tables.contains_key(&act.table) {
+            return Err(ErrorCode::UnknownTable(format!(
+                "table not found: {:}",
+                act.table
+            )));
+        }
+
+        Ok(self
+            .meta_node
+            .get_table_statistics(&act.db, &act.table)
+            .await)
+    }
+}
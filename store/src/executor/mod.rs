@@ -19,7 +19,12 @@ pub use action_handler::ReplySerializer;
 
 #[cfg(test)]
 mod action_handler_test;
+mod backup_handlers;
+mod cluster_handlers;
 mod kv_handlers;
 mod meta_handlers;
+mod query_streams;
+#[cfg(test)]
+mod query_streams_test;
 mod session_handlers;
 mod storage_handlers;
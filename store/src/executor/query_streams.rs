@@ -0,0 +1,150 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_runtime::tokio;
+use common_runtime::tokio::sync::oneshot;
+use futures::Stream;
+use futures::StreamExt;
+
+type Cancellers = HashMap<String, Vec<(u64, oneshot::Sender<()>)>>;
+
+/// The active streams of the queries, i.e., the reads and appends in progress, by query id.
+/// Killing a query cancels all of its streams.
+#[derive(Clone, Default)]
+pub struct QueryStreams {
+    cancellers: Arc<Mutex<Cancellers>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl QueryStreams {
+    /// Registers a stream of the query, which is deregistered when the returned signal is dropped.
+    /// A stream without query id can not be cancelled.
+    pub fn register(&self, query_id: &str) -> CancelSignal {
+        if query_id.is_empty() {
+            return CancelSignal {
+                query_id: String::new(),
+                rx: None,
+                _guard: None,
+            };
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.cancellers
+            .lock()
+            .entry(query_id.to_string())
+            .or_insert_with(Vec::new)
+            .push((id, tx));
+
+        CancelSignal {
+            query_id: query_id.to_string(),
+            rx: Some(rx),
+            _guard: Some(StreamGuard {
+                query_id: query_id.to_string(),
+                id,
+                cancellers: self.cancellers.clone(),
+            }),
+        }
+    }
+
+    /// Cancels all the active streams of the query and returns the number of them.
+    pub fn cancel(&self, query_id: &str) -> usize {
+        let cancellers = self.cancellers.lock().remove(query_id).unwrap_or_default();
+        let cancelled = cancellers.len();
+        for (_, tx) in cancellers {
+            let _ = tx.send(());
+        }
+        cancelled
+    }
+
+    /// The number of the active streams of the query.
+    pub fn active_streams(&self, query_id: &str) -> usize {
+        self.cancellers
+            .lock()
+            .get(query_id)
+            .map(|streams| streams.len())
+            .unwrap_or(0)
+    }
+}
+
+/// A future that resolves when the query of the stream is killed.
+pub struct CancelSignal {
+    query_id: String,
+    rx: Option<oneshot::Receiver<()>>,
+    _guard: Option<StreamGuard>,
+}
+
+impl CancelSignal {
+    pub fn aborted(&self) -> ErrorCode {
+        ErrorCode::AbortedQuery(format!("Query {} is killed", self.query_id))
+    }
+
+    /// Wraps the stream so that it ends with an `AbortedQuery` error once the query is killed.
+    pub fn wrap_stream<S, T>(self, stream: S) -> impl Stream<Item = Result<T>> + Send
+    where
+        S: Stream<Item = Result<T>> + Unpin + Send,
+        T: Send,
+    {
+        futures::stream::unfold(Some((self, stream)), |state| async move {
+            let (mut cancel, mut stream) = state?;
+            tokio::select! {
+                biased;
+                _ = &mut cancel => Some((Err(cancel.aborted()), None)),
+                item = stream.next() => item.map(|item| (item, Some((cancel, stream)))),
+            }
+        })
+    }
+}
+
+impl Future for CancelSignal {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match self.rx.as_mut() {
+            None => Poll::Pending,
+            // The sender is dropped without sending only when this signal is dropped.
+            Some(rx) => Pin::new(rx).poll(cx).map(|_| ()),
+        }
+    }
+}
+
+struct StreamGuard {
+    query_id: String,
+    id: u64,
+    cancellers: Arc<Mutex<Cancellers>>,
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        let mut cancellers = self.cancellers.lock();
+        if let Some(streams) = cancellers.get_mut(&self.query_id) {
+            streams.retain(|(id, _)| *id != self.id);
+            if streams.is_empty() {
+                cancellers.remove(&self.query_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_runtime::tokio;
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::executor::query_streams::QueryStreams;
+
+#[tokio::test]
+async fn test_query_streams_cancel() -> anyhow::Result<()> {
+    let streams = QueryStreams::default();
+
+    let s1 = streams.register("q1");
+    let s2 = streams.register("q1");
+    let s3 = streams.register("q2");
+    assert_eq!(2, streams.active_streams("q1"));
+
+    // A finished stream is deregistered.
+    drop(s2);
+    assert_eq!(1, streams.active_streams("q1"));
+
+    assert_eq!(1, streams.cancel("q1"));
+    assert_eq!(0, streams.active_streams("q1"));
+    s1.await;
+
+    // The other queries are not affected, and an unknown query is a no-op.
+    assert_eq!(0, streams.cancel("q3"));
+    assert_eq!(1, streams.active_streams("q2"));
+    drop(s3);
+    assert_eq!(0, streams.active_streams("q2"));
+
+    // A stream without query id is not tracked.
+    let _s4 = streams.register("");
+    assert_eq!(0, streams.active_streams(""));
+    assert_eq!(0, streams.cancel(""));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_streams_wrap_stream() -> anyhow::Result<()> {
+    let streams = QueryStreams::default();
+
+    let input =
+        futures::stream::iter(vec![Ok::<_, ErrorCode>(1), Ok(2)]).chain(futures::stream::pending());
+    let mut wrapped = Box::pin(streams.register("q1").wrap_stream(input));
+
+    assert_eq!(1, wrapped.next().await.unwrap()?);
+    assert_eq!(2, wrapped.next().await.unwrap()?);
+
+    streams.cancel("q1");
+    let res = wrapped.next().await.unwrap();
+    assert_eq!(ErrorCode::AbortedQuery("").code(), res.unwrap_err().code());
+    assert!(
+        wrapped.next().await.is_none(),
+        "the stream ends after aborted"
+    );
+
+    Ok(())
+}
@@ -19,8 +19,13 @@ use crate::executor::ActionHandler;
 
 #[async_trait::async_trait]
 impl RequestHandler<KillQueryReq> for ActionHandler {
-    async fn handle(&self, _act: KillQueryReq) -> common_exception::Result<()> {
-        // business logic to be specified
+    async fn handle(&self, act: KillQueryReq) -> common_exception::Result<()> {
+        let cancelled = self.query_streams.cancel(&act.query_id);
+        log::info!(
+            "kill query {}: {} active streams cancelled",
+            act.query_id,
+            cancelled
+        );
         Ok(())
     }
 }
@@ -22,6 +22,7 @@ use log::debug;
 
 use crate::executor::action_handler::RequestHandler;
 use crate::executor::ActionHandler;
+use crate::meta_service::table_history;
 use crate::meta_service::AppliedState;
 use crate::meta_service::Cmd;
 use crate::meta_service::LogEntry;
@@ -36,7 +37,14 @@ impl RequestHandler<ReadPlanAction> for ActionHandler {
         let db_name = splits[0];
         let tbl_name = splits[1];
 
-        Ok(self.meta_node.get_data_parts(db_name, tbl_name).await)
+        match act.scan_plan.table_version {
+            None => Ok(self.meta_node.get_data_parts(db_name, tbl_name).await),
+            Some(version) => {
+                self.meta_node
+                    .get_data_parts_at(db_name, tbl_name, version)
+                    .await
+            }
+        }
     }
 }
 
@@ -62,6 +70,7 @@ impl RequestHandler<TruncateTableAction> for ActionHandler {
             cmd: Cmd::TruncateTable {
                 db_name: db_name.clone(),
                 table_name: tbl_name.clone(),
+                time_ms: table_history::now_ms(),
             },
         };
 
@@ -12,11 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::pin::Pin;
+
 use async_trait::async_trait;
 use common_exception::exception;
+use futures::Stream;
 
 use crate::fs::ListResult;
 
+/// The chunks of a file, in order.
+pub type FileReadStream = Pin<Box<dyn Stream<Item = common_exception::Result<Vec<u8>>> + Send>>;
+
 /// Abstract storage layer API.
 #[async_trait]
 pub trait FileSystem
@@ -29,13 +35,19 @@ where Self: Sync + Send
     /// read all bytes from a file
     async fn read_all(&self, path: &str) -> exception::Result<Vec<u8>>;
 
+    /// Read at most `length` bytes starting at `offset` from a file.
+    /// Less bytes are returned if the end of file is reached.
+    async fn read(&self, path: &str, offset: u64, length: u64) -> exception::Result<Vec<u8>>;
+
+    /// Read a file chunk by chunk, without loading the whole file into memory.
+    async fn read_stream(&self, path: &str) -> exception::Result<FileReadStream>;
+
+    /// The size of a file in bytes.
+    async fn size(&self, path: &str) -> exception::Result<u64>;
+
+    /// Remove a file.
+    async fn delete(&self, path: &str) -> common_exception::Result<()>;
+
     /// List dir and returns directories and files.
     async fn list(&self, prefix: &str) -> common_exception::Result<ListResult>;
-
-    // async fn read(
-    //     path: &str,
-    //     offset: usize,
-    //     length: usize,
-    //     buf: &mut [u8],
-    // ) -> common_exception::Result<usize>;
 }
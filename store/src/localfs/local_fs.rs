@@ -11,7 +11,11 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::fs::File;
 use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
@@ -21,11 +25,17 @@ use async_trait::async_trait;
 use common_exception::exception;
 use common_exception::ErrorCode;
 use common_exception::ToErrorCode;
+use common_runtime::tokio;
+use common_runtime::tokio::io::AsyncReadExt;
 use common_tracing::tracing;
 
+use crate::fs::FileReadStream;
 use crate::fs::FileSystem;
 use crate::fs::ListResult;
 
+/// The size of the chunks returned by `read_stream`.
+const READ_CHUNK_SIZE: usize = 1024 * 1024;
+
 pub struct LocalFS {
     root: PathBuf,
 }
@@ -80,6 +90,74 @@ impl FileSystem for LocalFS {
         Ok(data)
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn read(&self, path: &str, offset: u64, length: u64) -> exception::Result<Vec<u8>> {
+        let p = Path::new(self.root.as_path()).join(path);
+
+        let mut data = vec![];
+        File::open(p.as_path())
+            .and_then(|mut f| {
+                f.seek(SeekFrom::Start(offset))?;
+                f.take(length).read_to_end(&mut data)
+            })
+            .map_err_to_code(ErrorCode::FileDamaged, || {
+                format!(
+                    "LocalFS: fail to read: {:?}, offset: {}, length: {}",
+                    path, offset, length
+                )
+            })?;
+        Ok(data)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn read_stream(&self, path: &str) -> exception::Result<FileReadStream> {
+        let p = Path::new(self.root.as_path()).join(path);
+        let f = tokio::fs::File::open(p.as_path())
+            .await
+            .map_err_to_code(ErrorCode::FileDamaged, || {
+                format!("LocalFS: fail to read: {:?}", path)
+            })?;
+
+        let path = path.to_string();
+        let stream = futures::stream::try_unfold(f, move |mut f| {
+            let path = path.clone();
+            async move {
+                let mut chunk = vec![0; READ_CHUNK_SIZE];
+                let n = f
+                    .read(&mut chunk)
+                    .await
+                    .map_err_to_code(ErrorCode::FileDamaged, || {
+                        format!("LocalFS: fail to read: {:?}", path)
+                    })?;
+
+                if n == 0 {
+                    return Ok(None);
+                }
+                chunk.truncate(n);
+                Ok(Some((chunk, f)))
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn size(&self, path: &str) -> exception::Result<u64> {
+        let p = Path::new(self.root.as_path()).join(path);
+        let meta = std::fs::metadata(p.as_path())
+            .map_err_to_code(ErrorCode::FileDamaged, || {
+                format!("LocalFS: fail to stat: {:?}", path)
+            })?;
+        Ok(meta.len())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete(&self, path: &str) -> common_exception::Result<()> {
+        let p = Path::new(self.root.as_path()).join(path);
+        std::fs::remove_file(p.as_path())
+            .with_context(|| format!("LocalFS: fail to delete {}", path))?;
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn list(&self, path: &str) -> common_exception::Result<ListResult> {
         let p = Path::new(self.root.as_path()).join(path);
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use common_runtime::tokio;
+use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 use tempfile::tempdir;
 
@@ -80,3 +81,49 @@ async fn test_localfs_read_all() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_localfs_ranged_read() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+
+    let f = LocalFS::try_create(root.to_str().unwrap().to_string())?;
+    f.add("foo.txt", "0123456789".as_bytes()).await?;
+
+    {
+        // size
+        assert_eq!(10, f.size("foo.txt").await?);
+        let got = f.size("bar.txt").await;
+        assert_eq!(
+            "LocalFS: fail to stat: \"bar.txt\", cause: No such file or directory (os error 2)",
+            got.err().unwrap().message()
+        );
+    }
+    {
+        // ranged read
+        let cases = vec![
+            (0, 3, "012"),
+            (7, 3, "789"),
+            (7, 5, "789"),
+            (10, 1, ""),
+            (3, 0, ""),
+        ];
+        for (offset, length, want) in cases {
+            let got = f.read("foo.txt", offset, length).await?;
+            assert_eq!(want, std::str::from_utf8(&got)?, "{} {}", offset, length);
+        }
+    }
+    {
+        // stream read
+        let got: Vec<Vec<u8>> = f.read_stream("foo.txt").await?.try_collect().await?;
+        assert_eq!("0123456789".as_bytes(), got.concat());
+    }
+    {
+        // delete
+        f.delete("foo.txt").await?;
+        assert!(f.read_all("foo.txt").await.is_err());
+        assert!(f.delete("foo.txt").await.is_err());
+    }
+
+    Ok(())
+}
@@ -18,6 +18,7 @@ use common_metatypes::Database;
 use common_metatypes::KVValue;
 use common_metatypes::SeqValue;
 use common_metatypes::Table;
+use common_planners::TableStatistics;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -70,6 +71,11 @@ pub enum AppliedState {
         result: Option<usize>,
     },
 
+    TableStatistics {
+        prev: Option<TableStatistics>,
+        result: Option<TableStatistics>,
+    },
+
     None,
 }
 
@@ -146,6 +152,15 @@ impl From<(Option<SeqValue<KVValue>>, Option<SeqValue<KVValue>>)> for AppliedSta
     }
 }
 
+impl From<(Option<TableStatistics>, Option<TableStatistics>)> for AppliedState {
+    fn from(v: (Option<TableStatistics>, Option<TableStatistics>)) -> Self {
+        AppliedState::TableStatistics {
+            prev: v.0,
+            result: v.1,
+        }
+    }
+}
+
 // === from and to transport message
 
 impl From<AppliedState> for RaftMes {
@@ -0,0 +1,83 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_flights::storage_api_impl::DataPartInfo;
+use common_metatypes::KVValue;
+use common_metatypes::SeqValue;
+use serde::Deserialize;
+use serde::Serialize;
+use sled::IVec;
+
+use crate::meta_service::sled_key_space::GenericKV;
+use crate::meta_service::sled_key_space::SledKeySpace;
+
+/// The meta to back up, taken from the state machine at a single applied log.
+///
+/// The catalog is kept in memory by the state machine, thus it is backed up along with the
+/// state machine snapshot, which contains only what is stored in sled.
+pub struct MetaBackup {
+    pub snapshot_id: String,
+    /// A consistent view of the state machine data, to be written with `write_snapshot_data()`
+    /// record by record instead of being serialized in memory at once.
+    pub snapshot: Box<dyn Iterator<Item = sled::Result<(IVec, IVec)>> + Send>,
+    pub databases: Vec<DatabaseBackup>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DatabaseBackup {
+    pub name: String,
+    pub tables: Vec<TableBackup>,
+}
+
+/// A table with the parts of its latest version. The previous versions are not backed up.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TableBackup {
+    pub name: String,
+    /// serialized schema
+    pub schema: Vec<u8>,
+    pub parts: Vec<DataPartInfo>,
+}
+
+/// The names of the parts referenced by any table, without duplicates.
+/// A part is shared by the tables cloned from each other.
+pub fn part_names(databases: &[DatabaseBackup]) -> Vec<String> {
+    let mut names = databases
+        .iter()
+        .flat_map(|db| db.tables.iter())
+        .flat_map(|t| t.parts.iter())
+        .map(|p| p.part.name.clone())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Decodes the records of the general purpose kv from the key-values of a state machine snapshot,
+/// e.g., read by a `SnapshotReader`.
+pub fn snapshot_generic_kvs(
+    snapshot: impl Iterator<Item = Result<(Vec<u8>, Vec<u8>)>>,
+) -> Result<Vec<(String, SeqValue<KVValue>)>> {
+    let mut kvs = vec![];
+    for kv in snapshot {
+        let (k, v) = kv?;
+        if k.first() == Some(&GenericKV::PREFIX) {
+            kvs.push((
+                GenericKV::deserialize_key(&k)?,
+                GenericKV::deserialize_value(&v)?,
+            ));
+        }
+    }
+    Ok(kvs)
+}
@@ -15,10 +15,13 @@
 use std::fmt;
 
 use async_raft::NodeId;
+use common_flights::storage_api_impl::AppendResult;
+use common_flights::storage_api_impl::DataPartInfo;
 use common_metatypes::Database;
 use common_metatypes::KVMeta;
 use common_metatypes::MatchSeq;
 use common_metatypes::Table;
+use common_planners::TableStatistics;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -34,12 +37,18 @@ pub enum Cmd {
     /// Override the record with key.
     SetFile { key: String, value: String },
 
+    /// Remove the record with key, if present.
+    DeleteFile { key: String },
+
     /// Increment the sequence number generator specified by `key` and returns the new value.
     IncrSeq { key: String },
 
     /// Add node if absent
     AddNode { node_id: NodeId, node: Node },
 
+    /// Remove node if present
+    RemoveNode { node_id: NodeId },
+
     /// Add a database if absent
     CreateDatabase {
         // TODO(ariesdevil): add `seq` for distinguish between the results of the execution of
@@ -56,6 +65,15 @@ pub enum Cmd {
         name: String,
     },
 
+    /// Create a database if absent, with a clone of every table of the source database.
+    CloneDatabase {
+        name: String,
+        src_name: String,
+        if_not_exists: bool,
+        /// The time in milliseconds since 1970 when the cloned tables are created.
+        time_ms: u64,
+    },
+
     /// Create a table if absent
     CreateTable {
         // TODO(ariesdevil): add `seq` for distinguish between the results of the execution of
@@ -66,6 +84,18 @@ pub enum Cmd {
         table: Table,
     },
 
+    /// Create a table if absent, with the schema and the latest data parts of the source table.
+    /// The data parts are shared, not copied.
+    CloneTable {
+        db_name: String,
+        table_name: String,
+        src_db_name: String,
+        src_table_name: String,
+        if_not_exists: bool,
+        /// The time in milliseconds since 1970 when the table is created.
+        time_ms: u64,
+    },
+
     /// Drop a table if absent
     DropTable {
         // TODO(ariesdevil): add `seq` for distinguish between the results of the execution of
@@ -91,8 +121,43 @@ pub enum Cmd {
         /// Meta data of a value.
         value_meta: Option<KVMeta>,
     },
-    /// Truncate Table
-    TruncateTable { db_name: String, table_name: String },
+    /// Remove all the parts of a table, as a new version of the table.
+    TruncateTable {
+        db_name: String,
+        table_name: String,
+        /// The time in milliseconds since 1970 when the new version of the table is created.
+        time_ms: u64,
+    },
+
+    /// Publish all the data parts written by an append, identified by `append_res.tx_id`, at once.
+    /// The parts are discarded if the table is not the one with `table_id`,
+    /// e.g., it has been dropped and created again since the parts are written.
+    AppendDataParts {
+        db_name: String,
+        table_name: String,
+        table_id: u64,
+        append_res: AppendResult,
+        /// The time in milliseconds since 1970 when the new version of the table is created.
+        time_ms: u64,
+    },
+
+    /// Add the data parts taken from a backup to a table as they are, as a new version of the table.
+    /// The parts are discarded if the table is not the one with `table_id`.
+    RestoreDataParts {
+        db_name: String,
+        table_name: String,
+        table_id: u64,
+        parts: Vec<DataPartInfo>,
+        /// The time in milliseconds since 1970 when the new version of the table is created.
+        time_ms: u64,
+    },
+
+    /// Replace the statistics of a table collected by `ANALYZE TABLE`.
+    SetTableStatistics {
+        db_name: String,
+        table_name: String,
+        statistics: TableStatistics,
+    },
 }
 
 impl fmt::Display for Cmd {
@@ -104,12 +169,18 @@ impl fmt::Display for Cmd {
             Cmd::SetFile { key, value } => {
                 write!(f, "set_file:{}={}", key, value)
             }
+            Cmd::DeleteFile { key } => {
+                write!(f, "delete_file:{}", key)
+            }
             Cmd::IncrSeq { key } => {
                 write!(f, "incr_seq:{}", key)
             }
             Cmd::AddNode { node_id, node } => {
                 write!(f, "add_node:{}={}", node_id, node)
             }
+            Cmd::RemoveNode { node_id } => {
+                write!(f, "remove_node:{}", node_id)
+            }
             Cmd::CreateDatabase {
                 name,
                 if_not_exists,
@@ -124,6 +195,18 @@ impl fmt::Display for Cmd {
             Cmd::DropDatabase { name } => {
                 write!(f, "drop_db:{}", name)
             }
+            Cmd::CloneDatabase {
+                name,
+                src_name,
+                if_not_exists,
+                ..
+            } => {
+                write!(
+                    f,
+                    "clone_db:{} from {}, if_not_exists:{}",
+                    name, src_name, if_not_exists
+                )
+            }
             Cmd::CreateTable {
                 db_name,
                 table_name,
@@ -136,6 +219,20 @@ impl fmt::Display for Cmd {
                     db_name, table_name, table, if_not_exists
                 )
             }
+            Cmd::CloneTable {
+                db_name,
+                table_name,
+                src_db_name,
+                src_table_name,
+                if_not_exists,
+                ..
+            } => {
+                write!(
+                    f,
+                    "clone_table:{}-{} from {}-{}, if_not_exists:{}",
+                    db_name, table_name, src_db_name, src_table_name, if_not_exists
+                )
+            }
             Cmd::DropTable {
                 db_name,
                 table_name,
@@ -162,9 +259,54 @@ impl fmt::Display for Cmd {
             Cmd::TruncateTable {
                 db_name,
                 table_name,
+                ..
             } => {
                 write!(f, "truncate table:{}-{}", db_name, table_name)
             }
+            Cmd::AppendDataParts {
+                db_name,
+                table_name,
+                table_id,
+                append_res,
+                ..
+            } => {
+                write!(
+                    f,
+                    "append_data_parts:{}-{}({}), tx_id:{}, parts:{}",
+                    db_name,
+                    table_name,
+                    table_id,
+                    append_res.tx_id,
+                    append_res.parts.len()
+                )
+            }
+            Cmd::RestoreDataParts {
+                db_name,
+                table_name,
+                table_id,
+                parts,
+                ..
+            } => {
+                write!(
+                    f,
+                    "restore_data_parts:{}-{}({}), parts:{}",
+                    db_name,
+                    table_name,
+                    table_id,
+                    parts.len()
+                )
+            }
+            Cmd::SetTableStatistics {
+                db_name,
+                table_name,
+                statistics,
+            } => {
+                write!(
+                    f,
+                    "set_table_statistics:{}-{}, rows:{}",
+                    db_name, table_name, statistics.row_count
+                )
+            }
         }
     }
 }
@@ -0,0 +1,84 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+use common_flights::cluster_api_impl::ClusterMembership;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::meta_service::NodeId;
+use crate::meta_service::RaftMes;
+use crate::meta_service::RetryableError;
+
+/// A change to the nodes of the cluster. It has to be carried out by the leader.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum MembershipChange {
+    /// Add a node and replicate logs to it without letting it vote.
+    AddLearner { node_id: NodeId, address: String },
+
+    /// Replace the voters. The nodes not in it but still in the cluster become learners.
+    ChangeMembership { voters: BTreeSet<NodeId> },
+
+    /// Remove a node from the voters, if it is one, and then from the cluster.
+    RemoveNode { node_id: NodeId },
+}
+
+impl tonic::IntoRequest<RaftMes> for MembershipChange {
+    fn into_request(self) -> tonic::Request<RaftMes> {
+        let mes = RaftMes {
+            data: serde_json::to_string(&self).expect("fail to serialize"),
+            error: "".to_string(),
+        };
+        tonic::Request::new(mes)
+    }
+}
+
+impl TryFrom<RaftMes> for MembershipChange {
+    type Error = tonic::Status;
+
+    fn try_from(mes: RaftMes) -> Result<Self, Self::Error> {
+        let req: MembershipChange =
+            serde_json::from_str(&mes.data).map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(req)
+    }
+}
+
+impl From<Result<ClusterMembership, RetryableError>> for RaftMes {
+    fn from(rst: Result<ClusterMembership, RetryableError>) -> Self {
+        match rst {
+            Ok(membership) => RaftMes {
+                data: serde_json::to_string(&membership).expect("fail to serialize"),
+                error: "".to_string(),
+            },
+            Err(err) => err.into(),
+        }
+    }
+}
+
+impl From<RaftMes> for Result<ClusterMembership, RetryableError> {
+    fn from(msg: RaftMes) -> Self {
+        if !msg.data.is_empty() {
+            let membership: ClusterMembership =
+                serde_json::from_str(&msg.data).expect("fail to deserialize");
+            Ok(membership)
+        } else {
+            let err: RetryableError =
+                serde_json::from_str(&msg.error).expect("fail to deserialize");
+            Err(err)
+        }
+    }
+}
@@ -23,6 +23,7 @@ use common_tracing::tracing;
 use crate::meta_service::GetReply;
 use crate::meta_service::GetReq;
 use crate::meta_service::LogEntry;
+use crate::meta_service::MembershipChange;
 use crate::meta_service::MetaNode;
 use crate::meta_service::MetaService;
 use crate::meta_service::RaftMes;
@@ -61,6 +62,28 @@ impl MetaService for MetaServiceImpl {
         Ok(tonic::Response::new(raft_mes))
     }
 
+    /// Handles a cluster membership change.
+    /// This node must be leader or an error returned.
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn change_membership(
+        &self,
+        request: tonic::Request<RaftMes>,
+    ) -> Result<tonic::Response<RaftMes>, tonic::Status> {
+        common_tracing::extract_remote_span_as_parent(&request);
+
+        let mes = request.into_inner();
+        let change: MembershipChange = mes.try_into()?;
+
+        let rst = self
+            .meta_node
+            .change_membership_on_local_leader(change)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        let raft_mes = rst.into();
+        Ok(tonic::Response::new(raft_mes))
+    }
+
     #[tracing::instrument(level = "info", skip(self))]
     async fn get(
         &self,
@@ -24,11 +24,11 @@ use common_runtime::tokio;
 use common_tracing::tracing;
 use maplit::btreeset;
 
-use crate::meta_service::state_machine::SerializableSnapshot;
 use crate::meta_service::state_machine_meta::StateMachineMetaKey::LastMembership;
 use crate::meta_service::testing::pretty_snapshot;
 use crate::meta_service::testing::snapshot_logs;
 use crate::meta_service::MetaStore;
+use crate::meta_service::SnapshotReader;
 use crate::meta_service::StateMachineMetaValue;
 use crate::tests::service::new_test_context;
 
@@ -221,6 +221,7 @@ async fn test_meta_store_do_log_compaction_empty() -> anyhow::Result<()> {
 
         let want = vec![
             "[3, 2]:{\"Bool\":true}", // sm meta: init
+            "[3, 4]:{\"Bool\":true}", // sm meta: part refs tracked
         ]
         .iter()
         .map(|x| x.to_string())
@@ -288,6 +289,7 @@ async fn test_meta_store_do_log_compaction_1_snap_ptr_1_log() -> anyhow::Result<
             "[3, 1]:{\"LogId\":{\"term\":1,\"index\":4}}", // sm meta: LastApplied
             "[3, 2]:{\"Bool\":true}",                      // sm meta: init
             "[3, 3]:{\"Membership\":{\"members\":[1,2,3],\"members_after_consensus\":null}}", // membership
+            "[3, 4]:{\"Bool\":true}", // sm meta: part refs tracked
             "[6, 97]:[1,{\"meta\":null,\"value\":[65]}]", // generic kv
             "[7, 103, 101, 110, 101, 114, 105, 99, 95, 107, 118]:1", // sequence: by upsertkv
         ]
@@ -400,16 +402,26 @@ async fn test_meta_store_do_log_compaction_current_snapshot() -> anyhow::Result<
     );
 
     tracing::info!("--- check snapshot");
+    let snap = ms.current_snapshot.read().await.clone().unwrap();
     {
-        let data = curr_snap.snapshot.into_inner();
-
-        let ser_snap: SerializableSnapshot = serde_json::from_slice(&data)?;
-        let res = pretty_snapshot(&ser_snap.kvs);
+        let res = pretty_snapshot(&read_snapshot_kvs(&snap.path)?);
         tracing::debug!("res: {:?}", res);
 
         assert_eq!(want, res);
     }
 
+    tracing::info!("--- reopen MetaStore, the snapshot is loaded");
+    {
+        drop(ms);
+        let ms = MetaStore::open_create(&tc.config, Some(()), None).await?;
+        let loaded = ms.current_snapshot.read().await.clone().unwrap();
+        assert_eq!(snap.meta.snapshot_id, loaded.meta.snapshot_id);
+        assert_eq!(snap.path, loaded.path);
+
+        let curr_snap = ms.get_current_snapshot().await?.unwrap();
+        assert_eq!(LogId { term: 1, index: 9 }, curr_snap.meta.last_log_id);
+    }
+
     Ok(())
 }
 
@@ -426,12 +438,11 @@ async fn test_meta_store_install_snapshot() -> anyhow::Result<()> {
     let (logs, want) = snapshot_logs();
 
     let id = 3;
-    let snap;
+    let mut tc0 = new_test_context();
+    tc0.config.id = id;
+    let path;
     {
-        let mut tc = new_test_context();
-        tc.config.id = id;
-
-        let ms = MetaStore::open_create(&tc.config, None, Some(())).await?;
+        let ms = MetaStore::open_create(&tc0.config, None, Some(())).await?;
 
         tracing::info!("--- feed logs and state machine");
 
@@ -439,11 +450,10 @@ async fn test_meta_store_install_snapshot() -> anyhow::Result<()> {
             ms.log.insert(l).await?;
             ms.state_machine.write().await.apply(l).await?;
         }
-        snap = ms.do_log_compaction().await?;
+        ms.do_log_compaction().await?;
+        path = ms.current_snapshot.read().await.clone().unwrap().path;
     }
 
-    let data = snap.snapshot.into_inner();
-
     tracing::info!("--- reopen a new MetaStore to install snapshot");
     {
         let mut tc = new_test_context();
@@ -454,7 +464,7 @@ async fn test_meta_store_install_snapshot() -> anyhow::Result<()> {
         tracing::info!("--- rejected because old sm is not cleaned");
         {
             ms.raft_state.write_state_machine_id(&(1, 2)).await?;
-            let res = ms.install_snapshot(&data).await;
+            let res = ms.install_snapshot(&path).await;
             assert!(res.is_err(), "different ids disallow installing snapshot");
             assert!(res.unwrap_err().to_string().starts_with(
                 "Code: 2404, displayText = another snapshot install is not finished yet: 1 2"
@@ -464,7 +474,7 @@ async fn test_meta_store_install_snapshot() -> anyhow::Result<()> {
         tracing::info!("--- install snapshot");
         {
             ms.raft_state.write_state_machine_id(&(0, 0)).await?;
-            ms.install_snapshot(&data).await?;
+            ms.install_snapshot(&path).await?;
         }
 
         tracing::info!("--- check installed meta");
@@ -486,11 +496,10 @@ async fn test_meta_store_install_snapshot() -> anyhow::Result<()> {
 
         tracing::info!("--- check snapshot");
         {
-            let curr_snap = ms.do_log_compaction().await?;
-            let data = curr_snap.snapshot.into_inner();
+            ms.do_log_compaction().await?;
+            let snap = ms.current_snapshot.read().await.clone().unwrap();
 
-            let ser_snap: SerializableSnapshot = serde_json::from_slice(&data)?;
-            let res = pretty_snapshot(&ser_snap.kvs);
+            let res = pretty_snapshot(&read_snapshot_kvs(&snap.path)?);
             tracing::debug!("res: {:?}", res);
 
             assert_eq!(want, res);
@@ -501,3 +510,12 @@ async fn test_meta_store_install_snapshot() -> anyhow::Result<()> {
 }
 
 // TODO(xp): test finalize_snapshot_installation
+
+fn read_snapshot_kvs(path: &std::path::Path) -> anyhow::Result<Vec<Vec<Vec<u8>>>> {
+    let mut kvs = vec![];
+    for kv in SnapshotReader::open(path)? {
+        let (k, v) = kv?;
+        kvs.push(vec![k, v]);
+    }
+    Ok(kvs)
+}
@@ -13,9 +13,11 @@
 // limitations under the License.
 
 pub mod applied_state;
+pub mod backup;
 pub mod cmd;
 pub mod errors;
 pub mod log_entry;
+pub mod membership;
 pub mod meta_service_impl;
 pub mod network;
 pub mod placement;
@@ -33,12 +35,14 @@ pub mod sled_tree;
 pub mod snapshot;
 pub mod state_machine;
 pub mod state_machine_meta;
+pub mod table_history;
 
 pub use applied_state::AppliedState;
 pub use cmd::Cmd;
 pub use errors::RetryableError;
 pub use errors::ShutdownError;
 pub use log_entry::LogEntry;
+pub use membership::MembershipChange;
 pub use meta_service_impl::MetaServiceImpl;
 pub use network::Network;
 pub use placement::Placement;
@@ -57,11 +61,14 @@ pub use sled_tree::AsKeySpace;
 pub use sled_tree::SledTree;
 pub use sled_tree::SledValueToKey;
 pub use snapshot::Snapshot;
+pub use snapshot::SnapshotReader;
+pub use snapshot::SnapshotStore;
 pub use state_machine::Node;
 pub use state_machine::Slot;
 pub use state_machine::StateMachine;
 pub use state_machine_meta::StateMachineMetaKey;
 pub use state_machine_meta::StateMachineMetaValue;
+pub use table_history::TableHistory;
 
 pub use crate::protobuf::meta_service_client::MetaServiceClient;
 pub use crate::protobuf::meta_service_server::MetaService;
@@ -89,6 +96,10 @@ mod sled_serde_test;
 #[cfg(test)]
 mod sled_tree_test;
 #[cfg(test)]
+mod snapshot_test;
+#[cfg(test)]
 mod state_machine_test;
 #[cfg(test)]
+mod table_history_test;
+#[cfg(test)]
 mod testing;
@@ -74,3 +74,6 @@ impl SledSerde for SeqValue<KVValue> {}
 
 /// For LogId to be able to stored in sled::Tree as a value.
 impl SledSerde for LogId {}
+
+/// For counters such as the part references to be stored in sled::Tree as a value.
+impl SledSerde for u64 {}
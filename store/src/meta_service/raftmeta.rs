@@ -14,8 +14,8 @@
 
 use std::collections::BTreeSet;
 use std::collections::HashSet;
-use std::io::Cursor;
 use std::ops::Bound;
+use std::path::Path;
 use std::sync::Arc;
 
 use async_raft::async_trait::async_trait;
@@ -27,6 +27,7 @@ use async_raft::raft::MembershipConfig;
 use async_raft::storage::CurrentSnapshotData;
 use async_raft::storage::HardState;
 use async_raft::storage::InitialState;
+use async_raft::ChangeConfigError;
 use async_raft::ClientWriteError;
 use async_raft::NodeId;
 use async_raft::Raft;
@@ -36,12 +37,15 @@ use async_raft::SnapshotMeta;
 use async_raft::SnapshotPolicy;
 use common_exception::prelude::ErrorCode;
 use common_exception::prelude::ToErrorCode;
-use common_flights::storage_api_impl::AppendResult;
+use common_flights::cluster_api_impl::ClusterMembership;
 use common_flights::storage_api_impl::DataPartInfo;
 use common_metatypes::Database;
 use common_metatypes::KVValue;
+use common_metatypes::MetaVersion;
 use common_metatypes::SeqValue;
 use common_metatypes::Table;
+use common_metatypes::TableVersionAt;
+use common_planners::TableStatistics;
 use common_runtime::tokio;
 use common_runtime::tokio::sync::watch;
 use common_runtime::tokio::sync::Mutex;
@@ -52,13 +56,14 @@ use common_tracing::tracing;
 use common_tracing::tracing::Instrument;
 
 use crate::configs;
+use crate::meta_service::backup::MetaBackup;
 use crate::meta_service::raft_db::get_sled_db;
 use crate::meta_service::raft_log::RaftLog;
 use crate::meta_service::raft_state::RaftState;
-use crate::meta_service::state_machine::SerializableSnapshot;
 use crate::meta_service::AppliedState;
 use crate::meta_service::Cmd;
 use crate::meta_service::LogEntry;
+use crate::meta_service::MembershipChange;
 use crate::meta_service::MetaServiceClient;
 use crate::meta_service::MetaServiceImpl;
 use crate::meta_service::MetaServiceServer;
@@ -67,7 +72,10 @@ use crate::meta_service::Node;
 use crate::meta_service::RetryableError;
 use crate::meta_service::ShutdownError;
 use crate::meta_service::Snapshot;
+use crate::meta_service::SnapshotReader;
+use crate::meta_service::SnapshotStore;
 use crate::meta_service::StateMachine;
+use crate::meta_service::StateMachineMetaKey;
 
 /// An storage system implementing the `async_raft::RaftStorage` trait.
 ///
@@ -114,6 +122,9 @@ pub struct MetaStore {
 
     /// The current snapshot.
     pub current_snapshot: RwLock<Option<Snapshot>>,
+
+    /// The dir the snapshots are persisted in.
+    pub snapshot_store: SnapshotStore,
 }
 
 // TODO(xp): the following is a draft struct when meta storage is migrated to sled based impl.
@@ -133,8 +144,8 @@ pub struct MetaStore {
 //     pub state_machine: RwLock<StateMachine>,
 //
 //     /// The current snapshot of the state machine.
-//     /// Currently snapshot data is a complete backup of the state machine and is not persisted on disk.
-//     /// When server restarts, a new snapshot is created on demand.
+//     /// The snapshot data is a complete backup of the state machine and is persisted on disk.
+//     /// When server restarts, the latest snapshot is loaded.
 //     pub current_snapshot: RwLock<Option<Snapshot>>,
 // }
 
@@ -175,7 +186,9 @@ impl MetaStore {
         }
 
         let sm = RwLock::new(StateMachine::open(config, sm_id).await?);
-        let current_snapshot = RwLock::new(None);
+
+        let snapshot_store = SnapshotStore::open(config)?;
+        let current_snapshot = RwLock::new(snapshot_store.load_latest()?);
 
         Ok(Self {
             id: raft_state.id,
@@ -186,6 +199,7 @@ impl MetaStore {
             log,
             state_machine: sm,
             current_snapshot,
+            snapshot_store,
         })
     }
 
@@ -199,8 +213,9 @@ impl MetaStore {
     }
 
     /// Install a snapshot to build a state machine from it and replace the old state machine with the new one.
-    #[tracing::instrument(level = "debug", skip(self, data))]
-    pub async fn install_snapshot(&self, data: &[u8]) -> common_exception::Result<()> {
+    /// The key-values are read from the snapshot data file one by one.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn install_snapshot(&self, path: &Path) -> common_exception::Result<()> {
         let mut sm = self.state_machine.write().await;
 
         let (sm_id, prev_sm_id) = self.raft_state.read_state_machine_id()?;
@@ -213,9 +228,7 @@ impl MetaStore {
 
         let new_sm_id = sm_id + 1;
 
-        tracing::debug!("snapshot data len: {}", data.len());
-
-        let snap: SerializableSnapshot = serde_json::from_slice(data)?;
+        let kvs = SnapshotReader::open(path)?;
 
         // If not finished, clean up the new tree.
         self.raft_state
@@ -223,19 +236,21 @@ impl MetaStore {
             .await?;
 
         let new_sm = StateMachine::open(&self.config, new_sm_id).await?;
+        // Whether the part references are tracked is up to the snapshot, not to the new tree.
+        new_sm
+            .sm_meta()
+            .remove(&StateMachineMetaKey::PartRefsTracked, false)
+            .await?;
 
-        tracing::info!(
-            "insert all key-value into new state machine, n={}",
-            snap.kvs.len()
-        );
+        tracing::info!("insert all key-value into new state machine");
 
         let tree = &new_sm.sm_tree.tree;
-        let nkvs = snap.kvs.len();
-        for x in snap.kvs.into_iter() {
-            let k = &x[0];
-            let v = &x[1];
-            tree.insert(k, v.clone())
+        let mut nkvs = 0;
+        for kv in kvs {
+            let (k, v) = kv?;
+            tree.insert(k, v)
                 .map_err_to_code(ErrorCode::MetaStoreDamaged, || "fail to insert snapshot")?;
+            nkvs += 1;
         }
 
         tracing::info!(
@@ -304,7 +319,7 @@ impl MetaStore {
 
 #[async_trait]
 impl RaftStorage<LogEntry, AppliedState> for MetaStore {
-    type Snapshot = Cursor<Vec<u8>>;
+    type Snapshot = tokio::fs::File;
     type ShutdownError = ShutdownError;
 
     #[tracing::instrument(level = "debug", skip(self), fields(id=self.id))]
@@ -424,28 +439,20 @@ impl RaftStorage<LogEntry, AppliedState> for MetaStore {
     async fn do_log_compaction(&self) -> anyhow::Result<CurrentSnapshotData<Self::Snapshot>> {
         // NOTE: do_log_compaction is guaranteed to be serialized called by RaftCore.
 
-        // TODO(xp): add test of small chunk snapshot transfer and installation
-
         // TODO(xp): disallow to install a snapshot with smaller last_applied_log
 
-        // 1. Take a serialized snapshot
+        // 1. Take a snapshot and persist it
 
         let (view, last_applied_log, last_membership, snapshot_id) =
             self.state_machine.write().await.snapshot()?;
 
-        let data = StateMachine::serialize_snapshot(view)?;
-        let snapshot_size = data.len();
-
         let snap_meta = SnapshotMeta {
             last_log_id: last_applied_log,
             snapshot_id,
             membership: last_membership.clone(),
         };
 
-        let snapshot = Snapshot {
-            meta: snap_meta.clone(),
-            data: data.clone(),
-        };
+        let snapshot = self.snapshot_store.build(snap_meta.clone(), view)?;
 
         // 2. Remove logs that are included in snapshot.
 
@@ -458,23 +465,29 @@ impl RaftStorage<LogEntry, AppliedState> for MetaStore {
 
         tracing::debug!("log range_remove complete");
 
+        let data = tokio::fs::File::open(&snapshot.path).await?;
+
         // Update the snapshot first.
         {
             let mut current_snapshot = self.current_snapshot.write().await;
             *current_snapshot = Some(snapshot);
         }
 
-        tracing::debug!(snapshot_size = snapshot_size, "log compaction complete");
+        tracing::debug!("log compaction complete");
 
         Ok(CurrentSnapshotData {
             meta: snap_meta,
-            snapshot: Box::new(Cursor::new(data)),
+            snapshot: Box::new(data),
         })
     }
 
+    /// Creates the file to receive a snapshot from the leader.
+    /// Every chunk is written at its offset, thus a chunk resent after a failure overwrites the same range,
+    /// and a transfer restarted from the first chunk starts over with an empty file.
     #[tracing::instrument(level = "info", skip(self), fields(id=self.id))]
     async fn create_snapshot(&self) -> anyhow::Result<Box<Self::Snapshot>> {
-        Ok(Box::new(Cursor::new(Vec::new())))
+        let f = tokio::fs::File::create(self.snapshot_store.receiving_path()).await?;
+        Ok(Box::new(f))
     }
 
     #[tracing::instrument(level = "info", skip(self, snapshot), fields(id=self.id))]
@@ -485,20 +498,16 @@ impl RaftStorage<LogEntry, AppliedState> for MetaStore {
     ) -> anyhow::Result<()> {
         // TODO(xp): disallow installing a snapshot with smaller last_applied.
 
-        tracing::debug!(
-            { snapshot_size = snapshot.get_ref().len() },
-            "decoding snapshot for installation"
-        );
+        // Wait for the received chunks to be written.
+        snapshot.sync_all().await?;
+        drop(snapshot);
 
-        let new_snapshot = Snapshot {
-            meta: meta.clone(),
-            data: snapshot.into_inner(),
-        };
+        let new_snapshot = self.snapshot_store.commit_received(meta.clone())?;
 
         tracing::debug!("SNAP META:{:?}", meta);
 
         // Replace state machine with the new one
-        let res = self.install_snapshot(&new_snapshot.data).await;
+        let res = self.install_snapshot(&new_snapshot.path).await;
         match res {
             Ok(_) => {}
             Err(e) => {
@@ -528,10 +537,11 @@ impl RaftStorage<LogEntry, AppliedState> for MetaStore {
         tracing::info!("got snapshot start");
         let snap = match &*self.current_snapshot.read().await {
             Some(snapshot) => {
-                let data = snapshot.data.clone();
+                // The data is sent to a follower in chunks, read from the file.
+                let data = tokio::fs::File::open(&snapshot.path).await?;
                 Ok(Some(CurrentSnapshotData {
                     meta: snapshot.meta.clone(),
-                    snapshot: Box::new(Cursor::new(data)),
+                    snapshot: Box::new(data),
                 }))
             }
             None => Ok(None),
@@ -697,6 +707,7 @@ impl MetaNode {
             .election_timeout_min(hb * 8)
             .election_timeout_max(hb * 12)
             .install_snapshot_timeout(config.install_snapshot_timeout)
+            .snapshot_max_chunk_size(config.snapshot_chunk_size)
             .snapshot_policy(SnapshotPolicy::LogsSinceLast(
                 config.snapshot_logs_since_last,
             ))
@@ -745,6 +756,39 @@ impl MetaNode {
         Ok(mn)
     }
 
+    /// Start the MetaNode of a store server, in one of the modes:
+    /// - boot mode: create the first node in a new cluster.
+    /// - single mode: create a single node cluster if absent, otherwise open it.
+    /// - join mode: create a node to be added into a cluster as a learner if absent, otherwise open it.
+    /// - open mode: open an existent node.
+    #[tracing::instrument(level = "info", skip(config), fields(config_id=config.config_id.as_str()))]
+    pub async fn start(config: &configs::Config) -> common_exception::Result<Arc<MetaNode>> {
+        tracing::info!(
+            "Starting MetaNode boot:{} single: {} join: {} with config: {:?}",
+            config.boot,
+            config.single,
+            config.join,
+            config
+        );
+
+        let mn = if config.boot {
+            MetaNode::boot(0, config).await?
+        } else if config.single {
+            let (mn, _is_open) =
+                MetaNode::open_create_boot(config, Some(()), Some(()), Some(())).await?;
+            mn
+        } else if config.join {
+            let (mn, _is_open) =
+                MetaNode::open_create_boot(config, Some(()), Some(()), None).await?;
+            mn
+        } else {
+            MetaNode::open(config).await?
+        };
+        tracing::info!("Done starting MetaNode: {:?}", config);
+
+        Ok(mn)
+    }
+
     /// Open or create a MetaStore node.
     /// Optionally boot a single node cluster.
     /// 1. If `open` is `Some`, try to open an existent one.
@@ -1001,6 +1045,168 @@ impl MetaNode {
         Ok(_resp)
     }
 
+    /// Returns the nodes of the cluster and their roles, seen by this node.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_membership(&self) -> common_exception::Result<ClusterMembership> {
+        let (leader, membership) = {
+            let metrics = self.metrics_rx.borrow();
+            (metrics.current_leader, metrics.membership_config.clone())
+        };
+        let voters = membership.all_nodes().into_iter().collect::<BTreeSet<_>>();
+
+        let nodes = {
+            let sm = self.sto.state_machine.read().await;
+            sm.nodes().range_kvs(..)?
+        };
+        let learners = nodes
+            .iter()
+            .map(|(node_id, _)| *node_id)
+            .filter(|node_id| !voters.contains(node_id))
+            .collect();
+
+        Ok(ClusterMembership {
+            leader,
+            voters,
+            learners,
+            nodes: nodes
+                .into_iter()
+                .map(|(node_id, node)| (node_id, node.address))
+                .collect(),
+        })
+    }
+
+    /// Submit a membership change to the known leader. Returns the membership after the change.
+    /// The change is checked against the local state first, thus an unsafe one fails without being forwarded.
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn change_membership(
+        &self,
+        change: MembershipChange,
+    ) -> common_exception::Result<ClusterMembership> {
+        self.check_membership_change(&change).await?;
+
+        let mut curr_leader = self.get_leader().await;
+        loop {
+            let rst = if curr_leader == self.sto.id {
+                self.change_membership_on_local_leader(change.clone())
+                    .await?
+            } else {
+                // forward to leader
+
+                let addr = self.sto.get_node_addr(&curr_leader).await?;
+
+                let mut client = MetaServiceClient::connect(format!("http://{}", addr))
+                    .await
+                    .map_err(|e| ErrorCode::CannotConnectNode(e.to_string()))?;
+                let resp = client.change_membership(change.clone()).await?;
+                let rst: Result<ClusterMembership, RetryableError> = resp.into_inner().into();
+                rst
+            };
+
+            match rst {
+                Ok(membership) => return Ok(membership),
+                Err(RetryableError::ForwardToLeader { leader }) => curr_leader = leader,
+            }
+        }
+    }
+
+    /// Apply a membership change through local raft node.
+    /// It works only when this node is the leader,
+    /// otherwise it returns RetryableError::ForwardToLeader error indicating the latest leader.
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn change_membership_on_local_leader(
+        &self,
+        change: MembershipChange,
+    ) -> common_exception::Result<Result<ClusterMembership, RetryableError>> {
+        // Check again with the state of the leader, which is the latest.
+        self.check_membership_change(&change).await?;
+
+        let rst = match change {
+            MembershipChange::AddLearner { node_id, address } => {
+                // The address has to be committed before the leader is able to send logs to it.
+                self.add_node(node_id, address).await?;
+                // It returns after the learner catches up with the logs.
+                self.raft.add_non_voter(node_id).await
+            }
+            MembershipChange::ChangeMembership { voters } => {
+                self.raft.change_membership(voters).await
+            }
+            MembershipChange::RemoveNode { node_id } => {
+                let mut voters = self.get_membership().await?.voters;
+                if voters.remove(&node_id) {
+                    if let Err(e) = to_retryable(self.raft.change_membership(voters).await)? {
+                        return Ok(Err(e));
+                    }
+                }
+
+                // async-raft does not stop replicating to a non-voter.
+                // Without the node info, the next leader does not add it back as a non-voter.
+                self.write(LogEntry {
+                    txid: None,
+                    cmd: Cmd::RemoveNode { node_id },
+                })
+                .await?;
+                Ok(())
+            }
+        };
+
+        if let Err(e) = to_retryable(rst)? {
+            return Ok(Err(e));
+        }
+        Ok(Ok(self.get_membership().await?))
+    }
+
+    /// Rejects a membership change that breaks the cluster, e.g., removing the last voter.
+    async fn check_membership_change(
+        &self,
+        change: &MembershipChange,
+    ) -> common_exception::Result<()> {
+        let membership = self.get_membership().await?;
+
+        match change {
+            MembershipChange::AddLearner { node_id, address } => {
+                if address.is_empty() {
+                    return Err(ErrorCode::InvalidMembership(format!(
+                        "node {} has no address",
+                        node_id
+                    )));
+                }
+                match membership.nodes.get(node_id) {
+                    Some(addr) if addr != address => Err(ErrorCode::NodeAlreadyExists(format!(
+                        "node {} is already added with address {}",
+                        node_id, addr
+                    ))),
+                    _ => Ok(()),
+                }
+            }
+            MembershipChange::ChangeMembership { voters } => {
+                if voters.is_empty() {
+                    return Err(ErrorCode::InvalidMembership("no voter is specified"));
+                }
+                for node_id in voters.iter() {
+                    if !membership.nodes.contains_key(node_id) {
+                        return Err(ErrorCode::UnknownNode(format!(
+                            "node {} must be added as a learner before becoming a voter",
+                            node_id
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            MembershipChange::RemoveNode { node_id } => {
+                if !membership.nodes.contains_key(node_id) && !membership.voters.contains(node_id) {
+                    return Err(ErrorCode::UnknownNode(format!("node id: {}", node_id)));
+                }
+                if membership.voters.len() == 1 && membership.voters.contains(node_id) {
+                    return Err(ErrorCode::InvalidMembership(format!(
+                        "node {} is the last voter",
+                        node_id
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+
     /// Get a database from local meta state machine.
     /// The returned value may not be the latest written.
     #[tracing::instrument(level = "debug", skip(self))]
@@ -1057,27 +1263,74 @@ impl MetaNode {
         sm.get_data_parts(db_name, table_name)
     }
 
+    /// Returns the data parts of a table as of a version, or None if the table does not exist.
     #[tracing::instrument(level = "debug", skip(self))]
-    pub async fn append_data_parts(
+    pub async fn get_data_parts_at(
         &self,
         db_name: &str,
         table_name: &str,
-        append_res: &AppendResult,
-    ) {
-        let mut sm = self.sto.state_machine.write().await;
-        sm.append_data_parts(db_name, table_name, append_res)
+        version: MetaVersion,
+    ) -> common_exception::Result<Option<Vec<DataPartInfo>>> {
+        let sm = self.sto.state_machine.read().await;
+        match sm.get_table_id(db_name, table_name) {
+            None => Ok(None),
+            Some(table_id) => sm.get_data_parts_at(table_id, version).map(Some),
+        }
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
-    pub async fn remove_table_data_parts(&self, db_name: &str, table_name: &str) {
+    pub async fn get_table_version(
+        &self,
+        table_id: u64,
+        at: &TableVersionAt,
+    ) -> common_exception::Result<MetaVersion> {
+        let sm = self.sto.state_machine.read().await;
+        sm.get_table_version(table_id, at)
+    }
+
+    /// Returns the statistics collected by the last `ANALYZE TABLE`, or None if there are none.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_table_statistics(
+        &self,
+        db_name: &str,
+        table_name: &str,
+    ) -> Option<TableStatistics> {
+        let sm = self.sto.state_machine.read().await;
+        sm.get_table_statistics(db_name, table_name)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn remove_table_data_parts(
+        &self,
+        db_name: &str,
+        table_name: &str,
+    ) -> common_exception::Result<()> {
         let mut sm = self.sto.state_machine.write().await;
-        sm.remove_table_data_parts(db_name, table_name)
+        sm.remove_table_data_parts(db_name, table_name).await
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
-    pub async fn remove_db_data_parts(&self, db_name: &str) {
+    pub async fn remove_db_data_parts(&self, db_name: &str) -> common_exception::Result<()> {
         let mut sm = self.sto.state_machine.write().await;
-        sm.remove_db_data_parts(db_name)
+        sm.remove_db_data_parts(db_name).await
+    }
+
+    /// Returns the part names that no table version references, of which the files can be deleted,
+    /// or None if the local state machine does not track the references.
+    #[tracing::instrument(level = "debug", skip(self, names))]
+    pub async fn get_unreferenced_parts(
+        &self,
+        names: Vec<String>,
+    ) -> common_exception::Result<Option<Vec<String>>> {
+        let sm = self.sto.state_machine.read().await;
+        sm.unreferenced_parts(names)
+    }
+
+    /// Takes the meta to back up from the local state machine.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn backup(&self) -> common_exception::Result<MetaBackup> {
+        let sm = self.sto.state_machine.read().await;
+        sm.backup()
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
@@ -1200,3 +1453,26 @@ impl MetaNode {
         }
     }
 }
+
+/// Converts the result of a raft membership change:
+/// a change on a non-leader is to be retried on the leader, and a change that changes nothing is a success.
+fn to_retryable(
+    rst: Result<(), ChangeConfigError>,
+) -> common_exception::Result<Result<(), RetryableError>> {
+    match rst {
+        Ok(()) | Err(ChangeConfigError::Noop) => Ok(Ok(())),
+        Err(ChangeConfigError::NodeNotLeader(Some(leader))) => {
+            Ok(Err(RetryableError::ForwardToLeader { leader }))
+        }
+        Err(ChangeConfigError::NodeNotLeader(None)) => Err(ErrorCode::MetaServiceUnavailable(
+            "no leader to change membership".to_string(),
+        )),
+        Err(ChangeConfigError::ConfigChangeInProgress) => Err(ErrorCode::InvalidMembership(
+            "another membership change is in progress",
+        )),
+        Err(ChangeConfigError::InoperableConfig) => {
+            Err(ErrorCode::InvalidMembership("the membership has no voter"))
+        }
+        Err(ChangeConfigError::RaftError(e)) => Err(ErrorCode::MetaServiceError(e.to_string())),
+    }
+}
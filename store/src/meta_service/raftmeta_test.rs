@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use async_raft::RaftMetrics;
 use async_raft::State;
+use common_exception::ErrorCode;
 use common_metatypes::MatchSeq;
 use common_runtime::tokio;
 use common_runtime::tokio::time::Duration;
@@ -28,6 +29,7 @@ use crate::configs;
 use crate::meta_service::AppliedState;
 use crate::meta_service::Cmd;
 use crate::meta_service::LogEntry;
+use crate::meta_service::MembershipChange;
 use crate::meta_service::MetaNode;
 use crate::meta_service::NodeId;
 use crate::meta_service::RaftTxId;
@@ -399,6 +401,8 @@ async fn test_meta_node_snapshot_replication() -> anyhow::Result<()> {
     let mut tc = new_test_context();
     tc.config.snapshot_logs_since_last = snap_logs;
     tc.config.install_snapshot_timeout = 10_1000; // milli seconds. In a CI multi-threads test delays async task badly.
+                                                  // Send the snapshot in many chunks.
+    tc.config.snapshot_chunk_size = 64;
     let addr = tc.config.meta_api_addr();
 
     let mn = MetaNode::boot(0, &tc.config).await?;
@@ -504,6 +508,134 @@ async fn test_meta_node_cluster_1_2_2() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+async fn test_meta_node_change_membership() -> anyhow::Result<()> {
+    // - Start a leader and 2 empty nodes.
+    // - Add the nodes as learners, one of them through a non-leader.
+    // - Promote them to voters, then remove them one by one.
+    // - Unsafe changes are rejected.
+
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let (_nid0, tc0) = setup_leader().await?;
+    let mn0 = tc0.meta_nodes[0].clone();
+
+    let tc1 = new_test_context();
+    let addr1 = tc1.config.meta_api_addr();
+    let mn1 = MetaNode::boot_non_voter(1, &tc1.config).await?;
+
+    let tc2 = new_test_context();
+    let addr2 = tc2.config.meta_api_addr();
+    let mn2 = MetaNode::boot_non_voter(2, &tc2.config).await?;
+
+    tracing::info!("--- add learners");
+    {
+        let got = mn0
+            .change_membership(MembershipChange::AddLearner {
+                node_id: 1,
+                address: addr1.clone(),
+            })
+            .await?;
+        assert_eq!(btreeset![0], got.voters);
+        assert_eq!(btreeset![1], got.learners);
+        wait_for_current_leader(&mn1, 0).await?;
+
+        // forwarded to the leader
+        let got = mn1
+            .change_membership(MembershipChange::AddLearner {
+                node_id: 2,
+                address: addr2.clone(),
+            })
+            .await?;
+        assert_eq!(btreeset![1, 2], got.learners);
+        assert_eq!(Some(&addr2), got.nodes.get(&2));
+
+        wait_for_state(&mn2, State::NonVoter).await?;
+        assert_set_file_synced(vec![mn0.clone(), mn1.clone(), mn2.clone()], "learner").await?;
+    }
+
+    tracing::info!("--- unsafe changes are rejected");
+    {
+        let res = mn0
+            .change_membership(MembershipChange::AddLearner {
+                node_id: 1,
+                address: addr2.clone(),
+            })
+            .await;
+        assert_eq!(
+            ErrorCode::NodeAlreadyExists("").code(),
+            res.unwrap_err().code()
+        );
+
+        let res = mn0
+            .change_membership(MembershipChange::ChangeMembership {
+                voters: btreeset![0, 1, 5],
+            })
+            .await;
+        assert_eq!(ErrorCode::UnknownNode("").code(), res.unwrap_err().code());
+
+        let res = mn0
+            .change_membership(MembershipChange::ChangeMembership {
+                voters: btreeset![],
+            })
+            .await;
+        assert_eq!(
+            ErrorCode::InvalidMembership("").code(),
+            res.unwrap_err().code()
+        );
+
+        let res = mn0
+            .change_membership(MembershipChange::RemoveNode { node_id: 5 })
+            .await;
+        assert_eq!(ErrorCode::UnknownNode("").code(), res.unwrap_err().code());
+    }
+
+    tracing::info!("--- promote learners to voters");
+    {
+        let got = mn0
+            .change_membership(MembershipChange::ChangeMembership {
+                voters: btreeset![0, 1, 2],
+            })
+            .await?;
+        assert_eq!(btreeset![0, 1, 2], got.voters);
+        assert!(got.learners.is_empty());
+
+        wait_for_state(&mn1, State::Follower).await?;
+        wait_for_state(&mn2, State::Follower).await?;
+    }
+
+    tracing::info!("--- remove nodes");
+    {
+        let got = mn1
+            .change_membership(MembershipChange::RemoveNode { node_id: 2 })
+            .await?;
+        assert_eq!(btreeset![0, 1], got.voters);
+        assert_eq!(None, got.nodes.get(&2));
+        // A removed node is shut down by the operator.
+        mn2.stop().await?;
+
+        assert_set_file_synced(vec![mn0.clone(), mn1.clone()], "removed").await?;
+
+        let got = mn0
+            .change_membership(MembershipChange::RemoveNode { node_id: 1 })
+            .await?;
+        assert_eq!(btreeset![0], got.voters);
+        assert_eq!(vec![0], got.nodes.keys().cloned().collect::<Vec<_>>());
+        mn1.stop().await?;
+
+        let res = mn0
+            .change_membership(MembershipChange::RemoveNode { node_id: 0 })
+            .await;
+        assert_eq!(
+            ErrorCode::InvalidMembership("").code(),
+            res.unwrap_err().code()
+        );
+    }
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_meta_node_restart() -> anyhow::Result<()> {
     // TODO check restarted follower.
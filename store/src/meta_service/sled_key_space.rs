@@ -177,3 +177,12 @@ impl SledKeySpace for Sequences {
     type K = String;
     type V = SeqNum;
 }
+
+/// Key-Value Types for the number of table versions referencing a data part in sled::Tree:
+pub struct PartRefs {}
+impl SledKeySpace for PartRefs {
+    const PREFIX: u8 = 8;
+    const NAME: &'static str = "part-refs";
+    type K = String;
+    type V = u64;
+}
@@ -12,15 +12,253 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
 use async_raft::SnapshotMeta;
-use serde::Deserialize;
-use serde::Serialize;
+use byteorder::BigEndian;
+use byteorder::ReadBytesExt;
+use byteorder::WriteBytesExt;
+use common_exception::ErrorCode;
+use common_exception::ToErrorCode;
+use common_tracing::tracing;
+use sled::IVec;
+
+use crate::configs;
+
+/// The magic bytes a snapshot data file starts with, including the version of the format.
+const SNAPSHOT_MAGIC: &[u8; 8] = b"fusesnp1";
+
+/// The data file a snapshot sent by the leader is written into, chunk by chunk.
+const RECEIVING_FILE: &str = "receiving.snap";
 
 /// The application snapshot type which the `MetaStore` works with.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// The data is in a file and is never loaded into memory as a whole.
+#[derive(Debug, Clone)]
 pub struct Snapshot {
     pub meta: SnapshotMeta,
 
-    /// The data of the state machine at the time of this snapshot.
-    pub data: Vec<u8>,
+    /// The path of the data file of the state machine at the time of this snapshot.
+    pub path: PathBuf,
+}
+
+/// The snapshots persisted in a dir, each of which has two files:
+/// - `<snapshot_id>.snap`: the data, i.e., the key-values of the state machine,
+///   in the format of `write_snapshot_data()`.
+/// - `<snapshot_id>.meta`: the json encoded `SnapshotMeta`, written after the data is synced.
+///   A snapshot without the meta is incomplete and is removed when the store is opened.
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Open the snapshot dir of a store, which is in the meta dir. It is created if absent.
+    pub fn open(config: &configs::Config) -> common_exception::Result<SnapshotStore> {
+        let dir = Path::new(&config.meta_dir).join(config.tree_name("raft_snapshots"));
+        fs::create_dir_all(&dir).map_err_to_code(ErrorCode::MetaStoreDamaged, || {
+            format!("create snapshot dir: {}", dir.display())
+        })?;
+        Ok(SnapshotStore { dir })
+    }
+
+    pub fn data_path(&self, snapshot_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.snap", snapshot_id))
+    }
+
+    fn meta_path(&self, snapshot_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.meta", snapshot_id))
+    }
+
+    /// The data file to write a snapshot received from the leader into.
+    /// There is at most one snapshot being received.
+    pub fn receiving_path(&self) -> PathBuf {
+        self.dir.join(RECEIVING_FILE)
+    }
+
+    /// Persists a snapshot built from a consistent view of the state machine.
+    pub fn build(
+        &self,
+        meta: SnapshotMeta,
+        view: impl Iterator<Item = sled::Result<(IVec, IVec)>>,
+    ) -> common_exception::Result<Snapshot> {
+        let path = self.data_path(&meta.snapshot_id);
+        let f = fs::File::create(&path)?;
+        let mut w = BufWriter::new(f);
+        write_snapshot_data(&mut w, view)?;
+        w.flush()?;
+        w.get_ref().sync_all()?;
+
+        self.commit(meta, path)
+    }
+
+    /// Persists a snapshot received from the leader, of which the data file is already synced.
+    pub fn commit_received(&self, meta: SnapshotMeta) -> common_exception::Result<Snapshot> {
+        let path = self.data_path(&meta.snapshot_id);
+        fs::rename(self.receiving_path(), &path)?;
+        self.commit(meta, path)
+    }
+
+    /// Marks the snapshot complete by writing its meta, then removes all the other snapshots.
+    fn commit(&self, meta: SnapshotMeta, path: PathBuf) -> common_exception::Result<Snapshot> {
+        let meta_path = self.meta_path(&meta.snapshot_id);
+        let tmp_path = meta_path.with_extension("meta.tmp");
+        {
+            let mut f = fs::File::create(&tmp_path)?;
+            f.write_all(&serde_json::to_vec(&meta)?)?;
+            f.sync_all()?;
+        }
+        fs::rename(&tmp_path, &meta_path)?;
+
+        let snapshot = Snapshot { meta, path };
+        self.purge(Some(&snapshot))?;
+        Ok(snapshot)
+    }
+
+    /// Loads the latest complete snapshot, if any, and removes all the others.
+    pub fn load_latest(&self) -> common_exception::Result<Option<Snapshot>> {
+        let mut latest: Option<Snapshot> = None;
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|x| x.to_str()) != Some("meta") {
+                continue;
+            }
+
+            let meta: SnapshotMeta = match serde_json::from_slice(&fs::read(&path)?) {
+                Ok(meta) => meta,
+                Err(e) => {
+                    tracing::warn!("ignore damaged snapshot meta {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            let snapshot = Snapshot {
+                path: self.data_path(&meta.snapshot_id),
+                meta,
+            };
+
+            let is_later = match &latest {
+                None => true,
+                Some(l) => snapshot.meta.last_log_id.index > l.meta.last_log_id.index,
+            };
+            if is_later && snapshot.path.exists() {
+                latest = Some(snapshot);
+            }
+        }
+
+        self.purge(latest.as_ref())?;
+        let receiving = self.receiving_path();
+        if receiving.exists() {
+            fs::remove_file(&receiving)?;
+        }
+
+        tracing::info!("loaded latest snapshot: {:?}", latest);
+        Ok(latest)
+    }
+
+    /// Removes the files of all the snapshots except `keep` and the one being received.
+    fn purge(&self, keep: Option<&Snapshot>) -> common_exception::Result<()> {
+        let keep_id = keep.map(|s| s.meta.snapshot_id.as_str());
+        let receiving = self.receiving_path();
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path == receiving {
+                continue;
+            }
+            let snapshot_id = path
+                .file_name()
+                .and_then(|x| x.to_str())
+                .and_then(|x| x.split('.').next());
+            if snapshot_id.is_some() && snapshot_id == keep_id {
+                continue;
+            }
+            if let Err(e) = fs::remove_file(&path) {
+                tracing::warn!("fail to remove snapshot file {}: {}", path.display(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the key-values in a compact binary format:
+/// the magic bytes, then for every key-value, the big-endian u32 length of the key, the key,
+/// the big-endian u32 length of the value and the value.
+pub fn write_snapshot_data(
+    w: &mut impl Write,
+    view: impl Iterator<Item = sled::Result<(IVec, IVec)>>,
+) -> common_exception::Result<()> {
+    w.write_all(SNAPSHOT_MAGIC)?;
+    for rkv in view {
+        let (k, v) = rkv.map_err_to_code(ErrorCode::MetaStoreDamaged, || "taking snapshot")?;
+        for x in [k, v] {
+            w.write_u32::<BigEndian>(x.len() as u32)?;
+            w.write_all(&x)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the key-values from a snapshot data file written by `write_snapshot_data()`.
+pub struct SnapshotReader<R: Read> {
+    r: R,
+}
+
+impl SnapshotReader<BufReader<fs::File>> {
+    pub fn open(path: &Path) -> common_exception::Result<Self> {
+        let f = fs::File::open(path).map_err_to_code(ErrorCode::IllegalSnapshot, || {
+            format!("open snapshot: {}", path.display())
+        })?;
+        SnapshotReader::new(BufReader::new(f))
+    }
+}
+
+impl<R: Read> SnapshotReader<R> {
+    pub fn new(mut r: R) -> common_exception::Result<Self> {
+        let mut magic = [0u8; 8];
+        r.read_exact(&mut magic)
+            .map_err_to_code(ErrorCode::IllegalSnapshot, || "read snapshot header")?;
+        if &magic != SNAPSHOT_MAGIC {
+            return Err(ErrorCode::IllegalSnapshot("not a snapshot data file"));
+        }
+        Ok(SnapshotReader { r })
+    }
+
+    fn read_bytes(&mut self, len: u32) -> common_exception::Result<Vec<u8>> {
+        let mut buf = vec![0u8; len as usize];
+        self.r
+            .read_exact(&mut buf)
+            .map_err_to_code(ErrorCode::IllegalSnapshot, || "read snapshot data")?;
+        Ok(buf)
+    }
+
+    fn read_kv(&mut self, key_len: u32) -> common_exception::Result<(Vec<u8>, Vec<u8>)> {
+        let k = self.read_bytes(key_len)?;
+        let value_len = self
+            .r
+            .read_u32::<BigEndian>()
+            .map_err_to_code(ErrorCode::IllegalSnapshot, || "read snapshot data")?;
+        let v = self.read_bytes(value_len)?;
+        Ok((k, v))
+    }
+}
+
+impl<R: Read> Iterator for SnapshotReader<R> {
+    type Item = common_exception::Result<(Vec<u8>, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key_len = match self.r.read_u32::<BigEndian>() {
+            Ok(x) => x,
+            // The data is only allowed to end between two key-values.
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return None,
+            Err(e) => return Some(Err(e.into())),
+        };
+        Some(self.read_kv(key_len))
+    }
 }
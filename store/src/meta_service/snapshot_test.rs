@@ -0,0 +1,128 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_raft::raft::MembershipConfig;
+use async_raft::LogId;
+use async_raft::SnapshotMeta;
+use common_exception::ErrorCode;
+use pretty_assertions::assert_eq;
+use sled::IVec;
+
+use crate::meta_service::snapshot::write_snapshot_data;
+use crate::meta_service::Snapshot;
+use crate::meta_service::SnapshotReader;
+use crate::meta_service::SnapshotStore;
+use crate::tests::service::new_test_context;
+
+fn view() -> impl Iterator<Item = sled::Result<(IVec, IVec)>> {
+    vec![
+        Ok((IVec::from(&b"a"[..]), IVec::from(&b"1"[..]))),
+        Ok((IVec::from(&b"bb"[..]), IVec::from(&b""[..]))),
+    ]
+    .into_iter()
+}
+
+fn meta(index: u64) -> SnapshotMeta {
+    SnapshotMeta {
+        last_log_id: LogId { term: 1, index },
+        snapshot_id: format!("1-{}-0", index),
+        membership: MembershipConfig::new_initial(0),
+    }
+}
+
+fn snapshot_id(snapshot: Option<Snapshot>) -> Option<String> {
+    snapshot.map(|s| s.meta.snapshot_id)
+}
+
+#[test]
+fn test_snapshot_data_write_read() -> anyhow::Result<()> {
+    let mut data = vec![];
+    write_snapshot_data(&mut data, view())?;
+
+    let got = SnapshotReader::new(&data[..])?.collect::<common_exception::Result<Vec<_>>>()?;
+    assert_eq!(
+        vec![(b"a".to_vec(), b"1".to_vec()), (b"bb".to_vec(), vec![])],
+        got
+    );
+
+    // truncated in the middle of a key-value
+    let res =
+        SnapshotReader::new(&data[..data.len() - 1])?.collect::<common_exception::Result<Vec<_>>>();
+    assert_eq!(
+        ErrorCode::IllegalSnapshot("").code(),
+        res.unwrap_err().code()
+    );
+
+    // not a snapshot data file, e.g., of the previous json format
+    let res = SnapshotReader::new(&br#"{"kvs":[]}"#[..]);
+    assert_eq!(
+        ErrorCode::IllegalSnapshot("").code(),
+        res.err().unwrap().code()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_store_build_load() -> anyhow::Result<()> {
+    let tc = new_test_context();
+    let store = SnapshotStore::open(&tc.config)?;
+    assert_eq!(None, snapshot_id(store.load_latest()?));
+
+    let s1 = store.build(meta(5), view())?;
+    let s2 = store.build(meta(9), view())?;
+    assert!(!s1.path.exists(), "the previous snapshot is removed");
+    assert_eq!(2, SnapshotReader::open(&s2.path)?.count());
+
+    check_reopen(&tc.config, Some("1-9-0"))?;
+
+    // An incomplete snapshot is ignored and removed when opened.
+    std::fs::write(store.receiving_path(), b"partial")?;
+    std::fs::write(store.data_path("1-20-0"), b"partial")?;
+    check_reopen(&tc.config, Some("1-9-0"))?;
+    assert!(!store.receiving_path().exists());
+    assert!(!store.data_path("1-20-0").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_store_commit_received() -> anyhow::Result<()> {
+    let tc = new_test_context();
+    let store = SnapshotStore::open(&tc.config)?;
+
+    let s1 = store.build(meta(5), view())?;
+
+    // The chunks are written into the receiving file, which is not removed by a local compaction.
+    std::fs::copy(&s1.path, store.receiving_path())?;
+    let s2 = store.build(meta(7), view())?;
+    assert!(store.receiving_path().exists());
+
+    let s3 = store.commit_received(meta(12))?;
+    assert!(!store.receiving_path().exists());
+    assert!(!s2.path.exists());
+    assert_eq!(2, SnapshotReader::open(&s3.path)?.count());
+
+    check_reopen(&tc.config, Some("1-12-0"))?;
+    Ok(())
+}
+
+/// Reopen the snapshot store and check the latest snapshot loaded.
+fn check_reopen(config: &crate::configs::Config, want: Option<&str>) -> anyhow::Result<()> {
+    let store = SnapshotStore::open(config)?;
+    assert_eq!(
+        want.map(|x| x.to_string()),
+        snapshot_id(store.load_latest()?)
+    );
+    Ok(())
+}
@@ -31,21 +31,28 @@ use common_flights::storage_api_impl::DataPartInfo;
 use common_metatypes::Database;
 use common_metatypes::KVValue;
 use common_metatypes::MatchSeqExt;
+use common_metatypes::MetaVersion;
 use common_metatypes::SeqValue;
 use common_metatypes::Table;
+use common_metatypes::TableVersionAt;
 use common_planners::Part;
 use common_planners::Statistics;
+use common_planners::TableStatistics;
 use common_tracing::tracing;
 use serde::Deserialize;
 use serde::Serialize;
 use sled::IVec;
 
 use crate::configs;
+use crate::meta_service::backup::DatabaseBackup;
+use crate::meta_service::backup::MetaBackup;
+use crate::meta_service::backup::TableBackup;
 use crate::meta_service::placement::rand_n_from_m;
 use crate::meta_service::raft_db::get_sled_db;
 use crate::meta_service::sled_key_space;
 use crate::meta_service::sled_key_space::StateMachineMeta;
 use crate::meta_service::state_machine_meta::StateMachineMetaKey::LastMembership;
+use crate::meta_service::table_history::TablePartsVersion;
 use crate::meta_service::AppliedState;
 use crate::meta_service::AsKeySpace;
 use crate::meta_service::Cmd;
@@ -57,7 +64,9 @@ use crate::meta_service::SledTree;
 use crate::meta_service::StateMachineMetaKey;
 use crate::meta_service::StateMachineMetaKey::Initialized;
 use crate::meta_service::StateMachineMetaKey::LastApplied;
+use crate::meta_service::StateMachineMetaKey::PartRefsTracked;
 use crate::meta_service::StateMachineMetaValue;
+use crate::meta_service::TableHistory;
 
 /// seq number key to generate seq for the value of a `generic_kv` record.
 const SEQ_GENERIC_KV: &str = "generic_kv";
@@ -121,8 +130,11 @@ pub struct StateMachine {
     /// table id to table mapping
     pub tables: BTreeMap<u64, Table>,
 
-    /// table parts， table id -> data parts
-    pub table_parts: HashMap<u64, Vec<DataPartInfo>>,
+    /// table parts， table id -> data parts of the versions within the retention period
+    pub table_parts: HashMap<u64, TableHistory>,
+
+    /// table id -> the statistics collected by the last `ANALYZE TABLE`
+    pub table_statistics: HashMap<u64, TableStatistics>,
 }
 
 /// Initialize state machine for the first time it is brought online.
@@ -234,6 +246,7 @@ impl StateMachine {
             databases: BTreeMap::new(),
             tables: BTreeMap::new(),
             table_parts: HashMap::new(),
+            table_statistics: HashMap::new(),
         };
 
         let inited = {
@@ -251,6 +264,9 @@ impl StateMachine {
             sm_meta
                 .insert(&Initialized, &StateMachineMetaValue::Bool(true))
                 .await?;
+            sm_meta
+                .insert(&PartRefsTracked, &StateMachineMetaValue::Bool(true))
+                .await?;
             Ok(sm)
         }
     }
@@ -304,6 +320,46 @@ impl StateMachine {
         Ok(snap)
     }
 
+    /// Takes a view of the sled data and the catalog with the latest parts of every table, for a backup.
+    /// Both are taken at the same applied log since no log is applied while `self` is borrowed.
+    pub fn backup(&self) -> common_exception::Result<MetaBackup> {
+        let (view, _last_applied, _last_membership, snapshot_id) = self.snapshot()?;
+
+        let databases = self
+            .databases
+            .iter()
+            .map(|(db_name, db)| {
+                let mut tables = db
+                    .tables
+                    .iter()
+                    .filter_map(|(table_name, table_id)| {
+                        let table = self.tables.get(table_id)?;
+                        Some(TableBackup {
+                            name: table_name.clone(),
+                            schema: table.schema.clone(),
+                            parts: self
+                                .table_parts
+                                .get(table_id)
+                                .map(|history| history.latest().parts.clone())
+                                .unwrap_or_default(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                tables.sort_by(|a, b| a.name.cmp(&b.name));
+                DatabaseBackup {
+                    name: db_name.clone(),
+                    tables,
+                }
+            })
+            .collect();
+
+        Ok(MetaBackup {
+            snapshot_id,
+            snapshot: Box::new(view),
+            databases,
+        })
+    }
+
     /// Internal func to get an auto-incr seq number.
     /// It is just what Cmd::IncrSeq does and is also used by Cmd that requires
     /// a unique id such as Cmd::AddDatabase which needs make a new database id.
@@ -410,6 +466,14 @@ impl StateMachine {
                 Ok((prev, Some(value.clone())).into())
             }
 
+            Cmd::DeleteFile { ref key } => {
+                let files = self.files();
+
+                let prev = files.remove(key, true).await?;
+                tracing::info!("applied DeleteFile: {}", key);
+                Ok((prev, None).into())
+            }
+
             Cmd::IncrSeq { ref key } => Ok(self.incr_seq(key).await?.into()),
 
             Cmd::AddNode {
@@ -429,6 +493,12 @@ impl StateMachine {
                 }
             }
 
+            Cmd::RemoveNode { ref node_id } => {
+                let prev = self.nodes().remove(node_id, true).await?;
+                tracing::info!("applied RemoveNode: {}={:?}", node_id, prev);
+                Ok((prev, None).into())
+            }
+
             Cmd::CreateDatabase { ref name, .. } => {
                 // - If the db present, return it.
                 // - Otherwise, create a new one with next seq number as database id, and add it in to store.
@@ -452,7 +522,7 @@ impl StateMachine {
             Cmd::DropDatabase { ref name } => {
                 let prev = self.databases.get(name).cloned();
                 if prev.is_some() {
-                    self.remove_db_data_parts(name);
+                    self.remove_db_data_parts(name).await?;
                     self.databases.remove(name);
                     self.incr_seq(SEQ_DATABASE_META_ID).await?;
                     tracing::debug!("applied DropDatabase: {}", name);
@@ -491,20 +561,85 @@ impl StateMachine {
                 }
             }
 
+            Cmd::CloneDatabase {
+                ref name,
+                ref src_name,
+                time_ms,
+                ..
+            } => {
+                if let Some(prev) = self.databases.get(name) {
+                    let prev = Some(prev.clone());
+                    return Ok((prev.clone(), prev).into());
+                }
+                let src_tables = match self.databases.get(src_name) {
+                    Some(src) => src.tables.clone(),
+                    None => return Ok((None::<Database>, None::<Database>).into()),
+                };
+
+                let mut db = Database {
+                    database_id: self.incr_seq(SEQ_DATABASE_ID).await?,
+                    tables: Default::default(),
+                };
+                for (table_name, src_table_id) in src_tables {
+                    let table = self.clone_table(src_table_id, time_ms).await?;
+                    db.tables.insert(table_name, table.table_id);
+                }
+                self.incr_seq(SEQ_DATABASE_META_ID).await?;
+
+                self.databases.insert(name.clone(), db.clone());
+                tracing::debug!("applied CloneDatabase: {}={:?} from {}", name, db, src_name);
+
+                Ok((None, Some(db)).into())
+            }
+
+            Cmd::CloneTable {
+                ref db_name,
+                ref table_name,
+                ref src_db_name,
+                ref src_table_name,
+                time_ms,
+                ..
+            } => {
+                let table_id = self.get_table_id(db_name, table_name);
+                if let Some(table_id) = table_id {
+                    let prev = self.tables.get(&table_id).cloned();
+                    return Ok((prev.clone(), prev).into());
+                }
+                let src_table_id = match self.get_table_id(src_db_name, src_table_name) {
+                    Some(src_table_id) if self.databases.contains_key(db_name) => src_table_id,
+                    _ => return Ok((None::<Table>, None::<Table>).into()),
+                };
+
+                let table = self.clone_table(src_table_id, time_ms).await?;
+                self.incr_seq(SEQ_DATABASE_META_ID).await?;
+                self.databases.entry(db_name.clone()).and_modify(|db| {
+                    db.tables.insert(table_name.clone(), table.table_id);
+                });
+                tracing::debug!(
+                    "applied CloneTable: {}-{}={:?} from {}-{}",
+                    db_name,
+                    table_name,
+                    table,
+                    src_db_name,
+                    src_table_name
+                );
+
+                Ok((None, Some(table)).into())
+            }
+
             Cmd::DropTable {
                 ref db_name,
                 ref table_name,
                 if_exists: _,
             } => {
-                let db = self.databases.get_mut(db_name).unwrap();
-                let tbl_id = db.tables.get(table_name);
+                let tbl_id = self.get_table_id(db_name, table_name);
                 if let Some(tbl_id) = tbl_id {
-                    let tbl_id = tbl_id.to_owned();
+                    self.remove_table_data_parts(db_name, table_name).await?;
+
+                    let db = self.databases.get_mut(db_name).unwrap();
                     db.tables.remove(table_name);
                     let prev = self.tables.remove(&tbl_id);
 
-                    self.remove_table_data_parts(db_name, table_name);
-
                     self.incr_seq(SEQ_DATABASE_META_ID).await?;
 
                     Ok((prev, None).into())
@@ -570,19 +705,79 @@ impl StateMachine {
             Cmd::TruncateTable {
                 ref db_name,
                 ref table_name,
+                time_ms,
             } => {
                 let db = self.databases.get_mut(db_name).unwrap();
                 let tbl_id = db.tables.get(table_name);
                 if let Some(tbl_id) = tbl_id {
-                    let _tbl_id = tbl_id.to_owned();
+                    let tbl_id = tbl_id.to_owned();
                     let pre_data_parts_count = self.get_data_parts_count(db_name, table_name);
-                    self.remove_table_data_parts(db_name, table_name);
+                    self.truncate_data_parts(tbl_id, time_ms).await?;
                     tracing::debug!("applied TruncateTable: {}", table_name);
                     Ok((Some(pre_data_parts_count), Some(0_usize)).into())
                 } else {
                     Ok((None::<usize>, None::<usize>).into())
                 }
             }
+
+            Cmd::AppendDataParts {
+                ref db_name,
+                ref table_name,
+                table_id,
+                ref append_res,
+                time_ms,
+            } => {
+                let current_table_id = self
+                    .databases
+                    .get(db_name)
+                    .and_then(|db| db.tables.get(table_name));
+
+                if current_table_id == Some(&table_id) {
+                    let (prev, result) = self
+                        .append_data_parts(table_id, append_res, time_ms)
+                        .await?;
+                    tracing::debug!(
+                        "applied AppendDataParts: {}-{}, tx_id: {}",
+                        db_name,
+                        table_name,
+                        append_res.tx_id
+                    );
+                    Ok((Some(prev), Some(result)).into())
+                } else {
+                    Ok((None::<Vec<DataPartInfo>>, None::<Vec<DataPartInfo>>).into())
+                }
+            }
+
+            Cmd::RestoreDataParts {
+                ref db_name,
+                ref table_name,
+                table_id,
+                ref parts,
+                time_ms,
+            } => {
+                if self.get_table_id(db_name, table_name) == Some(table_id) {
+                    let (prev, result) = self
+                        .add_data_parts(table_id, parts.clone(), time_ms)
+                        .await?;
+                    tracing::debug!("applied RestoreDataParts: {}-{}", db_name, table_name);
+                    Ok((Some(prev), Some(result)).into())
+                } else {
+                    Ok((None::<Vec<DataPartInfo>>, None::<Vec<DataPartInfo>>).into())
+                }
+            }
+
+            Cmd::SetTableStatistics {
+                ref db_name,
+                ref table_name,
+                ref statistics,
+            } => match self.get_table_id(db_name, table_name) {
+                Some(table_id) => {
+                    let prev = self.table_statistics.insert(table_id, statistics.clone());
+                    tracing::debug!("applied SetTableStatistics: {}-{}", db_name, table_name);
+                    Ok((prev, Some(statistics.clone())).into())
+                }
+                None => Ok((None::<TableStatistics>, None::<TableStatistics>).into()),
+            },
         }
     }
 
@@ -698,111 +893,240 @@ impl StateMachine {
     }
 
     pub fn get_data_parts(&self, db_name: &str, table_name: &str) -> Option<Vec<DataPartInfo>> {
-        let db = self.databases.get(db_name);
-        if let Some(db) = db {
-            let table_id = db.tables.get(table_name);
-            if let Some(table_id) = table_id {
-                return self.table_parts.get(table_id).map(Clone::clone);
-            }
+        let table_id = self.get_table_id(db_name, table_name)?;
+        self.table_parts
+            .get(&table_id)
+            .map(|history| history.latest().parts.clone())
+    }
+
+    /// Returns the data parts of a table as of a version.
+    pub fn get_data_parts_at(
+        &self,
+        table_id: u64,
+        version: MetaVersion,
+    ) -> common_exception::Result<Vec<DataPartInfo>> {
+        match self.table_parts.get(&table_id) {
+            // A table that has never been changed.
+            None => TableHistory::default()
+                .get(version)
+                .map(|v| v.parts.clone()),
+            Some(history) => history.get(version).map(|v| v.parts.clone()),
         }
-        None
     }
 
-    pub fn get_data_parts_count(&self, db_name: &str, table_name: &str) -> usize {
-        let db = self.databases.get(db_name);
-        if let Some(db) = db {
-            let table_id = db.tables.get(table_name);
-            if let Some(table_id) = table_id {
-                let parts_vec = self.table_parts.get(table_id);
-                if let Some(parts_vec) = parts_vec {
-                    return parts_vec.len();
-                } else {
-                    return 0;
-                }
-            }
+    /// Resolves the version of a table as of a point in its history.
+    pub fn get_table_version(
+        &self,
+        table_id: u64,
+        at: &TableVersionAt,
+    ) -> common_exception::Result<MetaVersion> {
+        match self.table_parts.get(&table_id) {
+            None => TableHistory::default().resolve(at),
+            Some(history) => history.resolve(at),
         }
-        0
     }
 
-    pub fn append_data_parts(
+    pub fn get_data_parts_count(&self, db_name: &str, table_name: &str) -> usize {
+        self.get_table_id(db_name, table_name)
+            .and_then(|table_id| self.table_parts.get(&table_id))
+            .map(|history| history.latest().parts.len())
+            .unwrap_or(0)
+    }
+
+    pub fn get_table_statistics(&self, db_name: &str, table_name: &str) -> Option<TableStatistics> {
+        let table_id = self.get_table_id(db_name, table_name)?;
+        self.table_statistics.get(&table_id).cloned()
+    }
+
+    pub fn get_table_id(&self, db_name: &str, table_name: &str) -> Option<u64> {
+        let db = self.databases.get(db_name)?;
+        db.tables.get(table_name).copied()
+    }
+
+    /// Adds all the parts of an append to the table as a new version,
+    /// returns the parts of the table before and after.
+    pub async fn append_data_parts(
         &mut self,
-        db_name: &str,
-        table_name: &str,
+        table_id: u64,
         append_res: &AppendResult,
-    ) {
+        time_ms: u64,
+    ) -> common_exception::Result<(Vec<DataPartInfo>, Vec<DataPartInfo>)> {
         let part_infos = append_res
             .parts
             .iter()
-            .map(|p| {
-                let loc = &p.location;
-                DataPartInfo {
-                    part: Part {
-                        name: loc.clone(),
-                        version: 0,
-                    },
-                    stats: Statistics::new_exact(p.rows, p.disk_bytes),
-                }
+            .map(|p| DataPartInfo {
+                part: Part {
+                    name: p.location.clone(),
+                    version: 0,
+                },
+                stats: Statistics::new_exact(p.rows, p.disk_bytes),
             })
             .collect::<Vec<_>>();
+        self.add_data_parts(table_id, part_infos, time_ms).await
+    }
 
-        let db = self.databases.get(db_name);
-        if let Some(db) = db {
-            let table_id = db.tables.get(table_name);
-            if let Some(table_id) = table_id {
-                for part in part_infos {
-                    let table = self.tables.get_mut(table_id).unwrap();
-                    table.parts.insert(part.part.name.clone());
-                    // These comments are intentionally left here.
-                    // As rustc not smart enough, it says:
-                    // for part in part_infos {
-                    //     ---- move occurs because `part` has type `DataPartInfo`, which does not implement the `Copy` trait
-                    //     .and_modify(|v| v.push(part))
-                    //                 ---        ---- variable moved due to use in closure
-                    //                 |
-                    //                 value moved into closure here
-                    //     .or_insert_with(|| vec![part]);
-                    //                     ^^      ---- use occurs due to use in closure
-                    //                     |
-                    //                     value used here after move
-                    // But obviously the two methods can't happen at the same time.
-                    // ============== previous =============
-                    // self.table_parts
-                    //     .entry(*table_id)
-                    //     .and_modify(|v| v.push(part))
-                    //     .or_insert_with(|| vec![part]);
-                    // ============ previous end ===========
-                    match self.table_parts.get_mut(table_id) {
-                        Some(p) => {
-                            p.push(part);
-                        }
-                        None => {
-                            self.table_parts.insert(*table_id, vec![part]);
-                        }
-                    }
-                }
+    /// Adds the parts to the table as a new version, returns the parts of the table before and after.
+    pub async fn add_data_parts(
+        &mut self,
+        table_id: u64,
+        part_infos: Vec<DataPartInfo>,
+        time_ms: u64,
+    ) -> common_exception::Result<(Vec<DataPartInfo>, Vec<DataPartInfo>)> {
+        if let Some(table) = self.tables.get_mut(&table_id) {
+            for part in &part_infos {
+                table.parts.insert(part.part.name.clone());
             }
         }
+
+        let prev = self
+            .table_parts
+            .get(&table_id)
+            .map(|history| history.latest().parts.clone())
+            .unwrap_or_default();
+        let mut parts = prev.clone();
+        parts.extend(part_infos);
+        self.change_data_parts(table_id, parts.clone(), time_ms)
+            .await?;
+        Ok((prev, parts))
+    }
+
+    /// Removes all the parts of the table as a new version.
+    /// The parts of the previous versions are still readable within the retention period.
+    pub async fn truncate_data_parts(
+        &mut self,
+        table_id: u64,
+        time_ms: u64,
+    ) -> common_exception::Result<()> {
+        self.tables.entry(table_id).and_modify(|t| t.parts.clear());
+        self.change_data_parts(table_id, vec![], time_ms).await
+    }
+
+    /// Removes the table parts of all the versions, e.g., when the table is dropped.
+    pub async fn remove_table_data_parts(
+        &mut self,
+        db_name: &str,
+        table_name: &str,
+    ) -> common_exception::Result<()> {
+        match self.get_table_id(db_name, table_name) {
+            Some(table_id) => self.remove_table_history(table_id).await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn remove_db_data_parts(&mut self, db_name: &str) -> common_exception::Result<()> {
+        let table_ids = match self.databases.get(db_name) {
+            Some(db) => db.tables.values().copied().collect::<Vec<_>>(),
+            None => return Ok(()),
+        };
+        for table_id in table_ids {
+            self.remove_table_history(table_id).await?;
+        }
+        Ok(())
     }
 
-    pub fn remove_table_data_parts(&mut self, db_name: &str, table_name: &str) {
-        let db = self.databases.get(db_name);
-        if let Some(db) = db {
-            let table_id = db.tables.get(table_name);
-            if let Some(table_id) = table_id {
-                self.tables.entry(*table_id).and_modify(|t| t.parts.clear());
-                self.table_parts.remove(table_id);
+    async fn remove_table_history(&mut self, table_id: u64) -> common_exception::Result<()> {
+        self.tables.entry(table_id).and_modify(|t| t.parts.clear());
+        self.table_statistics.remove(&table_id);
+        match self.table_parts.remove(&table_id) {
+            Some(history) => self.unref_parts(history.into_versions()).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Creates a table with the schema, the latest parts and the statistics of the source table,
+    /// the parts are shared by both tables instead of being copied.
+    async fn clone_table(
+        &mut self,
+        src_table_id: u64,
+        time_ms: u64,
+    ) -> common_exception::Result<Table> {
+        let src =
+            self.tables.get(&src_table_id).cloned().ok_or_else(|| {
+                ErrorCode::UnknownTable(format!("table not found: {}", src_table_id))
+            })?;
+        let table = Table {
+            table_id: self.incr_seq(SEQ_TABLE_ID).await?,
+            schema: src.schema,
+            parts: src.parts,
+        };
+        self.tables.insert(table.table_id, table.clone());
+
+        let parts = self
+            .table_parts
+            .get(&src_table_id)
+            .map(|history| history.latest().parts.clone())
+            .unwrap_or_default();
+        self.change_data_parts(table.table_id, parts, time_ms)
+            .await?;
+
+        if let Some(statistics) = self.table_statistics.get(&src_table_id).cloned() {
+            self.table_statistics.insert(table.table_id, statistics);
+        }
+        Ok(table)
+    }
+
+    /// Sets the parts of a table as a new version, and updates the part references by the new
+    /// version and by the versions out of the retention period.
+    async fn change_data_parts(
+        &mut self,
+        table_id: u64,
+        parts: Vec<DataPartInfo>,
+        time_ms: u64,
+    ) -> common_exception::Result<()> {
+        for part in &parts {
+            let name = &part.part.name;
+            let refs = self.part_refs().get(name)?.unwrap_or_default();
+            self.part_refs().insert(name, &(refs + 1)).await?;
+        }
+
+        let retention_ms = self.history_retention_ms();
+        let history = self.table_parts.entry(table_id).or_default();
+        let expired = history.add(parts, time_ms, retention_ms);
+        self.unref_parts(expired).await
+    }
+
+    /// Removes the references of the versions. The file meta of a part no version references
+    /// is removed along with its last reference, on every node.
+    async fn unref_parts(
+        &mut self,
+        versions: impl IntoIterator<Item = TablePartsVersion>,
+    ) -> common_exception::Result<()> {
+        for part in versions.into_iter().flat_map(|v| v.parts) {
+            let name = part.part.name;
+            match self.part_refs().get(&name)? {
+                Some(refs) if refs > 1 => {
+                    self.part_refs().insert(&name, &(refs - 1)).await?;
+                }
+                _ => {
+                    self.part_refs().remove(&name, true).await?;
+                    self.files().remove(&name, true).await?;
+                }
             }
         }
+        Ok(())
     }
 
-    pub fn remove_db_data_parts(&mut self, db_name: &str) {
-        let db = self.databases.get(db_name);
-        if let Some(db) = db {
-            for table_id in db.tables.values() {
-                self.tables.entry(*table_id).and_modify(|t| t.parts.clear());
-                self.table_parts.remove(table_id);
+    /// Returns the names that no table version references, i.e., of which the files can be deleted,
+    /// or None if the references are not tracked by this state machine, then nothing can be deleted.
+    pub fn unreferenced_parts(
+        &self,
+        names: Vec<String>,
+    ) -> common_exception::Result<Option<Vec<String>>> {
+        if self.sm_meta().get(&PartRefsTracked)?.is_none() {
+            return Ok(None);
+        }
+
+        let mut unreferenced = vec![];
+        for name in names {
+            if !self.part_refs().contains_key(&name)? {
+                unreferenced.push(name);
             }
         }
+        Ok(Some(unreferenced))
+    }
+
+    fn history_retention_ms(&self) -> u64 {
+        self.config.table_history_retention.saturating_mul(1000)
     }
 
     pub fn mget_kv(
@@ -893,6 +1217,14 @@ impl StateMachine {
         self.sm_tree.key_space()
     }
 
+    /// part name -> the number of table versions referencing the part.
+    /// A part is shared by the versions of a table and by the tables cloned from each other.
+    /// The local files of the parts not in it are deleted by the `PartGc` of every node.
+    /// It is kept in sled so that it survives restarts and is carried by the snapshots.
+    pub fn part_refs(&self) -> AsKeySpace<sled_key_space::PartRefs> {
+        self.sm_tree.key_space()
+    }
+
     /// A kv store of all other general purpose information.
     /// The value is tuple of a monotonic sequence number and userdata value in string.
     /// The sequence number is guaranteed to increment(by some value greater than 0) everytime the record changes.
@@ -34,6 +34,11 @@ pub enum StateMachineMetaKey {
 
     /// The last membership config
     LastMembership,
+
+    /// Whether the part references are counted since the state machine is created.
+    /// A state machine created before they are kept in sled lacks it, and its parts are
+    /// never collected.
+    PartRefsTracked,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum StateMachineMetaValue {
@@ -54,6 +59,9 @@ impl fmt::Display for StateMachineMetaKey {
             StateMachineMetaKey::LastMembership => {
                 write!(f, "last-membership")
             }
+            StateMachineMetaKey::PartRefsTracked => {
+                write!(f, "part-refs-tracked")
+            }
         }
     }
 }
@@ -64,6 +72,7 @@ impl SledOrderedSerde for StateMachineMetaKey {
             StateMachineMetaKey::LastApplied => 1,
             StateMachineMetaKey::Initialized => 2,
             StateMachineMetaKey::LastMembership => 3,
+            StateMachineMetaKey::PartRefsTracked => 4,
         };
 
         Ok(IVec::from(&[i]))
@@ -78,6 +87,8 @@ impl SledOrderedSerde for StateMachineMetaKey {
             return Ok(StateMachineMetaKey::Initialized);
         } else if slice[0] == 3 {
             return Ok(StateMachineMetaKey::LastMembership);
+        } else if slice[0] == 4 {
+            return Ok(StateMachineMetaKey::PartRefsTracked);
         }
 
         Err(ErrorCode::MetaStoreDamaged("invalid key IVec"))
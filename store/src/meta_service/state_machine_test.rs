@@ -20,15 +20,24 @@ use async_raft::raft::EntryNormal;
 use async_raft::raft::EntryPayload;
 use async_raft::raft::MembershipConfig;
 use async_raft::LogId;
+use common_flights::storage_api_impl::AppendResult;
+use common_flights::storage_api_impl::DataPartInfo;
 use common_metatypes::Database;
 use common_metatypes::KVMeta;
 use common_metatypes::KVValue;
 use common_metatypes::MatchSeq;
 use common_metatypes::SeqValue;
+use common_metatypes::Table;
+use common_metatypes::TableVersionAt;
+use common_planners::Part;
+use common_planners::Statistics;
+use common_planners::TableStatistics;
 use common_runtime::tokio;
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
 
+use crate::meta_service::sled_key_space::PartRefs;
+use crate::meta_service::sled_key_space::SledKeySpace;
 use crate::meta_service::state_machine::Replication;
 use crate::meta_service::state_machine::SerializableSnapshot;
 use crate::meta_service::testing::pretty_snapshot;
@@ -40,6 +49,7 @@ use crate::meta_service::LogEntry;
 use crate::meta_service::Node;
 use crate::meta_service::Slot;
 use crate::meta_service::StateMachine;
+use crate::meta_service::StateMachineMetaKey;
 use crate::tests::service::new_test_context;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -629,6 +639,530 @@ async fn test_state_machine_apply_set_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_delete_file() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_test_context();
+    let mut sm = StateMachine::open(&tc.config, 1).await?;
+
+    let cases = vec![
+        (
+            "add file",
+            Cmd::AddFile {
+                key: "k1".to_string(),
+                value: "v1".to_string(),
+            },
+            None,
+            Some("v1".to_string()),
+        ),
+        (
+            "delete existent file",
+            Cmd::DeleteFile {
+                key: "k1".to_string(),
+            },
+            Some("v1".to_string()),
+            None,
+        ),
+        (
+            "delete absent file",
+            Cmd::DeleteFile {
+                key: "k1".to_string(),
+            },
+            None,
+            None,
+        ),
+    ];
+
+    for (i, (name, cmd, want_prev, want_result)) in cases.iter().enumerate() {
+        let resp = sm
+            .apply(&Entry {
+                log_id: LogId {
+                    term: 0,
+                    index: 5 + i as u64,
+                },
+                payload: EntryPayload::Normal(EntryNormal {
+                    data: LogEntry {
+                        txid: None,
+                        cmd: cmd.clone(),
+                    },
+                }),
+            })
+            .await?;
+        assert_eq!(
+            AppliedState::String {
+                prev: want_prev.clone(),
+                result: want_result.clone()
+            },
+            resp,
+            "{}",
+            name
+        );
+    }
+
+    assert_eq!(None, sm.get_file("k1")?);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_append_data_parts() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_test_context();
+    let mut sm = StateMachine::open(&tc.config, 1).await?;
+
+    let apply = |cmd: Cmd| LogEntry { txid: None, cmd };
+
+    sm.apply_non_dup(&apply(Cmd::CreateDatabase {
+        name: "db1".to_string(),
+        if_not_exists: false,
+        db: Database::default(),
+    }))
+    .await?;
+    let table_id = match sm
+        .apply_non_dup(&apply(Cmd::CreateTable {
+            db_name: "db1".to_string(),
+            table_name: "tb1".to_string(),
+            if_not_exists: false,
+            table: Table::default(),
+        }))
+        .await?
+    {
+        AppliedState::Table {
+            result: Some(table),
+            ..
+        } => table.table_id,
+        other => panic!("unexpected applied state: {:?}", other),
+    };
+
+    let mut append_res = AppendResult {
+        tx_id: "tx1".to_string(),
+        ..AppendResult::default()
+    };
+    append_res.append_part("db1/tb1/p1", 1, 1, 1, 1);
+    append_res.append_part("db1/tb1/p2", 2, 1, 2, 2);
+
+    // All the parts of an append are published at once.
+    let resp = sm
+        .apply_non_dup(&apply(Cmd::AppendDataParts {
+            db_name: "db1".to_string(),
+            table_name: "tb1".to_string(),
+            table_id,
+            append_res: append_res.clone(),
+            time_ms: 1,
+        }))
+        .await?;
+    match resp {
+        AppliedState::DataParts {
+            prev: Some(prev),
+            result: Some(result),
+        } => {
+            assert_eq!(0, prev.len());
+            assert_eq!(vec!["db1/tb1/p1", "db1/tb1/p2"], part_names(&result));
+        }
+        other => panic!("unexpected applied state: {:?}", other),
+    }
+    assert_eq!(2, sm.get_data_parts_count("db1", "tb1"));
+    assert_eq!(2, sm.get_table(&table_id).unwrap().parts.len());
+
+    // The parts are discarded if the table is not the one they are written for.
+    let resp = sm
+        .apply_non_dup(&apply(Cmd::AppendDataParts {
+            db_name: "db1".to_string(),
+            table_name: "tb1".to_string(),
+            table_id: table_id + 1,
+            append_res,
+            time_ms: 1,
+        }))
+        .await?;
+    assert_eq!(
+        AppliedState::DataParts {
+            prev: None,
+            result: None
+        },
+        resp
+    );
+    assert_eq!(2, sm.get_data_parts_count("db1", "tb1"));
+
+    // A truncate creates a new version, the previous ones are still readable.
+    sm.apply_non_dup(&apply(Cmd::TruncateTable {
+        db_name: "db1".to_string(),
+        table_name: "tb1".to_string(),
+        time_ms: 2,
+    }))
+    .await?;
+    assert_eq!(0, sm.get_data_parts_count("db1", "tb1"));
+    assert_eq!(0, sm.get_data_parts_at(table_id, 0)?.len());
+    assert_eq!(
+        vec!["db1/tb1/p1", "db1/tb1/p2"],
+        part_names(&sm.get_data_parts_at(table_id, 1)?)
+    );
+    assert_eq!(0, sm.get_data_parts_at(table_id, 2)?.len());
+    assert_eq!(
+        1,
+        sm.get_table_version(table_id, &TableVersionAt::Timestamp(1))?
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_clone_part_refs() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    // Replaced versions are removed at once.
+    let mut tc = new_test_context();
+    tc.config.table_history_retention = 0;
+    let mut sm = StateMachine::open(&tc.config, 1).await?;
+
+    let apply = |cmd: Cmd| LogEntry { txid: None, cmd };
+    let s = |x: &str| x.to_string();
+
+    sm.apply_non_dup(&apply(Cmd::CreateDatabase {
+        name: s("db1"),
+        if_not_exists: false,
+        db: Database::default(),
+    }))
+    .await?;
+    let table_id = match sm
+        .apply_non_dup(&apply(Cmd::CreateTable {
+            db_name: s("db1"),
+            table_name: s("tb1"),
+            if_not_exists: false,
+            table: Table::default(),
+        }))
+        .await?
+    {
+        AppliedState::Table {
+            result: Some(table),
+            ..
+        } => table.table_id,
+        other => panic!("unexpected applied state: {:?}", other),
+    };
+
+    let mut append_res = AppendResult::default();
+    append_res.append_part("db1/tb1/p1", 1, 1, 1, 1);
+    append_res.append_part("db1/tb1/p2", 2, 1, 2, 2);
+    sm.apply_non_dup(&apply(Cmd::AppendDataParts {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        table_id,
+        append_res,
+        time_ms: 1,
+    }))
+    .await?;
+
+    let clone_table = |table_name: &str, src_table_name: &str, time_ms: u64| {
+        apply(Cmd::CloneTable {
+            db_name: s("db1"),
+            table_name: s(table_name),
+            src_db_name: s("db1"),
+            src_table_name: s(src_table_name),
+            if_not_exists: false,
+            time_ms,
+        })
+    };
+
+    // A clone shares the parts of the source table.
+    let resp = sm.apply_non_dup(&clone_table("tb2", "tb1", 2)).await?;
+    match resp {
+        AppliedState::Table {
+            prev: None,
+            result: Some(table),
+        } => assert_ne!(table_id, table.table_id),
+        other => panic!("unexpected applied state: {:?}", other),
+    }
+    let parts = sm.get_data_parts("db1", "tb2").unwrap();
+    assert_eq!(vec!["db1/tb1/p1", "db1/tb1/p2"], part_names(&parts));
+    assert_eq!(Some(2), sm.part_refs().get(&s("db1/tb1/p1"))?);
+
+    // Clone to an existent table or from an absent one does nothing.
+    let resp = sm.apply_non_dup(&clone_table("tb2", "tb1", 2)).await?;
+    assert!(matches!(resp, AppliedState::Table { prev: Some(_), .. }));
+    let resp = sm.apply_non_dup(&clone_table("tb3", "absent", 2)).await?;
+    assert_eq!(
+        AppliedState::Table {
+            prev: None,
+            result: None
+        },
+        resp
+    );
+
+    sm.apply_non_dup(&apply(Cmd::CloneDatabase {
+        name: s("db2"),
+        src_name: s("db1"),
+        if_not_exists: false,
+        time_ms: 3,
+    }))
+    .await?;
+    let parts = sm.get_data_parts("db2", "tb2").unwrap();
+    assert_eq!(vec!["db1/tb1/p1", "db1/tb1/p2"], part_names(&parts));
+    assert_eq!(Some(4), sm.part_refs().get(&s("db1/tb1/p1"))?);
+
+    // A part is not unused until the last table referencing it is removed.
+    sm.apply_non_dup(&apply(Cmd::TruncateTable {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        time_ms: 4,
+    }))
+    .await?;
+    sm.apply_non_dup(&apply(Cmd::DropDatabase { name: s("db2") }))
+        .await?;
+    assert_eq!(Some(1), sm.part_refs().get(&s("db1/tb1/p1"))?);
+    assert_eq!(
+        Some(vec![s("db1/tb1/p3")]),
+        sm.unreferenced_parts(vec![s("db1/tb1/p1"), s("db1/tb1/p3")])?
+    );
+
+    // The file meta of a part is removed along with its last reference.
+    for name in ["db1/tb1/p1", "db1/tb1/p2"] {
+        sm.apply_non_dup(&apply(Cmd::AddFile {
+            key: s(name),
+            value: s("data"),
+        }))
+        .await?;
+    }
+    sm.apply_non_dup(&apply(Cmd::DropTable {
+        db_name: s("db1"),
+        table_name: s("tb2"),
+        if_exists: false,
+    }))
+    .await?;
+    assert!(sm.part_refs().range_keys(..)?.is_empty());
+    assert_eq!(None, sm.files().get(&s("db1/tb1/p1"))?);
+    assert_eq!(None, sm.files().get(&s("db1/tb1/p2"))?);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_part_refs_persisted() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_test_context();
+    let mut sm = StateMachine::open(&tc.config, 1).await?;
+
+    let apply = |cmd: Cmd| LogEntry { txid: None, cmd };
+    let s = |x: &str| x.to_string();
+
+    sm.apply_non_dup(&apply(Cmd::CreateDatabase {
+        name: s("db1"),
+        if_not_exists: false,
+        db: Database::default(),
+    }))
+    .await?;
+    sm.apply_non_dup(&apply(Cmd::CreateTable {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        if_not_exists: false,
+        table: Table::default(),
+    }))
+    .await?;
+    let table_id = sm.get_database("db1").unwrap().tables["tb1"];
+
+    let mut append_res = AppendResult::default();
+    append_res.append_part("db1/tb1/p1", 1, 1, 1, 1);
+    sm.apply_non_dup(&apply(Cmd::AppendDataParts {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        table_id,
+        append_res,
+        time_ms: 1,
+    }))
+    .await?;
+
+    // The references are kept in sled, thus in the snapshots too, and survive a restart.
+    drop(sm);
+    let sm = StateMachine::open(&tc.config, 1).await?;
+    assert_eq!(Some(1), sm.part_refs().get(&s("db1/tb1/p1"))?);
+    assert_eq!(
+        Some(vec![s("db1/tb1/p2")]),
+        sm.unreferenced_parts(vec![s("db1/tb1/p1"), s("db1/tb1/p2")])?
+    );
+    let (view, _, _, _) = sm.snapshot()?;
+    let ref_key = PartRefs::serialize_key(&s("db1/tb1/p1"))?;
+    assert!(view.map(|kv| kv.unwrap().0).any(|k| k == ref_key));
+
+    // Nothing is unreferenced for a state machine not tracking the references.
+    sm.sm_meta()
+        .remove(&StateMachineMetaKey::PartRefsTracked, true)
+        .await?;
+    assert_eq!(None, sm.unreferenced_parts(vec![s("db1/tb1/p2")])?);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_restore_data_parts() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_test_context();
+    let mut sm = StateMachine::open(&tc.config, 1).await?;
+
+    let apply = |cmd: Cmd| LogEntry { txid: None, cmd };
+    let s = |x: &str| x.to_string();
+
+    sm.apply_non_dup(&apply(Cmd::CreateDatabase {
+        name: s("db1"),
+        if_not_exists: false,
+        db: Database::default(),
+    }))
+    .await?;
+    sm.apply_non_dup(&apply(Cmd::CreateTable {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        if_not_exists: false,
+        table: Table::default(),
+    }))
+    .await?;
+    let table_id = sm.get_table_id("db1", "tb1").unwrap();
+
+    let parts = vec![DataPartInfo {
+        part: Part {
+            name: s("db1/tb1/p1"),
+            version: 3,
+        },
+        stats: Statistics {
+            read_rows: 10,
+            read_bytes: 100,
+            is_exact: false,
+        },
+    }];
+    let restore = |table_id: u64| {
+        apply(Cmd::RestoreDataParts {
+            db_name: s("db1"),
+            table_name: s("tb1"),
+            table_id,
+            parts: parts.clone(),
+            time_ms: 1,
+        })
+    };
+
+    // The parts of another table are discarded.
+    let resp = sm.apply_non_dup(&restore(table_id + 1)).await?;
+    assert_eq!(
+        AppliedState::DataParts {
+            prev: None,
+            result: None
+        },
+        resp
+    );
+
+    // The parts are restored as they are backed up.
+    let resp = sm.apply_non_dup(&restore(table_id)).await?;
+    assert_eq!(
+        AppliedState::DataParts {
+            prev: Some(vec![]),
+            result: Some(parts.clone())
+        },
+        resp
+    );
+    assert_eq!(Some(parts), sm.get_data_parts("db1", "tb1"));
+    assert_eq!(Some(1), sm.part_refs().get(&s("db1/tb1/p1"))?);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_set_table_statistics() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_test_context();
+    let mut sm = StateMachine::open(&tc.config, 1).await?;
+
+    let apply = |cmd: Cmd| LogEntry { txid: None, cmd };
+    let s = |x: &str| x.to_string();
+    let set_statistics = |table_name: &str, row_count: u64| {
+        apply(Cmd::SetTableStatistics {
+            db_name: s("db1"),
+            table_name: s(table_name),
+            statistics: TableStatistics {
+                row_count,
+                columns: vec![],
+            },
+        })
+    };
+
+    sm.apply_non_dup(&apply(Cmd::CreateDatabase {
+        name: s("db1"),
+        if_not_exists: false,
+        db: Database::default(),
+    }))
+    .await?;
+    sm.apply_non_dup(&apply(Cmd::CreateTable {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        if_not_exists: false,
+        table: Table::default(),
+    }))
+    .await?;
+
+    // An absent table has no statistics to set.
+    let resp = sm.apply_non_dup(&set_statistics("absent", 1)).await?;
+    assert_eq!(
+        AppliedState::TableStatistics {
+            prev: None,
+            result: None
+        },
+        resp
+    );
+
+    let resp = sm.apply_non_dup(&set_statistics("tb1", 1)).await?;
+    assert!(matches!(resp, AppliedState::TableStatistics {
+        prev: None,
+        result: Some(_)
+    }));
+    let resp = sm.apply_non_dup(&set_statistics("tb1", 2)).await?;
+    match resp {
+        AppliedState::TableStatistics {
+            prev: Some(prev),
+            result: Some(result),
+        } => assert_eq!((1, 2), (prev.row_count, result.row_count)),
+        other => panic!("unexpected applied state: {:?}", other),
+    }
+    assert_eq!(
+        Some(2),
+        sm.get_table_statistics("db1", "tb1").map(|s| s.row_count)
+    );
+
+    // A clone has the same data, thus the same statistics.
+    sm.apply_non_dup(&apply(Cmd::CloneTable {
+        db_name: s("db1"),
+        table_name: s("tb2"),
+        src_db_name: s("db1"),
+        src_table_name: s("tb1"),
+        if_not_exists: false,
+        time_ms: 1,
+    }))
+    .await?;
+    assert_eq!(
+        Some(2),
+        sm.get_table_statistics("db1", "tb2").map(|s| s.row_count)
+    );
+
+    sm.apply_non_dup(&apply(Cmd::DropTable {
+        db_name: s("db1"),
+        table_name: s("tb1"),
+        if_exists: false,
+    }))
+    .await?;
+    assert!(sm.get_table_statistics("db1", "tb1").is_none());
+    assert_eq!(1, sm.table_statistics.len());
+
+    Ok(())
+}
+
+fn part_names(parts: &[DataPartInfo]) -> Vec<&str> {
+    parts.iter().map(|p| p.part.name.as_str()).collect()
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_snapshot() -> anyhow::Result<()> {
     // - Feed logs into state machine.
@@ -0,0 +1,149 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_flights::storage_api_impl::DataPartInfo;
+use common_metatypes::MetaVersion;
+use common_metatypes::TableVersionAt;
+
+/// The current time in milliseconds since 1970, as the creation time of a table version.
+/// It is taken when a change is proposed, so that all the replicas agree on it.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// The part list of a table at a version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TablePartsVersion {
+    pub version: MetaVersion,
+    /// The time in milliseconds since 1970 when the version is created.
+    pub created_ms: u64,
+    pub parts: Vec<DataPartInfo>,
+}
+
+/// The part lists of a table, one for every change of the parts, i.e., an append or a truncate.
+///
+/// A table starts at version 0 with no parts, and every change creates the next version.
+/// A version is kept until it has been replaced for the retention period,
+/// thus the table can be read as of any version within the period.
+/// The latest version is always kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableHistory {
+    /// In the order of version, the last one is the latest.
+    versions: VecDeque<TablePartsVersion>,
+}
+
+impl Default for TableHistory {
+    fn default() -> Self {
+        let mut versions = VecDeque::new();
+        versions.push_back(TablePartsVersion {
+            version: 0,
+            created_ms: 0,
+            parts: vec![],
+        });
+        TableHistory { versions }
+    }
+}
+
+impl TableHistory {
+    pub fn latest(&self) -> &TablePartsVersion {
+        // There is always at least one version.
+        self.versions.back().unwrap()
+    }
+
+    /// Adds the parts as the next version, and removes the versions out of the retention period.
+    /// Returns the removed versions.
+    pub fn add(
+        &mut self,
+        parts: Vec<DataPartInfo>,
+        now_ms: u64,
+        retention_ms: u64,
+    ) -> Vec<TablePartsVersion> {
+        let version = self.latest().version + 1;
+        self.versions.push_back(TablePartsVersion {
+            version,
+            created_ms: now_ms,
+            parts,
+        });
+
+        // A version is replaced when the next one is created.
+        let mut expired = vec![];
+        while self.versions.len() > 1
+            && self.versions[1].created_ms.saturating_add(retention_ms) <= now_ms
+        {
+            expired.extend(self.versions.pop_front());
+        }
+        expired
+    }
+
+    pub fn into_versions(self) -> Vec<TablePartsVersion> {
+        self.versions.into()
+    }
+
+    /// The names of the parts referenced by any version.
+    pub fn part_names(&self) -> HashSet<String> {
+        self.versions
+            .iter()
+            .flat_map(|v| v.parts.iter().map(|p| p.part.name.clone()))
+            .collect()
+    }
+
+    /// Resolves the version of the table as of a version or a timestamp.
+    pub fn resolve(&self, at: &TableVersionAt) -> Result<MetaVersion> {
+        match at {
+            TableVersionAt::Version(version) => self.get(*version).map(|v| v.version),
+            TableVersionAt::Latest => Ok(self.latest().version),
+            TableVersionAt::Timestamp(ts) => {
+                // The version created last, not after the timestamp.
+                let found = self.versions.iter().rev().find(|v| v.created_ms <= *ts);
+                match found {
+                    Some(v) => Ok(v.version),
+                    None => Err(ErrorCode::UnknownTableVersion(format!(
+                        "the version at timestamp {}ms is out of the retention period",
+                        ts
+                    ))),
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, version: MetaVersion) -> Result<&TablePartsVersion> {
+        let latest = self.latest().version;
+        if version > latest {
+            return Err(ErrorCode::UnknownTableVersion(format!(
+                "version {} does not exist, the latest version is {}",
+                version, latest
+            )));
+        }
+
+        self.versions
+            .iter()
+            .find(|v| v.version == version)
+            .ok_or_else(|| {
+                ErrorCode::UnknownTableVersion(format!(
+                    "version {} is out of the retention period",
+                    version
+                ))
+            })
+    }
+}
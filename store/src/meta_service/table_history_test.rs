@@ -0,0 +1,124 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_flights::storage_api_impl::DataPartInfo;
+use common_metatypes::TableVersionAt;
+use common_planners::Part;
+use common_planners::Statistics;
+use pretty_assertions::assert_eq;
+
+use crate::meta_service::TableHistory;
+
+fn parts(names: &[&str]) -> Vec<DataPartInfo> {
+    names
+        .iter()
+        .map(|name| DataPartInfo {
+            part: Part {
+                name: name.to_string(),
+                version: 0,
+            },
+            stats: Statistics::new_exact(1, 1),
+        })
+        .collect()
+}
+
+fn part_names(history: &TableHistory, version: u64) -> Vec<String> {
+    history
+        .get(version)
+        .unwrap()
+        .parts
+        .iter()
+        .map(|p| p.part.name.clone())
+        .collect()
+}
+
+#[test]
+fn test_table_history_versions() -> anyhow::Result<()> {
+    let mut history = TableHistory::default();
+    assert_eq!(0, history.latest().version);
+
+    history.add(parts(&["p1"]), 1000, 10_000);
+    assert_eq!(1, history.latest().version);
+    history.add(parts(&["p1", "p2"]), 2000, 10_000);
+    assert_eq!(2, history.latest().version);
+    let expired = history.add(vec![], 3000, 10_000);
+    assert_eq!(3, history.latest().version);
+    assert!(expired.is_empty());
+
+    assert_eq!(Vec::<String>::new(), part_names(&history, 0));
+    assert_eq!(vec!["p1"], part_names(&history, 1));
+    assert_eq!(vec!["p1", "p2"], part_names(&history, 2));
+    assert_eq!(Vec::<String>::new(), part_names(&history, 3));
+
+    let mut all = history.part_names().into_iter().collect::<Vec<_>>();
+    all.sort();
+    assert_eq!(vec!["p1", "p2"], all);
+
+    let r = history.get(4);
+    assert_eq!(
+        ErrorCode::UnknownTableVersion("").code(),
+        r.unwrap_err().code()
+    );
+
+    // A timestamp resolves to the version created last, not after it.
+    let cases = vec![(0, 0), (999, 0), (1000, 1), (2500, 2), (3000, 3), (9999, 3)];
+    for (ts, want) in cases {
+        assert_eq!(
+            want,
+            history.resolve(&TableVersionAt::Timestamp(ts))?,
+            "timestamp {}",
+            ts
+        );
+    }
+    assert_eq!(2, history.resolve(&TableVersionAt::Version(2))?);
+    assert_eq!(3, history.resolve(&TableVersionAt::Latest)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_table_history_retention() -> anyhow::Result<()> {
+    let mut history = TableHistory::default();
+    history.add(parts(&["p1"]), 1000, 1500);
+    history.add(parts(&["p1", "p2"]), 2000, 1500);
+
+    // Version 0 is replaced at 1000, version 1 at 2000.
+    let removed = history.add(parts(&["p1", "p2", "p3"]), 3000, 1500);
+    assert_eq!(
+        vec![0],
+        removed.iter().map(|v| v.version).collect::<Vec<_>>()
+    );
+
+    let expired = ErrorCode::UnknownTableVersion("").code();
+    assert_eq!(expired, history.get(0).unwrap_err().code());
+    assert_eq!(vec!["p1"], part_names(&history, 1));
+
+    let r = history.resolve(&TableVersionAt::Timestamp(500));
+    assert_eq!(expired, r.unwrap_err().code());
+    assert_eq!(1, history.resolve(&TableVersionAt::Timestamp(1500))?);
+
+    // Version 3 is just replaced thus it is kept, the ones before are removed.
+    let removed = history.add(vec![], 100_000, 1500);
+    assert_eq!(
+        vec![1, 2],
+        removed.iter().map(|v| v.version).collect::<Vec<_>>()
+    );
+    assert_eq!(expired, history.get(2).unwrap_err().code());
+    assert_eq!(vec!["p1", "p2", "p3"], part_names(&history, 3));
+    assert_eq!(3, history.resolve(&TableVersionAt::Timestamp(99_999))?);
+    assert_eq!(4, history.resolve(&TableVersionAt::Timestamp(200_000))?);
+
+    Ok(())
+}
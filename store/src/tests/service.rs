@@ -44,7 +44,8 @@ pub async fn start_store_server() -> Result<(StoreTestContext, String)> {
 }
 
 pub async fn start_store_server_with_context(tc: &mut StoreTestContext) -> Result<()> {
-    let srv = StoreServer::create(tc.config.clone());
+    let mn = MetaNode::start(&tc.config).await?;
+    let srv = StoreServer::create(tc.config.clone(), mn);
     let (stop_tx, fin_rx) = srv.start().await?;
 
     tc.channels = Some((stop_tx, fin_rx));
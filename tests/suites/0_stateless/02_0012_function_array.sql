@@ -0,0 +1,6 @@
+select [1, 2, 3];
+select length([1, 2, 3]), has([1, 2, 3], 2), indexOf([1, 2, 3], 3), [1, 2, 3][-1];
+select arrayJoin([1, 2, 3]) from numbers(1);
+select length(groupArray(number)) from numbers(10);
+select has(groupArray(number), 5) from numbers(10);
+select number % 2 as k, length(groupArray(number)) from numbers(10) group by k order by k;
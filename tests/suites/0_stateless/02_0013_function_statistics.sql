@@ -0,0 +1,13 @@
+SELECT var_pop(number) BETWEEN 8.2499 AND 8.2501, var_samp(number) BETWEEN 9.1666 AND 9.1667 FROM numbers_mt(10);
+SELECT stddev_pop(number) BETWEEN 2.8722 AND 2.8723, stddev_samp(number) BETWEEN 3.0276 AND 3.0277 FROM numbers_mt(10);
+SELECT covar_pop(number, number * 2) BETWEEN 16.4999 AND 16.5001, covar_samp(number, number * 2) BETWEEN 18.3333 AND 18.3334 FROM numbers_mt(10);
+SELECT corr(number, number * 2) BETWEEN 0.9999 AND 1.0001, corr(number, 10 - number) BETWEEN -1.0001 AND -0.9999 FROM numbers_mt(10);
+SELECT var_samp(number), stddev_samp(number), covar_samp(number, number), corr(number, number) FROM numbers_mt(1);
+
+SELECT median(number), quantile(0.5)(number) FROM numbers_mt(10);
+SELECT quantile(0.99)(number) BETWEEN 98000 AND 100000 FROM numbers_mt(100000);
+SELECT median(number) FROM numbers_mt(10) WHERE number > 100;
+
+SELECT uniqHLL(number) BETWEEN 95000 AND 105000 FROM numbers_mt(100000);
+SELECT uniqHLL(number % 3) FROM numbers_mt(100);
+SELECT number % 2 AS k, uniqHLL(number) BETWEEN 4750 AND 5250 FROM numbers_mt(10000) GROUP BY k ORDER BY k;
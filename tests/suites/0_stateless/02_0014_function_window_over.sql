@@ -0,0 +1,10 @@
+SELECT number, row_number() OVER (ORDER BY number DESC) FROM numbers(5) ORDER BY number;
+SELECT number % 2 AS g, number, rank() OVER (PARTITION BY number % 2 ORDER BY number) FROM numbers(6) ORDER BY number;
+SELECT number, dense_rank() OVER (ORDER BY number % 3), rank() OVER (ORDER BY number % 3) FROM numbers(6) ORDER BY number;
+SELECT number, lag(number) OVER (ORDER BY number), lead(number, 2, 100) OVER (ORDER BY number) FROM numbers(4) ORDER BY number;
+SELECT number, sum(number) OVER (ORDER BY number), sum(number) OVER (ORDER BY number ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING), count(*) OVER () FROM numbers(5) ORDER BY number;
+SELECT number, sum(number) OVER (ORDER BY number % 3 RANGE BETWEEN 1 PRECEDING AND CURRENT ROW) FROM numbers(6) ORDER BY number;
+SELECT number, first_value(number) OVER (PARTITION BY number % 2 ORDER BY number), last_value(number) OVER (PARTITION BY number % 2 ORDER BY number ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) FROM numbers(6) ORDER BY number;
+SELECT number % 3 AS k, sum(number) AS s, rank() OVER (ORDER BY sum(number) DESC) FROM numbers(10) GROUP BY k ORDER BY k;
+SELECT number, lead(number, 18446744073709551615, 100) OVER (ORDER BY number), sum(number) OVER (ORDER BY number ROWS BETWEEN CURRENT ROW AND 18446744073709551615 FOLLOWING) FROM numbers(3) ORDER BY number;
+SELECT sum(number) OVER (ORDER BY number ROWS BETWEEN UNBOUNDED FOLLOWING AND CURRENT ROW) FROM numbers(3); -- {ErrorCode 5}